@@ -444,6 +444,9 @@ impl_runtime_apis! {
 		) -> pallet_transaction_payment::FeeDetails<Balance> {
 			TransactionPayment::query_fee_details(uxt, len)
 		}
+		fn query_fee_multiplier() -> pallet_transaction_payment::Multiplier {
+			TransactionPayment::next_fee_multiplier()
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -266,6 +266,11 @@ pub fn new_full_base(
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	#[cfg(feature = "cli")]
+	if let Some(storage_monitor) = config.storage_monitor.clone() {
+		sc_service::spawn_storage_monitor(&task_manager, &config.database, storage_monitor);
+	}
+
 	let _rpc_handlers = sc_service::spawn_tasks(
 		sc_service::SpawnTasksParams {
 			config,
@@ -17,8 +17,15 @@
 
 //! Some configurable implementations as associated type for the substrate runtime.
 
-use frame_support::traits::{OnUnbalanced, Currency};
-use crate::{Balances, Authorship, NegativeImbalance};
+use frame_support::{
+	traits::{OnUnbalanced, Currency, Get},
+	weights::DispatchClass,
+};
+use node_primitives::AccountId;
+use pallet_identity::Judgement;
+use pallet_transaction_payment::{Multiplier, MultiplierUpdate, TargetedFeeAdjustment};
+use sp_runtime::{Perquintill, traits::Convert};
+use crate::{Balances, Authorship, Identity, NegativeImbalance};
 
 pub struct Author;
 impl OnUnbalanced<NegativeImbalance> for Author {
@@ -27,15 +34,131 @@ impl OnUnbalanced<NegativeImbalance> for Author {
 	}
 }
 
+/// Bounty curators must have an identity judged `Reasonable` or `KnownGood` by a registrar.
+pub struct JudgedIdentity;
+impl pallet_bounties::CuratorQualification<AccountId> for JudgedIdentity {
+	fn is_qualified(who: &AccountId) -> bool {
+		Identity::identity(who).map_or(false, |registration| {
+			registration.judgements.iter().any(|(_, judgement)|
+				matches!(judgement, Judgement::Reasonable | Judgement::KnownGood)
+			)
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn make_qualified(who: &AccountId) {
+		use sp_std::{convert::TryInto, vec};
+		use pallet_identity::{Data, IdentityInfo};
+		use crate::{BasicDeposit, FieldDeposit, MaxAdditionalFields, MaxRegistrars, Origin};
+
+		// `who` fills the remaining registrar slots and judges its own identity from each of
+		// them, so that the registration read by `is_qualified` is as large as possible.
+		let first_index = Identity::registrars().len() as u32;
+		for _ in first_index..MaxRegistrars::get() {
+			let _ = Identity::add_registrar(Origin::root(), who.clone());
+		}
+		let _ = Balances::deposit_creating(
+			who,
+			BasicDeposit::get() + FieldDeposit::get() * MaxAdditionalFields::get() as u128,
+		);
+		let data = Data::Raw(vec![0; 32].try_into().unwrap());
+		let info = IdentityInfo {
+			additional: vec![(data.clone(), data.clone()); MaxAdditionalFields::get() as usize]
+				.try_into()
+				.unwrap(),
+			display: data.clone(),
+			legal: data.clone(),
+			web: data.clone(),
+			riot: data.clone(),
+			email: data.clone(),
+			pgp_fingerprint: Some([0; 20]),
+			image: data.clone(),
+			twitter: data,
+		};
+		let _ = Identity::set_identity(Origin::signed(who.clone()), info);
+		for registrar_index in first_index..MaxRegistrars::get() {
+			let _ = Identity::provide_judgement(
+				Origin::signed(who.clone()),
+				registrar_index,
+				who.clone().into(),
+				Judgement::Reasonable,
+			);
+		}
+	}
+}
+
+/// A [`TargetedFeeAdjustment`] raising the multiplier with the variability `Up` and lowering it
+/// with the variability `Down`.
+///
+/// With `Down` smaller than `Up`, fees react quickly to congestion but take longer to drop again
+/// on an idle chain, so that a few empty blocks do not make the chain cheap to spam. As with
+/// [`TargetedFeeAdjustment`], the multiplier never goes below `M`.
+pub struct AsymmetricFeeAdjustment<T, S, Up, Down, M>(
+	sp_std::marker::PhantomData<(T, S, Up, Down, M)>,
+);
+
+impl<T, S, Up, Down, M> AsymmetricFeeAdjustment<T, S, Up, Down, M> where
+	T: frame_system::Config,
+	S: Get<Perquintill>,
+{
+	/// Whether the normal weight of the current block reaches the target.
+	fn above_target() -> bool {
+		let weights = T::BlockWeights::get();
+		let normal_max_weight = weights.get(DispatchClass::Normal).max_total
+			.unwrap_or_else(|| weights.max_block);
+		let normal_block_weight = *<frame_system::Pallet<T>>::block_weight()
+			.get(DispatchClass::Normal);
+		normal_block_weight.min(normal_max_weight) >= S::get() * normal_max_weight
+	}
+}
+
+impl<T, S, Up, Down, M> Convert<Multiplier, Multiplier>
+	for AsymmetricFeeAdjustment<T, S, Up, Down, M>
+where
+	T: frame_system::Config,
+	S: Get<Perquintill>,
+	Up: Get<Multiplier>,
+	Down: Get<Multiplier>,
+	M: Get<Multiplier>,
+{
+	fn convert(previous: Multiplier) -> Multiplier {
+		if Self::above_target() {
+			TargetedFeeAdjustment::<T, S, Up, M>::convert(previous)
+		} else {
+			TargetedFeeAdjustment::<T, S, Down, M>::convert(previous)
+		}
+	}
+}
+
+impl<T, S, Up, Down, M> MultiplierUpdate for AsymmetricFeeAdjustment<T, S, Up, Down, M> where
+	T: frame_system::Config,
+	S: Get<Perquintill>,
+	Up: Get<Multiplier>,
+	Down: Get<Multiplier>,
+	M: Get<Multiplier>,
+{
+	fn min() -> Multiplier {
+		M::get()
+	}
+	fn target() -> Perquintill {
+		S::get()
+	}
+	/// Only the variability `Up` the multiplier rises with, not the variability `Down` it drops
+	/// with.
+	fn variability() -> Multiplier {
+		Up::get()
+	}
+}
+
 #[cfg(test)]
 mod multiplier_tests {
 	use sp_runtime::{assert_eq_error_rate, FixedPointNumber, traits::{Convert, One, Zero}};
-	use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+	use pallet_transaction_payment::Multiplier;
 
 	use crate::{
 		constants::{currency::*, time::*},
 		TransactionPayment, Runtime, TargetBlockFullness,
-		AdjustmentVariable, System, MinimumMultiplier,
+		AdjustmentVariable, DownAdjustmentVariable, System, MinimumMultiplier,
 		RuntimeBlockWeights as BlockWeights,
 	};
 	use frame_support::weights::{Weight, WeightToFeePolynomial, DispatchClass};
@@ -55,12 +178,7 @@ mod multiplier_tests {
 
 	// update based on runtime impl.
 	fn runtime_multiplier_update(fm: Multiplier) -> Multiplier {
-		TargetedFeeAdjustment::<
-			Runtime,
-			TargetBlockFullness,
-			AdjustmentVariable,
-			MinimumMultiplier,
-		>::convert(fm)
+		<Runtime as pallet_transaction_payment::Config>::FeeMultiplierUpdate::convert(fm)
 	}
 
 	// update based on reference impl.
@@ -74,13 +192,19 @@ mod multiplier_tests {
 		let m = max_normal() as f64;
 		// block weight always truncated to max weight
 		let block_weight = (block_weight as f64).min(m);
-		let v: f64 = AdjustmentVariable::get().to_float();
 
 		// Ideal saturation in terms of weight
 		let ss = target() as f64;
 		// Current saturation in terms of weight
 		let s = block_weight;
 
+		// the multiplier rises and drops at different speeds.
+		let v: f64 = if s >= ss {
+			AdjustmentVariable::get().to_float()
+		} else {
+			DownAdjustmentVariable::get().to_float()
+		};
+
 		let t1 = v * (s/m - ss/m);
 		let t2 = v.powi(2) * (s/m - ss/m).powi(2) / 2.0;
 		let next_float = previous_float * (1.0 + t1 + t2);
@@ -88,12 +212,16 @@ mod multiplier_tests {
 	}
 
 	fn run_with_system_weight<F>(w: Weight, assertions: F) where F: Fn() -> () {
+		run_with_system_weight_and(w, assertions)
+	}
+
+	fn run_with_system_weight_and<F, R>(w: Weight, f: F) -> R where F: FnOnce() -> R {
 		let mut t: sp_io::TestExternalities =
 			frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap().into();
 		t.execute_with(|| {
 			System::set_block_consumed_resources(w, 0);
-			assertions()
-		});
+			f()
+		})
 	}
 
 	#[test]
@@ -146,10 +274,10 @@ mod multiplier_tests {
 		// v <~ (p / k(0 - s*))
 		// p > v * k * -0.1875
 		// to get p == -1 we'd need
-		// -1 > 0.00001 * k * -0.1875
-		// 1 < 0.00001 * k * 0.1875
-		// 10^9 / 1875 < k
-		// k > 533_333 ~ 18,5 days.
+		// -1 > 0.0000025 * k * -0.1875
+		// 1 < 0.0000025 * k * 0.1875
+		// 4 * 10^9 / 1875 < k
+		// k > 2_133_333 ~ 74 days.
 		run_with_system_weight(0, || {
 			// start from 1, the default.
 			let mut fm = Multiplier::one();
@@ -160,10 +288,20 @@ mod multiplier_tests {
 				if fm == min_multiplier() { break; }
 				iterations += 1;
 			}
-			assert!(iterations > 533_333);
+			assert!(iterations > 2_133_333);
 		})
 	}
 
+	#[test]
+	fn multiplier_drops_slower_than_it_rises() {
+		let fm = Multiplier::one();
+		let rise = run_with_system_weight_and(target() * 2, || runtime_multiplier_update(fm) - fm);
+		let drop = run_with_system_weight_and(0, || fm - runtime_multiplier_update(fm));
+		assert!(drop > Multiplier::zero());
+		// the block weights are as far from the target, only the variability differs.
+		assert!(drop < rise, "{:?} !< {:?}", drop, rise);
+	}
+
 	#[test]
 	fn min_change_per_day() {
 		run_with_system_weight(max_normal(), || {
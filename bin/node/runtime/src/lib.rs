@@ -27,7 +27,7 @@ use sp_std::prelude::*;
 use frame_support::{
 	construct_runtime, parameter_types, RuntimeDebug,
 	weights::{
-		Weight, IdentityFee,
+		Weight, PiecewisePolynomialFee, WeightToFeeCoefficient, WeightToFeePiece,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		DispatchClass,
 	},
@@ -86,10 +86,14 @@ pub use pallet_staking::StakerStatus;
 
 /// Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
-use impls::Author;
+use impls::{Author, JudgedIdentity};
+pub use impls::AsymmetricFeeAdjustment;
 
 /// Constant values used within the runtime.
 pub mod constants;
+
+/// Thresholds of the voter bags.
+mod voter_bags;
 use constants::{time::*, currency::*};
 use sp_runtime::generic::Era;
 
@@ -115,10 +119,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// and set impl_version to 0. If only runtime
 	// implementation changes and behavior does not, then leave spec_version as
 	// is and increment impl_version.
-	spec_version: 267,
+	spec_version: 268,
 	impl_version: 0,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 2,
+	transaction_version: 3,
 };
 
 /// The BABE epoch configuration at genesis.
@@ -223,6 +227,8 @@ impl pallet_randomness_collective_flip::Config for Runtime {}
 impl pallet_utility::Config for Runtime {
 	type Event = Event;
 	type Call = Call;
+	type OriginDowngrade = pallet_utility::RootAsDerivative<Runtime>;
+	type BatchHook = ();
 	type WeightInfo = pallet_utility::weights::SubstrateWeight<Runtime>;
 }
 
@@ -241,6 +247,9 @@ impl pallet_multisig::Config for Runtime {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = MaxSignatories;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
 	type WeightInfo = pallet_multisig::weights::SubstrateWeight<Runtime>;
 }
 
@@ -399,15 +408,37 @@ parameter_types! {
 	pub const TransactionByteFee: Balance = 10 * MILLICENTS;
 	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
 	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
+	/// Variability used to lower the multiplier: fees drop four times slower than they rise.
+	pub DownAdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 400_000);
 	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
+	pub const WeightToFeePieces: &'static [WeightToFeePiece<Balance>] = WEIGHT_TO_FEE_PIECES;
 }
 
+/// Weight to fee conversion, as `(weight threshold, polynomial)` pieces. A single linear piece
+/// charging one unit of balance per unit of weight.
+///
+/// A constant rather than an inline parameter value, as only constants keep the coefficients
+/// borrowed for `'static`.
+const WEIGHT_TO_FEE_PIECES: &[WeightToFeePiece<Balance>] = &[
+	(0, &[WeightToFeeCoefficient {
+		coeff_integer: 1,
+		coeff_frac: Perbill::zero(),
+		negative: false,
+		degree: 1,
+	}]),
+];
+
 impl pallet_transaction_payment::Config for Runtime {
 	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
 	type TransactionByteFee = TransactionByteFee;
-	type WeightToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate =
-		TargetedFeeAdjustment<Self, TargetBlockFullness, AdjustmentVariable, MinimumMultiplier>;
+	type WeightToFee = PiecewisePolynomialFee<WeightToFeePieces, Balance>;
+	type FeeMultiplierUpdate = AsymmetricFeeAdjustment<
+		Self,
+		TargetBlockFullness,
+		AdjustmentVariable,
+		DownAdjustmentVariable,
+		MinimumMultiplier,
+	>;
 }
 
 parameter_types! {
@@ -509,6 +540,7 @@ impl pallet_staking::Config for Runtime {
 	type ElectionProvider = ElectionProviderMultiPhase;
 	type GenesisElectionProvider =
 		onchain::OnChainSequentialPhragmen<pallet_election_provider_multi_phase::OnChainConfig<Self>>;
+	type SortedListProvider = BagsList;
 	type WeightInfo = pallet_staking::weights::SubstrateWeight<Runtime>;
 }
 
@@ -526,6 +558,9 @@ parameter_types! {
 	// fallback: no on-chain fallback.
 	pub const Fallback: FallbackStrategy = FallbackStrategy::Nothing;
 
+	// at most this many voters in the snapshot, taken from the heaviest bags of `BagsList`.
+	pub const VoterSnapshotPerBlock: u32 = 22_500;
+
 	pub SolutionImprovementThreshold: Perbill = Perbill::from_rational(1u32, 10_000);
 
 	// miner configs
@@ -590,11 +625,23 @@ impl pallet_election_provider_multi_phase::Config for Runtime {
 	type OnChainAccuracy = Perbill;
 	type CompactSolution = NposCompactSolution16;
 	type Fallback = Fallback;
+	type VoterSnapshotPerBlock = VoterSnapshotPerBlock;
 	type WeightInfo = pallet_election_provider_multi_phase::weights::SubstrateWeight<Runtime>;
 	type ForceOrigin = EnsureRootOrHalfCouncil;
 	type BenchmarkingConfig = BenchmarkConfig;
 }
 
+parameter_types! {
+	pub const BagThresholds: &'static [u64] = &voter_bags::THRESHOLDS;
+}
+
+impl pallet_bags_list::Config for Runtime {
+	type Event = Event;
+	type VoteWeightProvider = Staking;
+	type BagThresholds = BagThresholds;
+	type WeightInfo = pallet_bags_list::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub const LaunchPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
 	pub const VotingPeriod: BlockNumber = 28 * 24 * 60 * MINUTES;
@@ -711,6 +758,10 @@ parameter_types! {
 	pub const TechnicalMotionDuration: BlockNumber = 5 * DAYS;
 	pub const TechnicalMaxProposals: u32 = 100;
 	pub const TechnicalMaxMembers: u32 = 100;
+	pub const TechnicalTermDuration: BlockNumber = 28 * DAYS;
+	pub TechnicalRotationPeriod: BlockNumber =
+		SessionsPerEra::get() * EPOCH_DURATION_IN_BLOCKS;
+	pub const TechnicalMaxApplicants: u32 = 100;
 }
 
 type TechnicalCollective = pallet_collective::Instance2;
@@ -740,6 +791,9 @@ impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
 	type MembershipInitialized = TechnicalCommittee;
 	type MembershipChanged = TechnicalCommittee;
 	type MaxMembers = TechnicalMaxMembers;
+	type TermDuration = TechnicalTermDuration;
+	type RotationPeriod = TechnicalRotationPeriod;
+	type MaxApplicants = TechnicalMaxApplicants;
 	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
 }
 
@@ -759,6 +813,11 @@ parameter_types! {
 	pub const MaximumReasonLength: u32 = 16384;
 	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
 	pub const BountyValueMinimum: Balance = 5 * DOLLARS;
+	pub const MaximumTranches: u32 = 16;
+	pub const MaximumCurators: u32 = 7;
+	pub const BountyAutoApprovalLimit: Balance = 100 * DOLLARS;
+	pub const BountyObjectionPeriod: BlockNumber = 7 * DAYS;
+	pub const MaxAutoApprovalsPerBlock: u32 = 100;
 	pub const MaxApprovals: u32 = 100;
 }
 
@@ -796,6 +855,12 @@ impl pallet_bounties::Config for Runtime {
 	type BountyValueMinimum = BountyValueMinimum;
 	type DataDepositPerByte = DataDepositPerByte;
 	type MaximumReasonLength = MaximumReasonLength;
+	type MaximumTranches = MaximumTranches;
+	type MaximumCurators = MaximumCurators;
+	type CuratorQualification = JudgedIdentity;
+	type BountyAutoApprovalLimit = BountyAutoApprovalLimit;
+	type BountyObjectionPeriod = BountyObjectionPeriod;
+	type MaxAutoApprovalsPerBlock = MaxAutoApprovalsPerBlock;
 	type WeightInfo = pallet_bounties::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1021,6 +1086,7 @@ parameter_types! {
 	pub const MaxLockDuration: BlockNumber = 36 * 30 * DAYS;
 	pub const ChallengePeriod: BlockNumber = 7 * DAYS;
 	pub const MaxCandidateIntake: u32 = 10;
+	pub const SeedRetention: u32 = 64;
 	pub const SocietyPalletId: PalletId = PalletId(*b"py/socie");
 }
 
@@ -1028,7 +1094,7 @@ impl pallet_society::Config for Runtime {
 	type Event = Event;
 	type PalletId = SocietyPalletId;
 	type Currency = Balances;
-	type Randomness = RandomnessCollectiveFlip;
+	type Randomness = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
 	type CandidateDeposit = CandidateDeposit;
 	type WrongSideDeduction = WrongSideDeduction;
 	type MaxStrikes = MaxStrikes;
@@ -1040,10 +1106,12 @@ impl pallet_society::Config for Runtime {
 	type SuspensionJudgementOrigin = pallet_society::EnsureFounder<Runtime>;
 	type MaxCandidateIntake = MaxCandidateIntake;
 	type ChallengePeriod = ChallengePeriod;
+	type SeedRetention = SeedRetention;
 }
 
 parameter_types! {
 	pub const MinVestedTransfer: Balance = 100 * DOLLARS;
+	pub const MaxVestingSchedules: u32 = 28;
 }
 
 impl pallet_vesting::Config for Runtime {
@@ -1051,6 +1119,7 @@ impl pallet_vesting::Config for Runtime {
 	type Currency = Balances;
 	type BlockNumberToBalance = ConvertInto;
 	type MinVestedTransfer = MinVestedTransfer;
+	type MaxVestingSchedules = MaxVestingSchedules;
 	type WeightInfo = pallet_vesting::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1174,7 +1243,7 @@ construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Utility: pallet_utility::{Pallet, Call, Event},
+		Utility: pallet_utility::{Pallet, Call, Event<T>},
 		Babe: pallet_babe::{Pallet, Call, Storage, Config, ValidateUnsigned},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Authorship: pallet_authorship::{Pallet, Call, Storage, Inherent},
@@ -1213,6 +1282,7 @@ construct_runtime!(
 		Gilt: pallet_gilt::{Pallet, Call, Storage, Event<T>, Config},
 		Uniques: pallet_uniques::{Pallet, Call, Storage, Event<T>},
 		TransactionStorage: pallet_transaction_storage::{Pallet, Call, Storage, Inherent, Config<T>, Event<T>},
+		BagsList: pallet_bags_list::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -1473,6 +1543,54 @@ impl_runtime_apis! {
 		fn query_fee_details(uxt: <Block as BlockT>::Extrinsic, len: u32) -> FeeDetails<Balance> {
 			TransactionPayment::query_fee_details(uxt, len)
 		}
+		fn query_fee_multiplier() -> Multiplier {
+			TransactionPayment::next_fee_multiplier()
+		}
+	}
+
+	impl pallet_assets_rpc_runtime_api::AssetsApi<Block, AccountId, u32, u64> for Runtime {
+		fn account_balances(who: AccountId) -> Vec<(u32, u64)> {
+			Assets::account_balances(&who)
+		}
+	}
+
+	impl pallet_utility_rpc_runtime_api::UtilityApi<Block, AccountId> for Runtime {
+		fn derivative_account_id(who: AccountId, index: u16) -> AccountId {
+			Utility::derivative_account_id(who, index)
+		}
+	}
+
+	impl pallet_treasury_rpc_runtime_api::TreasuryApi<
+		Block,
+		AccountId,
+		Balance,
+		BlockNumber,
+		Hash,
+	> for Runtime {
+		fn proposals(
+			start: Option<pallet_treasury::ProposalIndex>,
+			count: u32,
+		) -> Vec<(pallet_treasury::ProposalIndex, pallet_treasury::Proposal<AccountId, Balance>)> {
+			Treasury::proposals_paged(start, count)
+		}
+
+		fn approved_proposals() -> Vec<pallet_treasury::ProposalIndex> {
+			Treasury::approvals().into_inner()
+		}
+
+		fn bounties(
+			start: Option<pallet_bounties::BountyIndex>,
+			count: u32,
+		) -> Vec<(pallet_bounties::BountyIndex, pallet_bounties::Bounty<AccountId, Balance, BlockNumber>)> {
+			Bounties::bounties_paged(start, count)
+		}
+
+		fn tips(
+			start: Option<Hash>,
+			count: u32,
+		) -> Vec<(Hash, pallet_tips::OpenTip<AccountId, Balance, BlockNumber, Hash>)> {
+			Tips::tips_paged(start, count)
+		}
 	}
 
 	impl pallet_mmr::primitives::MmrApi<
@@ -1570,6 +1688,7 @@ impl_runtime_apis! {
 
 			add_benchmark!(params, batches, pallet_assets, Assets);
 			add_benchmark!(params, batches, pallet_babe, Babe);
+			add_benchmark!(params, batches, pallet_bags_list, BagsList);
 			add_benchmark!(params, batches, pallet_balances, Balances);
 			add_benchmark!(params, batches, pallet_bounties, Bounties);
 			add_benchmark!(params, batches, pallet_collective, Council);
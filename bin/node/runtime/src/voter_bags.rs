@@ -0,0 +1,225 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Thresholds of the voter bags of the bags list pallet.
+//!
+//! Each threshold is the previous one multiplied by a constant ratio of about 1.2497, rounded and
+//! kept strictly increasing, starting at 1 and ending at `u64::MAX`.
+
+/// Upper thresholds of the voter bags.
+pub const THRESHOLDS: [u64; 200] = [
+	1,
+	2,
+	3,
+	4,
+	5,
+	6,
+	7,
+	8,
+	9,
+	10,
+	11,
+	12,
+	15,
+	18,
+	23,
+	28,
+	35,
+	44,
+	55,
+	69,
+	86,
+	108,
+	135,
+	169,
+	211,
+	263,
+	329,
+	411,
+	514,
+	642,
+	802,
+	1_003,
+	1_253,
+	1_566,
+	1_957,
+	2_446,
+	3_057,
+	3_820,
+	4_774,
+	5_967,
+	7_457,
+	9_319,
+	11_646,
+	14_554,
+	18_189,
+	22_731,
+	28_407,
+	35_501,
+	44_367,
+	55_446,
+	69_292,
+	86_596,
+	108_221,
+	135_246,
+	169_020,
+	211_228,
+	263_977,
+	329_897,
+	412_280,
+	515_236,
+	643_902,
+	804_699,
+	1_005_651,
+	1_256_785,
+	1_570_633,
+	1_962_856,
+	2_453_025,
+	3_065_601,
+	3_831_152,
+	4_787_877,
+	5_983_519,
+	7_477_740,
+	9_345_101,
+	11_678_786,
+	14_595_244,
+	18_240_008,
+	22_794_952,
+	28_487_370,
+	35_601_314,
+	44_491_771,
+	55_602_377,
+	69_487_554,
+	86_840_175,
+	108_526_140,
+	135_627_583,
+	169_496_873,
+	211_824_094,
+	264_721_384,
+	330_828_329,
+	413_443_681,
+	516_689_963,
+	645_719_188,
+	806_969_943,
+	1_008_488_676,
+	1_260_331_216,
+	1_575_064_561,
+	1_968_393_974,
+	2_459_946_681,
+	3_074_251_268,
+	3_841_961_670,
+	4_801_386_806,
+	6_000_402_201,
+	7_498_838_986,
+	9_371_469_489,
+	11_711_738_382,
+	14_636_425_599,
+	18_291_473_677,
+	22_859_270_319,
+	28_567_749_583,
+	35_701_765_842,
+	44_617_308_078,
+	55_759_263_811,
+	69_683_619_086,
+	87_085_202_297,
+	108_832_356_279,
+	136_010_268_803,
+	169_975_123_689,
+	212_421_774_674,
+	265_468_318_989,
+	331_761_790_876,
+	414_610_249_180,
+	518_147_850_212,
+	647_541_143_062,
+	809_246_881_535,
+	1_011_334_217_587,
+	1_263_887_353_786,
+	1_579_508_747_239,
+	1_973_947_974_975,
+	2_466_887_641_313,
+	3_082_925_544_144,
+	3_852_802_110_467,
+	4_814_934_350_464,
+	6_017_332_874_764,
+	7_519_997_634_491,
+	9_397_911_932_032,
+	11_744_784_104_337,
+	14_677_723_589_570,
+	18_343_084_713_858,
+	22_923_769_804_389,
+	28_648_356_055_819,
+	35_802_501_582_606,
+	44_743_199_821_834,
+	55_916_593_584_319,
+	69_880_237_679_116,
+	87_330_921_021_254,
+	109_139_436_552_028,
+	136_394_033_998_511,
+	170_454_723_774_558,
+	213_021_141_799_928,
+	266_217_361_706_922,
+	332_697_886_582_345,
+	415_780_109_256_041,
+	519_609_850_933_567,
+	649_368_238_587_237,
+	811_530_244_332_882,
+	1_014_187_787_964_183,
+	1_267_453_525_532_156,
+	1_583_965_473_108_835,
+	1_979_517_646_572_078,
+	2_473_848_186_475_600,
+	3_091_624_295_608_812,
+	3_863_673_137_847_564,
+	4_828_520_120_419_476,
+	6_034_311_320_207_636,
+	7_541_215_983_588_500,
+	9_424_428_985_074_940,
+	11_777_923_068_111_832,
+	14_719_138_105_666_120,
+	18_394_841_375_747_328,
+	22_988_451_280_897_384,
+	28_729_189_966_864_960,
+	35_903_521_558_151_484,
+	44_869_446_780_901_208,
+	56_074_367_277_965_152,
+	70_077_411_049_394_144,
+	87_577_333_062_757_568,
+	109_447_383_279_886_160,
+	136_778_882_022_251_056,
+	170_935_677_095_306_528,
+	213_622_200_094_292_224,
+	266_968_517_916_139_776,
+	333_636_623_566_656_000,
+	416_953_270_197_665_472,
+	521_075_976_821_814_912,
+	651_200_489_426_761_728,
+	813_820_049_844_026_112,
+	1_017_049_409_946_151_680,
+	1_271_029_759_551_958_272,
+	1_588_434_774_031_522_560,
+	1_985_103_033_497_801_984,
+	2_480_828_371_441_819_136,
+	3_100_347_591_382_330_368,
+	3_874_574_838_808_289_280,
+	4_842_144_223_845_829_632,
+	6_051_337_671_860_527_104,
+	7_562_494_202_164_495_360,
+	9_451_020_858_366_300_160,
+	11_811_155_536_451_145_728,
+	14_760_669_476_541_083_648,
+	18_446_744_073_709_551_615,
+];
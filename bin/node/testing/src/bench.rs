@@ -410,6 +410,7 @@ impl BenchDb {
 		let db_config = sc_client_db::DatabaseSettings {
 			state_cache_size: 16*1024*1024,
 			state_cache_child_ratio: Some((0, 100)),
+			trie_node_cache_size: 0,
 			state_pruning: PruningMode::ArchiveAll,
 			source: database_type.into_settings(dir.into()),
 			keep_blocks: sc_client_db::KeepBlocks::All,
@@ -634,6 +635,7 @@ impl Profile {
 				block_construction: ExecutionStrategy::AlwaysWasm,
 				offchain_worker: ExecutionStrategy::AlwaysWasm,
 				other: ExecutionStrategy::AlwaysWasm,
+				overrides: Default::default(),
 			},
 			Profile::Native => ExecutionStrategies {
 				syncing: ExecutionStrategy::NativeElseWasm,
@@ -641,6 +643,7 @@ impl Profile {
 				block_construction: ExecutionStrategy::NativeElseWasm,
 				offchain_worker: ExecutionStrategy::NativeElseWasm,
 				other: ExecutionStrategy::NativeElseWasm,
+				overrides: Default::default(),
 			}
 		}
 	}
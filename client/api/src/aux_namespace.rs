@@ -0,0 +1,75 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Namespaced auxiliary data.
+//!
+//! Components writing auxiliary data can keep it in their own namespace, with
+//! [`AuxStore::insert_aux_with_namespace`](crate::AuxStore::insert_aux_with_namespace), and
+//! register an [`AuxPruningCallback`] for it with a backend implementing [`AuxPruning`]. The
+//! callback is invoked whenever a block is finalized and returns the entries of the namespace that
+//! are no longer needed, e.g. entries of dead forks.
+
+use codec::Encode;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+
+/// Prefix of the database keys of namespaced auxiliary data.
+const AUX_NAMESPACE_PREFIX: &[u8] = b"aux_namespace";
+
+/// Callback returning the keys of the entries of a namespace to remove once a block is finalized.
+///
+/// It is called with the hash and number of the finalized block, and a function reading an
+/// entry of the namespace. Both the read and the returned keys are relative to the namespace.
+/// The reads do not see the changes made by the finalization itself.
+pub type AuxPruningCallback<Block> = Box<
+	dyn Fn(
+		&<Block as BlockT>::Hash,
+		NumberFor<Block>,
+		&dyn Fn(&[u8]) -> Option<Vec<u8>>,
+	) -> Vec<Vec<u8>> + Send + Sync
+>;
+
+/// Backend pruning namespaced auxiliary data when blocks are finalized.
+pub trait AuxPruning<Block: BlockT> {
+	/// Register a callback pruning the auxiliary data of the namespace `namespace`.
+	///
+	/// The callback is invoked on every finalized block and the entries it returns are removed
+	/// together with the finalization. Registering a callback for a namespace replaces the
+	/// previous one.
+	fn register_aux_pruning(&self, namespace: &[u8], callback: AuxPruningCallback<Block>);
+}
+
+/// Key of `key` in the auxiliary data namespace `namespace`.
+///
+/// The namespace is length-prefixed, so keys of different namespaces never collide.
+pub fn namespaced_key(namespace: &[u8], key: &[u8]) -> Vec<u8> {
+	let mut namespaced = AUX_NAMESPACE_PREFIX.to_vec();
+	namespace.encode_to(&mut namespaced);
+	namespaced.extend_from_slice(key);
+	namespaced
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn namespaces_do_not_collide() {
+		assert_ne!(namespaced_key(b"ab", b"c"), namespaced_key(b"a", b"bc"));
+		assert_eq!(namespaced_key(b"a", b"bc"), namespaced_key(b"a", b"bc"));
+	}
+}
@@ -34,6 +34,7 @@ use crate::{
 		Backend as BlockchainBackend, well_known_cache_keys
 	},
 	light::RemoteBlockchain,
+	aux_namespace::namespaced_key,
 	UsageInfo,
 };
 use sp_blockchain;
@@ -41,6 +42,7 @@ use sp_consensus::BlockOrigin;
 use parking_lot::RwLock;
 
 pub use sp_state_machine::Backend as StateBackend;
+pub use sp_state_machine::KeyChangesInRange;
 pub use sp_consensus::ImportedState;
 use std::marker::PhantomData;
 
@@ -82,6 +84,8 @@ pub struct ClientImportOperation<Block: BlockT, B: Backend<Block>> {
 	pub notify_imported: Option<ImportSummary<Block>>,
 	/// A list of hashes of blocks that got finalized.
 	pub notify_finalized: Vec<Block::Hash>,
+	/// A list of hashes of leaves that got discarded by the finalization.
+	pub notify_stale_heads: Vec<Block::Hash>,
 }
 
 /// Helper function to apply auxiliary data insertion into an operation.
@@ -275,6 +279,34 @@ pub trait AuxStore {
 
 	/// Query auxiliary data from key-value store.
 	fn get_aux(&self, key: &[u8]) -> sp_blockchain::Result<Option<Vec<u8>>>;
+
+	/// Insert auxiliary data into the namespace `namespace`.
+	///
+	/// Deletions occur after insertions.
+	fn insert_aux_with_namespace<
+		'a,
+		'b: 'a,
+		'c: 'a,
+		I: IntoIterator<Item=&'a(&'c [u8], &'c [u8])>,
+		D: IntoIterator<Item=&'a &'b [u8]>,
+	>(&self, namespace: &[u8], insert: I, delete: D) -> sp_blockchain::Result<()> {
+		let inserted: Vec<_> = insert.into_iter()
+			.map(|(k, v)| (namespaced_key(namespace, k), *v))
+			.collect();
+		let deleted: Vec<_> = delete.into_iter().map(|k| namespaced_key(namespace, k)).collect();
+		let insert: Vec<(&[u8], &[u8])> = inserted.iter().map(|(k, v)| (&k[..], *v)).collect();
+		let delete: Vec<&[u8]> = deleted.iter().map(|k| &k[..]).collect();
+		self.insert_aux(&insert, &delete)
+	}
+
+	/// Query auxiliary data from the namespace `namespace`.
+	fn get_aux_with_namespace(
+		&self,
+		namespace: &[u8],
+		key: &[u8],
+	) -> sp_blockchain::Result<Option<Vec<u8>>> {
+		self.get_aux(&namespaced_key(namespace, key))
+	}
 }
 
 /// An `Iterator` that iterates keys in a given block under a prefix.
@@ -419,6 +451,20 @@ pub trait StorageProvider<Block: BlockT, B: Backend<Block>> {
 		storage_key: Option<&PrefixedStorageKey>,
 		key: &StorageKey
 	) -> sp_blockchain::Result<Vec<(NumberFor<Block>, u32)>>;
+
+	/// Get pairs of (block, extrinsic) where key has been changed at given blocks range,
+	/// together with the blocks of the range that could not be checked.
+	///
+	/// Unlike `key_changes` this doesn't fail if changes tries are pruned or disabled for a part
+	/// of the range, or not supported at all. These blocks are reported in
+	/// [`KeyChangesInRange::unchecked`] instead.
+	fn key_changes_in_range(
+		&self,
+		first: NumberFor<Block>,
+		last: BlockId<Block>,
+		storage_key: Option<&PrefixedStorageKey>,
+		key: &StorageKey
+	) -> sp_blockchain::Result<KeyChangesInRange<NumberFor<Block>>>;
 }
 
 /// Client backend.
@@ -24,7 +24,7 @@ use sp_runtime::{
 	generic::BlockId, traits::{Block as BlockT, HashFor},
 };
 use sp_state_machine::{
-	OverlayedChanges, ExecutionManager, ExecutionStrategy, StorageProof,
+	OverlayedChanges, ExecutionManager, ExecutionStrategy, ExecutionLimits, StorageProof,
 };
 use sc_executor::{RuntimeVersion, NativeVersion};
 use sp_externalities::Extensions;
@@ -65,6 +65,20 @@ pub trait CallExecutor<B: BlockT> {
 		extensions: Option<Extensions>,
 	) -> Result<Vec<u8>, sp_blockchain::Error>;
 
+	/// Execute a call like [`Self::call`], but fail once the call exceeds the given `limits` on
+	/// the state it reads.
+	///
+	/// No changes are made.
+	fn call_with_limits(
+		&self,
+		id: &BlockId<B>,
+		method: &str,
+		call_data: &[u8],
+		strategy: ExecutionStrategy,
+		extensions: Option<Extensions>,
+		limits: ExecutionLimits,
+	) -> Result<Vec<u8>, sp_blockchain::Error>;
+
 	/// Execute a contextual call on top of state in a block of a given hash.
 	///
 	/// No changes are made.
@@ -95,6 +95,11 @@ pub trait BlockBackend<Block: BlockT> {
 		id: &BlockId<Block>,
 	) -> sp_blockchain::Result<Option<Vec<Vec<u8>>>>;
 
+	/// Get the number of extrinsics in the block body. Returns `None` if the body is not stored.
+	fn block_extrinsics_count(&self, id: &BlockId<Block>) -> sp_blockchain::Result<Option<u32>> {
+		Ok(self.block_body(id)?.map(|body| body.len() as u32))
+	}
+
 	/// Get full block by id.
 	fn block(&self, id: &BlockId<Block>) -> sp_blockchain::Result<Option<SignedBlock<Block>>>;
 
@@ -184,6 +189,8 @@ pub struct StateDbMemoryInfo {
 pub struct MemoryInfo {
 	/// Size of state cache.
 	pub state_cache: MemorySize,
+	/// Size of the trie node cache.
+	pub trie_node_cache: MemorySize,
 	/// Size of backend database cache.
 	pub database_cache: MemorySize,
 	/// Size of the state db.
@@ -215,6 +222,10 @@ pub struct IoInfo {
 	pub state_writes_cache: u64,
 	/// State write (trie nodes) to backend db.
 	pub state_writes_nodes: u64,
+	/// Trie node reads served by the trie node cache.
+	pub trie_node_cache_hits: u64,
+	/// Trie node reads missing the trie node cache.
+	pub trie_node_cache_misses: u64,
 }
 
 /// Usage statistics for running client instance.
@@ -234,10 +245,12 @@ impl fmt::Display for UsageInfo {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 		write!(
 			f,
-			"caches: ({} state, {} db overlay), \
+			"caches: ({} state, {} trie nodes, {} db overlay), \
 			 state db: ({} non-canonical, {} pruning, {} pinned), \
-			 i/o: ({} tx, {} write, {} read, {} avg tx, {}/{} key cache reads/total, {} trie nodes writes)",
+			 i/o: ({} tx, {} write, {} read, {} avg tx, {}/{} key cache reads/total, \
+			 {}/{} trie node cache hits/misses, {} trie nodes writes)",
 			self.memory.state_cache,
+			self.memory.trie_node_cache,
 			self.memory.database_cache,
 			self.memory.state_db.non_canonical,
 			self.memory.state_db.pruning.unwrap_or_default(),
@@ -248,6 +261,8 @@ impl fmt::Display for UsageInfo {
 			self.io.average_transaction_size,
 			self.io.state_reads_cache,
 			self.io.state_reads,
+			self.io.trie_node_cache_hits,
+			self.io.trie_node_cache_misses,
 			self.io.state_writes_nodes,
 		)
 	}
@@ -277,6 +292,8 @@ pub struct FinalityNotification<Block: BlockT> {
 	pub hash: Block::Hash,
 	/// Imported block header.
 	pub header: Block::Header,
+	/// Leaves discarded by finalizing this block, if it is the last block finalized at once.
+	pub stale_heads: Vec<Block::Hash>,
 }
 
 impl<B: BlockT> TryFrom<BlockImportNotification<B>> for ChainEvent<B> {
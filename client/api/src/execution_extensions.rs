@@ -34,7 +34,7 @@ use sp_runtime::{
 	traits,
 };
 use sp_state_machine::{ExecutionManager, DefaultHandler};
-pub use sp_state_machine::ExecutionStrategy;
+pub use sp_state_machine::{ExecutionStrategy, ExecutionStrategyOverrides};
 use sp_externalities::Extensions;
 use parking_lot::RwLock;
 use sc_transaction_pool_api::OffchainSubmitTransaction;
@@ -52,6 +52,9 @@ pub struct ExecutionStrategies {
 	pub offchain_worker: ExecutionStrategy,
 	/// Execution strategy used in other cases.
 	pub other: ExecutionStrategy,
+	/// Execution strategies of offchain and RPC calls by method name, overriding
+	/// `offchain_worker` and `other`.
+	pub overrides: ExecutionStrategyOverrides,
 }
 
 impl Default for ExecutionStrategies {
@@ -62,6 +65,7 @@ impl Default for ExecutionStrategies {
 			block_construction: ExecutionStrategy::AlwaysWasm,
 			offchain_worker: ExecutionStrategy::NativeWhenPossible,
 			other: ExecutionStrategy::NativeElseWasm,
+			overrides: Default::default(),
 		}
 	}
 }
@@ -144,6 +148,27 @@ impl<Block: traits::Block> ExecutionExtensions<Block> {
 		&self.strategies
 	}
 
+	/// Get the execution strategy of a call to `method` in the given `context`.
+	///
+	/// Only the strategies of offchain calls, including the calls made through RPC, can be
+	/// overridden per method. Syncing, importing and block construction always use their
+	/// configured strategy.
+	pub fn strategy_for(&self, context: &ExecutionContext, method: &str) -> ExecutionStrategy {
+		match context {
+			ExecutionContext::BlockConstruction => self.strategies.block_construction,
+			ExecutionContext::Syncing => self.strategies.syncing,
+			ExecutionContext::Importing => self.strategies.importing,
+			ExecutionContext::OffchainCall(call) => {
+				let strategy = match call {
+					Some((_, capabilities)) if capabilities.has_all() =>
+						self.strategies.offchain_worker,
+					_ => self.strategies.other,
+				};
+				self.strategies.overrides.strategy_for(method).unwrap_or(strategy)
+			},
+		}
+	}
+
 	/// Set the new extensions_factory
 	pub fn set_extensions_factory(&self, maker: Box<dyn ExtensionsFactory>) {
 		*self.extensions_factory.write() = maker;
@@ -208,26 +233,17 @@ impl<Block: traits::Block> ExecutionExtensions<Block> {
 	///
 	/// Based on the execution context and capabilities it produces
 	/// the right manager and extensions object to support desired set of APIs.
+	/// The manager follows the strategy of [`Self::strategy_for`] for `method`.
 	pub fn manager_and_extensions<E: std::fmt::Debug, R: codec::Codec>(
 		&self,
 		at: &BlockId<Block>,
 		context: ExecutionContext,
+		method: &str,
 	) -> (
 		ExecutionManager<DefaultHandler<R, E>>,
 		Extensions,
 	) {
-		let manager = match context {
-			ExecutionContext::BlockConstruction =>
-				self.strategies.block_construction.get_manager(),
-			ExecutionContext::Syncing =>
-				self.strategies.syncing.get_manager(),
-			ExecutionContext::Importing =>
-				self.strategies.importing.get_manager(),
-			ExecutionContext::OffchainCall(Some((_, capabilities))) if capabilities.has_all() =>
-				self.strategies.offchain_worker.get_manager(),
-			ExecutionContext::OffchainCall(_) =>
-				self.strategies.other.get_manager(),
-		};
+		let manager = self.strategy_for(&context, method).get_manager();
 
 		(manager, self.extensions(at, context))
 	}
@@ -252,3 +268,35 @@ impl<Block: traits::Block> offchain::TransactionPool for TransactionPoolAdapter<
 		self.pool.submit_at(&self.at, xt)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use substrate_test_runtime::Block;
+
+	#[test]
+	fn only_offchain_calls_are_overridden() {
+		let strategies = ExecutionStrategies {
+			overrides: ExecutionStrategyOverrides::default()
+				.with_override("Core_*", ExecutionStrategy::Both),
+			..Default::default()
+		};
+		let extensions = ExecutionExtensions::<Block>::new(strategies, None, None);
+
+		for context in [
+			ExecutionContext::Importing,
+			ExecutionContext::Syncing,
+			ExecutionContext::BlockConstruction,
+		].iter() {
+			assert_ne!(extensions.strategy_for(context, "Core_version"), ExecutionStrategy::Both);
+		}
+		assert_eq!(
+			extensions.strategy_for(&ExecutionContext::OffchainCall(None), "Core_version"),
+			ExecutionStrategy::Both,
+		);
+		assert_eq!(
+			extensions.strategy_for(&ExecutionContext::OffchainCall(None), "Metadata_metadata"),
+			ExecutionStrategy::NativeElseWasm,
+		);
+	}
+}
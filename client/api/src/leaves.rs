@@ -156,6 +156,23 @@ impl<H, N> LeafSet<H, N> where
 		}
 	}
 
+	/// The leaves which finalizing the given block height would displace, without displacing
+	/// them. See [`Self::finalize_height`].
+	pub fn displaced_by_finalize_height(&self, number: N) -> FinalizationDisplaced<H, N> {
+		let boundary = if number == N::zero() {
+			return FinalizationDisplaced { leaves: BTreeMap::new() };
+		} else {
+			number - N::one()
+		};
+
+		let below_boundary = self.storage.range(&Reverse(boundary)..)
+			.map(|(number, hashes)| (number.clone(), hashes.clone()))
+			.collect();
+		FinalizationDisplaced {
+			leaves: below_boundary,
+		}
+	}
+
 	/// Undo all pending operations.
 	///
 	/// This returns an `Undo` struct, where any
@@ -383,4 +400,24 @@ mod tests {
 		set.undo().undo_finalization(displaced);
 		assert!(set.contains(10, 10_1));
 	}
+
+	#[test]
+	fn displaced_by_finalization_does_not_displace() {
+		let mut set = LeafSet::new();
+		set.import(10_1u32, 10u32, 0u32);
+		set.import(11_1, 11, 10_2);
+		set.import(11_2, 11, 10_2);
+		set.import(12_1, 12, 11_123);
+
+		let displaced = set.displaced_by_finalize_height(12);
+		assert_eq!(displaced.leaves().into_iter().collect::<Vec<_>>(), vec![&11_1, &11_2, &10_1]);
+		assert!(set.contains(10, 10_1));
+		assert!(set.contains(11, 11_1));
+
+		let finalized = set.finalize_height(12);
+		assert_eq!(
+			finalized.leaves().into_iter().collect::<Vec<_>>(),
+			displaced.leaves().into_iter().collect::<Vec<_>>(),
+		);
+	}
 }
@@ -19,6 +19,7 @@
 //! Substrate client interfaces.
 #![warn(missing_docs)]
 
+pub mod aux_namespace;
 pub mod backend;
 pub mod call_executor;
 pub mod client;
@@ -31,6 +32,7 @@ pub mod notifications;
 pub mod proof_provider;
 
 pub use sp_blockchain as blockchain;
+pub use aux_namespace::*;
 pub use backend::*;
 pub use notifications::*;
 pub use call_executor::*;
@@ -34,9 +34,11 @@ use sp_runtime::{
 use sc_transaction_pool_api::{TransactionPool, InPoolTransaction};
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_INFO};
 use sc_block_builder::{BlockBuilderApi, BlockBuilderProvider};
-use sp_api::{ProvideRuntimeApi, ApiExt};
+use sp_api::{ProvideRuntimeApi, ApiExt, ApiError};
 use futures::{future, future::{Future, FutureExt}, channel::oneshot, select};
-use sp_blockchain::{HeaderBackend, ApplyExtrinsicFailed::Validity, Error::ApplyExtrinsicFailed};
+use sp_blockchain::{
+	HeaderBackend, ApplyExtrinsicFailed::Validity, Error::{ApplyExtrinsicFailed, RuntimeApiError},
+};
 use std::marker::PhantomData;
 
 use prometheus_endpoint::Registry as PrometheusRegistry;
@@ -393,6 +395,19 @@ impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
 						break;
 					}
 				}
+				Err(RuntimeApiError(e)) if is_execution_error(&e) => {
+					// The runtime call itself failed, e.g. because the extrinsic made the runtime
+					// panic. The changes were rolled back, so we can drop the extrinsic and continue.
+					// It needs to be banned even when skipping, otherwise it would be tried again in
+					// every block we author.
+					warn!("[{:?}] Failed to apply extrinsic, dropping it: {}", pending_tx_hash, e);
+					unqueue_invalid.push(pending_tx_hash);
+				}
+				Err(e @ RuntimeApiError(_)) => {
+					// The node failed to make the runtime call, which says nothing about the
+					// extrinsic. Keep it in the pool.
+					warn!("[{:?}] Failed to apply extrinsic: {}", pending_tx_hash, e);
+				}
 				Err(e) if skipped > 0 => {
 					trace!(
 						"[{:?}] Ignoring invalid transaction when skipping: {}",
@@ -463,6 +478,18 @@ impl<A, B, Block, C, PR> Proposer<B, Block, C, A, PR>
 	}
 }
 
+/// Returns `true` if the runtime failed while executing the call, as opposed to the node failing
+/// to make the call, e.g. because of a database error.
+fn is_execution_error(error: &ApiError) -> bool {
+	match error {
+		ApiError::Application(error) => matches!(
+			error.downcast_ref::<sp_blockchain::Error>(),
+			Some(sp_blockchain::Error::Execution(_)),
+		),
+		_ => false,
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -841,4 +868,17 @@ mod tests {
 		// block size and thus, one less transaction should fit into the limit.
 		assert_eq!(block.extrinsics().len(), extrinsics_num - 2);
 	}
+
+	#[test]
+	fn only_runtime_execution_errors_are_execution_errors() {
+		let execution = sp_blockchain::Error::Execution(Box::new("Runtime panicked".to_string()));
+		assert!(is_execution_error(&ApiError::Application(Box::new(execution))));
+
+		let backend = sp_blockchain::Error::Backend("Database error".into());
+		assert!(!is_execution_error(&ApiError::Application(Box::new(backend))));
+		assert!(!is_execution_error(&ApiError::FailedToDecodeReturnValue {
+			function: "apply_extrinsic",
+			error: "Invalid data".into(),
+		}));
+	}
 }
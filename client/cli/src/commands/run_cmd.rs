@@ -27,7 +27,7 @@ use crate::params::TransactionPoolParams;
 use crate::CliConfiguration;
 use regex::Regex;
 use sc_service::{
-	config::{BasePath, PrometheusConfig, TransactionPoolOptions},
+	config::{BasePath, ExecutionLimits, PrometheusConfig, TransactionPoolOptions},
 	ChainSpec, Role,
 };
 use sc_telemetry::TelemetryEndpoints;
@@ -105,6 +105,23 @@ pub struct RunCmd {
 	#[structopt(long = "rpc-max-payload")]
 	pub rpc_max_payload: Option<usize>,
 
+	/// Set the maximum number of storage reads of a `state_call` RPC request.
+	///
+	/// Every key visited while iterating storage counts as a read. Not limited by default.
+	#[structopt(long = "rpc-max-call-reads", value_name = "COUNT")]
+	pub rpc_max_call_reads: Option<u32>,
+
+	/// Set the maximum size of the storage keys and values read by a `state_call` RPC request,
+	/// in megabytes. Not limited by default.
+	#[structopt(long = "rpc-max-call-read-size", value_name = "MiB")]
+	pub rpc_max_call_read_size: Option<u64>,
+
+	/// Set the maximum duration of a `state_call` RPC request, in milliseconds.
+	///
+	/// The duration is only checked when the call reads storage. Not limited by default.
+	#[structopt(long = "rpc-max-call-duration", value_name = "MS")]
+	pub rpc_max_call_duration: Option<u64>,
+
 	/// Listen to all Prometheus data source interfaces.
 	///
 	/// Default is local.
@@ -445,6 +462,14 @@ impl CliConfiguration for RunCmd {
 		Ok(self.rpc_max_payload)
 	}
 
+	fn rpc_call_limits(&self) -> Result<ExecutionLimits> {
+		Ok(ExecutionLimits {
+			max_reads: self.rpc_max_call_reads,
+			max_read_bytes: self.rpc_max_call_read_size.map(|size| size.saturating_mul(1024 * 1024)),
+			max_duration: self.rpc_max_call_duration.map(std::time::Duration::from_millis),
+		})
+	}
+
 	fn transaction_pool(&self) -> Result<TransactionPoolOptions> {
 		Ok(self.pool_config.transaction_pool())
 	}
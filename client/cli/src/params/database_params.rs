@@ -18,7 +18,7 @@
 
 use crate::arg_enums::Database;
 use structopt::StructOpt;
-use sc_service::TransactionStorageMode;
+use sc_service::{StorageMonitorParams, TransactionStorageMode};
 
 /// Parameters for block import.
 #[derive(Debug, StructOpt, Clone)]
@@ -45,6 +45,13 @@ pub struct DatabaseParams {
 	/// in the block body column.
 	#[structopt(long)]
 	pub storage_chain: bool,
+
+	/// Free space required on the disk holding the database.
+	///
+	/// The node stops once less space is left, and warns from eight times that. `0` disables the
+	/// check.
+	#[structopt(long = "db-storage-threshold", value_name = "MiB", default_value = "128")]
+	pub db_storage_threshold: u64,
 }
 
 impl DatabaseParams {
@@ -66,4 +73,14 @@ impl DatabaseParams {
 			TransactionStorageMode::BlockBody
 		}
 	}
+
+	/// Monitoring of the free space on the disk holding the database.
+	pub fn storage_monitor(&self) -> Option<StorageMonitorParams> {
+		match self.db_storage_threshold {
+			0 => None,
+			threshold => Some(
+				StorageMonitorParams::with_critical_threshold(threshold.saturating_mul(1024 * 1024))
+			),
+		}
+	}
 }
@@ -23,7 +23,7 @@ use crate::arg_enums::{
 };
 use crate::params::DatabaseParams;
 use crate::params::PruningParams;
-use sc_client_api::execution_extensions::ExecutionStrategies;
+use sc_client_api::execution_extensions::{ExecutionStrategies, ExecutionStrategyOverrides};
 use structopt::StructOpt;
 use std::path::PathBuf;
 
@@ -79,6 +79,14 @@ pub struct ImportParams {
 		default_value = "67108864"
 	)]
 	pub state_cache_size: usize,
+
+	/// Specify the size of the cache of trie nodes read from the database.
+	#[structopt(
+		long = "trie-node-cache-size",
+		value_name = "Bytes",
+		default_value = "33554432"
+	)]
+	pub trie_node_cache_size: usize,
 }
 
 impl ImportParams {
@@ -87,6 +95,11 @@ impl ImportParams {
 		self.state_cache_size
 	}
 
+	/// Specify the trie node cache size.
+	pub fn trie_node_cache_size(&self) -> usize {
+		self.trie_node_cache_size
+	}
+
 	/// Get the WASM execution method from the parameters
 	pub fn wasm_method(&self) -> sc_service::config::WasmExecutionMethod {
 		self.wasm_method.into()
@@ -125,6 +138,10 @@ impl ImportParams {
 			offchain_worker:
 				exec_all_or(exec.execution_offchain_worker, DEFAULT_EXECUTION_OFFCHAIN_WORKER),
 			other: exec_all_or(exec.execution_other, DEFAULT_EXECUTION_OTHER),
+			overrides: exec.execution_override.iter().fold(
+				ExecutionStrategyOverrides::default(),
+				|overrides, (method, strategy)| overrides.with_override(method.clone(), (*strategy).into()),
+			),
 		}
 	}
 }
@@ -194,4 +211,29 @@ pub struct ExecutionStrategiesParams {
 		]
 	)]
 	pub execution: Option<ExecutionStrategy>,
+
+	/// Override the means of execution of the runtime calls made by off-chain workers or
+	/// through RPC, by method name.
+	///
+	/// This flag can be passed multiple times, the first override matching a method is used.
+	/// Expected format is 'METHOD=STRATEGY', e.g. `--execution-override Metadata_metadata=Wasm`.
+	/// A method ending with `*` matches all the methods starting with the rest of it.
+	#[structopt(
+		long = "execution-override",
+		value_name = "METHOD=STRATEGY",
+		parse(try_from_str = parse_execution_override),
+		number_of_values = 1,
+	)]
+	pub execution_override: Vec<(String, ExecutionStrategy)>,
+}
+
+fn parse_execution_override(s: &str) -> std::result::Result<(String, ExecutionStrategy), String> {
+	let mut parts = s.splitn(2, '=');
+	match (parts.next(), parts.next()) {
+		(Some(method), Some(strategy)) if !method.is_empty() => {
+			let strategy = strategy.parse()?;
+			Ok((method.to_string(), strategy))
+		},
+		_ => Err(format!("Expected 'METHOD=STRATEGY', found '{}'", s)),
+	}
 }
@@ -28,7 +28,7 @@
 mod slots;
 mod aux_schema;
 
-pub use slots::SlotInfo;
+pub use slots::{SlotInfo, SlotStream, ManualSlots, ManualSlotsHandle};
 use slots::Slots;
 pub use aux_schema::{check_equivocation, MAX_SLOT_CAPACITY, PRUNING_BOUND};
 
@@ -492,8 +492,8 @@ impl_inherent_data_provider_ext_tuple!(T, S, A, B, C, D, E, F, G, H, I, J);
 pub async fn start_slot_worker<B, C, W, T, SO, CIDP, CAW, Proof>(
 	slot_duration: SlotDuration<T>,
 	client: C,
-	mut worker: W,
-	mut sync_oracle: SO,
+	worker: W,
+	sync_oracle: SO,
 	create_inherent_data_providers: CIDP,
 	can_author_with: CAW,
 )
@@ -509,12 +509,32 @@ where
 {
 	let SlotDuration(slot_duration) = slot_duration;
 
-	let mut slots = Slots::new(
+	let slots = Slots::new(
 		slot_duration.slot_duration(),
 		create_inherent_data_providers,
 		client,
 	);
 
+	start_slot_worker_with_slots(slots, worker, sync_oracle, can_author_with).await
+}
+
+/// Start a new slot worker that is driven by the given [`SlotStream`].
+///
+/// Same as [`start_slot_worker`], but allows to provide the slots from a different source than
+/// the wall clock, e.g. [`ManualSlots`] in tests. Returns once `slots` fails to yield a slot.
+pub async fn start_slot_worker_with_slots<B, S, W, SO, CAW, Proof>(
+	mut slots: S,
+	mut worker: W,
+	mut sync_oracle: SO,
+	can_author_with: CAW,
+)
+where
+	B: BlockT,
+	S: SlotStream<B>,
+	W: SlotWorker<B, Proof>,
+	SO: SyncOracle + Send,
+	CAW: CanAuthorWith<B> + Send,
+{
 	loop {
 		let slot_info = match slots.next_slot().await {
 			Ok(r) => r,
@@ -930,6 +950,37 @@ mod test {
 		}
 	}
 
+	struct RecordingWorker(std::sync::Arc<std::sync::Mutex<Vec<Slot>>>);
+
+	#[async_trait::async_trait]
+	impl SlotWorker<Block, ()> for RecordingWorker {
+		async fn on_slot(&mut self, slot_info: SlotInfo<Block>) -> Option<SlotResult<Block, ()>> {
+			self.0.lock().unwrap().push(slot_info.slot);
+			None
+		}
+	}
+
+	#[test]
+	fn manual_slots_drive_slot_worker() {
+		let (slots, handle) = ManualSlots::new();
+		let seen = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+
+		handle.advance_slot(slot(1));
+		handle.advance_slot(slot(2));
+		handle.advance_slot(slot(5));
+		// the worker stops once all triggered slots are processed and the handle is gone.
+		drop(handle);
+
+		futures::executor::block_on(start_slot_worker_with_slots::<_, _, _, _, _, ()>(
+			slots,
+			RecordingWorker(seen.clone()),
+			sp_consensus::NoNetwork,
+			sp_consensus::AlwaysCanAuthor,
+		));
+
+		assert_eq!(*seen.lock().unwrap(), vec![Slot::from(1), Slot::from(2), Slot::from(5)]);
+	}
+
 	#[test]
 	fn linear_slot_lenience() {
 		// if no slots are skipped there should be no lenience
@@ -19,6 +19,9 @@
 //! Utility stream for yielding slots in a loop.
 //!
 //! This is used instead of `futures_timer::Interval` because it was unreliable.
+//!
+//! [`ManualSlots`] provides an alternative source of slots that is independent of the wall clock,
+//! so that slot workers can be tested deterministically.
 
 use super::{Slot, InherentDataProviderExt};
 use sp_consensus::{Error, SelectChain};
@@ -26,6 +29,7 @@ use sp_inherents::{InherentData, CreateInherentDataProviders, InherentDataProvid
 use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
 
 use std::time::{Duration, Instant};
+use futures::{channel::mpsc, StreamExt};
 use futures_timer::Delay;
 
 /// Returns current duration since unix epoch.
@@ -92,6 +96,15 @@ impl<B: BlockT> SlotInfo<B> {
 	}
 }
 
+/// A source of slots that drives a slot worker.
+#[async_trait::async_trait]
+pub trait SlotStream<B: BlockT> {
+	/// Returns a future that fires when the next slot starts.
+	///
+	/// An error signals that no further slots will be produced.
+	async fn next_slot(&mut self) -> Result<SlotInfo<B>, Error>;
+}
+
 /// A stream that returns every time there is a new slot.
 pub(crate) struct Slots<Block, C, IDP> {
 	last_slot: Slot,
@@ -120,15 +133,15 @@ impl<Block, C, IDP> Slots<Block, C, IDP> {
 	}
 }
 
-impl<Block, C, IDP> Slots<Block, C, IDP>
+#[async_trait::async_trait]
+impl<Block, C, IDP> SlotStream<Block> for Slots<Block, C, IDP>
 where
 	Block: BlockT,
 	C: SelectChain<Block>,
-	IDP: CreateInherentDataProviders<Block, ()>,
-	IDP::InherentDataProviders: crate::InherentDataProviderExt,
+	IDP: CreateInherentDataProviders<Block, ()> + Send,
+	IDP::InherentDataProviders: crate::InherentDataProviderExt + Send,
 {
-	/// Returns a future that fires when the next slot starts.
-	pub async fn next_slot(&mut self) -> Result<SlotInfo<Block>, Error> {
+	async fn next_slot(&mut self) -> Result<SlotInfo<Block>, Error> {
 		loop {
 			self.inner_delay = match self.inner_delay.take() {
 				None => {
@@ -196,3 +209,46 @@ where
 		}
 	}
 }
+
+/// A slot stream that only yields the slots triggered through its [`ManualSlotsHandle`].
+///
+/// The stream ends once all handles are dropped and every triggered slot was yielded.
+pub struct ManualSlots<B: BlockT> {
+	receiver: mpsc::UnboundedReceiver<SlotInfo<B>>,
+}
+
+impl<B: BlockT> ManualSlots<B> {
+	/// Create a new `ManualSlots` stream together with the handle to drive it.
+	pub fn new() -> (Self, ManualSlotsHandle<B>) {
+		let (sender, receiver) = mpsc::unbounded();
+		(ManualSlots { receiver }, ManualSlotsHandle { sender })
+	}
+}
+
+#[async_trait::async_trait]
+impl<B: BlockT> SlotStream<B> for ManualSlots<B> {
+	async fn next_slot(&mut self) -> Result<SlotInfo<B>, Error> {
+		self.receiver.next().await.ok_or(Error::IoTerminated)
+	}
+}
+
+/// Handle to trigger the slots of a [`ManualSlots`] stream.
+pub struct ManualSlotsHandle<B: BlockT> {
+	sender: mpsc::UnboundedSender<SlotInfo<B>>,
+}
+
+impl<B: BlockT> Clone for ManualSlotsHandle<B> {
+	fn clone(&self) -> Self {
+		ManualSlotsHandle { sender: self.sender.clone() }
+	}
+}
+
+impl<B: BlockT> ManualSlotsHandle<B> {
+	/// Start the given slot.
+	///
+	/// Slots are yielded by the stream in the order they were started. Starting a slot after the
+	/// stream was dropped is a no-op.
+	pub fn advance_slot(&self, slot_info: SlotInfo<B>) {
+		let _ = self.sender.unbounded_send(slot_info);
+	}
+}
@@ -283,6 +283,11 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 		self.state.borrow().as_ref().ok_or_else(state_err)?.storage_hash(key)
 	}
 
+	fn storage_size(&self, key: &[u8]) -> Result<Option<u32>, Self::Error> {
+		self.add_read_key(None, key);
+		self.state.borrow().as_ref().ok_or_else(state_err)?.storage_size(key)
+	}
+
 	fn child_storage(
 		&self,
 		child_info: &ChildInfo,
@@ -292,6 +297,24 @@ impl<B: BlockT> StateBackend<HashFor<B>> for BenchmarkingState<B> {
 		self.state.borrow().as_ref().ok_or_else(state_err)?.child_storage(child_info, key)
 	}
 
+	fn child_storage_hash(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<B::Hash>, Self::Error> {
+		self.add_read_key(Some(child_info.storage_key()), key);
+		self.state.borrow().as_ref().ok_or_else(state_err)?.child_storage_hash(child_info, key)
+	}
+
+	fn child_storage_size(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<u32>, Self::Error> {
+		self.add_read_key(Some(child_info.storage_key()), key);
+		self.state.borrow().as_ref().ok_or_else(state_err)?.child_storage_size(child_info, key)
+	}
+
 	fn exists_storage(&self, key: &[u8]) -> Result<bool, Self::Error> {
 		self.add_read_key(None, key);
 		self.state.borrow().as_ref().ok_or_else(state_err)?.exists_storage(key)
@@ -0,0 +1,321 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Integrity check of the block database.
+//!
+//! The check walks the meta keys and the canonical chain, looking for the inconsistencies an
+//! interrupted write or a damaged disk can leave behind. The inconsistencies whose fix follows
+//! from the rest of the database can be repaired.
+
+use codec::{Decode, Encode};
+use sp_database::Transaction;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, One, Zero};
+use sp_blockchain::Result as ClientResult;
+use crate::{columns, Database, DbHash};
+use crate::utils::{self, meta_keys};
+
+/// How much of the database to check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IntegrityCheckLevel {
+	/// The meta keys and the blocks they point to.
+	Meta,
+	/// Also the headers and lookup entries of the whole canonical chain.
+	Canonical,
+	/// Also the state of the best block and of the last finalized block with state.
+	State,
+}
+
+/// An inconsistency of the block database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IntegrityIssue<N, H> {
+	/// The genesis hash is not stored.
+	MissingGenesisHash,
+	/// The genesis block does not have the stored genesis hash. \[hash\]
+	GenesisMismatch(H),
+	/// The best block pointer is missing or does not point to a stored header.
+	DanglingBestBlock,
+	/// The finalized block pointer is missing or does not point to a stored header.
+	DanglingFinalizedBlock,
+	/// The finalized block is above the best block or not in the canonical chain.
+	/// \[number, hash\]
+	FinalizedNotCanonical(N, H),
+	/// The header of a canonical block is missing. \[number, hash\]
+	MissingHeader(N, H),
+	/// A canonical block has no number lookup entry. \[number, hash\]
+	MissingNumberLookup(N, H),
+	/// The number lookup entry of a canonical block points to another block. \[number, hash\]
+	WrongNumberLookup(N, H),
+	/// A canonical block has no hash lookup entry. \[number, hash\]
+	MissingHashLookup(N, H),
+	/// The state of a block which should have one is missing. \[number, hash\]
+	MissingState(N, H),
+}
+
+/// The result of an integrity check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IntegrityReport<N, H> {
+	/// The number of canonical blocks whose header was checked.
+	pub checked_blocks: u64,
+	/// The inconsistencies found.
+	pub issues: Vec<IntegrityIssue<N, H>>,
+	/// The inconsistencies of `issues` which were repaired.
+	pub repaired: Vec<IntegrityIssue<N, H>>,
+}
+
+impl<N, H> IntegrityReport<N, H> {
+	/// Whether the database is consistent.
+	pub fn is_ok(&self) -> bool {
+		self.issues.is_empty()
+	}
+}
+
+impl<N, H> Default for IntegrityReport<N, H> {
+	fn default() -> Self {
+		IntegrityReport { checked_blocks: 0, issues: Vec::new(), repaired: Vec::new() }
+	}
+}
+
+impl<N: Clone, H: Clone> IntegrityReport<N, H> {
+	fn found(&mut self, issue: IntegrityIssue<N, H>, repaired: bool) {
+		if repaired {
+			self.repaired.push(issue.clone());
+		}
+		self.issues.push(issue);
+	}
+}
+
+type Report<Block> = IntegrityReport<NumberFor<Block>, <Block as BlockT>::Hash>;
+
+/// Check the integrity of `db` up to `level`.
+///
+/// `have_state` tells whether the state of a block is available. With `repair`, the returned
+/// transaction fixes the inconsistencies listed in [`IntegrityReport::repaired`], and is empty
+/// otherwise.
+pub(crate) fn verify<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	level: IntegrityCheckLevel,
+	repair: bool,
+	have_state: &dyn Fn(&Block::Hash, NumberFor<Block>) -> bool,
+) -> ClientResult<(Report<Block>, Transaction<DbHash>)> {
+	let mut report = Report::<Block>::default();
+	let mut transaction = Transaction::new();
+
+	let genesis = match canonical_block::<Block>(db, Zero::zero())? {
+		Some(genesis) => genesis,
+		// Nothing was imported yet.
+		None => return Ok((report, transaction)),
+	};
+	match utils::read_genesis_hash::<Block::Hash>(db)? {
+		Some(genesis_hash) if genesis_hash == genesis.hash() => {},
+		Some(_) => report.found(IntegrityIssue::GenesisMismatch(genesis.hash()), false),
+		None => {
+			report.found(IntegrityIssue::MissingGenesisHash, repair);
+			if repair {
+				transaction.set_from_vec(columns::META, meta_keys::GENESIS_HASH, genesis.hash().encode());
+			}
+		},
+	}
+
+	let finalized = match read_pointer::<Block>(db, meta_keys::FINALIZED_BLOCK) {
+		Some(finalized) => finalized,
+		None => {
+			// Fall back to the highest canonical block below the recorded number.
+			let fallback = match pointed_number::<Block>(db, meta_keys::FINALIZED_BLOCK) {
+				Some(number) => highest_canonical_block::<Block>(db, number)?,
+				None => None,
+			};
+			let repaired = repair && fallback.is_some();
+			report.found(IntegrityIssue::DanglingFinalizedBlock, repaired);
+			let fallback = fallback.unwrap_or_else(|| genesis.clone());
+			if repaired {
+				set_pointer::<Block>(&mut transaction, meta_keys::FINALIZED_BLOCK, &fallback)?;
+			}
+			fallback
+		},
+	};
+
+	let best = match read_pointer::<Block>(db, meta_keys::BEST_BLOCK) {
+		Some(best) => best,
+		None => {
+			// Fall back to the highest canonical block below the recorded number, or to the end
+			// of the canonical chain going on from the finalized block.
+			let best = match pointed_number::<Block>(db, meta_keys::BEST_BLOCK) {
+				Some(number) => highest_canonical_block::<Block>(db, number)?,
+				None => None,
+			};
+			let best = match best {
+				Some(best) => best,
+				None => canonical_head::<Block>(db, finalized.clone())?,
+			};
+			report.found(IntegrityIssue::DanglingBestBlock, repair);
+			if repair {
+				set_pointer::<Block>(&mut transaction, meta_keys::BEST_BLOCK, &best)?;
+			}
+			best
+		},
+	};
+
+	if level == IntegrityCheckLevel::Meta {
+		let canonical = canonical_block::<Block>(db, *finalized.number())?;
+		if *finalized.number() > *best.number()
+			|| canonical.map(|header| header.hash()) != Some(finalized.hash())
+		{
+			report.found(
+				IntegrityIssue::FinalizedNotCanonical(*finalized.number(), finalized.hash()),
+				false,
+			);
+		}
+		return Ok((report, transaction))
+	}
+
+	if *finalized.number() > *best.number() {
+		report.found(
+			IntegrityIssue::FinalizedNotCanonical(*finalized.number(), finalized.hash()),
+			false,
+		);
+	}
+
+	let mut number = *best.number();
+	let mut hash = best.hash();
+	loop {
+		let lookup_key = utils::number_and_hash_to_lookup_key(number, hash)?;
+		let header = match read_header::<Block>(db, &lookup_key) {
+			Some(header) => header,
+			None => {
+				report.found(IntegrityIssue::MissingHeader(number, hash), false);
+				break
+			},
+		};
+		report.checked_blocks += 1;
+
+		match db.get(columns::KEY_LOOKUP, &utils::number_index_key(number)?) {
+			Some(key) if key == lookup_key => {},
+			found => {
+				let issue = match found {
+					Some(_) => IntegrityIssue::WrongNumberLookup(number, hash),
+					None => IntegrityIssue::MissingNumberLookup(number, hash),
+				};
+				report.found(issue, repair);
+				if repair {
+					utils::insert_number_to_key_mapping(
+						&mut transaction,
+						columns::KEY_LOOKUP,
+						number,
+						hash,
+					)?;
+				}
+			},
+		}
+
+		if db.get(columns::KEY_LOOKUP, hash.as_ref()).as_ref() != Some(&lookup_key) {
+			report.found(IntegrityIssue::MissingHashLookup(number, hash), repair);
+			if repair {
+				utils::insert_hash_to_key_mapping(&mut transaction, columns::KEY_LOOKUP, number, hash)?;
+			}
+		}
+
+		if number == *finalized.number() && hash != finalized.hash() {
+			report.found(IntegrityIssue::FinalizedNotCanonical(number, finalized.hash()), false);
+		}
+
+		if number.is_zero() {
+			break
+		}
+		number -= One::one();
+		hash = *header.parent_hash();
+	}
+
+	if level == IntegrityCheckLevel::State {
+		let mut with_state = vec![(*best.number(), best.hash())];
+		if let Some(finalized_state) = read_pointer::<Block>(db, meta_keys::FINALIZED_STATE) {
+			with_state.push((*finalized_state.number(), finalized_state.hash()));
+		}
+		for (number, hash) in with_state {
+			if !have_state(&hash, number) {
+				report.found(IntegrityIssue::MissingState(number, hash), false);
+			}
+		}
+	}
+
+	Ok((report, transaction))
+}
+
+fn read_header<Block: BlockT>(db: &dyn Database<DbHash>, lookup_key: &[u8]) -> Option<Block::Header> {
+	db.get(columns::HEADER, lookup_key)
+		.and_then(|header| Block::Header::decode(&mut &header[..]).ok())
+}
+
+/// The header of the block a meta key points to.
+fn read_pointer<Block: BlockT>(db: &dyn Database<DbHash>, key: &[u8]) -> Option<Block::Header> {
+	db.get(columns::META, key).and_then(|lookup_key| read_header::<Block>(db, &lookup_key))
+}
+
+/// The number of the block a meta key points to, read from the lookup key itself.
+fn pointed_number<Block: BlockT>(db: &dyn Database<DbHash>, key: &[u8]) -> Option<NumberFor<Block>> {
+	db.get(columns::META, key)
+		.and_then(|lookup_key| utils::lookup_key_to_number(&lookup_key).ok())
+}
+
+fn set_pointer<Block: BlockT>(
+	transaction: &mut Transaction<DbHash>,
+	key: &[u8],
+	header: &Block::Header,
+) -> ClientResult<()> {
+	let lookup_key = utils::number_and_hash_to_lookup_key(*header.number(), header.hash())?;
+	transaction.set_from_vec(columns::META, key, lookup_key);
+	Ok(())
+}
+
+/// The header of the canonical block at `number`, if both the lookup entry and header exist.
+fn canonical_block<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	number: NumberFor<Block>,
+) -> ClientResult<Option<Block::Header>> {
+	Ok(db.get(columns::KEY_LOOKUP, &utils::number_index_key(number)?)
+		.and_then(|lookup_key| read_header::<Block>(db, &lookup_key)))
+}
+
+/// The highest canonical block at or below `number`.
+fn highest_canonical_block<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	mut number: NumberFor<Block>,
+) -> ClientResult<Option<Block::Header>> {
+	loop {
+		if let Some(header) = canonical_block::<Block>(db, number)? {
+			return Ok(Some(header))
+		}
+		if number.is_zero() {
+			return Ok(None)
+		}
+		number -= One::one();
+	}
+}
+
+/// The last block of the canonical chain going on from `head`.
+fn canonical_head<Block: BlockT>(
+	db: &dyn Database<DbHash>,
+	mut head: Block::Header,
+) -> ClientResult<Block::Header> {
+	while let Some(next) = canonical_block::<Block>(db, *head.number() + One::one())? {
+		if *next.parent_hash() != head.hash() {
+			break
+		}
+		head = next;
+	}
+	Ok(head)
+}
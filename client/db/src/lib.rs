@@ -36,6 +36,7 @@ pub mod bench;
 
 mod children;
 mod cache;
+mod integrity;
 mod changes_tries_storage;
 mod storage_cache;
 #[cfg(any(feature = "with-kvdb-rocksdb", test))]
@@ -54,7 +55,7 @@ use linked_hash_map::LinkedHashMap;
 use log::{trace, debug, warn};
 
 use sc_client_api::{
-	UsageInfo, MemoryInfo, IoInfo, MemorySize,
+	UsageInfo, MemoryInfo, IoInfo, MemorySize, AuxPruning, AuxPruningCallback, namespaced_key,
 	backend::{NewBlockState, PrunableStateChangesTrieStorage, ProvideChtRoots},
 	leaves::{LeafSet, FinalizationDisplaced}, cht,
 	utils::is_descendent_of,
@@ -65,7 +66,7 @@ use sp_blockchain::{
 };
 use codec::{Decode, Encode};
 use hash_db::Prefix;
-use sp_trie::{MemoryDB, PrefixedMemoryDB, prefixed_key};
+use sp_trie::{MemoryDB, PrefixedMemoryDB, TrieNodeCache, TrieNodeCacheStats, prefixed_key};
 use sp_database::Transaction;
 use sp_core::ChangesTrieConfiguration;
 use sp_core::offchain::OffchainOverlayedChange;
@@ -90,6 +91,7 @@ use crate::stats::StateUsageStats;
 // Re-export the Database trait so that one can pass an implementation of it.
 pub use sp_database::Database;
 pub use sc_state_db::PruningMode;
+pub use integrity::{IntegrityCheckLevel, IntegrityIssue, IntegrityReport};
 
 #[cfg(any(feature = "with-kvdb-rocksdb", test))]
 pub use bench::BenchmarkingState;
@@ -100,6 +102,9 @@ const CACHE_HEADERS: usize = 8;
 /// Default value for storage cache child ratio.
 const DEFAULT_CHILD_RATIO: (usize, usize) = (1, 10);
 
+/// Default size of the cache of trie nodes shared by all the states, in bytes.
+pub const DEFAULT_TRIE_NODE_CACHE_SIZE: usize = 32 * 1024 * 1024;
+
 /// DB-backed patricia trie state, transaction type is an overlay of changes to commit.
 pub type DbState<B> = sp_state_machine::TrieBackend<
 	Arc<dyn sp_state_machine::Storage<HashFor<B>>>, HashFor<B>
@@ -165,6 +170,10 @@ impl<B: BlockT> StateBackend<HashFor<B>> for RefTrackingState<B> {
 		self.state.storage_hash(key)
 	}
 
+	fn storage_size(&self, key: &[u8]) -> Result<Option<u32>, Self::Error> {
+		self.state.storage_size(key)
+	}
+
 	fn child_storage(
 		&self,
 		child_info: &ChildInfo,
@@ -173,6 +182,22 @@ impl<B: BlockT> StateBackend<HashFor<B>> for RefTrackingState<B> {
 		self.state.child_storage(child_info, key)
 	}
 
+	fn child_storage_hash(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<B::Hash>, Self::Error> {
+		self.state.child_storage_hash(child_info, key)
+	}
+
+	fn child_storage_size(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<u32>, Self::Error> {
+		self.state.child_storage_size(child_info, key)
+	}
+
 	fn exists_storage(&self, key: &[u8]) -> Result<bool, Self::Error> {
 		self.state.exists_storage(key)
 	}
@@ -249,6 +274,27 @@ impl<B: BlockT> StateBackend<HashFor<B>> for RefTrackingState<B> {
 		self.state.child_storage_root(child_info, delta)
 	}
 
+	fn child_storage_roots<'a>(
+		&self,
+		child_deltas: impl Iterator<Item = (
+			&'a ChildInfo,
+			impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		)>,
+	) -> Vec<(B::Hash, bool, Self::Transaction)> where B::Hash: Ord {
+		self.state.child_storage_roots(child_deltas)
+	}
+
+	fn full_storage_root<'a>(
+		&self,
+		delta: impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		child_deltas: impl Iterator<Item = (
+			&'a ChildInfo,
+			impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		)>,
+	) -> (B::Hash, Self::Transaction) where B::Hash: Ord + Encode {
+		self.state.full_storage_root(delta, child_deltas)
+	}
+
 	fn pairs(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
 		self.state.pairs()
 	}
@@ -286,6 +332,8 @@ pub struct DatabaseSettings {
 	pub state_cache_size: usize,
 	/// Ratio of cache size dedicated to child tries.
 	pub state_cache_child_ratio: Option<(usize, usize)>,
+	/// Size of the cache of trie nodes shared by all the states, in bytes.
+	pub trie_node_cache_size: usize,
 	/// State pruning mode.
 	pub state_pruning: PruningMode,
 	/// Where to find the database.
@@ -380,6 +428,38 @@ pub(crate) mod columns {
 	pub const TRANSACTION: u32 = 11;
 }
 
+/// Information about a block body that is stored in the `META` column when the body is imported,
+/// so it can be served without reading and decoding the body.
+#[derive(Encode, Decode)]
+struct BodyInfo<Hash> {
+	/// Number of extrinsics in the body.
+	extrinsics_count: u32,
+	/// Root of the extrinsics trie built from the body when it was imported.
+	extrinsics_root: Hash,
+}
+
+impl<Hash> BodyInfo<Hash> {
+	fn new<Block: BlockT<Hash = Hash>>(body: &[Block::Extrinsic]) -> Self {
+		BodyInfo {
+			extrinsics_count: body.len() as u32,
+			extrinsics_root: extrinsics_root::<Block>(body),
+		}
+	}
+}
+
+/// Key of the [`BodyInfo`] of the block with the given lookup key in the `META` column.
+fn body_info_key(lookup_key: &[u8]) -> Vec<u8> {
+	let mut key = meta_keys::BODY_INFO_PREFIX.to_vec();
+	key.extend_from_slice(lookup_key);
+	key
+}
+
+fn extrinsics_root<Block: BlockT>(body: &[Block::Extrinsic]) -> Block::Hash {
+	<HashFor<Block> as sp_runtime::traits::Hash>::ordered_trie_root(
+		body.iter().map(Encode::encode).collect(),
+	)
+}
+
 struct PendingBlock<Block: BlockT> {
 	header: Block::Header,
 	justifications: Option<Justifications>,
@@ -469,6 +549,23 @@ impl<Block: BlockT> BlockchainDb<Block> {
 		}
 	}
 
+	/// Read the [`BodyInfo`] of the given block. Blocks imported before it was stored have none.
+	fn body_info(&self, id: BlockId<Block>) -> ClientResult<Option<BodyInfo<Block::Hash>>> {
+		let lookup_key = match utils::block_id_to_lookup_key(&*self.db, columns::KEY_LOOKUP, id)? {
+			Some(lookup_key) => lookup_key,
+			None => return Ok(None),
+		};
+		match self.db.get(columns::META, &body_info_key(&lookup_key)) {
+			Some(info) => match Decode::decode(&mut &info[..]) {
+				Ok(info) => Ok(Some(info)),
+				Err(err) => Err(sp_blockchain::Error::Backend(
+					format!("Error decoding body info: {}", err)
+				)),
+			},
+			None => Ok(None),
+		}
+	}
+
 	// Get block changes trie root, if available.
 	fn changes_trie_root(&self, block: BlockId<Block>) -> ClientResult<Option<Block::Hash>> {
 		self.header(block)
@@ -578,6 +675,20 @@ impl<Block: BlockT> sc_client_api::blockchain::Backend<Block> for BlockchainDb<B
 		}
 	}
 
+	fn extrinsics_count(&self, id: BlockId<Block>) -> ClientResult<Option<u32>> {
+		match self.body_info(id)? {
+			Some(info) => Ok(Some(info.extrinsics_count)),
+			None => Ok(self.body(id)?.map(|body| body.len() as u32)),
+		}
+	}
+
+	fn extrinsics_root(&self, id: BlockId<Block>) -> ClientResult<Option<Block::Hash>> {
+		match self.body_info(id)? {
+			Some(info) => Ok(Some(info.extrinsics_root)),
+			None => Ok(self.body(id)?.map(|body| extrinsics_root::<Block>(&body))),
+		}
+	}
+
 	fn justifications(&self, id: BlockId<Block>) -> ClientResult<Option<Justifications>> {
 		match read_db(&*self.db, columns::KEY_LOOKUP, columns::JUSTIFICATIONS, id)? {
 			Some(justifications) => match Decode::decode(&mut &justifications[..]) {
@@ -602,6 +713,14 @@ impl<Block: BlockT> sc_client_api::blockchain::Backend<Block> for BlockchainDb<B
 		Ok(self.leaves.read().hashes())
 	}
 
+	fn displaced_leaves_after_finalizing(
+		&self,
+		block_number: NumberFor<Block>,
+	) -> ClientResult<Vec<Block::Hash>> {
+		let displaced = self.leaves.read().displaced_by_finalize_height(block_number);
+		Ok(displaced.leaves().into_iter().cloned().collect())
+	}
+
 	fn children(&self, parent_hash: Block::Hash) -> ClientResult<Vec<Block::Hash>> {
 		children::read_children(&*self.db, columns::META, meta_keys::CHILDREN_PREFIX, parent_hash)
 	}
@@ -927,6 +1046,50 @@ struct StorageDb<Block: BlockT> {
 	pub db: Arc<dyn Database<DbHash>>,
 	pub state_db: StateDb<Block::Hash, Vec<u8>>,
 	prefix_keys: bool,
+	/// Cache of the nodes read from the database. Nodes of non-canonical blocks are served by the
+	/// state db overlay and never cached, so discarded forks do not need to be invalidated.
+	node_cache: TrieNodeCache,
+	/// Nodes pruned by canonicalizations that are not yet committed to the database.
+	pruned_nodes: Mutex<Vec<Vec<u8>>>,
+}
+
+impl<Block: BlockT> StorageDb<Block> {
+	/// Canonicalize the given block in the state db, noting the pruned nodes.
+	///
+	/// The pruned nodes are removed from the node cache by [`Self::apply_pending`], once the
+	/// deletions are committed to the database.
+	fn canonicalize_block(
+		&self,
+		hash: &Block::Hash,
+	) -> Result<sc_state_db::CommitSet<Vec<u8>>, sc_state_db::Error<io::Error>> {
+		let commit = self.state_db.canonicalize_block(hash)?;
+		self.pruned_nodes.lock().extend(commit.data.deleted.iter().cloned());
+		Ok(commit)
+	}
+
+	/// Apply the pending state db changes and remove the pruned nodes from the node cache.
+	///
+	/// Must be called after the changes are committed to the database.
+	fn apply_pending(&self) {
+		self.state_db.apply_pending();
+		self.remove_pruned_nodes();
+	}
+
+	/// Remove the nodes pruned by canonicalizations from the node cache.
+	///
+	/// Must be called after the canonicalizations are committed to the database.
+	fn remove_pruned_nodes(&self) {
+		let pruned_nodes = std::mem::take(&mut *self.pruned_nodes.lock());
+		if !pruned_nodes.is_empty() {
+			self.node_cache.remove(pruned_nodes.iter().map(|key| &key[..]));
+		}
+	}
+
+	/// Revert the pending state db changes.
+	fn revert_pending(&self) {
+		self.state_db.revert_pending();
+		self.pruned_nodes.lock().clear();
+	}
 }
 
 impl<Block: BlockT> sp_state_machine::Storage<HashFor<Block>> for StorageDb<Block> {
@@ -946,7 +1109,16 @@ impl<Block: BlockT> sc_state_db::NodeDb for StorageDb<Block> {
 	type Key = [u8];
 
 	fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Self::Error> {
-		Ok(self.db.get(columns::STATE, key))
+		if let Some(node) = self.node_cache.get(key) {
+			return Ok(Some(node))
+		}
+
+		let generation = self.node_cache.generation();
+		let node = self.db.get(columns::STATE, key);
+		if let Some(node) = &node {
+			self.node_cache.insert(key.to_vec(), node.clone(), generation);
+		}
+		Ok(node)
 	}
 }
 
@@ -1042,9 +1214,10 @@ pub struct Backend<Block: BlockT> {
 	is_archive: bool,
 	keep_blocks: KeepBlocks,
 	transaction_storage: TransactionStorageMode,
-	io_stats: FrozenForDuration<(kvdb::IoStats, StateUsageInfo)>,
+	io_stats: FrozenForDuration<(kvdb::IoStats, StateUsageInfo, TrieNodeCacheStats)>,
 	state_usage: Arc<StateUsageStats>,
 	genesis_state: RwLock<Option<Arc<DbGenesisStorage<Block>>>>,
+	aux_pruning: RwLock<Vec<(Vec<u8>, AuxPruningCallback<Block>)>>,
 }
 
 impl<Block: BlockT> Backend<Block> {
@@ -1078,6 +1251,7 @@ impl<Block: BlockT> Backend<Block> {
 		let db_setting = DatabaseSettings {
 			state_cache_size: 16777216,
 			state_cache_child_ratio: Some((50, 100)),
+			trie_node_cache_size: DEFAULT_TRIE_NODE_CACHE_SIZE,
 			state_pruning: PruningMode::keep_blocks(keep_blocks),
 			source: DatabaseSettingsSrc::Custom(db),
 			keep_blocks: KeepBlocks::Some(keep_blocks),
@@ -1105,6 +1279,8 @@ impl<Block: BlockT> Backend<Block> {
 			db: db.clone(),
 			state_db,
 			prefix_keys: !config.source.supports_ref_counting(),
+			node_cache: TrieNodeCache::new(config.trie_node_cache_size),
+			pruned_nodes: Default::default(),
 		};
 		let offchain_storage = offchain::LocalStorage::new(db.clone());
 		let changes_tries_storage = DbChangesTrieStorage::new(
@@ -1140,6 +1316,7 @@ impl<Block: BlockT> Backend<Block> {
 			keep_blocks: config.keep_blocks.clone(),
 			transaction_storage: config.transaction_storage.clone(),
 			genesis_state: RwLock::new(None),
+			aux_pruning: RwLock::new(Vec::new()),
 		};
 
 		// Older DB versions have no last state key. Check if the state is available and set it.
@@ -1156,9 +1333,77 @@ impl<Block: BlockT> Backend<Block> {
 				with_state: true,
 			});
 		}
+
+		backend.repair_canonicalization_gap()?;
 		Ok(backend)
 	}
 
+	/// Canonicalize the finalized blocks that were left out of the state db.
+	///
+	/// The last finalized block with state and the last canonicalized block may be out of sync
+	/// after an interrupted finalization. The blocks in between are canonicalized in order,
+	/// stopping at the first block that can't be canonicalized.
+	fn repair_canonicalization_gap(&self) -> ClientResult<()> {
+		let finalized_state = match self.blockchain.meta.read().finalized_state {
+			Some((_, number)) => number.saturated_into::<u64>(),
+			None => return Ok(()),
+		};
+		let best_canonical = match self.storage.state_db.best_canonical() {
+			Some(best_canonical) if best_canonical < finalized_state => best_canonical,
+			_ => return Ok(()),
+		};
+
+		// Blocks imported without state, e.g. by a fast or warp synced node, can't be
+		// canonicalized and are not a gap to repair.
+		let first = (best_canonical + 1).saturated_into::<NumberFor<Block>>();
+		match sc_client_api::blockchain::HeaderBackend::hash(&self.blockchain, first)? {
+			Some(hash) if sc_client_api::Backend::have_state_at(self, &hash, first) => {},
+			_ => return Ok(()),
+		}
+
+		warn!(
+			target: "db",
+			"Last canonicalized block #{} is behind the last finalized block #{}, repairing",
+			best_canonical,
+			finalized_state,
+		);
+
+		match self.try_repair_canonicalization_gap(best_canonical, finalized_state) {
+			Ok(()) => {
+				self.storage.apply_pending();
+				Ok(())
+			},
+			e @ Err(_) => {
+				self.storage.revert_pending();
+				e
+			},
+		}
+	}
+
+	fn try_repair_canonicalization_gap(&self, best_canonical: u64, finalized: u64) -> ClientResult<()> {
+		let mut transaction = Transaction::new();
+		for number in best_canonical + 1..=finalized {
+			let number = number.saturated_into::<NumberFor<Block>>();
+			let hash = match sc_client_api::blockchain::HeaderBackend::hash(&self.blockchain, number)? {
+				Some(hash) => hash,
+				None => break,
+			};
+			if !sc_client_api::Backend::have_state_at(self, &hash, number) {
+				break
+			}
+
+			match self.storage.canonicalize_block(&hash) {
+				Ok(commit) => apply_state_commit(&mut transaction, commit),
+				Err(e) => {
+					warn!(target: "db", "Failed to canonicalize block #{} ({:?}): {:?}", number, hash, e);
+					break
+				},
+			}
+		}
+		self.storage.db.commit(transaction)?;
+		Ok(())
+	}
+
 	/// Handle setting head within a transaction. `route_to` should be the last
 	/// block that existed in the database. `best_to` should be the best block
 	/// to be set.
@@ -1306,15 +1551,19 @@ impl<Block: BlockT> Backend<Block> {
 				sc_client_api::blockchain::HeaderBackend::hash(
 					&self.blockchain,
 					new_canonical.saturated_into(),
-				)?.expect("existence of block with number `new_canonical` \
-					implies existence of blocks with all numbers before it; qed")
+				)?.ok_or_else(|| sp_blockchain::Error::Backend(format!(
+					"Can't canonicalize missing block number #{} when importing {:?} (#{})",
+					new_canonical,
+					hash,
+					number,
+				)))?
 			};
 			if !sc_client_api::Backend::have_state_at(self, &hash, new_canonical.saturated_into()) {
 				return Ok(())
 			}
 
 			trace!(target: "db", "Canonicalize block #{} ({:?})", new_canonical, hash);
-			let commit = self.storage.state_db.canonicalize_block(&hash)
+			let commit = self.storage.canonicalize_block(&hash)
 				.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
 			apply_state_commit(transaction, commit);
 		}
@@ -1379,6 +1628,11 @@ impl<Block: BlockT> Backend<Block> {
 
 			transaction.set_from_vec(columns::HEADER, &lookup_key, pending_block.header.encode());
 			if let Some(body) = pending_block.body {
+				transaction.set_from_vec(
+					columns::META,
+					&body_info_key(&lookup_key),
+					BodyInfo::new::<Block>(&body).encode(),
+				);
 				match self.transaction_storage {
 					TransactionStorageMode::BlockBody => {
 						transaction.set_from_vec(columns::BODY, &lookup_key, body.encode());
@@ -1472,7 +1726,7 @@ impl<Block: BlockT> Backend<Block> {
 				apply_state_commit(&mut transaction, commit);
 				if number <= last_finalized_num {
 					// Canonicalize in the db when re-importing existing blocks with state.
-					let commit = self.storage.state_db.canonicalize_block(&hash)
+					let commit = self.storage.canonicalize_block(&hash)
 						.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
 					apply_state_commit(&mut transaction, commit);
 					meta_updates.push(MetaUpdate {
@@ -1653,6 +1907,44 @@ impl<Block: BlockT> Backend<Block> {
 		Ok(())
 	}
 
+	/// Check the consistency of the block database, e.g. after an unclean shutdown.
+	///
+	/// See [`IntegrityCheckLevel`] for what is checked. With `repair`, the inconsistencies whose
+	/// fix follows from the rest of the database are repaired, and listed in
+	/// [`IntegrityReport::repaired`]. Block import is blocked during the check.
+	pub fn verify_integrity(
+		&self,
+		level: IntegrityCheckLevel,
+		repair: bool,
+	) -> ClientResult<IntegrityReport<NumberFor<Block>, Block::Hash>> {
+		let _import_lock = self.import_lock.write();
+		let have_state = |hash: &Block::Hash, number| {
+			sc_client_api::Backend::have_state_at(self, hash, number)
+		};
+		let (report, transaction) =
+			integrity::verify::<Block>(&*self.storage.db, level, repair, &have_state)?;
+
+		if !report.repaired.is_empty() {
+			self.storage.db.commit(transaction)?;
+			*self.blockchain.meta.write() = read_meta::<Block>(&*self.storage.db, columns::HEADER)?;
+		}
+		Ok(report)
+	}
+
+	fn prune_aux(
+		&self,
+		transaction: &mut Transaction<DbHash>,
+		f_hash: &Block::Hash,
+		f_num: NumberFor<Block>,
+	) {
+		for (namespace, callback) in self.aux_pruning.read().iter() {
+			let get = |key: &[u8]| self.storage.db.get(columns::AUX, &namespaced_key(namespace, key));
+			for key in callback(f_hash, f_num, &get) {
+				transaction.remove(columns::AUX, &namespaced_key(namespace, &key));
+			}
+		}
+	}
+
 	// write stuff to a transaction after a new block is finalized.
 	// this canonicalizes finalized blocks. Fails if called with a block which
 	// was not a child of the last finalized block.
@@ -1677,7 +1969,7 @@ impl<Block: BlockT> Backend<Block> {
 		if sc_client_api::Backend::have_state_at(self, &f_hash, f_num) &&
 			self.storage.state_db.best_canonical().map(|c| f_num.saturated_into::<u64>() > c).unwrap_or(true)
 		{
-			let commit = self.storage.state_db.canonicalize_block(&f_hash)
+			let commit = self.storage.canonicalize_block(&f_hash)
 				.map_err(|e: sc_state_db::Error<io::Error>| sp_blockchain::Error::from_state_db(e))?;
 			apply_state_commit(transaction, commit);
 		}
@@ -1696,6 +1988,7 @@ impl<Block: BlockT> Backend<Block> {
 
 		let new_displaced = self.blockchain.leaves.write().finalize_height(f_num);
 		self.prune_blocks(transaction, f_num, &new_displaced)?;
+		self.prune_aux(transaction, &f_hash, f_num);
 		match displaced {
 			x @ &mut None => *x = Some(new_displaced),
 			&mut Some(ref mut displaced) => displaced.merge(new_displaced),
@@ -1757,6 +2050,13 @@ impl<Block: BlockT> Backend<Block> {
 					columns::BODY,
 					id,
 				)?;
+				if let Some(lookup_key) = utils::block_id_to_lookup_key(
+					&*self.storage.db,
+					columns::KEY_LOOKUP,
+					id,
+				)? {
+					transaction.remove(columns::META, &body_info_key(&lookup_key));
+				}
 				match self.transaction_storage {
 					TransactionStorageMode::BlockBody => {},
 					TransactionStorageMode::StorageChain => {
@@ -1877,6 +2177,14 @@ fn apply_index_ops<Block: BlockT>(
 	extrinsic_headers.encode()
 }
 
+impl<Block: BlockT> AuxPruning<Block> for Backend<Block> {
+	fn register_aux_pruning(&self, namespace: &[u8], callback: AuxPruningCallback<Block>) {
+		let mut aux_pruning = self.aux_pruning.write();
+		aux_pruning.retain(|(n, _)| n != namespace);
+		aux_pruning.push((namespace.to_vec(), callback));
+	}
+}
+
 impl<Block> sc_client_api::backend::AuxStore for Backend<Block> where Block: BlockT {
 	fn insert_aux<
 		'a,
@@ -1954,11 +2262,11 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 
 		match self.try_commit_operation(operation) {
 			Ok(_) => {
-				self.storage.state_db.apply_pending();
+				self.storage.apply_pending();
 				Ok(())
 			},
 			e @ Err(_) => {
-				self.storage.state_db.revert_pending();
+				self.storage.revert_pending();
 				e
 			}
 		}
@@ -1985,6 +2293,7 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 			&mut displaced,
 		)?;
 		self.storage.db.commit(transaction)?;
+		self.storage.remove_pruned_nodes();
 		self.blockchain.update_meta(m);
 		self.changes_tries_storage.post_commit(changes_trie_cache_ops);
 		Ok(())
@@ -2043,22 +2352,25 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 	}
 
 	fn usage_info(&self) -> Option<UsageInfo> {
-		let (io_stats, state_stats) = self.io_stats.take_or_else(||
+		let (io_stats, state_stats, node_cache_stats) = self.io_stats.take_or_else(||
 			(
 				// TODO: implement DB stats and cache size retrieval
 				kvdb::IoStats::empty(),
 				self.state_usage.take(),
+				self.storage.node_cache.take_stats(),
 			)
 		);
 		let database_cache = MemorySize::from_bytes(0);
 		let state_cache = MemorySize::from_bytes(
 			(*&self.shared_cache).read().used_storage_cache_size(),
 		);
+		let trie_node_cache = MemorySize::from_bytes(self.storage.node_cache.size());
 		let state_db = self.storage.state_db.memory_info();
 
 		Some(UsageInfo {
 			memory: MemoryInfo {
 				state_cache,
+				trie_node_cache,
 				database_cache,
 				state_db,
 			},
@@ -2074,6 +2386,8 @@ impl<Block: BlockT> sc_client_api::backend::Backend<Block> for Backend<Block> {
 				state_writes_cache: state_stats.overlay_writes.ops,
 				state_reads_cache: state_stats.cache_reads.ops,
 				state_writes_nodes: state_stats.nodes_writes.ops,
+				trie_node_cache_hits: node_cache_stats.hits,
+				trie_node_cache_misses: node_cache_stats.misses,
 			},
 		})
 	}
@@ -2361,7 +2675,7 @@ pub(crate) mod tests {
 	use super::*;
 	use crate::columns;
 	use sp_core::H256;
-	use sc_client_api::backend::{Backend as BTrait, BlockImportOperation as Op};
+	use sc_client_api::backend::{AuxStore, Backend as BTrait, BlockImportOperation as Op};
 	use sc_client_api::blockchain::Backend as BLBTrait;
 	use sp_runtime::ConsensusEngineId;
 	use sp_runtime::testing::{Header, Block as RawBlock, ExtrinsicWrapper};
@@ -2490,6 +2804,7 @@ pub(crate) mod tests {
 		let backend = Backend::<Block>::new(DatabaseSettings {
 			state_cache_size: 16777216,
 			state_cache_child_ratio: Some((50, 100)),
+			trie_node_cache_size: DEFAULT_TRIE_NODE_CACHE_SIZE,
 			state_pruning: PruningMode::keep_blocks(1),
 			source: DatabaseSettingsSrc::Custom(backing),
 			keep_blocks: KeepBlocks::All,
@@ -2501,6 +2816,44 @@ pub(crate) mod tests {
 		}
 	}
 
+	#[test]
+	fn canonicalization_gap_is_repaired_on_reopen() {
+		let backing = {
+			let backend = Backend::<Block>::new_test(10, 10);
+			let mut hash = Default::default();
+			for number in 0..4 {
+				hash = insert_block(&backend, number, hash, None, Default::default(), vec![], None);
+			}
+			assert_eq!(backend.storage.state_db.best_canonical(), Some(0));
+
+			// Finalization of block 3 was interrupted after the meta was written.
+			let lookup_key = utils::number_and_hash_to_lookup_key(3, hash).unwrap();
+			let mut transaction = Transaction::new();
+			transaction.set_from_vec(columns::META, meta_keys::FINALIZED_BLOCK, lookup_key.clone());
+			transaction.set_from_vec(columns::META, meta_keys::FINALIZED_STATE, lookup_key);
+			backend.storage.db.commit(transaction).unwrap();
+			backend.storage.db.clone()
+		};
+
+		let backend = Backend::<Block>::new(DatabaseSettings {
+			state_cache_size: 16777216,
+			state_cache_child_ratio: Some((50, 100)),
+			trie_node_cache_size: DEFAULT_TRIE_NODE_CACHE_SIZE,
+			state_pruning: PruningMode::keep_blocks(10),
+			source: DatabaseSettingsSrc::Custom(backing),
+			keep_blocks: KeepBlocks::All,
+			transaction_storage: TransactionStorageMode::BlockBody,
+		}, 10).unwrap();
+		assert_eq!(backend.blockchain().info().finalized_number, 3);
+		assert_eq!(backend.storage.state_db.best_canonical(), Some(3));
+		assert!(backend.state_at(BlockId::Number(3)).is_ok());
+
+		// Block import keeps working on top of the repaired state db.
+		let hash = backend.blockchain().hash(3).unwrap().unwrap();
+		insert_block(&backend, 4, hash, None, Default::default(), vec![], None);
+		assert_eq!(backend.blockchain().info().best_number, 4);
+	}
+
 	#[test]
 	fn set_state_data() {
 		let db = Backend::<Block>::new_test(2, 0);
@@ -2745,6 +3098,57 @@ pub(crate) mod tests {
 		).is_none());
 	}
 
+	#[test]
+	fn pruned_nodes_are_not_served_from_the_node_cache() {
+		let backend = Backend::<Block>::new_test(1, 0);
+		let db_key = |key| sp_trie::prefixed_key::<BlakeTwo256>(&key, EMPTY_PREFIX);
+		let mut key = Default::default();
+		let mut parent_hash = Default::default();
+
+		for number in 0..4 {
+			let mut op = backend.begin_operation().unwrap();
+			let parent = if number == 0 {
+				BlockId::Hash(Default::default())
+			} else {
+				BlockId::Number(number - 1)
+			};
+			backend.begin_state_operation(&mut op, parent).unwrap();
+			let mut header = Header {
+				number,
+				parent_hash,
+				state_root: Default::default(),
+				digest: Default::default(),
+				extrinsics_root: Default::default(),
+			};
+			header.state_root = op.old_state.storage_root(std::iter::empty()).0.into();
+			parent_hash = header.hash();
+
+			match number {
+				0 => {
+					op.reset_storage(Default::default()).unwrap();
+					key = op.db_updates.insert(EMPTY_PREFIX, b"hello");
+				},
+				1 => op.db_updates.remove(&key, EMPTY_PREFIX),
+				_ => {},
+			}
+			op.set_block_data(header, Some(vec![]), None, NewBlockState::Best).unwrap();
+			backend.commit_operation(op).unwrap();
+
+			if number == 0 {
+				// The node is canonical, so it is read from the database and cached.
+				assert_eq!(
+					sp_state_machine::Storage::get(&*backend.storage, &key, EMPTY_PREFIX).unwrap(),
+					Some(b"hello".to_vec()),
+				);
+				assert!(backend.storage.node_cache.get(&db_key(key)).is_some());
+			}
+		}
+
+		assert!(backend.storage.db.get(columns::STATE, &db_key(key)).is_none());
+		assert!(backend.storage.node_cache.get(&db_key(key)).is_none());
+		assert_eq!(sp_state_machine::Storage::get(&*backend.storage, &key, EMPTY_PREFIX).unwrap(), None);
+	}
+
 	#[test]
 	fn tree_route_works() {
 		let backend = Backend::<Block>::new_test(1000, 100);
@@ -2954,6 +3358,40 @@ pub(crate) mod tests {
 		assert!(backend.get_aux(b"test").unwrap().is_none());
 	}
 
+	#[test]
+	fn test_aux_with_namespace() {
+		let backend = Backend::<Block>::new_test(1000, 100);
+		backend.insert_aux_with_namespace(b"ns", &[(&b"test"[..], &b"hello"[..])], &[]).unwrap();
+		assert_eq!(backend.get_aux_with_namespace(b"ns", b"test").unwrap(), Some(b"hello".to_vec()));
+		assert!(backend.get_aux_with_namespace(b"other", b"test").unwrap().is_none());
+		assert!(backend.get_aux(b"test").unwrap().is_none());
+
+		// Prune the entry named after the finalized block number.
+		backend.register_aux_pruning(b"ns", Box::new(
+			|_: &H256, number: u64, get: &dyn Fn(&[u8]) -> Option<Vec<u8>>| {
+				let key = number.encode();
+				get(&key).map(|_| vec![key]).unwrap_or_default()
+			},
+		));
+		backend.insert_aux_with_namespace(
+			b"ns",
+			&[(&1u64.encode()[..], &b"one"[..]), (&2u64.encode()[..], &b"two"[..])],
+			&[],
+		).unwrap();
+
+		let block0 = insert_header(&backend, 0, Default::default(), None, Default::default());
+		let block1 = insert_header(&backend, 1, block0, None, Default::default());
+		let _ = insert_header(&backend, 2, block1, None, Default::default());
+		backend.finalize_block(BlockId::hash(block1), None).unwrap();
+
+		assert!(backend.get_aux_with_namespace(b"ns", &1u64.encode()).unwrap().is_none());
+		assert_eq!(
+			backend.get_aux_with_namespace(b"ns", &2u64.encode()).unwrap(),
+			Some(b"two".to_vec()),
+		);
+		assert_eq!(backend.get_aux_with_namespace(b"ns", b"test").unwrap(), Some(b"hello".to_vec()));
+	}
+
 	#[test]
 	fn test_finalize_block_with_justification() {
 		use sc_client_api::blockchain::{Backend as BlockChainBackend};
@@ -3166,6 +3604,57 @@ pub(crate) mod tests {
 		assert_eq!(cht_root_2, cht_root_3);
 	}
 
+	#[test]
+	fn body_info_is_stored_on_import() {
+		for storage in &[TransactionStorageMode::BlockBody, TransactionStorageMode::StorageChain] {
+			let backend = Backend::<Block>::new_test_with_tx_storage(2, 0, *storage);
+			let hash0 = insert_block(&backend, 0, Default::default(), None, Default::default(), vec![], None);
+			let hash1 = insert_block(&backend, 1, hash0, None, Default::default(), vec![1.into(), 2.into()], None);
+
+			let bc = backend.blockchain();
+			assert_eq!(Some(0), bc.extrinsics_count(BlockId::hash(hash0)).unwrap());
+			assert_eq!(Some(2), bc.extrinsics_count(BlockId::hash(hash1)).unwrap());
+			assert_eq!(None, bc.extrinsics_count(BlockId::hash(H256::from_low_u64_be(42))).unwrap());
+
+			let body: Vec<ExtrinsicWrapper<u64>> = vec![1.into(), 2.into()];
+			assert_eq!(
+				Some(extrinsics_root::<Block>(&body)),
+				bc.extrinsics_root(BlockId::hash(hash1)).unwrap(),
+			);
+			// The header was built with a default root, so the body does not match it.
+			let header = bc.header(BlockId::hash(hash1)).unwrap().unwrap();
+			assert_ne!(
+				Some(*header.extrinsics_root()),
+				bc.extrinsics_root(BlockId::hash(hash1)).unwrap(),
+			);
+			assert!(bc.body_info(BlockId::hash(hash1)).unwrap().is_some());
+		}
+	}
+
+	#[test]
+	fn body_info_is_pruned_with_the_body() {
+		let backend = Backend::<Block>::new_test_with_tx_storage(2, 0, TransactionStorageMode::BlockBody);
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0 .. 5 {
+			let hash = insert_block(&backend, i, prev_hash, None, Default::default(), vec![i.into()], None);
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		let mut op = backend.begin_operation().unwrap();
+		backend.begin_state_operation(&mut op, BlockId::Hash(blocks[4])).unwrap();
+		for i in 1 .. 5 {
+			op.mark_finalized(BlockId::Hash(blocks[i]), None).unwrap();
+		}
+		backend.commit_operation(op).unwrap();
+
+		let bc = backend.blockchain();
+		assert!(bc.body_info(BlockId::hash(blocks[0])).unwrap().is_none());
+		assert_eq!(None, bc.extrinsics_count(BlockId::hash(blocks[0])).unwrap());
+		assert_eq!(Some(1), bc.extrinsics_count(BlockId::hash(blocks[4])).unwrap());
+	}
+
 	#[test]
 	fn prune_blocks_on_finalize() {
 		for storage in &[TransactionStorageMode::BlockBody, TransactionStorageMode::StorageChain] {
@@ -3325,4 +3814,46 @@ pub(crate) mod tests {
 		assert_eq!(None, backend.blockchain().header(BlockId::hash(prev_hash.clone())).unwrap());
 		assert!(!backend.have_state_at(&prev_hash, 1));
 	}
+
+	#[test]
+	fn verify_integrity_finds_and_repairs_inconsistencies() {
+		let backend = Backend::<Block>::new_test(10, 10);
+		let mut blocks = Vec::new();
+		let mut prev_hash = Default::default();
+		for i in 0 .. 4 {
+			let hash = insert_header(&backend, i, prev_hash, None, Default::default());
+			blocks.push(hash);
+			prev_hash = hash;
+		}
+
+		let report = backend.verify_integrity(IntegrityCheckLevel::State, false).unwrap();
+		assert!(report.is_ok());
+		assert_eq!(report.checked_blocks, 4);
+
+		// Lose the number lookup of block 2 and point the best block to an unknown block.
+		let mut transaction = Transaction::new();
+		utils::remove_number_to_key_mapping(&mut transaction, columns::KEY_LOOKUP, 2u64).unwrap();
+		transaction.set_from_vec(
+			columns::META,
+			meta_keys::BEST_BLOCK,
+			utils::number_and_hash_to_lookup_key(4u64, H256::random()).unwrap(),
+		);
+		backend.storage.db.commit(transaction).unwrap();
+
+		let issues = vec![
+			IntegrityIssue::DanglingBestBlock,
+			IntegrityIssue::MissingNumberLookup(2, blocks[2]),
+		];
+		let report = backend.verify_integrity(IntegrityCheckLevel::Meta, false).unwrap();
+		assert_eq!(report.issues, issues[..1].to_vec());
+		let report = backend.verify_integrity(IntegrityCheckLevel::Canonical, false).unwrap();
+		assert_eq!(report.issues, issues);
+		assert!(report.repaired.is_empty());
+
+		let report = backend.verify_integrity(IntegrityCheckLevel::Canonical, true).unwrap();
+		assert_eq!(report.repaired, issues);
+		assert_eq!(backend.blockchain().hash(2).unwrap(), Some(blocks[2]));
+		assert_eq!(backend.blockchain().info().best_hash, blocks[3]);
+		assert!(backend.verify_integrity(IntegrityCheckLevel::State, false).unwrap().is_ok());
+	}
 }
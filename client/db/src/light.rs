@@ -576,6 +576,7 @@ impl<Block> Storage<Block> for LightStorage<Block>
 			memory: MemoryInfo {
 				database_cache,
 				state_cache: Default::default(),
+				trie_node_cache: Default::default(),
 				state_db: Default::default(),
 			},
 			io: IoInfo {
@@ -591,6 +592,8 @@ impl<Block> Storage<Block> for LightStorage<Block>
 				state_reads_cache: 0,
 				state_writes_cache: 0,
 				state_writes_nodes: 0,
+				trie_node_cache_hits: 0,
+				trie_node_cache_misses: 0,
 			}
 		})
 	}
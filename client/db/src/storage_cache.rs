@@ -567,6 +567,25 @@ impl<S: StateBackend<HashFor<B>>, B: BlockT> StateBackend<HashFor<B>> for Cachin
 		Ok(hash)
 	}
 
+	fn storage_size(&self, key: &[u8]) -> Result<Option<u32>, Self::Error> {
+		// Sizes are not cached, but a cached value saves the lookup.
+		if let Some(entry) = self.cache.local_cache.read().storage.get(key) {
+			trace!("Found size in local cache: {:?}", HexDisplay::from(&key));
+			return Ok(entry.as_ref().map(|value| value.len() as u32))
+		}
+		{
+			let cache = self.cache.shared_cache.upgradable_read();
+			if Self::is_allowed(Some(key), None, &self.cache.parent_hash, &cache.modifications) {
+				let mut cache = RwLockUpgradableReadGuard::upgrade(cache);
+				if let Some(entry) = cache.lru_storage.get(key) {
+					trace!("Found size in shared cache: {:?}", HexDisplay::from(&key));
+					return Ok(entry.as_ref().map(|value| value.len() as u32))
+				}
+			}
+		}
+		self.state.storage_size(key)
+	}
+
 	fn child_storage(
 		&self,
 		child_info: &ChildInfo,
@@ -678,6 +697,27 @@ impl<S: StateBackend<HashFor<B>>, B: BlockT> StateBackend<HashFor<B>> for Cachin
 		self.state.child_storage_root(child_info, delta)
 	}
 
+	fn child_storage_roots<'a>(
+		&self,
+		child_deltas: impl Iterator<Item = (
+			&'a ChildInfo,
+			impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		)>,
+	) -> Vec<(B::Hash, bool, Self::Transaction)> where B::Hash: Ord {
+		self.state.child_storage_roots(child_deltas)
+	}
+
+	fn full_storage_root<'a>(
+		&self,
+		delta: impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		child_deltas: impl Iterator<Item = (
+			&'a ChildInfo,
+			impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		)>,
+	) -> (B::Hash, Self::Transaction) where B::Hash: Ord + codec::Encode {
+		self.state.full_storage_root(delta, child_deltas)
+	}
+
 	fn pairs(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
 		self.state.pairs()
 	}
@@ -788,6 +828,10 @@ impl<S: StateBackend<HashFor<B>>, B: BlockT> StateBackend<HashFor<B>> for Syncin
 		self.caching_state().storage_hash(key)
 	}
 
+	fn storage_size(&self, key: &[u8]) -> Result<Option<u32>, Self::Error> {
+		self.caching_state().storage_size(key)
+	}
+
 	fn child_storage(
 		&self,
 		child_info: &ChildInfo,
@@ -796,6 +840,22 @@ impl<S: StateBackend<HashFor<B>>, B: BlockT> StateBackend<HashFor<B>> for Syncin
 		self.caching_state().child_storage(child_info, key)
 	}
 
+	fn child_storage_hash(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<B::Hash>, Self::Error> {
+		self.caching_state().child_storage_hash(child_info, key)
+	}
+
+	fn child_storage_size(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<u32>, Self::Error> {
+		self.caching_state().child_storage_size(child_info, key)
+	}
+
 	fn exists_storage(&self, key: &[u8]) -> Result<bool, Self::Error> {
 		self.caching_state().exists_storage(key)
 	}
@@ -872,6 +932,27 @@ impl<S: StateBackend<HashFor<B>>, B: BlockT> StateBackend<HashFor<B>> for Syncin
 		self.caching_state().child_storage_root(child_info, delta)
 	}
 
+	fn child_storage_roots<'a>(
+		&self,
+		child_deltas: impl Iterator<Item = (
+			&'a ChildInfo,
+			impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		)>,
+	) -> Vec<(B::Hash, bool, Self::Transaction)> where B::Hash: Ord {
+		self.caching_state().child_storage_roots(child_deltas)
+	}
+
+	fn full_storage_root<'a>(
+		&self,
+		delta: impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		child_deltas: impl Iterator<Item = (
+			&'a ChildInfo,
+			impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		)>,
+	) -> (B::Hash, Self::Transaction) where B::Hash: Ord + codec::Encode {
+		self.caching_state().full_storage_root(delta, child_deltas)
+	}
+
 	fn pairs(&self) -> Vec<(Vec<u8>, Vec<u8>)> {
 		self.caching_state().pairs()
 	}
@@ -1261,6 +1342,37 @@ mod tests {
 		assert_eq!(s.storage_hash(&key).unwrap().unwrap(), BlakeTwo256::hash(&vec![1]));
 	}
 
+	#[test]
+	fn storage_size_is_read_from_the_shared_cache() {
+		let root_parent = H256::random();
+		let key = H256::random()[..].to_vec();
+		let h0 = H256::random();
+
+		let shared = new_shared_cache::<Block>(256 * 1024, (0, 1));
+		let mut s = CachingState::new(
+			InMemoryBackend::<BlakeTwo256>::default(),
+			shared.clone(),
+			Some(root_parent),
+		);
+		s.cache.sync_cache(
+			&[],
+			&[],
+			vec![(key.clone(), Some(vec![1, 2, 3]))],
+			vec![],
+			Some(h0),
+			Some(0),
+			true,
+		);
+
+		// The backend is empty, the value is only in the shared cache.
+		let s = CachingState::new(
+			InMemoryBackend::<BlakeTwo256>::default(),
+			shared.clone(),
+			Some(h0),
+		);
+		assert_eq!(s.storage_size(&key).unwrap(), Some(3));
+	}
+
 	#[test]
 	fn should_track_used_size_correctly() {
 		let root_parent = H256::random();
@@ -37,20 +37,48 @@ const CURRENT_VERSION: u32 = 3;
 const V1_NUM_COLUMNS: u32 = 11;
 const V2_NUM_COLUMNS: u32 = 12;
 
+/// A single migration step, upgrading the database by exactly one version.
+type Migration = fn(&Path, DatabaseType) -> sp_blockchain::Result<()>;
+
+/// Ordered list of known migrations, paired with the version they upgrade the database to.
+///
+/// The last entry must always upgrade to `CURRENT_VERSION`.
+fn migrations<Block: BlockT>() -> [(u32, Migration); 2] {
+	[
+		(2, migrate_1_to_2::<Block>),
+		(3, migrate_2_to_3::<Block>),
+	]
+}
+
 /// Upgrade database to current version.
+///
+/// Migrations are applied one at a time and the version file is updated after each of them,
+/// so an interrupted upgrade resumes from the last completed migration.
 pub fn upgrade_db<Block: BlockT>(db_path: &Path, db_type: DatabaseType) -> sp_blockchain::Result<()> {
 	let is_empty = db_path.read_dir().map_or(true, |mut d| d.next().is_none());
 	if !is_empty {
-		let db_version = current_version(db_path)?;
+		let mut db_version = current_version(db_path)?;
 		match db_version {
 			0 => Err(sp_blockchain::Error::Backend(format!("Unsupported database version: {}", db_version)))?,
-			1 => {
-				migrate_1_to_2::<Block>(db_path, db_type)?;
-				migrate_2_to_3::<Block>(db_path, db_type)?
-			},
-			2 => migrate_2_to_3::<Block>(db_path, db_type)?,
-			CURRENT_VERSION => (),
-			_ => Err(sp_blockchain::Error::Backend(format!("Future database version: {}", db_version)))?,
+			v if v > CURRENT_VERSION =>
+				Err(sp_blockchain::Error::Backend(format!("Future database version: {}", db_version)))?,
+			_ => (),
+		}
+
+		for (target_version, migration) in migrations::<Block>().iter() {
+			if db_version >= *target_version {
+				continue
+			}
+
+			log::info!(
+				target: "db",
+				"Upgrading database from version {} to {}",
+				db_version,
+				target_version,
+			);
+			migration(db_path, db_type)?;
+			write_version(db_path, *target_version)?;
+			db_version = *target_version;
 		}
 	}
 
@@ -122,9 +150,14 @@ fn db_err(err: std::io::Error) -> sp_blockchain::Error {
 /// Writes current database version to the file.
 /// Creates a new file if the version file does not exist yet.
 fn update_version(path: &Path) -> sp_blockchain::Result<()> {
+	write_version(path, CURRENT_VERSION)
+}
+
+/// Writes the given database version to the file.
+fn write_version(path: &Path, version: u32) -> sp_blockchain::Result<()> {
 	fs::create_dir_all(path).map_err(db_err)?;
 	let mut file = fs::File::create(version_file_path(path)).map_err(db_err)?;
-	file.write_all(format!("{}", CURRENT_VERSION).as_bytes()).map_err(db_err)?;
+	file.write_all(format!("{}", version).as_bytes()).map_err(db_err)?;
 	Ok(())
 }
 
@@ -154,6 +187,7 @@ mod tests {
 		crate::utils::open_database::<Block>(&DatabaseSettings {
 			state_cache_size: 0,
 			state_cache_child_ratio: None,
+			trie_node_cache_size: 0,
 			state_pruning: PruningMode::ArchiveAll,
 			source: DatabaseSettingsSrc::RocksDb { path: db_path.to_owned(), cache_size: 128 },
 			keep_blocks: KeepBlocks::All,
@@ -176,6 +210,13 @@ mod tests {
 		assert_eq!(current_version(db_dir.path()).unwrap(), CURRENT_VERSION);
 	}
 
+	#[test]
+	fn migrations_end_at_current_version() {
+		let migrations = migrations::<Block>();
+		assert_eq!(migrations.last().map(|(version, _)| *version), Some(CURRENT_VERSION));
+		assert!(migrations.windows(2).all(|w| w[0].0 + 1 == w[1].0));
+	}
+
 	#[test]
 	fn upgrade_to_3_works() {
 		for version_from_file in &[None, Some(1), Some(2)] {
@@ -61,6 +61,8 @@ pub mod meta_keys {
 	pub const LEAF_PREFIX: &[u8; 4] = b"leaf";
 	/// Children prefix list key.
 	pub const CHILDREN_PREFIX: &[u8; 8] = b"children";
+	/// Block body information prefix key.
+	pub const BODY_INFO_PREFIX: &[u8; 9] = b"body_info";
 }
 
 /// Database metadata.
@@ -21,12 +21,12 @@
 use ansi_term::Colour;
 use futures::prelude::*;
 use futures_timer::Delay;
-use log::{info, trace, warn};
+use log::{debug, info, trace, warn};
 use parity_util_mem::MallocSizeOf;
 use sc_client_api::{BlockchainEvents, UsageProvider};
 use sc_network::NetworkService;
 use sp_blockchain::HeaderMetadata;
-use sp_runtime::traits::{Block as BlockT, Header};
+use sp_runtime::{generic::PreRuntimeDigestRegistry, traits::{Block as BlockT, Header}};
 use sc_transaction_pool_api::TransactionPool;
 use std::{fmt::Display, sync::Arc, time::Duration, collections::VecDeque};
 
@@ -44,12 +44,20 @@ pub struct OutputFormat {
 	///
 	/// Is enabled by default.
 	pub enable_color: bool,
+	/// Decoders of the pre-runtime digests of imported blocks, which are logged at debug level.
+	///
+	/// The BABE pre-digest is registered by default.
+	pub pre_runtime_digests: PreRuntimeDigestRegistry,
 }
 
 impl Default for OutputFormat {
 	fn default() -> Self {
+		let mut pre_runtime_digests = PreRuntimeDigestRegistry::default();
+		sp_consensus_babe::digests::register_pre_digest(&mut pre_runtime_digests);
+
 		Self {
 			enable_color: true,
+			pre_runtime_digests,
 		}
 	}
 }
@@ -110,11 +118,14 @@ where
 
 	futures::select! {
 		() = display_notifications.fuse() => (),
-		() = display_block_import(client).fuse() => (),
+		() = display_block_import(client, format.pre_runtime_digests).fuse() => (),
 	};
 }
 
-fn display_block_import<B: BlockT, C>(client: Arc<C>) -> impl Future<Output = ()>
+fn display_block_import<B: BlockT, C>(
+	client: Arc<C>,
+	pre_runtime_digests: PreRuntimeDigestRegistry,
+) -> impl Future<Output = ()>
 where
 	C: UsageProvider<B> + HeaderMetadata<B> + BlockchainEvents<B>,
 	<C as HeaderMetadata<B>>::Error: Display,
@@ -171,6 +182,16 @@ where
 				Colour::White.bold().paint(format!("{}", n.header.number())),
 				n.hash,
 			);
+
+			for (engine, pre_digest) in pre_runtime_digests.decode_digest(n.header.digest()) {
+				debug!(
+					target: "substrate",
+					"Pre-runtime digest of #{} from {}: {}",
+					n.header.number(),
+					String::from_utf8_lossy(&engine),
+					pre_digest,
+				);
+			}
 		}
 
 		future::ready(())
@@ -31,8 +31,9 @@ use sp_runtime::{
 };
 use sp_externalities::Extensions;
 use sp_state_machine::{
-	self, Backend as StateBackend, OverlayedChanges, ExecutionStrategy, create_proof_check_backend,
-	execution_proof_check_on_trie_backend, ExecutionManager, StorageProof,
+	self, Backend as StateBackend, OverlayedChanges, ExecutionStrategy, ExecutionLimits,
+	create_proof_check_backend, execution_proof_check_on_trie_backend, ExecutionManager,
+	StorageProof,
 };
 use hash_db::Hasher;
 
@@ -96,6 +97,21 @@ impl<Block, B, Local> CallExecutor<Block> for
 		}
 	}
 
+	fn call_with_limits(
+		&self,
+		id: &BlockId<Block>,
+		method: &str,
+		call_data: &[u8],
+		strategy: ExecutionStrategy,
+		extensions: Option<Extensions>,
+		limits: ExecutionLimits,
+	) -> ClientResult<Vec<u8>> {
+		match self.backend.is_local_state_available(id) {
+			true => self.local.call_with_limits(id, method, call_data, strategy, extensions, limits),
+			false => Err(ClientError::NotAvailableOnLightClient),
+		}
+	}
+
 	fn contextual_call<
 		EM: Fn(
 			Result<NativeOrEncoded<R>, Self::Error>,
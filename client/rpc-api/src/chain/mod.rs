@@ -42,6 +42,10 @@ pub trait ChainApi<Number, Hash, Header, SignedBlock> {
 	#[rpc(name = "chain_getBlock")]
 	fn block(&self, hash: Option<Hash>) -> FutureResult<Option<SignedBlock>>;
 
+	/// Get the number of extrinsics in a relay chain block.
+	#[rpc(name = "chain_getBlockExtrinsicsCount")]
+	fn block_extrinsics_count(&self, hash: Option<Hash>) -> FutureResult<Option<u32>>;
+
 	/// Get hash of the n-th block in the canon chain.
 	///
 	/// By default returns latest block hash.
@@ -57,6 +57,18 @@ pub trait ChildStateApi<Hash> {
 		hash: Option<Hash>,
 	) -> FutureResult<Vec<StorageKey>>;
 
+	/// Returns the number of keys with prefix in a child storage, leave empty to count all the
+	/// keys.
+	///
+	/// The keys are counted on the node, so they don't need to be fetched to be counted.
+	#[rpc(name = "childstate_getKeysCount", alias("childstate_getKeysCountAt"))]
+	fn storage_keys_count(
+		&self,
+		child_storage_key: PrefixedStorageKey,
+		prefix: Option<StorageKey>,
+		hash: Option<Hash>,
+	) -> FutureResult<u64>;
+
 	/// Returns a child storage entry at a specific block's state.
 	#[rpc(name = "childstate_getStorage")]
 	fn storage(
@@ -63,6 +63,16 @@ pub trait StateApi<Hash> {
 		hash: Option<Hash>,
 	) -> FutureResult<Vec<StorageKey>>;
 
+	/// Returns the number of keys with prefix, leave empty to count all the keys.
+	///
+	/// The keys are counted on the node, so they don't need to be fetched to be counted.
+	#[rpc(name = "state_getKeysCount", alias("state_getKeysCountAt"))]
+	fn storage_keys_count(
+		&self,
+		prefix: Option<StorageKey>,
+		hash: Option<Hash>,
+	) -> FutureResult<u64>;
+
 	/// Returns a storage entry at a specific block's state.
 	#[rpc(name = "state_getStorage", alias("state_getStorageAt"))]
 	fn storage(&self, key: StorageKey, hash: Option<Hash>) -> FutureResult<Option<StorageData>>;
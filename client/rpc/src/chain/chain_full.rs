@@ -77,4 +77,11 @@ impl<Block, Client> ChainBackend<Client, Block> for FullChain<Block, Client> whe
 			.map_err(client_err)
 		))
 	}
+
+	fn block_extrinsics_count(&self, hash: Option<Block::Hash>) -> FutureResult<Option<u32>> {
+		Box::new(result(self.client
+			.block_extrinsics_count(&BlockId::Hash(self.unwrap_or_best(hash)))
+			.map_err(client_err)
+		))
+	}
 }
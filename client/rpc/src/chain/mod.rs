@@ -72,6 +72,15 @@ trait ChainBackend<Client, Block: BlockT>: Send + Sync + 'static
 	/// Get header and body of a relay chain block.
 	fn block(&self, hash: Option<Block::Hash>) -> FutureResult<Option<SignedBlock<Block>>>;
 
+	/// Get the number of extrinsics in a relay chain block.
+	///
+	/// By default the whole block is fetched to count them.
+	fn block_extrinsics_count(&self, hash: Option<Block::Hash>) -> FutureResult<Option<u32>> {
+		Box::new(self.block(hash).map(|block|
+			block.map(|block| block.block.extrinsics().len() as u32)
+		))
+	}
+
 	/// Get hash of the n-th block in the canon chain.
 	///
 	/// By default returns latest block hash.
@@ -241,6 +250,10 @@ impl<Block, Client> ChainApi<NumberFor<Block>, Block::Hash, Block::Header, Signe
 		self.backend.block(hash)
 	}
 
+	fn block_extrinsics_count(&self, hash: Option<Block::Hash>) -> FutureResult<Option<u32>> {
+		self.backend.block_extrinsics_count(hash)
+	}
+
 	fn block_hash(
 		&self,
 		number: Option<ListOrValue<NumberOrHex>>,
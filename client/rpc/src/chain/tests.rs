@@ -111,6 +111,24 @@ fn should_return_a_block() {
 	);
 }
 
+#[test]
+fn should_return_block_extrinsics_count() {
+	let mut client = Arc::new(substrate_test_runtime_client::new());
+	let api = new_full(client.clone(), SubscriptionManager::new(Arc::new(TaskExecutor)));
+
+	let mut builder = client.new_block(Default::default()).unwrap();
+	builder.push_storage_change(vec![1], None).unwrap();
+	builder.push_storage_change(vec![2], None).unwrap();
+	let block = builder.build().unwrap().block;
+	let block_hash = block.hash();
+	executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+
+	assert_matches!(api.block_extrinsics_count(Some(client.genesis_hash()).into()).wait(), Ok(Some(0)));
+	assert_matches!(api.block_extrinsics_count(Some(block_hash).into()).wait(), Ok(Some(2)));
+	assert_matches!(api.block_extrinsics_count(None.into()).wait(), Ok(Some(2)));
+	assert_matches!(api.block_extrinsics_count(Some(H256::from_low_u64_be(5)).into()).wait(), Ok(None));
+}
+
 #[test]
 fn should_return_block_hash() {
 	let mut client = Arc::new(substrate_test_runtime_client::new());
@@ -44,6 +44,7 @@ use sc_client_api::{
 	ExecutorProvider, StorageProvider, BlockchainEvents, Backend, BlockBackend, ProofProvider
 };
 use sp_blockchain::{HeaderMetadata, HeaderBackend};
+use sp_state_machine::ExecutionLimits;
 
 const STORAGE_KEYS_PAGED_MAX_COUNT: u32 = 1000;
 
@@ -84,6 +85,13 @@ pub trait StateBackend<Block: BlockT, Client>: Send + Sync + 'static
 		start_key: Option<StorageKey>,
 	) -> FutureResult<Vec<StorageKey>>;
 
+	/// Returns the number of keys with the given prefix.
+	fn storage_keys_count(
+		&self,
+		block: Option<Block::Hash>,
+		prefix: Option<StorageKey>,
+	) -> FutureResult<u64>;
+
 	/// Returns a storage entry at a specific block's state.
 	fn storage(
 		&self,
@@ -183,6 +191,7 @@ pub fn new_full<BE, Block: BlockT, Client>(
 	subscriptions: SubscriptionManager,
 	deny_unsafe: DenyUnsafe,
 	rpc_max_payload: Option<usize>,
+	call_limits: ExecutionLimits,
 ) -> (State<Block, Client>, ChildState<Block, Client>)
 	where
 		Block: BlockT + 'static,
@@ -195,11 +204,13 @@ pub fn new_full<BE, Block: BlockT, Client>(
 {
 	let child_backend = Box::new(
 		self::state_full::FullState::new(
-			client.clone(), subscriptions.clone(), rpc_max_payload
+			client.clone(), subscriptions.clone(), rpc_max_payload, call_limits,
 		)
 	);
-	let backend = Box::new(self::state_full::FullState::new(client, subscriptions, rpc_max_payload));
-	(State { backend, deny_unsafe }, ChildState { backend: child_backend })
+	let backend = Box::new(self::state_full::FullState::new(
+		client, subscriptions, rpc_max_payload, call_limits,
+	));
+	(State { backend, deny_unsafe }, ChildState { backend: child_backend, deny_unsafe })
 }
 
 /// Create new state API that works on light node.
@@ -232,7 +243,7 @@ pub fn new_light<BE, Block: BlockT, Client, F: Fetcher<Block>>(
 			remote_blockchain,
 			fetcher,
 	));
-	(State { backend, deny_unsafe }, ChildState { backend: child_backend })
+	(State { backend, deny_unsafe }, ChildState { backend: child_backend, deny_unsafe })
 }
 
 /// State API with subscriptions support.
@@ -291,6 +302,18 @@ impl<Block, Client> StateApi<Block::Hash> for State<Block, Client>
 		self.backend.storage_keys_paged(block, prefix, count, start_key)
 	}
 
+	fn storage_keys_count(
+		&self,
+		prefix: Option<StorageKey>,
+		block: Option<Block::Hash>,
+	) -> FutureResult<u64> {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			return Box::new(result(Err(err.into())))
+		}
+
+		self.backend.storage_keys_count(block, prefix)
+	}
+
 	fn storage(&self, key: StorageKey, block: Option<Block::Hash>) -> FutureResult<Option<StorageData>> {
 		self.backend.storage(block, key)
 	}
@@ -412,6 +435,14 @@ pub trait ChildStateBackend<Block: BlockT, Client>: Send + Sync + 'static
 		start_key: Option<StorageKey>,
 	) -> FutureResult<Vec<StorageKey>>;
 
+	/// Returns the number of keys with the given prefix in a child storage.
+	fn storage_keys_count(
+		&self,
+		block: Option<Block::Hash>,
+		storage_key: PrefixedStorageKey,
+		prefix: Option<StorageKey>,
+	) -> FutureResult<u64>;
+
 	/// Returns a child storage entry at a specific block's state.
 	fn storage(
 		&self,
@@ -443,6 +474,8 @@ pub trait ChildStateBackend<Block: BlockT, Client>: Send + Sync + 'static
 /// Child state API with subscriptions support.
 pub struct ChildState<Block, Client> {
 	backend: Box<dyn ChildStateBackend<Block, Client>>,
+	/// Whether to deny unsafe calls
+	deny_unsafe: DenyUnsafe,
 }
 
 impl<Block, Client> ChildStateApi<Block::Hash> for ChildState<Block, Client>
@@ -490,6 +523,19 @@ impl<Block, Client> ChildStateApi<Block::Hash> for ChildState<Block, Client>
 		self.backend.storage_keys_paged(block, storage_key, prefix, count, start_key)
 	}
 
+	fn storage_keys_count(
+		&self,
+		storage_key: PrefixedStorageKey,
+		prefix: Option<StorageKey>,
+		block: Option<Block::Hash>,
+	) -> FutureResult<u64> {
+		if let Err(err) = self.deny_unsafe.check_if_safe() {
+			return Box::new(result(Err(err.into())))
+		}
+
+		self.backend.storage_keys_count(block, storage_key, prefix)
+	}
+
 	fn storage_hash(
 		&self,
 		storage_key: PrefixedStorageKey,
@@ -18,9 +18,8 @@
 
 //! State API backend for full nodes.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
-use std::ops::Range;
 use futures::{future, StreamExt as _, TryStreamExt as _};
 use log::warn;
 use jsonrpc_pubsub::{typed::Subscriber, SubscriptionId, manager::SubscriptionManager};
@@ -32,35 +31,30 @@ use sp_blockchain::{
 	HeaderBackend
 };
 use sp_core::{
-	Bytes, storage::{well_known_keys, StorageKey, StorageData, StorageChangeSet,
+	Bytes, ExecutionContext, storage::{well_known_keys, StorageKey, StorageData, StorageChangeSet,
 	ChildInfo, ChildType, PrefixedStorageKey},
 };
 use sp_version::RuntimeVersion;
 use sp_runtime::{
-	generic::BlockId, traits::{Block as BlockT, NumberFor, SaturatedConversion, CheckedSub},
+	generic::BlockId, traits::{Block as BlockT, NumberFor, SaturatedConversion, One},
 };
 
 use sp_api::{Metadata, ProvideRuntimeApi, CallApiAt};
 
 use super::{StateBackend, ChildStateBackend, error::{FutureResult, Error, Result}, client_err};
 use std::marker::PhantomData;
+use sp_state_machine::ExecutionLimits;
 use sc_client_api::{
 	Backend, BlockBackend, BlockchainEvents, CallExecutor, StorageProvider, ExecutorProvider,
 	ProofProvider
 };
 
-/// Ranges to query in state_queryStorage.
+/// Blocks to query in state_queryStorage.
 struct QueryStorageRange<Block: BlockT> {
 	/// Hashes of all the blocks in the range.
 	pub hashes: Vec<Block::Hash>,
 	/// Number of the first block in the range.
 	pub first_number: NumberFor<Block>,
-	/// Blocks subrange ([begin; end) indices within `hashes`) where we should read keys at
-	/// each state to get changes.
-	pub unfiltered_range: Range<usize>,
-	/// Blocks subrange ([begin; end) indices within `hashes`) where we could pre-filter
-	/// blocks-with-changes by using changes tries.
-	pub filtered_range: Option<Range<usize>>,
 }
 
 /// State API backend for full nodes.
@@ -69,6 +63,7 @@ pub struct FullState<BE, Block: BlockT, Client> {
 	subscriptions: SubscriptionManager,
 	_phantom: PhantomData<(BE, Block)>,
 	rpc_max_payload: Option<usize>,
+	call_limits: ExecutionLimits,
 }
 
 impl<BE, Block: BlockT, Client> FullState<BE, Block, Client>
@@ -83,8 +78,9 @@ impl<BE, Block: BlockT, Client> FullState<BE, Block, Client>
 		client: Arc<Client>,
 		subscriptions: SubscriptionManager,
 		rpc_max_payload: Option<usize>,
+		call_limits: ExecutionLimits,
 	) -> Self {
-		Self { client, subscriptions, _phantom: PhantomData, rpc_max_payload }
+		Self { client, subscriptions, _phantom: PhantomData, rpc_max_payload, call_limits }
 	}
 
 	/// Returns given block hash or best block hash if None is passed.
@@ -92,10 +88,8 @@ impl<BE, Block: BlockT, Client> FullState<BE, Block, Client>
 		Ok(hash.unwrap_or_else(|| self.client.info().best_hash))
 	}
 
-	/// Splits the `query_storage` block range into 'filtered' and 'unfiltered' subranges.
-	/// Blocks that contain changes within filtered subrange could be filtered using changes tries.
-	/// Blocks that contain changes within unfiltered subrange must be filtered manually.
-	fn split_query_storage_range(
+	/// Collects the hashes of the `query_storage` block range.
+	fn query_storage_range(
 		&self,
 		from: Block::Hash,
 		to: Option<Block::Hash>
@@ -129,101 +123,59 @@ impl<BE, Block: BlockT, Client> FullState<BE, Block, Client>
 			hashes
 		};
 
-		// check if we can filter blocks-with-changes from some (sub)range using changes tries
-		let changes_trie_range = self.client
-			.max_key_changes_range(from_number, BlockId::Hash(to_meta.hash))
-			.map_err(client_err)?;
-		let filtered_range_begin = changes_trie_range
-			.and_then(|(begin, _)| {
-				// avoids a corner case where begin < from_number (happens when querying genesis)
-				begin.checked_sub(&from_number).map(|x| x.saturated_into::<usize>())
-			});
-		let (unfiltered_range, filtered_range) = split_range(hashes.len(), filtered_range_begin);
-
 		Ok(QueryStorageRange {
 			hashes,
 			first_number: from_number,
-			unfiltered_range,
-			filtered_range,
 		})
 	}
 
-	/// Iterates through range.unfiltered_range and check each block for changes of keys' values.
-	fn query_storage_unfiltered(
-		&self,
-		range: &QueryStorageRange<Block>,
-		keys: &[StorageKey],
-		last_values: &mut HashMap<StorageKey, Option<StorageData>>,
-		changes: &mut Vec<StorageChangeSet<Block::Hash>>,
-	) -> Result<()> {
-		for block in range.unfiltered_range.start..range.unfiltered_range.end {
-			let block_hash = range.hashes[block].clone();
-			let mut block_changes = StorageChangeSet { block: block_hash.clone(), changes: Vec::new() };
-			let id = BlockId::hash(block_hash);
-			for key in keys {
-				let (has_changed, data) = {
-					let curr_data = self.client.storage(&id, key).map_err(client_err)?;
-					match last_values.get(key) {
-						Some(prev_data) => (curr_data != *prev_data, curr_data),
-						None => (true, curr_data),
-					}
-				};
-				if has_changed {
-					block_changes.changes.push((key.clone(), data.clone()));
-				}
-				last_values.insert(key.clone(), data);
-			}
-			if !block_changes.changes.is_empty() {
-				changes.push(block_changes);
-			}
-		}
-		Ok(())
-	}
-
-	/// Iterates through all blocks that are changing keys within range.filtered_range and collects these changes.
-	fn query_storage_filtered(
+	/// Collects the changes of `keys` within `range`.
+	///
+	/// Values are read at the first block of the range, and at the blocks where changes tries
+	/// report a change of the key. Blocks that changes tries can't tell anything about are
+	/// checked by reading the value at each of them.
+	fn query_storage_changes(
 		&self,
 		range: &QueryStorageRange<Block>,
 		keys: &[StorageKey],
-		last_values: &HashMap<StorageKey, Option<StorageData>>,
-		changes: &mut Vec<StorageChangeSet<Block::Hash>>,
-	) -> Result<()> {
-		let (begin, end) = match range.filtered_range {
-			Some(ref filtered_range) => (
-				range.first_number + filtered_range.start.saturated_into(),
-				BlockId::Hash(range.hashes[filtered_range.end - 1].clone())
-			),
-			None => return Ok(()),
-		};
+	) -> Result<Vec<StorageChangeSet<Block::Hash>>> {
 		let mut changes_map: BTreeMap<NumberFor<Block>, StorageChangeSet<Block::Hash>> = BTreeMap::new();
 		for key in keys {
-			let mut last_block = None;
-			let mut last_value = last_values.get(key).cloned().unwrap_or_default();
-			let key_changes = self.client.key_changes(begin, end, None, key).map_err(client_err)?;
-			for (block, _) in key_changes.into_iter().rev() {
-				if last_block == Some(block) {
-					continue;
+			let mut blocks = BTreeSet::new();
+			blocks.insert(range.first_number);
+			if range.hashes.len() > 1 {
+				let key_changes = self.client.key_changes_in_range(
+					range.first_number + One::one(),
+					BlockId::Hash(range.hashes[range.hashes.len() - 1].clone()),
+					None,
+					key,
+				).map_err(client_err)?;
+				blocks.extend(key_changes.changes.into_iter().map(|(block, _)| block));
+				for (begin, end) in key_changes.unchecked {
+					let mut block = begin;
+					while block <= end {
+						blocks.insert(block);
+						block += One::one();
+					}
 				}
+			}
 
+			let mut last_value = None;
+			for block in blocks {
 				let block_hash = range.hashes[(block - range.first_number).saturated_into::<usize>()].clone();
 				let id = BlockId::Hash(block_hash);
 				let value_at_block = self.client.storage(&id, key).map_err(client_err)?;
-				if last_value == value_at_block {
+				if last_value.as_ref() == Some(&value_at_block) {
 					continue;
 				}
 
 				changes_map.entry(block)
 					.or_insert_with(|| StorageChangeSet { block: block_hash, changes: Vec::new() })
 					.changes.push((key.clone(), value_at_block.clone()));
-				last_block = Some(block);
-				last_value = value_at_block;
+				last_value = Some(value_at_block);
 			}
 		}
-		if let Some(additional_capacity) = changes_map.len().checked_sub(changes.len()) {
-			changes.reserve(additional_capacity);
-		}
-		changes.extend(changes_map.into_iter().map(|(_, cs)| cs));
-		Ok(())
+		Ok(changes_map.into_iter().map(|(_, cs)| cs).collect())
 	}
 }
 
@@ -248,12 +200,14 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 			.and_then(|block| self
 				.client
 				.executor()
-				.call(
+				.call_with_limits(
 					&BlockId::Hash(block),
 					&method,
 					&*call_data,
-					self.client.execution_extensions().strategies().other,
+					self.client.execution_extensions()
+						.strategy_for(&ExecutionContext::OffchainCall(None), &method),
 					None,
+					self.call_limits,
 				)
 				.map(Into::into)
 			).map_err(client_err);
@@ -300,6 +254,20 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 				.map_err(client_err)))
 	}
 
+	fn storage_keys_count(
+		&self,
+		block: Option<Block::Hash>,
+		prefix: Option<StorageKey>,
+	) -> FutureResult<u64> {
+		Box::new(result(
+			self.block_or_best(block)
+				.and_then(|block|
+					self.client.storage_keys_iter(&BlockId::Hash(block), prefix.as_ref(), None)
+				)
+				.map(|iter| iter.count() as u64)
+				.map_err(client_err)))
+	}
+
 	fn storage(
 		&self,
 		block: Option<Block::Hash>,
@@ -381,12 +349,8 @@ impl<BE, Block, Client> StateBackend<Block, Client> for FullState<BE, Block, Cli
 		keys: Vec<StorageKey>,
 	) -> FutureResult<Vec<StorageChangeSet<Block::Hash>>> {
 		let call_fn = move || {
-			let range = self.split_query_storage_range(from, to)?;
-			let mut changes = Vec::new();
-			let mut last_values = HashMap::new();
-			self.query_storage_unfiltered(&range, &keys, &mut last_values, &mut changes)?;
-			self.query_storage_filtered(&range, &keys, &last_values, &mut changes)?;
-			Ok(changes)
+			let range = self.query_storage_range(from, to)?;
+			self.query_storage_changes(&range, &keys)
 		};
 		Box::new(result(call_fn()))
 	}
@@ -641,6 +605,27 @@ impl<BE, Block, Client> ChildStateBackend<Block, Client> for FullState<BE, Block
 				.map_err(client_err)))
 	}
 
+	fn storage_keys_count(
+		&self,
+		block: Option<Block::Hash>,
+		storage_key: PrefixedStorageKey,
+		prefix: Option<StorageKey>,
+	) -> FutureResult<u64> {
+		Box::new(result(
+			self.block_or_best(block)
+				.and_then(|block| {
+					let child_info = match ChildType::from_prefixed_key(&storage_key) {
+						Some((ChildType::ParentKeyId, storage_key)) => ChildInfo::new_default(storage_key),
+						None => return Err(sp_blockchain::Error::InvalidChildStorageKey),
+					};
+					self.client.child_storage_keys_iter(
+						&BlockId::Hash(block), child_info, prefix.as_ref(), None,
+					)
+				})
+				.map(|iter| iter.count() as u64)
+				.map_err(client_err)))
+	}
+
 	fn storage(
 		&self,
 		block: Option<Block::Hash>,
@@ -686,27 +671,6 @@ impl<BE, Block, Client> ChildStateBackend<Block, Client> for FullState<BE, Block
 	}
 }
 
-/// Splits passed range into two subranges where:
-/// - first range has at least one element in it;
-/// - second range (optionally) starts at given `middle` element.
-pub(crate) fn split_range(size: usize, middle: Option<usize>) -> (Range<usize>, Option<Range<usize>>) {
-	// check if we can filter blocks-with-changes from some (sub)range using changes tries
-	let range2_begin = match middle {
-		// some of required changes tries are pruned => use available tries
-		Some(middle) if middle != 0 => Some(middle),
-		// all required changes tries are available, but we still want values at first block
-		// => do 'unfiltered' read for the first block and 'filtered' for the rest
-		Some(_) if size > 1 => Some(1),
-		// range contains single element => do not use changes tries
-		Some(_) => None,
-		// changes tries are not available => do 'unfiltered' read for the whole range
-		None => None,
-	};
-	let range1 = 0..range2_begin.unwrap_or(size);
-	let range2 = range2_begin.map(|begin| begin..size);
-	(range1, range2)
-}
-
 fn invalid_block_range<B: BlockT>(
 	from: &CachedHeaderMetadata<B>,
 	to: &CachedHeaderMetadata<B>,
@@ -17,7 +17,6 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
-use super::state_full::split_range;
 use self::error::Error;
 
 use std::sync::Arc;
@@ -64,6 +63,7 @@ fn should_return_storage() {
 		SubscriptionManager::new(Arc::new(TaskExecutor)),
 		DenyUnsafe::No,
 		None,
+		Default::default(),
 	);
 	let key = StorageKey(KEY.to_vec());
 
@@ -95,6 +95,46 @@ fn should_return_storage() {
 	);
 }
 
+#[test]
+fn should_return_keys_count() {
+	const CHILD_VALUE: &[u8] = b"hello world !";
+
+	let child_info = ChildInfo::new_default(STORAGE_KEY);
+	let client = Arc::new(TestClientBuilder::new()
+		.add_extra_child_storage(&child_info, b":map:acc1".to_vec(), CHILD_VALUE.to_vec())
+		.add_extra_child_storage(&child_info, b":map:acc2".to_vec(), CHILD_VALUE.to_vec())
+		.add_extra_child_storage(&child_info, b":other".to_vec(), CHILD_VALUE.to_vec())
+		.add_extra_storage(b":map:acc1".to_vec(), vec![1, 2])
+		.add_extra_storage(b":map:acc2".to_vec(), vec![1, 2, 3])
+		.build());
+	let genesis_hash = client.genesis_hash();
+	let (state, child) = new_full(
+		client.clone(),
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::No,
+		None,
+		Default::default(),
+	);
+	let map_prefix = Some(StorageKey(b":map".to_vec()));
+
+	assert_eq!(state.storage_keys_count(map_prefix.clone(), Some(genesis_hash)).wait().unwrap(), 2);
+	assert_eq!(
+		child.storage_keys_count(prefixed_storage_key(), map_prefix.clone(), None).wait().unwrap(),
+		2,
+	);
+	assert_eq!(child.storage_keys_count(prefixed_storage_key(), None, None).wait().unwrap(), 3);
+
+	let (state, child) = new_full(
+		client,
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::Yes,
+		None,
+		Default::default(),
+	);
+	assert!(state.storage_keys_count(map_prefix.clone(), None).wait().is_err());
+	assert!(child.storage_keys_count(prefixed_storage_key(), map_prefix, None).wait().is_err());
+}
+
 #[test]
 fn should_return_child_storage() {
 	let child_info = ChildInfo::new_default(STORAGE_KEY);
@@ -107,6 +147,7 @@ fn should_return_child_storage() {
 		SubscriptionManager::new(Arc::new(TaskExecutor)),
 		DenyUnsafe::No,
 		None,
+		Default::default(),
 	);
 	let child_key = prefixed_storage_key();
 	let key = StorageKey(b"key".to_vec());
@@ -147,6 +188,7 @@ fn should_call_contract() {
 		SubscriptionManager::new(Arc::new(TaskExecutor)),
 		DenyUnsafe::No,
 		None,
+		Default::default(),
 	);
 
 	assert_matches!(
@@ -155,6 +197,38 @@ fn should_call_contract() {
 	)
 }
 
+#[test]
+fn should_fail_calls_exceeding_the_limits() {
+	let client = Arc::new(substrate_test_runtime_client::new());
+	let genesis_hash = client.genesis_hash();
+	let call_data = Bytes(AccountKeyring::Alice.to_raw_public().to_vec());
+
+	let (unlimited, _child) = new_full(
+		client.clone(),
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::No,
+		None,
+		Default::default(),
+	);
+	assert!(
+		unlimited.call("TestAPI_balance_of".into(), call_data.clone(), Some(genesis_hash).into())
+			.wait()
+			.is_ok()
+	);
+
+	let (limited, _child) = new_full(
+		client,
+		SubscriptionManager::new(Arc::new(TaskExecutor)),
+		DenyUnsafe::No,
+		None,
+		ExecutionLimits { max_reads: Some(0), ..Default::default() },
+	);
+	assert_matches!(
+		limited.call("TestAPI_balance_of".into(), call_data, Some(genesis_hash).into()).wait(),
+		Err(Error::Client(_))
+	);
+}
+
 #[test]
 fn should_notify_about_storage_changes() {
 	let (subscriber, id, transport) = Subscriber::new_test("test");
@@ -166,6 +240,7 @@ fn should_notify_about_storage_changes() {
 			SubscriptionManager::new(Arc::new(TaskExecutor)),
 			DenyUnsafe::No,
 			None,
+			Default::default(),
 		);
 
 		api.subscribe_storage(Default::default(), subscriber, None.into());
@@ -205,6 +280,7 @@ fn should_send_initial_storage_changes_and_notifications() {
 			SubscriptionManager::new(Arc::new(TaskExecutor)),
 			DenyUnsafe::No,
 			None,
+			Default::default(),
 		);
 
 		let alice_balance_key = blake2_256(&runtime::system::balance_of_key(AccountKeyring::Alice.into()));
@@ -248,6 +324,7 @@ fn should_query_storage() {
 			SubscriptionManager::new(Arc::new(TaskExecutor)),
 			DenyUnsafe::No,
 			None,
+			Default::default(),
 		);
 
 		let mut add_block = |nonce| {
@@ -452,15 +529,6 @@ fn should_query_storage() {
 	);
 }
 
-#[test]
-fn should_split_ranges() {
-	assert_eq!(split_range(1, None), (0..1, None));
-	assert_eq!(split_range(100, None), (0..100, None));
-	assert_eq!(split_range(1, Some(0)), (0..1, None));
-	assert_eq!(split_range(100, Some(50)), (0..50, Some(50..100)));
-	assert_eq!(split_range(100, Some(99)), (0..99, Some(99..100)));
-}
-
 
 #[test]
 fn should_return_runtime_version() {
@@ -470,6 +538,7 @@ fn should_return_runtime_version() {
 		SubscriptionManager::new(Arc::new(TaskExecutor)),
 		DenyUnsafe::No,
 		None,
+		Default::default(),
 	);
 
 	let result = "{\"specName\":\"test\",\"implName\":\"parity-test\",\"authoringVersion\":1,\
@@ -498,6 +567,7 @@ fn should_notify_on_runtime_version_initially() {
 			SubscriptionManager::new(Arc::new(TaskExecutor)),
 			DenyUnsafe::No,
 			None,
+			Default::default(),
 		);
 
 		api.subscribe_runtime_version(Default::default(), subscriber);
@@ -23,7 +23,7 @@ use sp_runtime::{
 };
 use sp_state_machine::{
 	self, OverlayedChanges, Ext, ExecutionManager, StateMachine, ExecutionStrategy,
-	backend::Backend as _, StorageProof,
+	ExecutionLimits, backend::Backend as _, StorageProof,
 };
 use sc_executor::{RuntimeVersion, RuntimeInfo, NativeVersion};
 use sp_externalities::Extensions;
@@ -172,6 +172,42 @@ where
 		Ok(return_data.into_encoded())
 	}
 
+	fn call_with_limits(
+		&self,
+		id: &BlockId<Block>,
+		method: &str,
+		call_data: &[u8],
+		strategy: ExecutionStrategy,
+		extensions: Option<Extensions>,
+		limits: ExecutionLimits,
+	) -> sp_blockchain::Result<Vec<u8>> {
+		let mut changes = OverlayedChanges::default();
+		let changes_trie = backend::changes_tries_state_at_block(
+			id, self.backend.changes_trie_storage()
+		)?;
+		let state = self.backend.state_at(*id)?;
+		let state_runtime_code = sp_state_machine::backend::BackendRuntimeCode::new(&state);
+		let runtime_code = state_runtime_code.runtime_code()
+			.map_err(sp_blockchain::Error::RuntimeCode)?;
+		let runtime_code = self.check_override(runtime_code, id)?;
+
+		let (return_data, _) = sp_state_machine::execute_with_limits::<_, _, NumberFor<Block>, _, _>(
+			&state,
+			changes_trie,
+			&mut changes,
+			&self.executor,
+			self.spawn_handle.clone(),
+			method,
+			call_data,
+			extensions.unwrap_or_default(),
+			&runtime_code,
+			strategy,
+			limits,
+		).map_err(sp_blockchain::Error::from_state)?;
+
+		Ok(return_data)
+	}
+
 	fn contextual_call<
 		EM: Fn(
 			Result<NativeOrEncoded<R>, Self::Error>,
@@ -51,7 +51,8 @@ use sp_runtime::{
 use sp_state_machine::{
 	DBValue, Backend as StateBackend, ChangesTrieAnchorBlockId,
 	prove_read, prove_child_read, ChangesTrieRootsStorage, ChangesTrieStorage,
-	ChangesTrieConfigurationRange, key_changes, key_changes_proof,
+	ChangesTrieConfigurationRange, key_changes, key_changes_in_range, key_changes_proof,
+	KeyChangesInRange,
 	prove_range_read_with_size, read_range_proof_check,
 };
 use sc_executor::RuntimeVersion;
@@ -280,14 +281,20 @@ impl<B, E, Block, RA> LockImportRun<Block, B> for Client<B, E, Block, RA>
 				op: self.backend.begin_operation()?,
 				notify_imported: None,
 				notify_finalized: Vec::new(),
+				notify_stale_heads: Vec::new(),
 			};
 
 			let r = f(&mut op)?;
 
-			let ClientImportOperation { op, notify_imported, notify_finalized } = op;
+			let ClientImportOperation {
+				op,
+				notify_imported,
+				notify_finalized,
+				notify_stale_heads,
+			} = op;
 			self.backend.commit_operation(op)?;
 
-			self.notify_finalized(notify_finalized)?;
+			self.notify_finalized(notify_finalized, notify_stale_heads)?;
 			self.notify_imported(notify_imported)?;
 
 			Ok(r)
@@ -881,6 +888,10 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 		if make_notifications || tree_route.is_some() {
 			if finalized {
 				operation.notify_finalized.push(hash);
+				let mut stale_heads = self.backend.blockchain()
+					.displaced_leaves_after_finalizing(*import_headers.post().number())?;
+				stale_heads.retain(|leaf| *leaf != parent_hash);
+				operation.notify_stale_heads = stale_heads;
 			}
 
 			operation.notify_imported = Some(ImportSummary {
@@ -1000,11 +1011,15 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			return Err(sp_blockchain::Error::NotInFinalizedChain);
 		}
 
-		let route_from_best = sp_blockchain::tree_route(self.backend.blockchain(), best_block, block)?;
+		let block_number = self.backend.blockchain().expect_block_number_from_id(&BlockId::Hash(block))?;
+		let stale_heads = self.backend.blockchain().displaced_leaves_after_finalizing(block_number)?;
 
-		// if the block is not a direct ancestor of the current best chain,
-		// then some other block is the common ancestor.
-		if route_from_best.common_block().hash != block {
+		// if the best block is one of the leaves discarded by finality, or otherwise not a
+		// descendant of the block, then some other block is the common ancestor.
+		let is_best_stale = stale_heads.contains(&best_block) ||
+			sp_blockchain::tree_route(self.backend.blockchain(), best_block, block)?
+				.common_block().hash != block;
+		if is_best_stale {
 			// NOTE: we're setting the finalized block as best block, this might
 			// be slightly inaccurate since we might have a "better" block
 			// further along this chain, but since best chain selection logic is
@@ -1032,6 +1047,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			for finalized in &enacted[start..] {
 				operation.notify_finalized.push(finalized.hash);
 			}
+			operation.notify_stale_heads = stale_heads;
 		}
 
 		Ok(())
@@ -1040,6 +1056,7 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 	fn notify_finalized(
 		&self,
 		notify_finalized: Vec<Block::Hash>,
+		stale_heads: Vec<Block::Hash>,
 	) -> sp_blockchain::Result<()> {
 		let mut sinks = self.finality_notification_sinks.lock();
 
@@ -1070,16 +1087,25 @@ impl<B, E, Block, RA> Client<B, E, Block, RA> where
 			);
 		}
 
-		for finalized_hash in notify_finalized {
+		// the stale heads are only reported along with the last finalized block.
+		let last_index = notify_finalized.len() - 1;
+		let mut stale_heads = Some(stale_heads);
+		for (index, finalized_hash) in notify_finalized.into_iter().enumerate() {
 			let header = self.header(&BlockId::Hash(finalized_hash))?
 				.expect(
 					"Header already known to exist in DB because it is \
 					indicated in the tree route; qed"
 				);
 
+			let stale_heads = if index == last_index {
+				stale_heads.take().unwrap_or_default()
+			} else {
+				Vec::new()
+			};
 			let notification = FinalityNotification {
 				header,
 				hash: finalized_hash,
+				stale_heads,
 			};
 
 			sinks.retain(|sink| sink.unbounded_send(notification.clone()).is_ok());
@@ -1641,6 +1667,71 @@ impl<B, E, Block, RA> StorageProvider<Block, B> for Client<B, E, Block, RA> wher
 
 		Ok(result)
 	}
+
+	fn key_changes_in_range(
+		&self,
+		first: NumberFor<Block>,
+		last: BlockId<Block>,
+		storage_key: Option<&PrefixedStorageKey>,
+		key: &StorageKey
+	) -> sp_blockchain::Result<KeyChangesInRange<NumberFor<Block>>> {
+		let last_number = self.backend.blockchain().expect_block_number_from_id(&last)?;
+		let last_hash = self.backend.blockchain().expect_block_hash_from_id(&last)?;
+		if first > last_number {
+			return Err(sp_blockchain::Error::ChangesTrieAccessFailed("Invalid changes trie range".into()));
+		}
+
+		let storage = match self.backend.changes_trie_storage() {
+			Some(storage) => storage,
+			None => return Ok(KeyChangesInRange {
+				changes: Vec::new(),
+				unchecked: vec![(first, last_number)],
+			}),
+		};
+
+		// unlike `require_changes_trie`, continue past the ranges where changes tries are disabled
+		let mut configs = Vec::new();
+		let mut current = last_hash;
+		loop {
+			let config_range = storage.configuration_at(&BlockId::Hash(current))?;
+			if let Some(config) = config_range.config {
+				configs.push((config_range.zero.0, config_range.end, config));
+			}
+
+			if config_range.zero.0 < first || config_range.zero.0.is_zero() {
+				break;
+			}
+
+			current = *self.backend.blockchain().expect_header(BlockId::Hash(config_range.zero.1))?.parent_hash();
+		}
+
+		let config_ranges = configs.iter()
+			.map(|(config_zero, config_end, config)| {
+				let anchor = match config_end {
+					Some((config_end_number, config_end_hash)) if last_number > *config_end_number =>
+						ChangesTrieAnchorBlockId { hash: convert_hash(config_end_hash), number: *config_end_number },
+					_ => ChangesTrieAnchorBlockId { hash: convert_hash(&last_hash), number: last_number },
+				};
+				let config_range = ChangesTrieConfigurationRange {
+					config,
+					zero: *config_zero,
+					end: config_end.as_ref().map(|(config_end_number, _)| *config_end_number),
+				};
+				(config_range, anchor)
+			})
+			.collect::<Vec<_>>();
+		let first_available = storage.oldest_pruned_digest_range_end() + One::one();
+		key_changes_in_range::<HashFor<Block>, _>(
+			&config_ranges,
+			storage.storage(),
+			first_available,
+			first,
+			last_number,
+			self.backend.blockchain().info().best_number,
+			storage_key,
+			&key.0,
+		).map_err(|err| sp_blockchain::Error::ChangesTrieAccessFailed(err))
+	}
 }
 
 impl<B, E, Block, RA> HeaderMetadata<Block> for Client<B, E, Block, RA> where
@@ -1790,6 +1881,7 @@ impl<B, E, Block, RA> CallApiAt<Block> for Client<B, E, Block, RA> where
 		let (manager, extensions) = self.execution_extensions.manager_and_extensions(
 			at,
 			params.context,
+			params.function,
 		);
 
 		self.executor.contextual_call::<fn(_,_) -> _, _, _>(
@@ -2057,6 +2149,10 @@ impl<B, E, Block, RA> BlockBackend<Block> for Client<B, E, Block, RA>
 		self.body(id)
 	}
 
+	fn block_extrinsics_count(&self, id: &BlockId<Block>) -> sp_blockchain::Result<Option<u32>> {
+		self.backend.blockchain().extrinsics_count(*id)
+	}
+
 	fn block(&self, id: &BlockId<Block>) -> sp_blockchain::Result<Option<SignedBlock<Block>>> {
 		Ok(match (self.header(id)?, self.body(id)?, self.justifications(id)?) {
 			(Some(header), Some(extrinsics), justifications) =>
@@ -33,6 +33,7 @@ pub mod client;
 #[cfg(not(feature = "test-helpers"))]
 mod client;
 mod task_manager;
+mod storage_monitor;
 
 use std::{io, pin::Pin};
 use std::net::SocketAddr;
@@ -78,6 +79,9 @@ pub use sc_network::config::{
 pub use sc_tracing::TracingReceiver;
 pub use task_manager::SpawnTaskHandle;
 pub use task_manager::TaskManager;
+pub use storage_monitor::{LowSpaceAction, StorageMonitorParams};
+#[cfg(not(target_os = "unknown"))]
+pub use storage_monitor::{spawn_storage_monitor, run_storage_monitor};
 pub use sp_consensus::import_queue::ImportQueue;
 pub use self::client::{LocalCallExecutor, ClientConfig};
 use sc_client_api::{blockchain::HeaderBackend, BlockchainEvents};
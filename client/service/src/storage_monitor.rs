@@ -0,0 +1,200 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Monitoring of the free space left on the disk holding the database.
+//!
+//! Running out of disk space while the database is writing can corrupt it. The monitor
+//! periodically checks the free space, warns when it runs low and can stop the node before the
+//! disk is full.
+
+use std::time::Duration;
+#[cfg(not(target_os = "unknown"))]
+use std::path::{Path, PathBuf};
+
+#[cfg(not(target_os = "unknown"))]
+use futures_timer::Delay;
+#[cfg(not(target_os = "unknown"))]
+use log::{error, warn};
+
+#[cfg(not(target_os = "unknown"))]
+use crate::{config::DatabaseConfig, TaskManager};
+
+#[cfg(not(target_os = "unknown"))]
+const LOG_TARGET: &str = "storage-monitor";
+
+/// What to do once the free space drops below [`StorageMonitorParams::critical_threshold`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LowSpaceAction {
+	/// Only log an error on every check.
+	Warn,
+	/// Stop the node.
+	Shutdown,
+}
+
+/// Configuration of the storage monitor.
+#[derive(Debug, Clone)]
+pub struct StorageMonitorParams {
+	/// Free space, in bytes, below which a warning is logged.
+	pub warn_threshold: u64,
+	/// Free space, in bytes, below which `action` is taken.
+	pub critical_threshold: u64,
+	/// What to do once the free space drops below `critical_threshold`.
+	pub action: LowSpaceAction,
+	/// Time between two checks of the free space.
+	pub polling_period: Duration,
+}
+
+impl Default for StorageMonitorParams {
+	fn default() -> Self {
+		Self {
+			warn_threshold: 1024 * 1024 * 1024,
+			critical_threshold: 128 * 1024 * 1024,
+			action: LowSpaceAction::Shutdown,
+			polling_period: Duration::from_secs(10),
+		}
+	}
+}
+
+/// Free space relative to the configured thresholds.
+#[cfg(not(target_os = "unknown"))]
+#[derive(Debug, PartialEq, Eq)]
+enum SpaceStatus {
+	Sufficient,
+	Low,
+	Critical,
+}
+
+impl StorageMonitorParams {
+	/// Stop the node once less than `critical_threshold` bytes are free, and warn from eight times
+	/// that.
+	pub fn with_critical_threshold(critical_threshold: u64) -> Self {
+		Self {
+			warn_threshold: critical_threshold.saturating_mul(8),
+			critical_threshold,
+			..Default::default()
+		}
+	}
+
+	#[cfg(not(target_os = "unknown"))]
+	fn status(&self, available: u64) -> SpaceStatus {
+		if available < self.critical_threshold {
+			SpaceStatus::Critical
+		} else if available < self.warn_threshold {
+			SpaceStatus::Low
+		} else {
+			SpaceStatus::Sufficient
+		}
+	}
+}
+
+/// Spawn the storage monitor for the database described by `database`.
+///
+/// The monitor is spawned as an essential task, so stopping it because of
+/// [`LowSpaceAction::Shutdown`] stops the node. Nothing is spawned for databases without a path.
+#[cfg(not(target_os = "unknown"))]
+pub fn spawn_storage_monitor(
+	task_manager: &TaskManager,
+	database: &DatabaseConfig,
+	params: StorageMonitorParams,
+) {
+	if let Some(path) = database.path() {
+		task_manager.spawn_essential_handle().spawn(
+			"storage-monitor",
+			run_storage_monitor(path.to_owned(), params),
+		);
+	}
+}
+
+/// Check the free space at `path` until it drops below the critical threshold while
+/// [`LowSpaceAction::Shutdown`] is configured, then return.
+#[cfg(not(target_os = "unknown"))]
+pub async fn run_storage_monitor(path: PathBuf, params: StorageMonitorParams) {
+	loop {
+		match available_space(&path) {
+			Ok(available) => match params.status(available) {
+				SpaceStatus::Sufficient => {},
+				SpaceStatus::Low => warn!(
+					target: LOG_TARGET,
+					"Low disk space on {}: {} MiB left",
+					path.display(),
+					available / (1024 * 1024),
+				),
+				SpaceStatus::Critical => {
+					error!(
+						target: LOG_TARGET,
+						"Critically low disk space on {}: {} MiB left",
+						path.display(),
+						available / (1024 * 1024),
+					);
+					if params.action == LowSpaceAction::Shutdown {
+						error!(target: LOG_TARGET, "Stopping the node to protect the database");
+						return
+					}
+				},
+			},
+			Err(e) => warn!(
+				target: LOG_TARGET,
+				"Failed to check the free disk space on {}: {}",
+				path.display(),
+				e,
+			),
+		}
+
+		Delay::new(params.polling_period).await;
+	}
+}
+
+#[cfg(not(target_os = "unknown"))]
+fn available_space(path: &Path) -> std::io::Result<u64> {
+	// The database directory may not exist yet right after startup.
+	let path = path.ancestors().find(|p| p.exists()).unwrap_or(path);
+	fs2::available_space(path)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn status_follows_thresholds() {
+		let params = StorageMonitorParams {
+			warn_threshold: 100,
+			critical_threshold: 10,
+			..Default::default()
+		};
+
+		assert_eq!(params.status(1000), SpaceStatus::Sufficient);
+		assert_eq!(params.status(100), SpaceStatus::Sufficient);
+		assert_eq!(params.status(99), SpaceStatus::Low);
+		assert_eq!(params.status(10), SpaceStatus::Low);
+		assert_eq!(params.status(9), SpaceStatus::Critical);
+	}
+
+	#[test]
+	fn monitor_stops_on_critical_space() {
+		let dir = tempfile::tempdir().unwrap();
+		let params = StorageMonitorParams {
+			warn_threshold: u64::max_value(),
+			critical_threshold: u64::max_value(),
+			action: LowSpaceAction::Shutdown,
+			polling_period: Duration::from_millis(10),
+		};
+
+		futures::executor::block_on(run_storage_monitor(dir.path().join("db"), params));
+	}
+}
@@ -29,7 +29,7 @@ use substrate_test_runtime_client::{
 	BlockBuilderExt, DefaultTestClientBuilderExt, TestClientBuilderExt, ClientExt,
 };
 use sc_client_api::{
-	StorageProvider, BlockBackend, in_mem, BlockchainEvents,
+	StorageProvider, BlockBackend, in_mem, BlockchainEvents, KeyChangesInRange,
 };
 use sc_client_db::{
 	Backend, DatabaseSettings, DatabaseSettingsSrc, PruningMode, KeepBlocks, TransactionStorageMode
@@ -1268,6 +1268,41 @@ fn key_changes_works() {
 	}
 }
 
+#[test]
+fn key_changes_in_range_works() {
+	let (client, _, test_cases) = prepare_client_with_key_changes();
+
+	for (index, (begin, end, key, expected_result)) in test_cases.into_iter().enumerate() {
+		let end = client.block_hash(end).unwrap().unwrap();
+		let actual_result = client.key_changes_in_range(
+			begin,
+			BlockId::Hash(end),
+			None,
+			&StorageKey(key),
+		).unwrap();
+		if !actual_result.is_complete() || actual_result.changes != expected_result {
+			panic!(
+				"Failed test {}: actual = {:?}, expected = {:?}",
+				index,
+				actual_result,
+				expected_result,
+			);
+		}
+	}
+
+	// changes tries are not enabled for clients by default
+	let client = substrate_test_runtime_client::new();
+	let genesis_hash = client.block_hash(0).unwrap().unwrap();
+	let result = client.key_changes_in_range(
+		0,
+		BlockId::Hash(genesis_hash),
+		None,
+		&StorageKey(vec![1]),
+	).unwrap();
+	assert_eq!(result, KeyChangesInRange { changes: Vec::new(), unchecked: vec![(0, 0)] });
+	assert!(!result.is_complete());
+}
+
 #[test]
 fn import_with_justification() {
 	let mut client = substrate_test_runtime_client::new();
@@ -1420,6 +1455,8 @@ fn finalizing_diverged_block_should_trigger_reorg() {
 		a2.hash(),
 	);
 
+	let mut finality_notifications = client.finality_notification_stream();
+
 	// we finalize block B1 which is on a different branch from current best
 	// which should trigger a re-org.
 	ClientExt::finalize_block(&client, BlockId::Hash(b1.hash()), None).unwrap();
@@ -1430,6 +1467,11 @@ fn finalizing_diverged_block_should_trigger_reorg() {
 		b1.hash(),
 	);
 
+	// A2 is discarded by the finalization
+	let notification = finality_notifications.try_next().unwrap().unwrap();
+	assert_eq!(notification.hash, b1.hash());
+	assert_eq!(notification.stale_heads, vec![a2.hash()]);
+
 	// and B1 should be the new best block (`finalize_block` as no way of
 	// knowing about B2)
 	assert_eq!(
@@ -1539,6 +1581,7 @@ fn doesnt_import_blocks_that_revert_finality() {
 		DatabaseSettings {
 			state_cache_size: 1 << 20,
 			state_cache_child_ratio: None,
+			trie_node_cache_size: 0,
 			state_pruning: PruningMode::ArchiveAll,
 			keep_blocks: KeepBlocks::All,
 			transaction_storage: TransactionStorageMode::BlockBody,
@@ -1743,6 +1786,7 @@ fn returns_status_for_pruned_blocks() {
 		DatabaseSettings {
 			state_cache_size: 1 << 20,
 			state_cache_child_ratio: None,
+			trie_node_cache_size: 0,
 			state_pruning: PruningMode::keep_blocks(1),
 			keep_blocks: KeepBlocks::All,
 			transaction_storage: TransactionStorageMode::BlockBody,
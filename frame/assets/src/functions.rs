@@ -43,6 +43,15 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			.unwrap_or_else(Zero::zero)
 	}
 
+	/// Get the assets held by `who` together with their balances.
+	///
+	/// Iterates over all assets, so it is meant to be used off-chain, e.g. through a runtime API.
+	pub fn account_balances(who: &T::AccountId) -> Vec<(T::AssetId, T::Balance)> {
+		Asset::<T, I>::iter_keys()
+			.filter_map(|id| Account::<T, I>::try_get(id, who).ok().map(|a| (id, a.balance)))
+			.collect()
+	}
+
 	pub(super) fn new_account(
 		who: &T::AccountId,
 		d: &mut AssetDetails<T::Balance, T::AccountId, DepositBalanceOf<T, I>>,
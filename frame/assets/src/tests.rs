@@ -34,6 +34,24 @@ fn basic_minting_should_work() {
 	});
 }
 
+#[test]
+fn account_balances_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 0, 1, true, 1));
+		assert_ok!(Assets::force_create(Origin::root(), 1, 1, true, 1));
+		assert_ok!(Assets::force_create(Origin::root(), 2, 1, true, 1));
+		assert_ok!(Assets::mint(Origin::signed(1), 0, 1, 100));
+		assert_ok!(Assets::mint(Origin::signed(1), 2, 1, 50));
+		assert_ok!(Assets::mint(Origin::signed(1), 1, 2, 10));
+
+		let mut balances = Assets::account_balances(&1);
+		balances.sort();
+		assert_eq!(balances, vec![(0, 100), (2, 50)]);
+		assert_eq!(Assets::account_balances(&2), vec![(1, 10)]);
+		assert_eq!(Assets::account_balances(&3), vec![]);
+	});
+}
+
 #[test]
 fn approval_lifecycle_works() {
 	new_test_ext().execute_with(|| {
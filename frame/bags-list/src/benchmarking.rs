@@ -0,0 +1,114 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bags list pallet benchmarking.
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{ensure, traits::Get};
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+benchmarks! {
+	// Worst case: the account is in the middle of its bag and moves to the end of another
+	// non-empty bag, so both its neighbours and the tail of the destination bag are updated.
+	rebag {
+		let thresholds = T::BagThresholds::get();
+		let origin_upper = *thresholds.first().expect("the benchmark needs bag thresholds");
+		let dest_upper = thresholds.get(1).copied().unwrap_or(VoteWeight::MAX);
+
+		for (i, name) in ["head", "middle", "tail"].iter().enumerate() {
+			let id: T::AccountId = account(name, i as u32, SEED);
+			List::<T>::insert(id, origin_upper).expect("accounts are unique");
+		}
+		let dest_tail: T::AccountId = account("dest_tail", 0, SEED);
+		List::<T>::insert(dest_tail, dest_upper).expect("accounts are unique");
+
+		let middle: T::AccountId = account("middle", 1, SEED);
+		T::VoteWeightProvider::set_vote_weight_of(&middle, dest_upper);
+		let caller = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), middle.clone())
+	verify {
+		ensure!(
+			ListNodes::<T>::get(&middle).map(|node| node.bag_upper) == Some(dest_upper),
+			"account was not rebagged",
+		);
+		List::<T>::sanity_check()?;
+	}
+
+	// Worst case: the account goes to the end of a non-empty bag.
+	on_insert {
+		let upper = *T::BagThresholds::get().first().expect("the benchmark needs bag thresholds");
+		let tail: T::AccountId = account("tail", 0, SEED);
+		List::<T>::insert(tail, upper).expect("accounts are unique");
+		let new: T::AccountId = account("new", 0, SEED);
+	}: {
+		<Pallet<T> as SortedListProvider<T::AccountId>>::on_insert(new.clone(), upper)
+			.expect("accounts are unique");
+	}
+	verify {
+		ensure!(ListNodes::<T>::contains_key(&new), "account was not inserted");
+		List::<T>::sanity_check()?;
+	}
+
+	// Worst case: the same move as `rebag`, after checking that the account is listed like
+	// staking does.
+	on_update {
+		let thresholds = T::BagThresholds::get();
+		let origin_upper = *thresholds.first().expect("the benchmark needs bag thresholds");
+		let dest_upper = thresholds.get(1).copied().unwrap_or(VoteWeight::MAX);
+
+		for (i, name) in ["head", "middle", "tail"].iter().enumerate() {
+			let id: T::AccountId = account(name, i as u32, SEED);
+			List::<T>::insert(id, origin_upper).expect("accounts are unique");
+		}
+		let dest_tail: T::AccountId = account("dest_tail", 0, SEED);
+		List::<T>::insert(dest_tail, dest_upper).expect("accounts are unique");
+
+		let middle: T::AccountId = account("middle", 1, SEED);
+	}: {
+		if <Pallet<T> as SortedListProvider<T::AccountId>>::contains(&middle) {
+			<Pallet<T> as SortedListProvider<T::AccountId>>::on_update(&middle, dest_upper);
+		}
+	}
+	verify {
+		ensure!(
+			ListNodes::<T>::get(&middle).map(|node| node.bag_upper) == Some(dest_upper),
+			"account was not moved",
+		);
+		List::<T>::sanity_check()?;
+	}
+
+	// Worst case: the account is in the middle of its bag, so both its neighbours are updated.
+	on_remove {
+		let upper = *T::BagThresholds::get().first().expect("the benchmark needs bag thresholds");
+		for (i, name) in ["head", "middle", "tail"].iter().enumerate() {
+			let id: T::AccountId = account(name, i as u32, SEED);
+			List::<T>::insert(id, upper).expect("accounts are unique");
+		}
+		let middle: T::AccountId = account("middle", 1, SEED);
+	}: {
+		<Pallet<T> as SortedListProvider<T::AccountId>>::on_remove(&middle);
+	}
+	verify {
+		ensure!(!ListNodes::<T>::contains_key(&middle), "account was not removed");
+		List::<T>::sanity_check()?;
+	}
+}
+
+impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Runtime);
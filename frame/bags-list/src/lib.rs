@@ -0,0 +1,221 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Bags List Pallet
+//!
+//! A semi-sorted list of accounts, implementing [`SortedListProvider`]. Staking uses it to take
+//! the heaviest nominators first when the election snapshot cannot hold all of them.
+//!
+//! ## Overview
+//!
+//! Accounts are put in bags by vote weight. Each bag has an upper threshold taken from
+//! [`Config::BagThresholds`], and holds the accounts whose weight is at most that threshold and
+//! above the threshold of the previous bag. Accounts heavier than the last threshold go in an
+//! implicit bag with the threshold `VoteWeight::MAX`.
+//!
+//! Within a bag, accounts form a doubly linked list in insertion order, so inserting, moving and
+//! removing an account only touches a few storage items. Iterating the list goes through the
+//! bags from the heaviest to the lightest: the list is sorted up to the granularity of the bags.
+//!
+//! The pallet using the list reports the weight changes it knows of through
+//! [`SortedListProvider::on_update`]. If the weight of an account changes otherwise, e.g. with the
+//! total issuance, anyone can move the account to its proper bag with [`Call::rebag`].
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `rebag` - Move an account to the bag matching its current vote weight.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_election_provider_support::{SortedListProvider, VoteWeight, VoteWeightProvider};
+use frame_support::weights::Weight;
+use sp_std::prelude::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+mod list;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use list::{Bag, List, ListError, Node};
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	#[pallet::generate_store(pub(super) trait Store)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The current vote weight of the accounts, used by [`Call::rebag`].
+		type VoteWeightProvider: VoteWeightProvider<Self::AccountId>;
+
+		/// The upper thresholds of the bags, strictly increasing.
+		///
+		/// An account of weight `w` goes in the bag of the first threshold `t` with `w <= t`. The
+		/// accounts heavier than the last threshold go in a bag with the threshold
+		/// `VoteWeight::MAX`.
+		///
+		/// Changing the thresholds of a running chain is possible, but the accounts only move to
+		/// the new bags when they are rebagged. Until then, they are iterated with the bag of
+		/// their old threshold, and not at all if that threshold was removed.
+		#[pallet::constant]
+		type BagThresholds: Get<&'static [VoteWeight]>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The number of accounts in the list.
+	#[pallet::storage]
+	pub(crate) type CounterForListNodes<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The node of each account in the list.
+	#[pallet::storage]
+	pub(crate) type ListNodes<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, Node<T::AccountId>>;
+
+	/// The non-empty bags, by upper threshold.
+	#[pallet::storage]
+	pub(crate) type ListBags<T: Config> =
+		StorageMap<_, Twox64Concat, VoteWeight, Bag<T::AccountId>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	#[pallet::metadata(T::AccountId = "AccountId")]
+	pub enum Event<T: Config> {
+		/// An account moved from one bag to another. \[who, from, to\]
+		Rebagged(T::AccountId, VoteWeight, VoteWeight),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is not in the list.
+		NotInList,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn integrity_test() {
+			let thresholds = T::BagThresholds::get();
+			assert!(
+				thresholds.windows(2).all(|pair| pair[0] < pair[1]),
+				"bag thresholds must be strictly increasing",
+			);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Move `dislocated` to the bag matching its current vote weight.
+		///
+		/// Anyone can call this, e.g. once the weight of `dislocated` changed without the list
+		/// being told. Does nothing if `dislocated` is already in the right bag.
+		///
+		/// Emits `Rebagged` if `dislocated` moved.
+		#[pallet::weight(T::WeightInfo::rebag())]
+		pub fn rebag(origin: OriginFor<T>, dislocated: T::AccountId) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(ListNodes::<T>::contains_key(&dislocated), Error::<T>::NotInList);
+			let weight = T::VoteWeightProvider::vote_weight(&dislocated);
+			Self::do_rebag(&dislocated, weight);
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Move `account` to the bag matching `new_weight`, if it is in the list.
+	///
+	/// Returns the upper thresholds of the bags it moved from and to, if it moved.
+	pub fn do_rebag(account: &T::AccountId, new_weight: VoteWeight) -> Option<(VoteWeight, VoteWeight)> {
+		let moved = List::<T>::update(account, new_weight);
+		if let Some((from, to)) = moved {
+			Self::deposit_event(Event::<T>::Rebagged(account.clone(), from, to));
+		}
+		moved
+	}
+}
+
+impl<T: Config> SortedListProvider<T::AccountId> for Pallet<T> {
+	type Error = ListError;
+
+	fn iter() -> Box<dyn Iterator<Item = T::AccountId>> {
+		Box::new(List::<T>::iter().map(|node| node.id))
+	}
+
+	fn count() -> u32 {
+		CounterForListNodes::<T>::get()
+	}
+
+	fn contains(id: &T::AccountId) -> bool {
+		ListNodes::<T>::contains_key(id)
+	}
+
+	fn on_insert(id: T::AccountId, weight: VoteWeight) -> Result<(), ListError> {
+		List::<T>::insert(id, weight)
+	}
+
+	fn on_update(id: &T::AccountId, weight: VoteWeight) {
+		Self::do_rebag(id, weight);
+	}
+
+	fn on_remove(id: &T::AccountId) {
+		List::<T>::remove(id)
+	}
+
+	fn on_insert_weight() -> Weight {
+		T::WeightInfo::on_insert()
+	}
+
+	fn on_update_weight() -> Weight {
+		T::WeightInfo::on_update()
+	}
+
+	fn on_remove_weight() -> Weight {
+		T::WeightInfo::on_remove()
+	}
+
+	fn clear() {
+		List::<T>::clear()
+	}
+
+	fn regenerate(
+		all: impl IntoIterator<Item = T::AccountId>,
+		weight_of: Box<dyn Fn(&T::AccountId) -> VoteWeight>,
+	) -> u32 {
+		List::<T>::regenerate(all, weight_of)
+	}
+
+	fn sanity_check() -> Result<(), &'static str> {
+		List::<T>::sanity_check()
+	}
+}
@@ -0,0 +1,218 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The list of bags and the operations on it.
+
+use codec::{Encode, Decode};
+use frame_support::traits::Get;
+use sp_runtime::RuntimeDebug;
+use sp_std::{marker::PhantomData, prelude::*};
+use crate::{Config, CounterForListNodes, ListBags, ListNodes, VoteWeight};
+
+/// Error of the list operations.
+#[derive(RuntimeDebug, Clone, Copy, PartialEq, Eq)]
+pub enum ListError {
+	/// The account is already in the list.
+	Duplicate,
+}
+
+/// A bag of accounts, as a doubly linked list of [`Node`]s.
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq)]
+pub struct Bag<AccountId> {
+	/// The first account of the bag.
+	pub head: Option<AccountId>,
+	/// The last account of the bag.
+	pub tail: Option<AccountId>,
+}
+
+/// An account in a [`Bag`].
+#[derive(Encode, Decode, RuntimeDebug, Clone, PartialEq, Eq)]
+pub struct Node<AccountId> {
+	/// The account.
+	pub id: AccountId,
+	/// The previous account of the bag.
+	pub prev: Option<AccountId>,
+	/// The next account of the bag.
+	pub next: Option<AccountId>,
+	/// The upper threshold of the bag of the account.
+	pub bag_upper: VoteWeight,
+}
+
+/// The list of bags stored by the pallet.
+pub struct List<T: Config>(PhantomData<T>);
+
+impl<T: Config> List<T> {
+	/// The upper threshold of the bag of an account of the given weight.
+	pub fn notional_bag_for(weight: VoteWeight) -> VoteWeight {
+		let thresholds = T::BagThresholds::get();
+		let index = match thresholds.binary_search(&weight) {
+			Ok(index) | Err(index) => index,
+		};
+		thresholds.get(index).copied().unwrap_or(VoteWeight::MAX)
+	}
+
+	/// All the nodes of the list, from the heaviest bag to the lightest.
+	pub fn iter() -> impl Iterator<Item = Node<T::AccountId>> {
+		let thresholds = T::BagThresholds::get();
+		let implicit_upper = match thresholds.last() {
+			Some(&VoteWeight::MAX) => None,
+			_ => Some(VoteWeight::MAX),
+		};
+		implicit_upper
+			.into_iter()
+			.chain(thresholds.iter().rev().copied())
+			.filter_map(ListBags::<T>::get)
+			.flat_map(|bag| {
+				sp_std::iter::successors(
+					bag.head.and_then(ListNodes::<T>::get),
+					|node| node.next.as_ref().and_then(ListNodes::<T>::get),
+				)
+			})
+	}
+
+	/// Insert `id` at the end of the bag of `weight`.
+	pub fn insert(id: T::AccountId, weight: VoteWeight) -> Result<(), ListError> {
+		if ListNodes::<T>::contains_key(&id) {
+			return Err(ListError::Duplicate)
+		}
+
+		Self::push_back(id, Self::notional_bag_for(weight));
+		CounterForListNodes::<T>::mutate(|count| *count = count.saturating_add(1));
+		Ok(())
+	}
+
+	/// Remove `id` from the list, if it is in it.
+	pub fn remove(id: &T::AccountId) {
+		if let Some(node) = ListNodes::<T>::take(id) {
+			Self::unlink(&node);
+			CounterForListNodes::<T>::mutate(|count| *count = count.saturating_sub(1));
+		}
+	}
+
+	/// Move `id` to the end of the bag of `weight` if it is in another bag.
+	///
+	/// Returns the upper thresholds of the bags it moved from and to, if it moved.
+	pub fn update(id: &T::AccountId, weight: VoteWeight) -> Option<(VoteWeight, VoteWeight)> {
+		let node = ListNodes::<T>::get(id)?;
+		let bag_upper = Self::notional_bag_for(weight);
+		if bag_upper == node.bag_upper {
+			return None
+		}
+
+		Self::unlink(&node);
+		Self::push_back(node.id, bag_upper);
+		Some((node.bag_upper, bag_upper))
+	}
+
+	/// Remove all the accounts of the list.
+	pub fn clear() {
+		ListNodes::<T>::remove_all(None);
+		ListBags::<T>::remove_all(None);
+		CounterForListNodes::<T>::kill();
+	}
+
+	/// Replace the accounts of the list with `all`. Returns the number of accounts inserted.
+	pub fn regenerate(
+		all: impl IntoIterator<Item = T::AccountId>,
+		weight_of: Box<dyn Fn(&T::AccountId) -> VoteWeight>,
+	) -> u32 {
+		Self::clear();
+		all.into_iter()
+			.filter(|id| {
+				let weight = weight_of(id);
+				Self::insert(id.clone(), weight).is_ok()
+			})
+			.count() as u32
+	}
+
+	/// Check that the bags and nodes are consistent with each other and with the counter.
+	pub fn sanity_check() -> Result<(), &'static str> {
+		let mut iterated = 0u32;
+		for (bag_upper, bag) in ListBags::<T>::iter() {
+			let mut prev: Option<Node<T::AccountId>> = None;
+			let mut current = bag.head.clone();
+			while let Some(id) = current {
+				let node = ListNodes::<T>::get(&id).ok_or("node of a bag is missing")?;
+				frame_support::ensure!(node.bag_upper == bag_upper, "node is in the wrong bag");
+				frame_support::ensure!(
+					node.prev == prev.as_ref().map(|p| p.id.clone()),
+					"node has the wrong previous node",
+				);
+				iterated = iterated.saturating_add(1);
+				current = node.next.clone();
+				prev = Some(node);
+			}
+			frame_support::ensure!(
+				prev.map(|p| p.id) == bag.tail,
+				"bag tail is not its last node",
+			);
+		}
+
+		let count = CounterForListNodes::<T>::get();
+		frame_support::ensure!(iterated == count, "bags do not hold all the nodes");
+		frame_support::ensure!(
+			ListNodes::<T>::iter().count() as u32 == count,
+			"counter does not match the number of nodes",
+		);
+		Ok(())
+	}
+
+	/// Append `id` to the bag of threshold `bag_upper`, writing its node.
+	fn push_back(id: T::AccountId, bag_upper: VoteWeight) {
+		let mut bag = ListBags::<T>::get(bag_upper).unwrap_or(Bag { head: None, tail: None });
+		let node = Node { id: id.clone(), prev: bag.tail.clone(), next: None, bag_upper };
+
+		match &bag.tail {
+			Some(tail) => ListNodes::<T>::mutate(tail, |tail| if let Some(tail) = tail {
+				tail.next = Some(id.clone());
+			}),
+			None => bag.head = Some(id.clone()),
+		}
+		bag.tail = Some(id.clone());
+
+		ListNodes::<T>::insert(&id, node);
+		ListBags::<T>::insert(bag_upper, bag);
+	}
+
+	/// Remove `node` from its bag, without removing the node itself.
+	fn unlink(node: &Node<T::AccountId>) {
+		if let Some(prev) = &node.prev {
+			ListNodes::<T>::mutate(prev, |prev| if let Some(prev) = prev {
+				prev.next = node.next.clone();
+			});
+		}
+		if let Some(next) = &node.next {
+			ListNodes::<T>::mutate(next, |next| if let Some(next) = next {
+				next.prev = node.prev.clone();
+			});
+		}
+
+		if let Some(mut bag) = ListBags::<T>::get(node.bag_upper) {
+			if bag.head.as_ref() == Some(&node.id) {
+				bag.head = node.next.clone();
+			}
+			if bag.tail.as_ref() == Some(&node.id) {
+				bag.tail = node.prev.clone();
+			}
+			if bag.head.is_none() {
+				ListBags::<T>::remove(node.bag_upper);
+			} else {
+				ListBags::<T>::insert(node.bag_upper, bag);
+			}
+		}
+	}
+}
@@ -0,0 +1,128 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test environment for the bags list pallet.
+
+use super::*;
+use crate as pallet_bags_list;
+
+use std::{cell::RefCell, collections::HashMap};
+use sp_core::H256;
+use sp_runtime::{traits::{BlakeTwo256, IdentityLookup}, testing::Header};
+use frame_support::{parameter_types, construct_runtime};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+pub type AccountId = u64;
+
+construct_runtime!(
+	pub enum Runtime where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		BagsList: pallet_bags_list::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+}
+impl frame_system::Config for Runtime {
+	type BaseCallFilter = frame_support::traits::AllowAll;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type Origin = Origin;
+	type Call = Call;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = AccountId;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type Event = Event;
+	type BlockHashCount = BlockHashCount;
+	type DbWeight = ();
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ();
+	type OnSetCode = ();
+}
+
+thread_local! {
+	static VOTE_WEIGHTS: RefCell<HashMap<AccountId, VoteWeight>> = RefCell::new(HashMap::new());
+}
+
+/// Vote weights of the accounts, as staking would provide them.
+pub struct StakingMock;
+impl VoteWeightProvider<AccountId> for StakingMock {
+	fn vote_weight(who: &AccountId) -> VoteWeight {
+		VOTE_WEIGHTS.with(|w| w.borrow().get(who).copied().unwrap_or_default())
+	}
+
+	fn set_vote_weight_of(who: &AccountId, weight: VoteWeight) {
+		VOTE_WEIGHTS.with(|w| w.borrow_mut().insert(*who, weight));
+	}
+}
+
+pub const THRESHOLDS: [VoteWeight; 3] = [10, 20, 30];
+
+parameter_types! {
+	pub const BagThresholds: &'static [VoteWeight] = &THRESHOLDS;
+}
+
+impl Config for Runtime {
+	type Event = Event;
+	type VoteWeightProvider = StakingMock;
+	type BagThresholds = BagThresholds;
+	type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let t = frame_system::GenesisConfig::default().build_storage::<Runtime>().unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Insert the given accounts, with the weight of their bag, and check the list.
+pub(crate) fn insert_all(accounts: &[(AccountId, VoteWeight)]) {
+	for (id, weight) in accounts {
+		StakingMock::set_vote_weight_of(id, *weight);
+		assert_eq!(List::<Runtime>::insert(*id, *weight), Ok(()));
+	}
+	assert_eq!(List::<Runtime>::sanity_check(), Ok(()));
+}
+
+/// The accounts of the list, by bag from the heaviest to the lightest.
+pub(crate) fn bags() -> Vec<(VoteWeight, Vec<AccountId>)> {
+	let mut bags: Vec<(VoteWeight, Vec<AccountId>)> = vec![];
+	for node in List::<Runtime>::iter() {
+		match bags.last_mut() {
+			Some((upper, ids)) if *upper == node.bag_upper => ids.push(node.id),
+			_ => bags.push((node.bag_upper, vec![node.id])),
+		}
+	}
+	bags
+}
@@ -0,0 +1,113 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the bags list pallet.
+
+use super::*;
+use crate::mock::*;
+use frame_support::{assert_noop, assert_ok};
+
+#[test]
+fn notional_bag_for_works() {
+	assert_eq!(List::<Runtime>::notional_bag_for(0), 10);
+	assert_eq!(List::<Runtime>::notional_bag_for(10), 10);
+	assert_eq!(List::<Runtime>::notional_bag_for(11), 20);
+	assert_eq!(List::<Runtime>::notional_bag_for(30), 30);
+	assert_eq!(List::<Runtime>::notional_bag_for(31), VoteWeight::MAX);
+}
+
+#[test]
+fn iteration_goes_from_heaviest_bag_in_insertion_order() {
+	new_test_ext().execute_with(|| {
+		insert_all(&[(1, 5), (2, 100), (3, 15), (4, 2), (5, 40)]);
+
+		assert_eq!(bags(), vec![(VoteWeight::MAX, vec![2, 5]), (20, vec![3]), (10, vec![1, 4])]);
+		assert_eq!(
+			<BagsList as SortedListProvider<_>>::iter().collect::<Vec<_>>(),
+			vec![2, 5, 3, 1, 4],
+		);
+		assert_eq!(<BagsList as SortedListProvider<_>>::count(), 5);
+	});
+}
+
+#[test]
+fn insert_rejects_duplicates() {
+	new_test_ext().execute_with(|| {
+		insert_all(&[(1, 5)]);
+		assert_eq!(List::<Runtime>::insert(1, 25), Err(ListError::Duplicate));
+		assert_eq!(bags(), vec![(10, vec![1])]);
+	});
+}
+
+#[test]
+fn remove_works() {
+	new_test_ext().execute_with(|| {
+		insert_all(&[(1, 5), (2, 5), (3, 5), (4, 15)]);
+
+		// Middle of a bag.
+		List::<Runtime>::remove(&2);
+		assert_eq!(bags(), vec![(20, vec![4]), (10, vec![1, 3])]);
+		// Head and tail of a bag.
+		List::<Runtime>::remove(&1);
+		List::<Runtime>::remove(&3);
+		// Only account of a bag, which is removed.
+		List::<Runtime>::remove(&4);
+		// Not in the list.
+		List::<Runtime>::remove(&5);
+
+		assert_eq!(bags(), vec![]);
+		assert!(ListBags::<Runtime>::iter().next().is_none());
+		assert_eq!(CounterForListNodes::<Runtime>::get(), 0);
+		assert_eq!(List::<Runtime>::sanity_check(), Ok(()));
+	});
+}
+
+#[test]
+fn rebag_works() {
+	new_test_ext().execute_with(|| {
+		insert_all(&[(1, 5), (2, 5), (3, 5), (4, 15)]);
+
+		assert_noop!(BagsList::rebag(Origin::signed(0), 5), Error::<Runtime>::NotInList);
+
+		// Same bag, nothing happens.
+		StakingMock::set_vote_weight_of(&2, 8);
+		assert_ok!(BagsList::rebag(Origin::signed(0), 2));
+		assert_eq!(bags(), vec![(20, vec![4]), (10, vec![1, 2, 3])]);
+
+		StakingMock::set_vote_weight_of(&2, 18);
+		assert_ok!(BagsList::rebag(Origin::signed(0), 2));
+		assert_eq!(bags(), vec![(20, vec![4, 2]), (10, vec![1, 3])]);
+		System::assert_last_event(mock::Event::BagsList(crate::Event::Rebagged(2, 10, 20)));
+
+		StakingMock::set_vote_weight_of(&4, 1_000);
+		assert_ok!(BagsList::rebag(Origin::signed(0), 4));
+		assert_eq!(bags(), vec![(VoteWeight::MAX, vec![4]), (20, vec![2]), (10, vec![1, 3])]);
+		assert_eq!(List::<Runtime>::sanity_check(), Ok(()));
+	});
+}
+
+#[test]
+fn regenerate_works() {
+	new_test_ext().execute_with(|| {
+		insert_all(&[(1, 5), (2, 15)]);
+
+		let inserted = List::<Runtime>::regenerate(vec![3, 4, 3], Box::new(|id: &AccountId| *id * 10));
+		assert_eq!(inserted, 2);
+		assert_eq!(bags(), vec![(VoteWeight::MAX, vec![4]), (30, vec![3])]);
+		assert_eq!(List::<Runtime>::sanity_check(), Ok(()));
+	});
+}
@@ -57,6 +57,7 @@ fn setup_bounty<T: Config>(u: u32, d: u32) -> (
 	let deposit = T::BountyDepositBase::get() + T::DataDepositPerByte::get() * MAX_BYTES.into();
 	let _ = T::Currency::make_free_balance_be(&caller, deposit);
 	let curator = account("curator", u, SEED);
+	T::CuratorQualification::make_qualified(&curator);
 	let _ = T::Currency::make_free_balance_be(&curator, fee / 2u32.into());
 	let reason = vec![0; d as usize];
 	(caller, curator, fee, value, reason)
@@ -77,6 +78,15 @@ fn create_bounty<T: Config>() -> Result<(
 	Ok((curator_lookup, bounty_id))
 }
 
+// Make a proposed bounty pending automatic approval at `approve_at`, whatever its value.
+fn make_pending_auto_approval<T: Config>(bounty_id: BountyIndex, approve_at: T::BlockNumber) {
+	crate::Bounties::<T>::mutate(bounty_id, |maybe_bounty| if let Some(bounty) = maybe_bounty {
+		bounty.status = BountyStatus::PendingAutoApproval { approve_at };
+	});
+	BountyAutoApprovals::<T>::try_append(approve_at, bounty_id)
+		.expect("no more than `MaxAutoApprovalsPerBlock` bounties are pending; qed");
+}
+
 fn setup_pot_account<T: Config>() {
 	let pot_account = Bounties::<T>::account_id();
 	let value = T::Currency::minimum_balance().saturating_mul(1_000_000_000u32.into());
@@ -112,6 +122,21 @@ benchmarks! {
 		Bounties::<T>::on_initialize(T::BlockNumber::zero());
 	}: _(RawOrigin::Root, bounty_id, curator_lookup, fee)
 
+	update_curator_fee {
+		setup_pot_account::<T>();
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, MAX_BYTES);
+		let curator_lookup = T::Lookup::unlookup(curator.clone());
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+		Bounties::<T>::on_initialize(T::BlockNumber::zero());
+		Bounties::<T>::propose_curator(RawOrigin::Root.into(), bounty_id, curator_lookup, fee)?;
+		let new_fee = fee / 2u32.into();
+	}: _(RawOrigin::Root, bounty_id, new_fee)
+	verify {
+		ensure!(Bounties::<T>::bounties(bounty_id).map(|b| b.fee) == Some(new_fee), "Fee not updated");
+	}
+
 	// Worst case when curator is inactive and any sender unassigns the curator.
 	unassign_curator {
 		setup_pot_account::<T>();
@@ -170,6 +195,43 @@ benchmarks! {
 		let bounty_id = BountyCount::get() - 1;
 	}: close_bounty(RawOrigin::Root, bounty_id)
 
+	close_bounty_pending_auto_approval {
+		setup_pot_account::<T>();
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, 0);
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		make_pending_auto_approval::<T>(bounty_id, T::BountyObjectionPeriod::get());
+	}: close_bounty(RawOrigin::Root, bounty_id)
+	verify {
+		ensure!(Bounties::<T>::bounties(bounty_id).is_none(), "Bounty not closed");
+	}
+
+	close_bounty_funded {
+		setup_pot_account::<T>();
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, MAX_BYTES);
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+		Treasury::<T>::on_initialize(T::BlockNumber::zero());
+	}: close_bounty(RawOrigin::Root, bounty_id)
+	verify {
+		assert_last_event::<T>(RawEvent::BountyCanceled(bounty_id).into())
+	}
+
+	close_bounty_curator_proposed {
+		setup_pot_account::<T>();
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, MAX_BYTES);
+		let curator_lookup = T::Lookup::unlookup(curator.clone());
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		Bounties::<T>::approve_bounty(RawOrigin::Root.into(), bounty_id)?;
+		Treasury::<T>::on_initialize(T::BlockNumber::zero());
+		Bounties::<T>::propose_curator(RawOrigin::Root.into(), bounty_id, curator_lookup, fee)?;
+	}: close_bounty(RawOrigin::Root, bounty_id)
+	verify {
+		assert_last_event::<T>(RawEvent::BountyCanceled(bounty_id).into())
+	}
+
 	close_bounty_active {
 		setup_pot_account::<T>();
 		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
@@ -214,6 +276,82 @@ benchmarks! {
 		ensure!(missed_any == false, "Missed some");
 		assert_last_event::<T>(RawEvent::BountyBecameActive(b - 1).into())
 	}
+
+	// Worst case when the bounty already has the maximum number of tranches but one.
+	award_bounty_partial {
+		setup_pot_account::<T>();
+		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
+		Bounties::<T>::on_initialize(T::BlockNumber::zero());
+
+		let bounty_id = BountyCount::get() - 1;
+		let curator = T::Lookup::lookup(curator_lookup)?;
+		let tranche_value = T::Currency::minimum_balance();
+		for i in 1 .. T::MaximumTranches::get() {
+			let beneficiary = T::Lookup::unlookup(account("beneficiary", i, SEED));
+			Bounties::<T>::award_bounty_partial(
+				RawOrigin::Signed(curator.clone()).into(),
+				bounty_id,
+				beneficiary,
+				tranche_value,
+			)?;
+		}
+		let beneficiary = T::Lookup::unlookup(account("beneficiary", 0, SEED));
+	}: _(RawOrigin::Signed(curator), bounty_id, beneficiary, tranche_value)
+	verify {
+		ensure!(
+			Bounties::<T>::bounty_tranches(bounty_id).len() as u32 == T::MaximumTranches::get(),
+			"Tranche not awarded",
+		);
+	}
+
+	claim_bounty_tranches {
+		let t in 1 .. T::MaximumTranches::get();
+		setup_pot_account::<T>();
+		let (curator_lookup, bounty_id) = create_bounty::<T>()?;
+		Bounties::<T>::on_initialize(T::BlockNumber::zero());
+
+		let bounty_id = BountyCount::get() - 1;
+		let curator = T::Lookup::lookup(curator_lookup)?;
+		for i in 0 .. t {
+			let beneficiary = T::Lookup::unlookup(account("beneficiary", i, SEED));
+			Bounties::<T>::award_bounty_partial(
+				RawOrigin::Signed(curator.clone()).into(),
+				bounty_id,
+				beneficiary,
+				T::Currency::minimum_balance(),
+			)?;
+		}
+
+		frame_system::Pallet::<T>::set_block_number(T::BountyDepositPayoutDelay::get());
+	}: _(RawOrigin::Signed(curator), bounty_id)
+	verify {
+		ensure!(Bounties::<T>::bounty_tranches(bounty_id).is_empty(), "Tranches not claimed");
+	}
+
+	object_to_bounty {
+		let (caller, curator, fee, value, reason) = setup_bounty::<T>(0, MAX_BYTES);
+		Bounties::<T>::propose_bounty(RawOrigin::Signed(caller.clone()).into(), value, reason)?;
+		let bounty_id = BountyCount::get() - 1;
+		make_pending_auto_approval::<T>(bounty_id, T::BountyObjectionPeriod::get());
+	}: _(RawOrigin::Signed(caller.clone()), bounty_id)
+	verify {
+		assert_last_event::<T>(RawEvent::BountyObjected(bounty_id, caller).into())
+	}
+
+	auto_approve_bounties {
+		let b in 0 .. T::MaxAutoApprovalsPerBlock::get();
+		let approve_at = T::BlockNumber::one();
+		for i in 0 .. b {
+			let (caller, _curator, _fee, value, reason) = setup_bounty::<T>(i, MAX_BYTES);
+			Bounties::<T>::propose_bounty(RawOrigin::Signed(caller).into(), value, reason)?;
+			make_pending_auto_approval::<T>(BountyCount::get() - 1, approve_at);
+		}
+	}: {
+		Bounties::<T>::on_initialize(approve_at);
+	}
+	verify {
+		ensure!(BountyApprovals::get().len() == b as usize, "Not all bounties approved");
+	}
 }
 
 impl_benchmark_test_suite!(
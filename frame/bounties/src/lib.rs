@@ -35,6 +35,20 @@
 //! curator or once the bounty is active or payout is pending, resulting in the slash of the
 //! curator's deposit.
 //!
+//! Instead of a single curator, the Council can assign a council of curators to a bounty. The
+//! bounty becomes active once every member accepted, and it is awarded once a threshold of members
+//! approved the same beneficiary. The curator fee is shared equally by the members.
+//!
+//! The runtime can restrict which accounts may curate bounties through the
+//! [`Config::CuratorQualification`] hook, e.g. to accounts with a verified identity. Accounts
+//! that do not qualify can neither be proposed as curator nor accept the role.
+//!
+//! Bounties of a value below [`Config::BountyAutoApprovalLimit`] do not need the approval of the
+//! Council: they are approved automatically once [`Config::BountyObjectionPeriod`] has passed
+//! after they were proposed. During that period, anyone can object to the bounty with
+//! `object_to_bounty`, after which it needs the approval of the Council like any other bounty.
+//! The Council can also approve or reject the bounty before the end of the period.
+//!
 //!
 //! ### Terminology
 //!
@@ -44,6 +58,8 @@
 //! - **Proposer:** An account proposing a bounty spending.
 //! - **Curator:** An account managing the bounty and assigning a payout address receiving the
 //!   reward for the completion of work.
+//! - **Curator council:** A set of curators jointly managing a bounty, a threshold of which must
+//!   agree on the payout address.
 //! - **Deposit:** The amount held on deposit for placing a bounty proposal plus the amount held on
 //!   deposit per byte within the bounty description.
 //! - **Curator deposit:** The payment from a candidate willing to curate an approved bounty. The
@@ -64,11 +80,18 @@
 //!   tasks and stake the required deposit.
 //! - `approve_bounty` - Accept a specific treasury amount to be earmarked for a predefined body of
 //!   work.
+//! - `object_to_bounty` - Require the approval of the Council for a bounty pending automatic
+//!   approval.
 //! - `propose_curator` - Assign an account to a bounty as candidate curator.
+//! - `propose_curator_council` - Assign a set of accounts to a bounty as candidate curator council.
 //! - `accept_curator` - Accept a bounty assignment from the Council, setting a curator deposit.
+//! - `update_curator_fee` - Change the fee of a candidate curator or curator council before they
+//!   accept the bounty.
 //! - `extend_bounty_expiry` - Extend the expiry block number of the bounty and stay active.
 //! - `award_bounty` - Close and pay out the specified amount for the completed work.
 //! - `claim_bounty` - Claim a specific bounty amount from the Payout Address.
+//! - `award_bounty_partial` - Pay out a part of an active bounty, e.g. for a completed milestone.
+//! - `claim_bounty_tranches` - Claim the unlocked parts of a bounty.
 //! - `unassign_curator` - Unassign an accepted curator from a specific earmark.
 //! - `close_bounty` - Cancel the earmark for a specific treasury amount and close the bounty.
 
@@ -80,14 +103,15 @@ pub mod weights;
 
 use sp_std::prelude::*;
 
-use frame_support::{decl_module, decl_storage, decl_event, ensure, decl_error};
+use frame_support::{decl_module, decl_storage, decl_event, ensure, decl_error, BoundedVec};
+use frame_support::storage::{IterableStorageMap, TryAppendMap};
 
 use frame_support::traits::{
 	Currency, Get, Imbalance, OnUnbalanced, ExistenceRequirement::{AllowDeath},
 	ReservableCurrency};
 
-use sp_runtime::{Permill, RuntimeDebug, DispatchResult, traits::{
-	Zero, StaticLookup, AccountIdConversion, Saturating, BadOrigin
+use sp_runtime::{Permill, RuntimeDebug, DispatchResult, DispatchError, traits::{
+	Zero, One, StaticLookup, AccountIdConversion, Saturating, BadOrigin
 }};
 
 use frame_support::dispatch::DispatchResultWithPostInfo;
@@ -129,10 +153,50 @@ pub trait Config: frame_system::Config + pallet_treasury::Config {
 	/// Maximum acceptable reason length.
 	type MaximumReasonLength: Get<u32>;
 
+	/// Maximum number of unclaimed tranches awarded for a single bounty.
+	type MaximumTranches: Get<u32>;
+
+	/// Maximum number of members of a curator council.
+	type MaximumCurators: Get<u32>;
+
+	/// Accounts which may be proposed as curator of a bounty and accept the role.
+	type CuratorQualification: CuratorQualification<Self::AccountId>;
+
+	/// Bounties of a value below this limit are approved without the Council, unless someone
+	/// objects during `BountyObjectionPeriod`. Zero disables the automatic approval.
+	type BountyAutoApprovalLimit: Get<BalanceOf<Self>>;
+
+	/// The number of blocks, after the one a bounty is proposed in, during which anyone can object
+	/// to its automatic approval.
+	type BountyObjectionPeriod: Get<Self::BlockNumber>;
+
+	/// Maximum number of bounties approved automatically in a single block.
+	type MaxAutoApprovalsPerBlock: Get<u32>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
 
+/// Decides which accounts may curate bounties.
+pub trait CuratorQualification<AccountId> {
+	/// Returns `true` if `who` may be proposed as curator and accept the role.
+	///
+	/// The weight of the check is not charged separately, so `make_qualified` must set up the
+	/// most expensive case for the benchmarks.
+	fn is_qualified(who: &AccountId) -> bool;
+
+	/// Make `who` qualified to curate bounties, for benchmarking.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn make_qualified(_who: &AccountId) {}
+}
+
+/// Any account may curate bounties.
+impl<AccountId> CuratorQualification<AccountId> for () {
+	fn is_qualified(_who: &AccountId) -> bool {
+		true
+	}
+}
+
 /// An index of a bounty. Just a `u32`.
 pub type BountyIndex = u32;
 
@@ -153,6 +217,15 @@ pub struct Bounty<AccountId, Balance, BlockNumber> {
 	status: BountyStatus<AccountId, BlockNumber>,
 }
 
+/// A set of curators jointly managing a bounty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct CuratorCouncil<AccountId> {
+	/// The members of the council, sorted and without duplicates.
+	pub members: Vec<AccountId>,
+	/// The number of members that must approve the same beneficiary to award the bounty.
+	pub threshold: u32,
+}
+
 /// The status of a bounty proposal.
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 pub enum BountyStatus<AccountId, BlockNumber> {
@@ -183,6 +256,49 @@ pub enum BountyStatus<AccountId, BlockNumber> {
 		/// When the bounty can be claimed.
 		unlock_at: BlockNumber,
 	},
+	/// The bounty is proposed and will be approved without the Council, unless someone objects
+	/// before.
+	PendingAutoApproval {
+		/// When the bounty is approved.
+		approve_at: BlockNumber,
+	},
+	/// A curator council has been proposed by the `ApproveOrigin`. Waiting for acceptance from
+	/// all of its members.
+	CouncilProposed {
+		/// The assigned curator council of this bounty.
+		council: CuratorCouncil<AccountId>,
+		/// The members that accepted already, sorted.
+		accepted: Vec<AccountId>,
+	},
+	/// The bounty is active, managed by a curator council, and waiting to be awarded.
+	CouncilActive {
+		/// The curator council of this bounty.
+		council: CuratorCouncil<AccountId>,
+		/// An update from the council is due by this block, else it is considered inactive.
+		update_due: BlockNumber,
+		/// The beneficiary approved by each member that approved one so far.
+		approvals: Vec<(AccountId, AccountId)>,
+	},
+	/// The bounty is awarded by a curator council and waiting to released after a delay.
+	CouncilPendingPayout {
+		/// The curator council of this bounty.
+		council: CuratorCouncil<AccountId>,
+		/// The beneficiary of the bounty.
+		beneficiary: AccountId,
+		/// When the bounty can be claimed.
+		unlock_at: BlockNumber,
+	},
+}
+
+/// A part of a bounty awarded by the curator while the bounty is still active.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct BountyTranche<AccountId, Balance, BlockNumber> {
+	/// The beneficiary of the tranche.
+	pub beneficiary: AccountId,
+	/// The amount paid to the beneficiary.
+	pub value: Balance,
+	/// When the tranche can be claimed.
+	pub unlock_at: BlockNumber,
 }
 
 // Note :: For backward compatibility reasons,
@@ -205,6 +321,16 @@ decl_storage! {
 
 		/// Bounty indices that have been approved but not yet funded.
 		pub BountyApprovals get(fn bounty_approvals): Vec<BountyIndex>;
+
+		/// The tranches of each bounty that are awarded but not claimed yet.
+		pub BountyTranches get(fn bounty_tranches):
+		map hasher(twox_64_concat) BountyIndex
+		=> Vec<BountyTranche<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+		/// Bounty indices pending automatic approval, by the block they are approved at.
+		pub BountyAutoApprovals get(fn bounty_auto_approvals):
+		map hasher(twox_64_concat) T::BlockNumber
+		=> BoundedVec<BountyIndex, T::MaxAutoApprovalsPerBlock>;
 	}
 }
 
@@ -228,6 +354,23 @@ decl_event!(
 		BountyCanceled(BountyIndex),
 		/// A bounty expiry is extended. \[index\]
 		BountyExtended(BountyIndex),
+		/// A bounty curator did not update the bounty in time; the curator was slashed and
+		/// unassigned. \[index, curator\]
+		BountyTimedOut(BountyIndex, AccountId),
+		/// A tranche of a bounty is awarded to a beneficiary. \[index, beneficiary, value\]
+		BountyTrancheAwarded(BountyIndex, AccountId, Balance),
+		/// A tranche of a bounty is claimed by its beneficiary. \[index, value, beneficiary\]
+		BountyTrancheClaimed(BountyIndex, Balance, AccountId),
+		/// A bounty was approved without the Council. \[index\]
+		BountyAutoApproved(BountyIndex),
+		/// An account objected to the automatic approval of a bounty, which now needs the approval
+		/// of the Council. \[index, who\]
+		BountyObjected(BountyIndex, AccountId),
+		/// The fee of a proposed curator or curator council is updated. \[index, fee\]
+		CuratorFeeUpdated(BountyIndex, Balance),
+		/// A member of a curator council approved a beneficiary, which did not reach the threshold
+		/// yet. \[index, curator, beneficiary\]
+		BountyAwardApproved(BountyIndex, AccountId, AccountId),
 	}
 );
 
@@ -253,6 +396,18 @@ decl_error! {
 		PendingPayout,
 		/// The bounties cannot be claimed/closed because it's still in the countdown period.
 		Premature,
+		/// The bounty has too many unclaimed tranches.
+		TooManyTranches,
+		/// The account is not qualified to curate bounties.
+		CuratorNotQualified,
+		/// The bounty has tranches that are awarded but not claimed yet.
+		UnclaimedTranches,
+		/// The curator council is empty, too large or its threshold is out of range.
+		InvalidCouncil,
+		/// The curator accepted the bounty already.
+		AlreadyAccepted,
+		/// Too many bounties are approved automatically in the same block.
+		TooManyAutoApprovals,
 	}
 }
 
@@ -282,10 +437,44 @@ decl_module! {
 		/// Maximum acceptable reason length.
 		const MaximumReasonLength: u32 = T::MaximumReasonLength::get();
 
+		/// Maximum number of unclaimed tranches awarded for a single bounty.
+		const MaximumTranches: u32 = T::MaximumTranches::get();
+
+		/// Maximum number of members of a curator council.
+		const MaximumCurators: u32 = T::MaximumCurators::get();
+
+		/// Bounties of a value below this limit are approved without the Council.
+		const BountyAutoApprovalLimit: BalanceOf<T> = T::BountyAutoApprovalLimit::get();
+
+		/// The number of blocks during which anyone can object to the automatic approval of a
+		/// bounty.
+		const BountyObjectionPeriod: T::BlockNumber = T::BountyObjectionPeriod::get();
+
+		/// Maximum number of bounties approved automatically in a single block.
+		const MaxAutoApprovalsPerBlock: u32 = T::MaxAutoApprovalsPerBlock::get();
+
 		type Error = Error<T>;
 
 		fn deposit_event() = default;
 
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let pending = BountyAutoApprovals::<T>::take(n);
+			let pending_len = pending.len() as u32;
+			for bounty_id in pending {
+				Bounties::<T>::mutate(bounty_id, |maybe_bounty| {
+					// Bounties approved, rejected or objected to in the meantime are skipped.
+					if let Some(bounty) = maybe_bounty {
+						if bounty.status == (BountyStatus::PendingAutoApproval { approve_at: n }) {
+							bounty.status = BountyStatus::Approved;
+							BountyApprovals::append(bounty_id);
+							Self::deposit_event(Event::<T>::BountyAutoApproved(bounty_id));
+						}
+					}
+				});
+			}
+			<T as Config>::WeightInfo::auto_approve_bounties(pending_len)
+		}
+
 		/// Propose a new bounty.
 		///
 		/// The dispatch origin for this call must be _Signed_.
@@ -294,6 +483,10 @@ decl_module! {
 		/// `DataDepositPerByte` for each byte in `reason`. It will be unreserved upon approval,
 		/// or slashed when rejected.
 		///
+		/// A bounty of a value below `BountyAutoApprovalLimit` is approved automatically after
+		/// `BountyObjectionPeriod`, unless someone objects to it before. It is rejected if
+		/// `MaxAutoApprovalsPerBlock` bounties are approved automatically in the same block already.
+		///
 		/// - `curator`: The curator account whom will manage this bounty.
 		/// - `fee`: The curator fee.
 		/// - `value`: The total payment amount of this bounty, curator fee included.
@@ -311,7 +504,8 @@ decl_module! {
 		/// Approve a bounty proposal. At a later time, the bounty will be funded and become active
 		/// and the original deposit will be returned.
 		///
-		/// May only be called from `T::ApproveOrigin`.
+		/// May only be called from `T::ApproveOrigin`. Bounties pending automatic approval can be
+		/// approved before the end of their objection period.
 		///
 		/// # <weight>
 		/// - O(1).
@@ -322,7 +516,10 @@ decl_module! {
 
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
 				let mut bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
-				ensure!(bounty.status == BountyStatus::Proposed, Error::<T>::UnexpectedStatus);
+				match bounty.status {
+					BountyStatus::Proposed | BountyStatus::PendingAutoApproval { .. } => {},
+					_ => return Err(Error::<T>::UnexpectedStatus.into()),
+				}
 
 				bounty.status = BountyStatus::Approved;
 
@@ -332,9 +529,39 @@ decl_module! {
 			})?;
 		}
 
+		/// Object to the automatic approval of a bounty, which then needs the approval of the
+		/// Council like any other bounty.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `bounty_id`: Bounty ID pending automatic approval.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// # </weight>
+		#[weight = <T as Config>::WeightInfo::object_to_bounty()]
+		fn object_to_bounty(origin, #[compact] bounty_id: BountyIndex) {
+			let who = ensure_signed(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let mut bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+				match bounty.status {
+					BountyStatus::PendingAutoApproval { .. } => {},
+					_ => return Err(Error::<T>::UnexpectedStatus.into()),
+				}
+
+				bounty.status = BountyStatus::Proposed;
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::BountyObjected(bounty_id, who));
+		}
+
 		/// Assign a curator to a funded bounty.
 		///
-		/// May only be called from `T::ApproveOrigin`.
+		/// May only be called from `T::ApproveOrigin`. The curator must be qualified by
+		/// `T::CuratorQualification`.
 		///
 		/// # <weight>
 		/// - O(1).
@@ -349,11 +576,14 @@ decl_module! {
 			T::ApproveOrigin::ensure_origin(origin)?;
 
 			let curator = T::Lookup::lookup(curator)?;
+			ensure!(T::CuratorQualification::is_qualified(&curator), Error::<T>::CuratorNotQualified);
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
 
 				let mut bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
 				match bounty.status {
-					BountyStatus::Proposed | BountyStatus::Approved | BountyStatus::Funded => {},
+					BountyStatus::Proposed |
+					BountyStatus::Approved |
+					BountyStatus::Funded => {},
 					_ => return Err(Error::<T>::UnexpectedStatus.into()),
 				};
 
@@ -379,20 +609,26 @@ decl_module! {
 		///
 		/// Finally, the origin can be anyone if and only if the curator is "inactive". This allows
 		/// anyone in the community to call out that a curator is not doing their due diligence, and
-		/// we should pick a new curator. In this case the curator should also be slashed.
+		/// we should pick a new curator. In this case the curator should also be slashed and
+		/// `BountyTimedOut` is emitted.
+		///
+		/// A curator council is unassigned as a whole: the members are slashed together, and any
+		/// member giving up dissolves the council, returning the deposits of all members.
 		///
 		/// # <weight>
-		/// - O(1).
+		/// - O(C) where C is the number of curators, bounded by `MaximumCurators`.
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::unassign_curator()]
+		#[weight = council_weight(<T as Config>::WeightInfo::unassign_curator(), T::MaximumCurators::get())]
 		fn unassign_curator(
 			origin,
 			#[compact] bounty_id: BountyIndex,
-		) {
+		) -> DispatchResultWithPostInfo {
 			let maybe_sender = ensure_signed(origin.clone())
 				.map(Some)
 				.or_else(|_| T::RejectOrigin::ensure_origin(origin).map(|_| None))?;
 
+			let mut timed_out_curators = Vec::new();
+			let mut curators = 1;
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
 				let mut bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
 
@@ -401,9 +637,26 @@ decl_module! {
 					T::OnSlash::on_unbalanced(imbalance);
 					*curator_deposit = Zero::zero();
 				};
+				let slash_council = |members: &[T::AccountId], curator_deposit: &mut BalanceOf<T>| {
+					for member in members {
+						let imbalance = T::Currency::slash_reserved(member, *curator_deposit).0;
+						T::OnSlash::on_unbalanced(imbalance);
+					}
+					*curator_deposit = Zero::zero();
+				};
+				let refund_council = |members: &[T::AccountId], curator_deposit: &mut BalanceOf<T>| {
+					for member in members {
+						let err_amount = T::Currency::unreserve(member, *curator_deposit);
+						debug_assert!(err_amount.is_zero());
+					}
+					*curator_deposit = Zero::zero();
+				};
 
 				match bounty.status {
-					BountyStatus::Proposed | BountyStatus::Approved | BountyStatus::Funded => {
+					BountyStatus::Proposed |
+					BountyStatus::PendingAutoApproval { .. } |
+					BountyStatus::Approved |
+					BountyStatus::Funded => {
 						// No curator to unassign at this point.
 						return Err(Error::<T>::UnexpectedStatus.into())
 					}
@@ -427,6 +680,7 @@ decl_module! {
 									let block_number = system::Pallet::<T>::block_number();
 									if *update_due < block_number {
 										slash_curator(curator, &mut bounty.curator_deposit);
+										timed_out_curators.push(curator.clone());
 										// Continue to change bounty status below...
 									} else {
 										// Curator has more time to give an update.
@@ -450,17 +704,63 @@ decl_module! {
 						slash_curator(curator, &mut bounty.curator_deposit);
 						// Continue to change bounty status below...
 					}
+					BountyStatus::CouncilProposed { ref council, ref accepted } => {
+						// Either `RejectOrigin` or one of the proposed members can unassign the
+						// council. The members that accepted already get their deposit back.
+						ensure!(
+							maybe_sender.map_or(true, |sender| council.members.binary_search(&sender).is_ok()),
+							BadOrigin,
+						);
+						refund_council(accepted, &mut bounty.curator_deposit);
+						curators = council.members.len() as u32;
+					},
+					BountyStatus::CouncilActive { ref council, ref update_due, .. } => {
+						match maybe_sender {
+							None => {
+								slash_council(&council.members, &mut bounty.curator_deposit);
+							},
+							Some(sender) => {
+								if council.members.binary_search(&sender).is_err() {
+									let block_number = system::Pallet::<T>::block_number();
+									if *update_due < block_number {
+										slash_council(&council.members, &mut bounty.curator_deposit);
+										timed_out_curators = council.members.clone();
+									} else {
+										// The council has more time to give an update.
+										return Err(Error::<T>::Premature.into())
+									}
+								} else {
+									// A member gives up, which dissolves the council.
+									refund_council(&council.members, &mut bounty.curator_deposit);
+								}
+							},
+						}
+						curators = council.members.len() as u32;
+					},
+					BountyStatus::CouncilPendingPayout { ref council, .. } => {
+						ensure!(maybe_sender.is_none(), BadOrigin);
+						slash_council(&council.members, &mut bounty.curator_deposit);
+						curators = council.members.len() as u32;
+					},
 				};
 
 				bounty.status = BountyStatus::Funded;
 				Ok(())
 			})?;
+
+			for curator in timed_out_curators {
+				Self::deposit_event(Event::<T>::BountyTimedOut(bounty_id, curator));
+			}
+
+			Ok(Some(council_weight(<T as Config>::WeightInfo::unassign_curator(), curators)).into())
 		}
 
 		/// Accept the curator role for a bounty.
 		/// A deposit will be reserved from curator and refund upon successful payout.
 		///
-		/// May only be called from the curator.
+		/// May only be called from the curator, who must still be qualified by
+		/// `T::CuratorQualification`. A curator council becomes active once all of its members
+		/// accepted, each of them reserving a deposit for their share of the fee.
 		///
 		/// # <weight>
 		/// - O(1).
@@ -475,6 +775,10 @@ decl_module! {
 				match bounty.status {
 					BountyStatus::CuratorProposed { ref curator } => {
 						ensure!(signer == *curator, Error::<T>::RequireCurator);
+						ensure!(
+							T::CuratorQualification::is_qualified(curator),
+							Error::<T>::CuratorNotQualified,
+						);
 
 						let deposit = T::BountyCuratorDeposit::get() * bounty.fee;
 						T::Currency::reserve(curator, deposit)?;
@@ -485,6 +789,33 @@ decl_module! {
 
 						Ok(())
 					},
+					BountyStatus::CouncilProposed { ref council, ref mut accepted } => {
+						ensure!(council.members.binary_search(&signer).is_ok(), Error::<T>::RequireCurator);
+						let position = accepted.binary_search(&signer)
+							.err()
+							.ok_or(Error::<T>::AlreadyAccepted)?;
+						ensure!(
+							T::CuratorQualification::is_qualified(&signer),
+							Error::<T>::CuratorNotQualified,
+						);
+
+						let share = curator_share::<T>(bounty.fee, council.members.len());
+						let deposit = T::BountyCuratorDeposit::get() * share;
+						T::Currency::reserve(&signer, deposit)?;
+						bounty.curator_deposit = deposit;
+						accepted.insert(position, signer);
+
+						if accepted.len() == council.members.len() {
+							let update_due = system::Pallet::<T>::block_number() + T::BountyUpdatePeriod::get();
+							bounty.status = BountyStatus::CouncilActive {
+								council: council.clone(),
+								update_due,
+								approvals: Vec::new(),
+							};
+						}
+
+						Ok(())
+					},
 					_ => Err(Error::<T>::UnexpectedStatus.into()),
 				}
 			})?;
@@ -492,7 +823,10 @@ decl_module! {
 
 		/// Award bounty to a beneficiary account. The beneficiary will be able to claim the funds after a delay.
 		///
-		/// The dispatch origin for this call must be the curator of this bounty.
+		/// The dispatch origin for this call must be the curator of this bounty, or a member of its
+		/// curator council. A bounty managed by a council is awarded once `threshold` members
+		/// approved the same beneficiary; a member approving another beneficiary replaces their
+		/// previous approval.
 		///
 		/// - `bounty_id`: Bounty ID to award.
 		/// - `beneficiary`: The beneficiary account whom will receive the payout.
@@ -505,54 +839,84 @@ decl_module! {
 			let signer = ensure_signed(origin)?;
 			let beneficiary = T::Lookup::lookup(beneficiary)?;
 
-			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+			let awarded = Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> Result<bool, DispatchError> {
 				let mut bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
-				match &bounty.status {
-					BountyStatus::Active {
-						curator,
-						..
-					} => {
-						ensure!(signer == *curator, Error::<T>::RequireCurator);
+				let unlock_at = system::Pallet::<T>::block_number() + T::BountyDepositPayoutDelay::get();
+				bounty.status = match bounty.status.clone() {
+					BountyStatus::Active { curator, .. } => {
+						ensure!(signer == curator, Error::<T>::RequireCurator);
+						BountyStatus::PendingPayout { curator, beneficiary: beneficiary.clone(), unlock_at }
+					},
+					BountyStatus::CouncilActive { council, update_due, mut approvals } => {
+						ensure!(council.members.binary_search(&signer).is_ok(), Error::<T>::RequireCurator);
+						approvals.retain(|(member, _)| *member != signer);
+						approvals.push((signer.clone(), beneficiary.clone()));
+						let votes = approvals.iter().filter(|(_, approved)| *approved == beneficiary).count();
+						if votes as u32 >= council.threshold {
+							BountyStatus::CouncilPendingPayout { council, beneficiary: beneficiary.clone(), unlock_at }
+						} else {
+							BountyStatus::CouncilActive { council, update_due, approvals }
+						}
 					},
 					_ => return Err(Error::<T>::UnexpectedStatus.into()),
-				}
-				bounty.status = BountyStatus::PendingPayout {
-					curator: signer,
-					beneficiary: beneficiary.clone(),
-					unlock_at: system::Pallet::<T>::block_number() + T::BountyDepositPayoutDelay::get(),
 				};
 
-				Ok(())
+				Ok(!matches!(bounty.status, BountyStatus::CouncilActive { .. }))
 			})?;
 
-			Self::deposit_event(Event::<T>::BountyAwarded(bounty_id, beneficiary));
+			if awarded {
+				Self::deposit_event(Event::<T>::BountyAwarded(bounty_id, beneficiary));
+			} else {
+				Self::deposit_event(Event::<T>::BountyAwardApproved(bounty_id, signer, beneficiary));
+			}
 		}
 
 		/// Claim the payout from an awarded bounty after payout delay.
 		///
 		/// The dispatch origin for this call must be the beneficiary of this bounty.
 		///
+		/// The unclaimed tranches of the bounty are paid out first. The members of a curator council
+		/// each receive an equal share of the curator fee.
+		///
 		/// - `bounty_id`: Bounty ID to claim.
 		///
 		/// # <weight>
-		/// - O(1).
+		/// - O(T + C) where T is the number of unclaimed tranches and C the number of curators.
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::claim_bounty()]
-		fn claim_bounty(origin, #[compact] bounty_id: BountyIndex) {
+		#[weight = council_weight(<T as Config>::WeightInfo::claim_bounty(), T::MaximumCurators::get())
+			.saturating_add(<T as Config>::WeightInfo::claim_bounty_tranches(T::MaximumTranches::get()))]
+		fn claim_bounty(origin, #[compact] bounty_id: BountyIndex) -> DispatchResultWithPostInfo {
 			let _ = ensure_signed(origin)?; // anyone can trigger claim
 
-			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
-				let bounty = maybe_bounty.take().ok_or(Error::<T>::InvalidIndex)?;
-				if let BountyStatus::PendingPayout { curator, beneficiary, unlock_at } = bounty.status {
+			let (curators_len, tranches_len) = Bounties::<T>::try_mutate_exists(
+				bounty_id,
+				|maybe_bounty| -> Result<(u32, u32), DispatchError> {
+					let bounty = maybe_bounty.take().ok_or(Error::<T>::InvalidIndex)?;
+					let (curators, beneficiary, unlock_at) = match bounty.status {
+						BountyStatus::PendingPayout { curator, beneficiary, unlock_at } =>
+							(vec![curator], beneficiary, unlock_at),
+						BountyStatus::CouncilPendingPayout { council, beneficiary, unlock_at } =>
+							(council.members, beneficiary, unlock_at),
+						_ => return Err(Error::<T>::UnexpectedStatus.into()),
+					};
 					ensure!(system::Pallet::<T>::block_number() >= unlock_at, Error::<T>::Premature);
+					// Tranches are awarded before the bounty, so they are unlocked as well.
+					let tranches = BountyTranches::<T>::take(bounty_id);
+					let tranches_len = tranches.len() as u32;
+					for tranche in tranches {
+						Self::pay_tranche(bounty_id, tranche);
+					}
 					let bounty_account = Self::bounty_account_id(bounty_id);
 					let balance = T::Currency::free_balance(&bounty_account);
 					let fee = bounty.fee.min(balance); // just to be safe
-					let payout = balance.saturating_sub(fee);
-					let err_amount = T::Currency::unreserve(&curator, bounty.curator_deposit);
-					debug_assert!(err_amount.is_zero());
-					let res = T::Currency::transfer(&bounty_account, &curator, fee, AllowDeath); // should not fail
-					debug_assert!(res.is_ok());
+					let share = curator_share::<T>(fee, curators.len());
+					let payout = balance.saturating_sub(share.saturating_mul((curators.len() as u32).into()));
+					for curator in &curators {
+						let err_amount = T::Currency::unreserve(curator, bounty.curator_deposit);
+						debug_assert!(err_amount.is_zero());
+						let res = T::Currency::transfer(&bounty_account, curator, share, AllowDeath); // should not fail
+						debug_assert!(res.is_ok());
+					}
 					let res = T::Currency::transfer(&bounty_account, &beneficiary, payout, AllowDeath); // should not fail
 					debug_assert!(res.is_ok());
 
@@ -561,66 +925,95 @@ decl_module! {
 					BountyDescriptions::remove(bounty_id);
 
 					Self::deposit_event(Event::<T>::BountyClaimed(bounty_id, payout, beneficiary));
-					Ok(())
-				} else {
-					Err(Error::<T>::UnexpectedStatus.into())
-				}
-			})?;
+					Ok((curators.len() as u32, tranches_len))
+				},
+			)?;
+
+			Ok(Some(
+				council_weight(<T as Config>::WeightInfo::claim_bounty(), curators_len)
+					.saturating_add(<T as Config>::WeightInfo::claim_bounty_tranches(tranches_len))
+			).into())
 		}
 
 		/// Cancel a proposed or active bounty. All the funds will be sent to treasury and
 		/// the curator deposit will be unreserved if possible.
 		///
-		/// Only `T::RejectOrigin` is able to cancel a bounty.
+		/// Only `T::RejectOrigin` is able to cancel a bounty. A bounty with tranches that are
+		/// awarded but not claimed yet can only be cancelled once they are claimed.
 		///
 		/// - `bounty_id`: Bounty ID to cancel.
 		///
 		/// # <weight>
-		/// - O(1).
+		/// - O(C) where C is the number of curators, bounded by `MaximumCurators`.
 		/// # </weight>
-		#[weight = <T as Config>::WeightInfo::close_bounty_proposed().max(<T as Config>::WeightInfo::close_bounty_active())]
+		#[weight = <T as Config>::WeightInfo::close_bounty_proposed()
+			.max(<T as Config>::WeightInfo::close_bounty_pending_auto_approval())
+			.max(<T as Config>::WeightInfo::close_bounty_funded())
+			.max(<T as Config>::WeightInfo::close_bounty_curator_proposed())
+			.max(council_weight(<T as Config>::WeightInfo::close_bounty_active(), T::MaximumCurators::get()))]
 		fn close_bounty(origin, #[compact] bounty_id: BountyIndex) -> DispatchResultWithPostInfo {
 			T::RejectOrigin::ensure_origin(origin)?;
 
 			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResultWithPostInfo {
 				let bounty = maybe_bounty.as_ref().ok_or(Error::<T>::InvalidIndex)?;
+				// The awarded tranches belong to their beneficiaries.
+				ensure!(!BountyTranches::<T>::contains_key(bounty_id), Error::<T>::UnclaimedTranches);
 
-				match &bounty.status {
-					BountyStatus::Proposed => {
+				let weight = match &bounty.status {
+					BountyStatus::Proposed | BountyStatus::PendingAutoApproval { .. } => {
 						// The reject origin would like to cancel a proposed bounty.
 						BountyDescriptions::remove(bounty_id);
 						let value = bounty.bond;
 						let imbalance = T::Currency::slash_reserved(&bounty.proposer, value).0;
 						T::OnSlash::on_unbalanced(imbalance);
+						let weight = match bounty.status {
+							BountyStatus::Proposed => <T as Config>::WeightInfo::close_bounty_proposed(),
+							_ => <T as Config>::WeightInfo::close_bounty_pending_auto_approval(),
+						};
 						*maybe_bounty = None;
 
 						Self::deposit_event(Event::<T>::BountyRejected(bounty_id, value));
 						// Return early, nothing else to do.
-						return Ok(Some(<T as Config>::WeightInfo::close_bounty_proposed()).into())
+						return Ok(Some(weight).into())
 					},
 					BountyStatus::Approved => {
 						// For weight reasons, we don't allow a council to cancel in this phase.
 						// We ask for them to wait until it is funded before they can cancel.
 						return Err(Error::<T>::UnexpectedStatus.into())
 					},
-					BountyStatus::Funded |
+					BountyStatus::Funded => {
+						// Nothing extra to do besides the removal of the bounty below.
+						<T as Config>::WeightInfo::close_bounty_funded()
+					},
 					BountyStatus::CuratorProposed { .. } => {
 						// Nothing extra to do besides the removal of the bounty below.
+						<T as Config>::WeightInfo::close_bounty_curator_proposed()
 					},
 					BountyStatus::Active { curator, .. } => {
 						// Cancelled by council, refund deposit of the working curator.
 						let err_amount = T::Currency::unreserve(&curator, bounty.curator_deposit);
 						debug_assert!(err_amount.is_zero());
 						// Then execute removal of the bounty below.
+						<T as Config>::WeightInfo::close_bounty_active()
 					},
-					BountyStatus::PendingPayout { .. } => {
+					BountyStatus::CouncilProposed { accepted: curators, .. } |
+					BountyStatus::CouncilActive { council: CuratorCouncil { members: curators, .. }, .. } => {
+						// Cancelled by council, refund the deposits of the curators that accepted.
+						for curator in curators {
+							let err_amount = T::Currency::unreserve(curator, bounty.curator_deposit);
+							debug_assert!(err_amount.is_zero());
+						}
+						// Then execute removal of the bounty below.
+						council_weight(<T as Config>::WeightInfo::close_bounty_active(), curators.len() as u32)
+					},
+					BountyStatus::PendingPayout { .. } | BountyStatus::CouncilPendingPayout { .. } => {
 						// Bounty is already pending payout. If council wants to cancel
 						// this bounty, it should mean the curator was acting maliciously.
 						// So the council should first unassign the curator, slashing their
 						// deposit.
 						return Err(Error::<T>::PendingPayout.into())
 					}
-				}
+				};
 
 				let bounty_account = Self::bounty_account_id(bounty_id);
 
@@ -632,13 +1025,14 @@ decl_module! {
 				*maybe_bounty = None;
 
 				Self::deposit_event(Event::<T>::BountyCanceled(bounty_id));
-				Ok(Some(<T as Config>::WeightInfo::close_bounty_active()).into())
+				Ok(Some(weight).into())
 			})
 		}
 
 		/// Extend the expiry time of an active bounty.
 		///
-		/// The dispatch origin for this call must be the curator of this bounty.
+		/// The dispatch origin for this call must be the curator of this bounty, or a member of its
+		/// curator council.
 		///
 		/// - `bounty_id`: Bounty ID to extend.
 		/// - `remark`: additional information.
@@ -658,6 +1052,10 @@ decl_module! {
 						ensure!(*curator == signer, Error::<T>::RequireCurator);
 						*update_due = (system::Pallet::<T>::block_number() + T::BountyUpdatePeriod::get()).max(*update_due);
 					},
+					BountyStatus::CouncilActive { ref council, ref mut update_due, .. } => {
+						ensure!(council.members.binary_search(&signer).is_ok(), Error::<T>::RequireCurator);
+						*update_due = (system::Pallet::<T>::block_number() + T::BountyUpdatePeriod::get()).max(*update_due);
+					},
 					_ => return Err(Error::<T>::UnexpectedStatus.into()),
 				}
 
@@ -666,9 +1064,197 @@ decl_module! {
 
 			Self::deposit_event(Event::<T>::BountyExtended(bounty_id));
 		}
+
+		/// Award a part of an active bounty to a beneficiary account. The beneficiary will be able
+		/// to claim the funds after a delay, with `claim_bounty_tranches`.
+		///
+		/// The bounty stays active, so the curator can keep awarding tranches until the value of the
+		/// bounty, excluding the curator fee, is exhausted.
+		///
+		/// The dispatch origin for this call must be the curator of this bounty. Bounties managed by
+		/// a curator council can only be awarded as a whole, with `award_bounty`.
+		///
+		/// - `bounty_id`: Bounty ID to award.
+		/// - `beneficiary`: The beneficiary account whom will receive the payout.
+		/// - `value`: The amount paid to the beneficiary.
+		///
+		/// # <weight>
+		/// - O(T) where T is the number of unclaimed tranches, bounded by `MaximumTranches`.
+		/// # </weight>
+		#[weight = <T as Config>::WeightInfo::award_bounty_partial()]
+		fn award_bounty_partial(
+			origin,
+			#[compact] bounty_id: BountyIndex,
+			beneficiary: <T::Lookup as StaticLookup>::Source,
+			#[compact] value: BalanceOf<T>,
+		) {
+			let signer = ensure_signed(origin)?;
+			let beneficiary = T::Lookup::lookup(beneficiary)?;
+			ensure!(!value.is_zero(), Error::<T>::InvalidValue);
+
+			let bounty = Self::bounties(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+			match &bounty.status {
+				BountyStatus::Active { curator, .. } => {
+					ensure!(signer == *curator, Error::<T>::RequireCurator);
+				},
+				_ => return Err(Error::<T>::UnexpectedStatus.into()),
+			}
+
+			BountyTranches::<T>::try_mutate(bounty_id, |tranches| -> DispatchResult {
+				ensure!((tranches.len() as u32) < T::MaximumTranches::get(), Error::<T>::TooManyTranches);
+
+				// The curator fee and the unclaimed tranches must stay in the bounty account.
+				let balance = T::Currency::free_balance(&Self::bounty_account_id(bounty_id));
+				let awarded = tranches.iter()
+					.fold(Zero::zero(), |awarded: BalanceOf<T>, tranche| awarded.saturating_add(tranche.value));
+				let available = balance.saturating_sub(bounty.fee).saturating_sub(awarded);
+				ensure!(value <= available, Error::<T>::InvalidValue);
+
+				tranches.push(BountyTranche {
+					beneficiary: beneficiary.clone(),
+					value,
+					unlock_at: system::Pallet::<T>::block_number() + T::BountyDepositPayoutDelay::get(),
+				});
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::BountyTrancheAwarded(bounty_id, beneficiary, value));
+		}
+
+		/// Claim the unlocked tranches of a bounty.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `bounty_id`: Bounty ID to claim the tranches of.
+		///
+		/// # <weight>
+		/// - O(T) where T is the number of unclaimed tranches, bounded by `MaximumTranches`.
+		/// # </weight>
+		#[weight = <T as Config>::WeightInfo::claim_bounty_tranches(T::MaximumTranches::get())]
+		fn claim_bounty_tranches(origin, #[compact] bounty_id: BountyIndex) {
+			let _ = ensure_signed(origin)?; // anyone can trigger claim
+			ensure!(Bounties::<T>::contains_key(bounty_id), Error::<T>::InvalidIndex);
+
+			let now = system::Pallet::<T>::block_number();
+			let (unlocked, locked): (Vec<_>, Vec<_>) = Self::bounty_tranches(bounty_id)
+				.into_iter()
+				.partition(|tranche| tranche.unlock_at <= now);
+			ensure!(!unlocked.is_empty(), Error::<T>::Premature);
+
+			if locked.is_empty() {
+				BountyTranches::<T>::remove(bounty_id);
+			} else {
+				BountyTranches::<T>::insert(bounty_id, locked);
+			}
+
+			for tranche in unlocked {
+				Self::pay_tranche(bounty_id, tranche);
+			}
+		}
+
+		/// Update the fee of a curator that was proposed but did not accept the bounty yet, or of a
+		/// curator council none of whose members accepted yet.
+		///
+		/// May only be called from `T::ApproveOrigin`.
+		///
+		/// # <weight>
+		/// - O(1).
+		/// # </weight>
+		#[weight = <T as Config>::WeightInfo::update_curator_fee()]
+		fn update_curator_fee(
+			origin,
+			#[compact] bounty_id: BountyIndex,
+			#[compact] fee: BalanceOf<T>,
+		) {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let mut bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+				match bounty.status {
+					BountyStatus::CuratorProposed { .. } => {},
+					// The deposits of the members that accepted already depend on the fee.
+					BountyStatus::CouncilProposed { ref accepted, .. } if accepted.is_empty() => {},
+					_ => return Err(Error::<T>::UnexpectedStatus.into()),
+				};
+
+				ensure!(fee < bounty.value, Error::<T>::InvalidFee);
+
+				bounty.fee = fee;
+
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::<T>::CuratorFeeUpdated(bounty_id, fee));
+		}
+
+		/// Assign a curator council to a funded bounty. The bounty becomes active once all of the
+		/// members accepted, and it is awarded once `threshold` of them approved the same
+		/// beneficiary.
+		///
+		/// May only be called from `T::ApproveOrigin`. The members must be qualified by
+		/// `T::CuratorQualification`. They share the `fee` equally.
+		///
+		/// # <weight>
+		/// - O(C) where C is the number of curators, bounded by `MaximumCurators`.
+		/// # </weight>
+		#[weight = council_weight(<T as Config>::WeightInfo::propose_curator(), curators.len() as u32)]
+		fn propose_curator_council(
+			origin,
+			#[compact] bounty_id: BountyIndex,
+			curators: Vec<<T::Lookup as StaticLookup>::Source>,
+			#[compact] threshold: u32,
+			#[compact] fee: BalanceOf<T>,
+		) {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			ensure!(curators.len() as u32 <= T::MaximumCurators::get(), Error::<T>::InvalidCouncil);
+
+			let mut members = curators.into_iter()
+				.map(T::Lookup::lookup)
+				.collect::<Result<Vec<_>, _>>()?;
+			members.sort();
+			members.dedup();
+			ensure!(threshold > 0 && threshold as usize <= members.len(), Error::<T>::InvalidCouncil);
+			ensure!(
+				members.iter().all(T::CuratorQualification::is_qualified),
+				Error::<T>::CuratorNotQualified,
+			);
+
+			Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+				let mut bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+				match bounty.status {
+					BountyStatus::Proposed |
+					BountyStatus::Approved |
+					BountyStatus::Funded => {},
+					_ => return Err(Error::<T>::UnexpectedStatus.into()),
+				};
+
+				ensure!(fee < bounty.value, Error::<T>::InvalidFee);
+
+				bounty.status = BountyStatus::CouncilProposed {
+					council: CuratorCouncil { members, threshold },
+					accepted: Vec::new(),
+				};
+				bounty.fee = fee;
+
+				Ok(())
+			})?;
+		}
 	}
 }
 
+/// The weight of a call for a curator council of `curators` members, given the `weight` of the
+/// same call for a single curator.
+///
+/// The work done for each member is at most the work done for a single curator.
+fn council_weight(weight: Weight, curators: u32) -> Weight {
+	weight.saturating_mul(curators.max(1) as Weight)
+}
+
+/// The part of the curator `fee` paid to each member of a curator council of `curators` members.
+fn curator_share<T: Config>(fee: BalanceOf<T>, curators: usize) -> BalanceOf<T> {
+	fee / (curators.max(1) as u32).into()
+}
+
 impl<T: Config> Module<T> {
 	// Add public immutables and private mutables.
 
@@ -687,6 +1273,22 @@ impl<T: Config> Module<T> {
 		T::PalletId::get().into_sub_account(("bt", id))
 	}
 
+	/// Up to `count` bounties following the bounty `start`, or from the first bounty if `start`
+	/// is `None`.
+	///
+	/// Bounties are returned in storage order, not by index, so the last index of a page is the
+	/// `start` of the next one. Only meant to be used by off-chain queries.
+	pub fn bounties_paged(
+		start: Option<BountyIndex>,
+		count: u32,
+	) -> Vec<(BountyIndex, Bounty<T::AccountId, BalanceOf<T>, T::BlockNumber>)> {
+		let bounties = match start {
+			Some(start) => Bounties::<T>::iter_from(Bounties::<T>::hashed_key_for(start)),
+			None => Bounties::<T>::iter(),
+		};
+		bounties.take(count as usize).collect()
+	}
+
 	fn create_bounty(
 		proposer: T::AccountId,
 		description: Vec<u8>,
@@ -697,6 +1299,20 @@ impl<T: Config> Module<T> {
 
 		let index = Self::bounty_count();
 
+		let auto_approve_at = if value < T::BountyAutoApprovalLimit::get() {
+			let approve_at = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::BountyObjectionPeriod::get())
+				.saturating_add(One::one());
+			ensure!(
+				BountyAutoApprovals::<T>::decode_len(approve_at).unwrap_or_default()
+					< T::MaxAutoApprovalsPerBlock::get() as usize,
+				Error::<T>::TooManyAutoApprovals,
+			);
+			Some(approve_at)
+		} else {
+			None
+		};
+
 		// reserve deposit for new bounty
 		let bond = T::BountyDepositBase::get()
 			+ T::DataDepositPerByte::get() * (description.len() as u32).into();
@@ -705,13 +1321,22 @@ impl<T: Config> Module<T> {
 
 		BountyCount::put(index + 1);
 
+		let status = match auto_approve_at {
+			Some(approve_at) => {
+				BountyAutoApprovals::<T>::try_append(approve_at, index)
+					.map_err(|_| Error::<T>::TooManyAutoApprovals)?;
+				BountyStatus::PendingAutoApproval { approve_at }
+			},
+			None => BountyStatus::Proposed,
+		};
+
 		let bounty = Bounty {
 			proposer,
 			value,
 			fee: 0u32.into(),
 			curator_deposit: 0u32.into(),
 			bond,
-			status: BountyStatus::Proposed,
+			status,
 		};
 
 		Bounties::<T>::insert(index, &bounty);
@@ -722,6 +1347,16 @@ impl<T: Config> Module<T> {
 		Ok(())
 	}
 
+	fn pay_tranche(
+		bounty_id: BountyIndex,
+		tranche: BountyTranche<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+	) {
+		let bounty_account = Self::bounty_account_id(bounty_id);
+		let res = T::Currency::transfer(&bounty_account, &tranche.beneficiary, tranche.value, AllowDeath); // should not fail
+		debug_assert!(res.is_ok());
+
+		Self::deposit_event(Event::<T>::BountyTrancheClaimed(bounty_id, tranche.value, tranche.beneficiary));
+	}
 }
 
 impl<T: Config> pallet_treasury::SpendFunds<T> for Module<T> {
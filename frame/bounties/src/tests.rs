@@ -99,6 +99,7 @@ impl pallet_balances::Config for Test {
 }
 thread_local! {
 	static TEN_TO_FOURTEEN: RefCell<Vec<u128>> = RefCell::new(vec![10,11,12,13,14]);
+	static UNQUALIFIED_CURATORS: RefCell<Vec<u128>> = RefCell::new(vec![]);
 }
 parameter_types! {
 	pub const ProposalBond: Permill = Permill::from_percent(5);
@@ -133,6 +134,20 @@ parameter_types! {
 	pub const BountyCuratorDeposit: Permill = Permill::from_percent(50);
 	pub const BountyValueMinimum: u64 = 1;
 	pub const MaximumReasonLength: u32 = 16384;
+	pub const MaximumTranches: u32 = 2;
+	pub const MaximumCurators: u32 = 3;
+	pub static BountyAutoApprovalLimit: u64 = 0;
+	pub const BountyObjectionPeriod: u64 = 5;
+	pub const MaxAutoApprovalsPerBlock: u32 = 2;
+}
+pub struct TestCuratorQualification;
+impl CuratorQualification<u128> for TestCuratorQualification {
+	fn is_qualified(who: &u128) -> bool {
+		UNQUALIFIED_CURATORS.with(|u| !u.borrow().contains(who))
+	}
+}
+fn set_unqualified_curators(curators: Vec<u128>) {
+	UNQUALIFIED_CURATORS.with(|u| *u.borrow_mut() = curators);
 }
 impl Config for Test {
 	type Event = Event;
@@ -143,6 +158,12 @@ impl Config for Test {
 	type BountyValueMinimum = BountyValueMinimum;
 	type DataDepositPerByte = DataDepositPerByte;
 	type MaximumReasonLength = MaximumReasonLength;
+	type MaximumTranches = MaximumTranches;
+	type MaximumCurators = MaximumCurators;
+	type CuratorQualification = TestCuratorQualification;
+	type BountyAutoApprovalLimit = BountyAutoApprovalLimit;
+	type BountyObjectionPeriod = BountyObjectionPeriod;
+	type MaxAutoApprovalsPerBlock = MaxAutoApprovalsPerBlock;
 	type WeightInfo = ();
 }
 
@@ -413,6 +434,34 @@ fn propose_bounty_works() {
 	});
 }
 
+#[test]
+fn bounties_paged_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&0, 1000);
+
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 10, b"first".to_vec()));
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 20, b"second".to_vec()));
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 30, b"third".to_vec()));
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 1));
+		assert_ok!(Bounties::close_bounty(Origin::root(), 2));
+
+		let mut bounties = Bounties::bounties_paged(None, 10);
+		let first = bounties[0].0;
+		assert_eq!(Bounties::bounties_paged(None, 1).len(), 1);
+		assert_eq!(Bounties::bounties_paged(Some(first), 10), bounties[1..].to_vec());
+		assert!(Bounties::bounties_paged(Some(bounties[1].0), 10).is_empty());
+
+		bounties.sort_by_key(|(index, _)| *index);
+		assert_eq!(bounties.len(), 2);
+		assert_eq!(bounties[0].0, 0);
+		assert_eq!(bounties[0].1.status, BountyStatus::Proposed);
+		assert_eq!(bounties[1].0, 1);
+		assert_eq!(bounties[1].1.status, BountyStatus::Approved);
+	});
+}
+
 #[test]
 fn propose_bounty_validation_works() {
 	new_test_ext().execute_with(|| {
@@ -512,6 +561,132 @@ fn approve_bounty_works() {
 	});
 }
 
+#[test]
+fn small_bounty_is_approved_without_council() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		BountyAutoApprovalLimit::set(20);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		// Not below the limit.
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 20, b"1".to_vec()));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Proposed);
+
+		assert_ok!(Bounties::propose_bounty(Origin::signed(1), 10, b"2".to_vec()));
+		let approve_at = 1 + BountyObjectionPeriod::get() + 1;
+		assert_eq!(
+			Bounties::bounties(1).unwrap().status,
+			BountyStatus::PendingAutoApproval { approve_at },
+		);
+		assert_eq!(Bounties::bounty_auto_approvals(approve_at), vec![1]);
+
+		<Bounties as OnInitialize<u64>>::on_initialize(approve_at - 1);
+		assert_eq!(
+			Bounties::bounties(1).unwrap().status,
+			BountyStatus::PendingAutoApproval { approve_at },
+		);
+
+		<Bounties as OnInitialize<u64>>::on_initialize(approve_at);
+		assert_eq!(last_event(), RawEvent::BountyAutoApproved(1));
+		assert_eq!(Bounties::bounties(1).unwrap().status, BountyStatus::Approved);
+		assert_eq!(Bounties::bounty_approvals(), vec![1]);
+		assert!(Bounties::bounty_auto_approvals(approve_at).is_empty());
+
+		// Funded at the next spend period like any approved bounty.
+		<Treasury as OnInitialize<u64>>::on_initialize(approve_at + 1);
+		assert_eq!(Bounties::bounties(1).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn objection_requires_council_approval() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		BountyAutoApprovalLimit::set(20);
+
+		assert_noop!(Bounties::object_to_bounty(Origin::signed(2), 0), Error::<Test>::InvalidIndex);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 10, b"1".to_vec()));
+		let approve_at = 1 + BountyObjectionPeriod::get() + 1;
+
+		assert_ok!(Bounties::object_to_bounty(Origin::signed(2), 0));
+		assert_eq!(last_event(), RawEvent::BountyObjected(0, 2));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Proposed);
+		assert_noop!(
+			Bounties::object_to_bounty(Origin::signed(2), 0),
+			Error::<Test>::UnexpectedStatus,
+		);
+
+		// Not approved at the end of the objection period anymore.
+		<Bounties as OnInitialize<u64>>::on_initialize(approve_at);
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Proposed);
+		assert!(Bounties::bounty_approvals().is_empty());
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Approved);
+	});
+}
+
+#[test]
+fn auto_approvals_per_block_are_limited() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		BountyAutoApprovalLimit::set(20);
+		Balances::make_free_balance_be(&3, 200);
+
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 10, b"1".to_vec()));
+		assert_ok!(Bounties::propose_bounty(Origin::signed(1), 10, b"2".to_vec()));
+		assert_noop!(
+			Bounties::propose_bounty(Origin::signed(3), 10, b"3".to_vec()),
+			Error::<Test>::TooManyAutoApprovals,
+		);
+		// Bounties that need the approval of the Council are not limited.
+		assert_ok!(Bounties::propose_bounty(Origin::signed(3), 20, b"3".to_vec()));
+
+		// Bounties proposed in the next block are approved in the next block.
+		System::set_block_number(2);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(3), 10, b"4".to_vec()));
+		assert_eq!(
+			Bounties::bounties(3).unwrap().status,
+			BountyStatus::PendingAutoApproval { approve_at: 2 + BountyObjectionPeriod::get() + 1 },
+		);
+	});
+}
+
+#[test]
+fn curator_cannot_be_proposed_before_auto_approval() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		BountyAutoApprovalLimit::set(20);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 10, b"1".to_vec()));
+		let approve_at = 1 + BountyObjectionPeriod::get() + 1;
+
+		assert_noop!(
+			Bounties::propose_curator(Origin::root(), 0, 4, 4),
+			Error::<Test>::UnexpectedStatus,
+		);
+		assert_noop!(
+			Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5], 1, 4),
+			Error::<Test>::UnexpectedStatus,
+		);
+
+		<Bounties as OnInitialize<u64>>::on_initialize(approve_at);
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Approved);
+
+		<Treasury as OnInitialize<u64>>::on_initialize(approve_at + 1);
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::reserved_balance(0), 0);
+
+		assert_ok!(Bounties::propose_curator(Origin::root(), 0, 4, 4));
+		assert_eq!(
+			Bounties::bounties(0).unwrap().status,
+			BountyStatus::CuratorProposed { curator: 4 },
+		);
+	});
+}
+
 #[test]
 fn assign_curator_works() {
 	new_test_ext().execute_with(|| {
@@ -566,6 +741,68 @@ fn assign_curator_works() {
 	});
 }
 
+#[test]
+fn curator_must_be_qualified() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		System::set_block_number(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		set_unqualified_curators(vec![4]);
+		assert_noop!(
+			Bounties::propose_curator(Origin::root(), 0, 4, 4),
+			Error::<Test>::CuratorNotQualified,
+		);
+
+		set_unqualified_curators(vec![]);
+		assert_ok!(Bounties::propose_curator(Origin::root(), 0, 4, 4));
+		Balances::make_free_balance_be(&4, 10);
+
+		// The curator lost their qualification after being proposed.
+		set_unqualified_curators(vec![4]);
+		assert_noop!(Bounties::accept_curator(Origin::signed(4), 0), Error::<Test>::CuratorNotQualified);
+
+		set_unqualified_curators(vec![]);
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+		assert_eq!(Balances::reserved_balance(&4), 2);
+	});
+}
+
+#[test]
+fn update_curator_fee_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&4, 10);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		System::set_block_number(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		assert_noop!(Bounties::update_curator_fee(Origin::root(), 0, 4), Error::<Test>::UnexpectedStatus);
+
+		assert_ok!(Bounties::propose_curator(Origin::root(), 0, 4, 10));
+
+		assert_noop!(Bounties::update_curator_fee(Origin::signed(4), 0, 4), BadOrigin);
+		assert_noop!(Bounties::update_curator_fee(Origin::root(), 0, 50), Error::<Test>::InvalidFee);
+		assert_ok!(Bounties::update_curator_fee(Origin::root(), 0, 4));
+		assert_eq!(last_event(), RawEvent::CuratorFeeUpdated(0, 4));
+
+		assert_eq!(Bounties::bounties(0).unwrap().fee, 4);
+
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+		assert_eq!(Balances::reserved_balance(4), 2); // half of the updated fee
+
+		assert_noop!(Bounties::update_curator_fee(Origin::root(), 0, 6), Error::<Test>::UnexpectedStatus);
+	});
+}
+
 #[test]
 fn unassign_curator_works() {
 	new_test_ext().execute_with(|| {
@@ -672,6 +909,93 @@ fn award_and_claim_bounty_works() {
 	});
 }
 
+#[test]
+fn award_and_claim_bounty_tranches_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&4, 10);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		System::set_block_number(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		assert_ok!(Bounties::propose_curator(Origin::root(), 0, 4, 4));
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+
+		assert_noop!(Bounties::award_bounty_partial(Origin::signed(1), 0, 3, 10), Error::<Test>::RequireCurator);
+		assert_noop!(Bounties::award_bounty_partial(Origin::signed(4), 0, 3, 0), Error::<Test>::InvalidValue);
+		// The curator fee can't be awarded.
+		assert_noop!(Bounties::award_bounty_partial(Origin::signed(4), 0, 3, 47), Error::<Test>::InvalidValue);
+
+		assert_ok!(Bounties::award_bounty_partial(Origin::signed(4), 0, 3, 10));
+		assert_eq!(last_event(), RawEvent::BountyTrancheAwarded(0, 3, 10));
+
+		System::set_block_number(3);
+		assert_ok!(Bounties::award_bounty_partial(Origin::signed(4), 0, 5, 20));
+		assert_noop!(Bounties::award_bounty_partial(Origin::signed(4), 0, 5, 1), Error::<Test>::TooManyTranches);
+
+		assert_noop!(Bounties::claim_bounty_tranches(Origin::signed(1), 0), Error::<Test>::Premature);
+
+		// Only the first tranche is unlocked.
+		System::set_block_number(5);
+		assert_ok!(Bounties::claim_bounty_tranches(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::BountyTrancheClaimed(0, 10, 3));
+		assert_eq!(Balances::free_balance(3), 10);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 40);
+		assert_eq!(Bounties::bounty_tranches(0).len(), 1);
+
+		// 40 left, 20 still awarded and 4 for the curator.
+		assert_noop!(Bounties::award_bounty_partial(Origin::signed(4), 0, 5, 17), Error::<Test>::InvalidValue);
+		assert_ok!(Bounties::award_bounty_partial(Origin::signed(4), 0, 5, 16));
+		assert_ok!(Bounties::award_bounty(Origin::signed(4), 0, 3));
+
+		System::set_block_number(8);
+		assert_ok!(Bounties::claim_bounty(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::BountyClaimed(0, 0, 3));
+
+		// The remaining tranches are paid out with the bounty.
+		assert_eq!(Balances::free_balance(5), 36);
+		assert_eq!(Balances::free_balance(4), 14); // initial 10 + fee 4
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 0);
+
+		assert_eq!(Bounties::bounties(0), None);
+		assert_eq!(Bounties::bounty_tranches(0), vec![]);
+	});
+}
+
+#[test]
+fn close_bounty_requires_claimed_tranches() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&4, 10);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		System::set_block_number(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		assert_ok!(Bounties::propose_curator(Origin::root(), 0, 4, 4));
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+		assert_ok!(Bounties::award_bounty_partial(Origin::signed(4), 0, 3, 10));
+
+		assert_noop!(Bounties::close_bounty(Origin::root(), 0), Error::<Test>::UnclaimedTranches);
+
+		System::set_block_number(5);
+		assert_ok!(Bounties::claim_bounty_tranches(Origin::signed(1), 0));
+		assert_eq!(Balances::free_balance(3), 10);
+
+		assert_ok!(Bounties::close_bounty(Origin::root(), 0));
+		assert_eq!(Bounties::bounties(0), None);
+		// The rest of the bounty goes back to the treasury.
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 0);
+	});
+}
+
 #[test]
 fn claim_handles_high_fee() {
 	new_test_ext().execute_with(|| {
@@ -815,6 +1139,7 @@ fn expire_and_unassign() {
 		<Treasury as OnInitialize<u64>>::on_initialize(23);
 
 		assert_ok!(Bounties::unassign_curator(Origin::signed(0), 0));
+		assert_eq!(last_event(), RawEvent::BountyTimedOut(0, 1));
 
 		assert_eq!(Bounties::bounties(0).unwrap(), Bounty {
 			proposer: 0,
@@ -889,6 +1214,230 @@ fn extend_expiry() {
 	});
 }
 
+#[test]
+fn propose_curator_council_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		System::set_block_number(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		assert_noop!(Bounties::propose_curator_council(Origin::signed(1), 0, vec![4, 5], 2, 8), BadOrigin);
+		assert_noop!(
+			Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5], 0, 8),
+			Error::<Test>::InvalidCouncil,
+		);
+		// Duplicates are counted once.
+		assert_noop!(
+			Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5, 5], 3, 8),
+			Error::<Test>::InvalidCouncil,
+		);
+		assert_noop!(
+			Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5, 6, 7], 2, 8),
+			Error::<Test>::InvalidCouncil,
+		);
+		assert_noop!(
+			Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5], 2, 50),
+			Error::<Test>::InvalidFee,
+		);
+		set_unqualified_curators(vec![6]);
+		assert_noop!(
+			Bounties::propose_curator_council(Origin::root(), 0, vec![4, 6], 2, 8),
+			Error::<Test>::CuratorNotQualified,
+		);
+
+		set_unqualified_curators(vec![]);
+		assert_ok!(Bounties::propose_curator_council(Origin::root(), 0, vec![5, 4], 2, 8));
+
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::CouncilProposed {
+			council: CuratorCouncil { members: vec![4, 5], threshold: 2 },
+			accepted: vec![],
+		});
+	});
+}
+
+#[test]
+fn accept_curator_council_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&4, 10);
+		Balances::make_free_balance_be(&5, 10);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		System::set_block_number(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		assert_ok!(Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5], 2, 10));
+		assert_ok!(Bounties::update_curator_fee(Origin::root(), 0, 8));
+
+		assert_noop!(Bounties::accept_curator(Origin::signed(6), 0), Error::<Test>::RequireCurator);
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+		assert_noop!(Bounties::accept_curator(Origin::signed(4), 0), Error::<Test>::AlreadyAccepted);
+		// Half of the share of the fee of each member.
+		assert_eq!(Balances::reserved_balance(4), 2);
+
+		// The fee can't change once a member reserved a deposit for it.
+		assert_noop!(Bounties::update_curator_fee(Origin::root(), 0, 6), Error::<Test>::UnexpectedStatus);
+
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::CouncilProposed {
+			council: CuratorCouncil { members: vec![4, 5], threshold: 2 },
+			accepted: vec![4],
+		});
+
+		assert_ok!(Bounties::accept_curator(Origin::signed(5), 0));
+		assert_eq!(Balances::reserved_balance(5), 2);
+
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::CouncilActive {
+			council: CuratorCouncil { members: vec![4, 5], threshold: 2 },
+			update_due: 22,
+			approvals: vec![],
+		});
+	});
+}
+
+#[test]
+fn award_and_claim_bounty_council_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&4, 10);
+		Balances::make_free_balance_be(&5, 10);
+		Balances::make_free_balance_be(&6, 10);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		System::set_block_number(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		assert_ok!(Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5, 6], 2, 12));
+		for curator in 4..=6 {
+			assert_ok!(Bounties::accept_curator(Origin::signed(curator), 0));
+		}
+
+		assert_noop!(Bounties::award_bounty(Origin::signed(7), 0, 3), Error::<Test>::RequireCurator);
+		assert_noop!(Bounties::award_bounty_partial(Origin::signed(4), 0, 3, 10), Error::<Test>::UnexpectedStatus);
+
+		assert_ok!(Bounties::award_bounty(Origin::signed(4), 0, 3));
+		assert_eq!(last_event(), RawEvent::BountyAwardApproved(0, 4, 3));
+		// A member changing their mind replaces their approval.
+		assert_ok!(Bounties::award_bounty(Origin::signed(4), 0, 8));
+		assert_ok!(Bounties::award_bounty(Origin::signed(5), 0, 3));
+		assert_eq!(last_event(), RawEvent::BountyAwardApproved(0, 5, 3));
+
+		assert_ok!(Bounties::award_bounty(Origin::signed(6), 0, 3));
+		assert_eq!(last_event(), RawEvent::BountyAwarded(0, 3));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::CouncilPendingPayout {
+			council: CuratorCouncil { members: vec![4, 5, 6], threshold: 2 },
+			beneficiary: 3,
+			unlock_at: 5,
+		});
+
+		assert_noop!(Bounties::claim_bounty(Origin::signed(1), 0), Error::<Test>::Premature);
+
+		System::set_block_number(5);
+		assert_ok!(Bounties::claim_bounty(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::BountyClaimed(0, 38, 3));
+
+		// Each member gets their deposit back and a third of the fee.
+		for curator in 4..=6 {
+			assert_eq!(Balances::free_balance(curator), 14);
+			assert_eq!(Balances::reserved_balance(curator), 0);
+		}
+		assert_eq!(Balances::free_balance(3), 38);
+		assert_eq!(Balances::free_balance(Bounties::bounty_account_id(0)), 0);
+		assert_eq!(Bounties::bounties(0), None);
+	});
+}
+
+#[test]
+fn unassign_curator_council_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&4, 10);
+		Balances::make_free_balance_be(&5, 10);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		System::set_block_number(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		assert_ok!(Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5], 1, 8));
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+
+		// A proposed member declining returns the deposits of the members that accepted.
+		assert_noop!(Bounties::unassign_curator(Origin::signed(1), 0), BadOrigin);
+		assert_ok!(Bounties::unassign_curator(Origin::signed(5), 0));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::reserved_balance(4), 0);
+
+		assert_ok!(Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5], 1, 8));
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+		assert_ok!(Bounties::accept_curator(Origin::signed(5), 0));
+
+		// A member giving up dissolves the council.
+		assert_ok!(Bounties::unassign_curator(Origin::signed(4), 0));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::free_balance(4), 10);
+		assert_eq!(Balances::free_balance(5), 10);
+
+		assert_ok!(Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5], 1, 8));
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+		assert_ok!(Bounties::accept_curator(Origin::signed(5), 0));
+
+		System::set_block_number(10);
+		assert_ok!(Bounties::extend_bounty_expiry(Origin::signed(5), 0, Vec::new()));
+		assert_noop!(Bounties::unassign_curator(Origin::signed(1), 0), Error::<Test>::Premature);
+
+		// An inactive council is slashed as a whole.
+		System::set_block_number(31);
+		assert_ok!(Bounties::unassign_curator(Origin::signed(1), 0));
+		assert_eq!(last_event(), RawEvent::BountyTimedOut(0, 5));
+		assert_eq!(Bounties::bounties(0).unwrap().status, BountyStatus::Funded);
+		assert_eq!(Balances::free_balance(4), 8);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::free_balance(5), 8);
+		assert_eq!(Balances::reserved_balance(5), 0);
+	});
+}
+
+#[test]
+fn close_bounty_refunds_curator_council() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		Balances::make_free_balance_be(&4, 10);
+		Balances::make_free_balance_be(&5, 10);
+		assert_ok!(Bounties::propose_bounty(Origin::signed(0), 50, b"12345".to_vec()));
+
+		assert_ok!(Bounties::approve_bounty(Origin::root(), 0));
+
+		System::set_block_number(2);
+		<Treasury as OnInitialize<u64>>::on_initialize(2);
+
+		assert_ok!(Bounties::propose_curator_council(Origin::root(), 0, vec![4, 5], 2, 8));
+		assert_ok!(Bounties::accept_curator(Origin::signed(4), 0));
+		assert_ok!(Bounties::accept_curator(Origin::signed(5), 0));
+
+		assert_ok!(Bounties::close_bounty(Origin::root(), 0));
+
+		assert_eq!(Bounties::bounties(0), None);
+		assert_eq!(Balances::free_balance(4), 10);
+		assert_eq!(Balances::reserved_balance(4), 0);
+		assert_eq!(Balances::free_balance(5), 10);
+		assert_eq!(Balances::reserved_balance(5), 0);
+	});
+}
+
 #[test]
 fn genesis_funding_works() {
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
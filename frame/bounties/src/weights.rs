@@ -52,9 +52,17 @@ pub trait WeightInfo {
 	fn award_bounty() -> Weight;
 	fn claim_bounty() -> Weight;
 	fn close_bounty_proposed() -> Weight;
+	fn close_bounty_pending_auto_approval() -> Weight;
+	fn close_bounty_funded() -> Weight;
+	fn close_bounty_curator_proposed() -> Weight;
 	fn close_bounty_active() -> Weight;
 	fn extend_bounty_expiry() -> Weight;
 	fn spend_funds(b: u32, ) -> Weight;
+	fn award_bounty_partial() -> Weight;
+	fn claim_bounty_tranches(t: u32, ) -> Weight;
+	fn object_to_bounty() -> Weight;
+	fn auto_approve_bounties(b: u32, ) -> Weight;
+	fn update_curator_fee() -> Weight;
 }
 
 /// Weights for pallet_bounties using the Substrate node and recommended hardware.
@@ -102,6 +110,24 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn close_bounty_pending_auto_approval() -> Weight {
+		(40_464_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn close_bounty_funded() -> Weight {
+		(72_318_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn close_bounty_curator_proposed() -> Weight {
+		(72_318_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
 	fn close_bounty_active() -> Weight {
 		(84_042_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
@@ -121,6 +147,42 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(b as Weight)))
 	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn award_bounty_partial() -> Weight {
+		(31_374_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn claim_bounty_tranches(t: u32, ) -> Weight {
+		(21_163_000 as Weight)
+			.saturating_add((57_296_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn object_to_bounty() -> Weight {
+		(20_417_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn auto_approve_bounties(b: u32, ) -> Weight {
+		(2_863_000 as Weight)
+			.saturating_add((11_552_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(b as Weight)))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn update_curator_fee() -> Weight {
+		(8_914_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -167,6 +229,24 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn close_bounty_pending_auto_approval() -> Weight {
+		(40_464_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn close_bounty_funded() -> Weight {
+		(72_318_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn close_bounty_curator_proposed() -> Weight {
+		(72_318_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
 	fn close_bounty_active() -> Weight {
 		(84_042_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
@@ -186,4 +266,40 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(b as Weight)))
 	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn award_bounty_partial() -> Weight {
+		(31_374_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn claim_bounty_tranches(t: u32, ) -> Weight {
+		(21_163_000 as Weight)
+			.saturating_add((57_296_000 as Weight).saturating_mul(t as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(t as Weight)))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn object_to_bounty() -> Weight {
+		(20_417_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn auto_approve_bounties(b: u32, ) -> Weight {
+		(2_863_000 as Weight)
+			.saturating_add((11_552_000 as Weight).saturating_mul(b as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(b as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(b as Weight)))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_bounties` benchmarks.
+	fn update_curator_fee() -> Weight {
+		(8_914_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -661,6 +661,13 @@ pub mod pallet {
 		/// Configuration for the fallback
 		type Fallback: Get<FallbackStrategy>;
 
+		/// Maximum number of voters to put in the snapshot.
+		///
+		/// The snapshot is created in a single block, so this bounds the weight of its creation.
+		/// It is further capped by the maximum voter index of [`Config::CompactSolution`].
+		#[pallet::constant]
+		type VoterSnapshotPerBlock: Get<u32>;
+
 		/// Origin that can control this pallet. Note that any action taken by this origin (such)
 		/// as providing an emergency solution is not checked. Thus, it must be a trusted origin.
 		type ForceOrigin: EnsureOrigin<Self::Origin>;
@@ -1292,7 +1299,9 @@ impl<T: Config> Pallet<T> {
 	/// Returns `Ok(consumed_weight)` if operation is okay.
 	pub fn create_snapshot() -> Result<Weight, ElectionError> {
 		let target_limit = <CompactTargetIndexOf<T>>::max_value().saturated_into::<usize>();
-		let voter_limit = <CompactVoterIndexOf<T>>::max_value().saturated_into::<usize>();
+		let voter_limit = <CompactVoterIndexOf<T>>::max_value()
+			.saturated_into::<usize>()
+			.min(T::VoterSnapshotPerBlock::get().saturated_into::<usize>());
 
 		let (targets, w1) =
 			T::DataProvider::targets(Some(target_limit)).map_err(ElectionError::DataProvider)?;
@@ -1727,7 +1736,7 @@ mod tests {
 		Phase,
 		mock::{
 			ExtBuilder, MultiPhase, Runtime, roll_to, MockWeightInfo, AccountId, TargetIndex,
-			Targets, multi_phase_events, System, SignedMaxSubmissions,
+			Targets, Voters, multi_phase_events, System, SignedMaxSubmissions,
 		},
 	};
 	use frame_election_provider_support::ElectionProvider;
@@ -1992,6 +2001,16 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn snapshot_respects_voter_limit() {
+		ExtBuilder::default().voter_snapshot_per_block(3).build_and_execute(|| {
+			roll_to(15);
+			assert_eq!(MultiPhase::current_phase(), Phase::Signed);
+			assert_eq!(MultiPhase::snapshot_metadata().unwrap().voters, 3);
+			assert_eq!(MultiPhase::snapshot().unwrap().voters, Voters::get()[..3].to_vec());
+		})
+	}
+
 	#[test]
 	fn untrusted_score_verification_is_respected() {
 		ExtBuilder::default().build_and_execute(|| {
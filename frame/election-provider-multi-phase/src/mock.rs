@@ -273,6 +273,7 @@ parameter_types! {
 	pub static OffchainRepeat: BlockNumber = 5;
 	pub static MinerMaxWeight: Weight = BlockWeights::get().max_block;
 	pub static MinerMaxLength: u32 = 256;
+	pub static VoterSnapshotPerBlock: u32 = u32::max_value();
 	pub static MockWeightInfo: bool = false;
 
 	pub static EpochLength: u64 = 30;
@@ -381,6 +382,7 @@ impl crate::Config for Runtime {
 	type BenchmarkingConfig = ();
 	type OnChainAccuracy = Perbill;
 	type Fallback = Fallback;
+	type VoterSnapshotPerBlock = VoterSnapshotPerBlock;
 	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
 	type CompactSolution = TestCompact;
 }
@@ -414,9 +416,9 @@ impl ElectionDataProvider<AccountId, u64> for StakingMock {
 	fn voters(
 		maybe_max_len: Option<usize>,
 	) -> data_provider::Result<(Vec<(AccountId, VoteWeight, Vec<AccountId>)>, Weight)> {
-		let voters = Voters::get();
-		if maybe_max_len.map_or(false, |max_len| voters.len() > max_len) {
-			return Err("Voters too big");
+		let mut voters = Voters::get();
+		if let Some(max_len) = maybe_max_len {
+			voters.truncate(max_len);
 		}
 
 		Ok((voters, 0))
@@ -484,6 +486,10 @@ impl ExtBuilder {
 		<Fallback>::set(fallback);
 		self
 	}
+	pub fn voter_snapshot_per_block(self, max: u32) -> Self {
+		<VoterSnapshotPerBlock>::set(max);
+		self
+	}
 	pub fn miner_weight(self, weight: Weight) -> Self {
 		<MinerMaxWeight>::set(weight);
 		self
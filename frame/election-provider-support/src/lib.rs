@@ -264,6 +264,67 @@ impl<AccountId, BlockNumber> ElectionDataProvider<AccountId, BlockNumber> for ()
 	}
 }
 
+/// A list of voters, sorted at least roughly by decreasing vote weight.
+///
+/// Lets an [`ElectionDataProvider`] take the heaviest voters first when it cannot provide all of
+/// them. The owner of the voters keeps the list up to date through the `on_*` functions.
+pub trait SortedListProvider<AccountId> {
+	/// The error returned by [`Self::on_insert`].
+	type Error: Debug;
+
+	/// All the voters in the list, heaviest first.
+	fn iter() -> Box<dyn Iterator<Item = AccountId>>;
+
+	/// The number of voters in the list.
+	fn count() -> u32;
+
+	/// Returns `true` if `id` is in the list.
+	fn contains(id: &AccountId) -> bool;
+
+	/// Insert `id` with the given vote weight. Fails if `id` is already in the list.
+	fn on_insert(id: AccountId, weight: VoteWeight) -> Result<(), Self::Error>;
+
+	/// The vote weight of `id` changed to `weight`. Does nothing if `id` is not in the list.
+	fn on_update(id: &AccountId, weight: VoteWeight);
+
+	/// Remove `id` from the list. Does nothing if `id` is not in the list.
+	fn on_remove(id: &AccountId);
+
+	/// The worst case weight of [`Self::on_insert`].
+	fn on_insert_weight() -> Weight;
+
+	/// The worst case weight of [`Self::on_update`], including checking with [`Self::contains`]
+	/// that the voter is in the list.
+	fn on_update_weight() -> Weight;
+
+	/// The worst case weight of [`Self::on_remove`].
+	fn on_remove_weight() -> Weight;
+
+	/// Remove all the voters from the list.
+	fn clear();
+
+	/// Replace all the voters in the list with `all`, weighted by `weight_of`.
+	///
+	/// Returns the number of voters inserted. Meant for migrations, as it is very expensive.
+	fn regenerate(
+		all: impl IntoIterator<Item = AccountId>,
+		weight_of: Box<dyn Fn(&AccountId) -> VoteWeight>,
+	) -> u32;
+
+	/// Check the integrity of the list, for tests and `try-runtime` checks.
+	fn sanity_check() -> Result<(), &'static str>;
+}
+
+/// Something that knows the current vote weight of the voters.
+pub trait VoteWeightProvider<AccountId> {
+	/// The current vote weight of `who`.
+	fn vote_weight(who: &AccountId) -> VoteWeight;
+
+	/// Set the vote weight of `who`, for benchmarking.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_vote_weight_of(_who: &AccountId, _weight: VoteWeight) {}
+}
+
 /// Something that can compute the result of an election and pass it back to the caller.
 ///
 /// This trait only provides an interface to _request_ an election, i.e.
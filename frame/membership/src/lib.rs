@@ -19,25 +19,48 @@
 //!
 //! Allows control of membership of a set of `AccountId`s, useful for managing membership of of a
 //! collective. A prime member may be set
+//!
+//! Members may serve for a limited term of `TermDuration` blocks. Every `RotationPeriod` blocks,
+//! the members whose term ended are replaced by the applicants queued by `AddOrigin`, in order. A
+//! member whose term ended stays until there is an applicant to replace it.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use sp_std::prelude::*;
 use frame_support::{
-	decl_module, decl_storage, decl_event, decl_error,
+	decl_module, decl_storage, decl_event, decl_error, ensure,
 	traits::{ChangeMembers, InitializeMembers, EnsureOrigin, Contains, SortedMembers, Get},
+	weights::Weight,
 };
 use frame_system::ensure_signed;
+use sp_runtime::{RuntimeDebug, traits::{Saturating, Zero}};
+use codec::{Encode, Decode};
 
+pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
+// A value placed in storage that represents the current version of the membership storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run storage
+// migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	V0,
+	V1,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V0
+	}
+}
+
 pub trait Config<I = DefaultInstance>: frame_system::Config {
 	/// The overarching event type.
 	type Event: From<Event<Self, I>> + Into<<Self as frame_system::Config>::Event>;
 
-	/// Required origin for adding a member (though can always be Root).
+	/// Required origin for adding a member or an applicant (though can always be Root).
 	type AddOrigin: EnsureOrigin<Self::Origin>;
 
 	/// Required origin for removing a member (though can always be Root).
@@ -67,6 +90,19 @@ pub trait Config<I = DefaultInstance>: frame_system::Config {
 	/// This is not enforced in the code; the membership size can exceed this limit.
 	type MaxMembers: Get<u32>;
 
+	/// The number of blocks a member serves before its seat is given to an applicant.
+	///
+	/// Zero means members serve until they are removed.
+	type TermDuration: Get<Self::BlockNumber>;
+
+	/// The number of blocks between two rotations of the members whose term ended.
+	///
+	/// Zero disables the rotation.
+	type RotationPeriod: Get<Self::BlockNumber>;
+
+	/// The maximum number of accounts waiting for a seat.
+	type MaxApplicants: Get<u32>;
+
 	/// Weight information for extrinsics in this pallet.
 	type WeightInfo: WeightInfo;
 }
@@ -78,6 +114,18 @@ decl_storage! {
 
 		/// The current prime member, if one exists.
 		Prime get(fn prime): Option<T::AccountId>;
+
+		/// The block at which the term of each member ends. Members without an entry serve until
+		/// they are removed.
+		TermEnds get(fn term_end): map hasher(twox_64_concat) T::AccountId => Option<T::BlockNumber>;
+
+		/// The accounts waiting for a seat, in order.
+		Applicants get(fn applicants): Vec<T::AccountId>;
+
+		/// Storage version of the pallet.
+		///
+		/// New networks start with the last version.
+		StorageVersion build(|_: &GenesisConfig<T, I>| Releases::V1): Releases;
 	}
 	add_extra_genesis {
 		config(members): Vec<T::AccountId>;
@@ -91,6 +139,7 @@ decl_storage! {
 
 			members.sort();
 			T::MembershipInitialized::initialize_members(&members);
+			members.iter().for_each(<Module<T, I>>::start_term);
 			<Members<T, I>>::put(members);
 		})
 	}
@@ -111,6 +160,10 @@ decl_event!(
 		MembersReset,
 		/// One of the members' keys changed.
 		KeyChanged,
+		/// An account was queued for a seat. \[who\]
+		ApplicantAdded(AccountId),
+		/// The members whose term ended were replaced by applicants. \[outgoing, incoming\]
+		MembersRotated(Vec<AccountId>, Vec<AccountId>),
 		/// Phantom member, never used.
 		Dummy(sp_std::marker::PhantomData<(AccountId, Event)>),
 	}
@@ -123,6 +176,10 @@ decl_error! {
 		AlreadyMember,
 		/// Not a member.
 		NotMember,
+		/// Already waiting for a seat.
+		AlreadyApplicant,
+		/// The queue of applicants is full.
+		TooManyApplicants,
 	}
 }
 
@@ -135,6 +192,24 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		fn on_runtime_upgrade() -> Weight {
+			if <StorageVersion<I>>::get() == Releases::V0 {
+				<StorageVersion<I>>::put(Releases::V1);
+				migrations::v1::migrate::<T, I>()
+					.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let period = T::RotationPeriod::get();
+			if period.is_zero() || !(n % period).is_zero() {
+				return 0
+			}
+			Self::rotate_members(n)
+		}
+
 		/// Add a member `who` to the set.
 		///
 		/// May only be called from `T::AddOrigin`.
@@ -148,6 +223,7 @@ decl_module! {
 
 			Self::maybe_warn_max_members(&members);
 			<Members<T, I>>::put(&members);
+			Self::start_term(&who);
 
 			T::MembershipChanged::change_members_sorted(&[who], &[], &members[..]);
 
@@ -167,6 +243,7 @@ decl_module! {
 
 			Self::maybe_warn_max_members(&members);
 			<Members<T, I>>::put(&members);
+			<TermEnds<T, I>>::remove(&who);
 
 			T::MembershipChanged::change_members_sorted(&[], &[who], &members[..]);
 			Self::rejig_prime(&members);
@@ -193,6 +270,8 @@ decl_module! {
 
 			Self::maybe_warn_max_members(&members);
 			<Members<T, I>>::put(&members);
+			<TermEnds<T, I>>::remove(&remove);
+			Self::start_term(&add);
 
 			T::MembershipChanged::change_members_sorted(
 				&[add],
@@ -215,6 +294,10 @@ decl_module! {
 			let mut members = members;
 			members.sort();
 			<Members<T, I>>::mutate(|m| {
+				let (incoming, outgoing) =
+					T::MembershipChanged::compute_members_diff_sorted(&members[..], m);
+				outgoing.iter().for_each(<TermEnds<T, I>>::remove);
+				incoming.iter().for_each(Self::start_term);
 				T::MembershipChanged::set_members_sorted(&members[..], m);
 				Self::rejig_prime(&members);
 				Self::maybe_warn_max_members(&members);
@@ -228,7 +311,7 @@ decl_module! {
 		///
 		/// May only be called from `Signed` origin of a current member.
 		///
-		/// Prime membership is passed from the origin account to `new`, if extant.
+		/// Prime membership and the term are passed from the origin account to `new`, if extant.
 		#[weight = 50_000_000]
 		pub fn change_key(origin, new: T::AccountId) {
 			let remove = ensure_signed(origin)?;
@@ -242,6 +325,9 @@ decl_module! {
 
 				Self::maybe_warn_max_members(&members);
 				<Members<T, I>>::put(&members);
+				if let Some(term_end) = <TermEnds<T, I>>::take(&remove) {
+					<TermEnds<T, I>>::insert(&new, term_end);
+				}
 
 				T::MembershipChanged::change_members_sorted(
 					&[new.clone()],
@@ -278,10 +364,110 @@ decl_module! {
 			Prime::<T, I>::kill();
 			T::MembershipChanged::set_prime(None);
 		}
+
+		/// Add `who` to the back of the queue of applicants.
+		///
+		/// The seats of the members whose term ended are given to the applicants, in order,
+		/// when the members are rotated. A member whose term ended may only be queued again
+		/// once it has been replaced.
+		///
+		/// May only be called from `T::AddOrigin`, for an account which is neither a member nor
+		/// an applicant.
+		#[weight = T::WeightInfo::add_applicant(T::MaxApplicants::get())]
+		pub fn add_applicant(origin, who: T::AccountId) {
+			T::AddOrigin::ensure_origin(origin)?;
+			ensure!(Self::members().binary_search(&who).is_err(), Error::<T, I>::AlreadyMember);
+
+			let mut applicants = <Applicants<T, I>>::get();
+			ensure!(!applicants.contains(&who), Error::<T, I>::AlreadyApplicant);
+			ensure!(
+				(applicants.len() as u32) < T::MaxApplicants::get(),
+				Error::<T, I>::TooManyApplicants,
+			);
+			applicants.push(who.clone());
+			<Applicants<T, I>>::put(applicants);
+
+			Self::deposit_event(RawEvent::ApplicantAdded(who));
+		}
 	}
 }
 
 impl<T: Config<I>, I: Instance> Module<T, I> {
+	/// Start the term of the new member `who`, if terms are limited.
+	fn start_term(who: &T::AccountId) {
+		let duration = T::TermDuration::get();
+		if !duration.is_zero() {
+			let term_end = <frame_system::Pallet<T>>::block_number().saturating_add(duration);
+			<TermEnds<T, I>>::insert(who, term_end);
+		}
+	}
+
+	/// Replace the members whose term ended by `now` with the first applicants, the members
+	/// whose term ended first being replaced first.
+	///
+	/// Members without an applicant to replace them stay, so the seats are never left empty.
+	/// Applicants which are members are dropped from the queue.
+	fn rotate_members(now: T::BlockNumber) -> Weight {
+		let (mut expired, members): (Vec<_>, Vec<_>) = <Members<T, I>>::get()
+			.into_iter()
+			.map(|who| {
+				let term_end = Self::term_end(&who);
+				(who, term_end)
+			})
+			.partition(|(_, term_end)| term_end.map_or(false, |term_end| term_end <= now));
+		let mut members = members.into_iter().map(|(who, _)| who).collect::<Vec<_>>();
+		let applicants = <Applicants<T, I>>::get();
+		let weight = T::WeightInfo::rotate_members(
+			(expired.len() + members.len()) as u32,
+			applicants.len() as u32,
+		);
+		if expired.is_empty() || applicants.is_empty() {
+			return weight
+		}
+
+		let mut incoming = Vec::new();
+		let mut applicants = applicants.into_iter();
+		while incoming.len() < expired.len() {
+			let who = match applicants.next() {
+				Some(who) => who,
+				None => break,
+			};
+			if expired.iter().any(|(expired, _)| expired == &who) {
+				continue
+			}
+			if let Err(location) = members.binary_search(&who) {
+				members.insert(location, who.clone());
+				incoming.push(who);
+			}
+		}
+		<Applicants<T, I>>::put(applicants.collect::<Vec<_>>());
+
+		// The sort is stable, so members whose term ended together go in account order.
+		expired.sort_by_key(|(_, term_end)| *term_end);
+		let staying = expired.split_off(incoming.len());
+		let mut outgoing = expired.into_iter().map(|(who, _)| who).collect::<Vec<_>>();
+		for (who, _) in staying {
+			if let Err(location) = members.binary_search(&who) {
+				members.insert(location, who);
+			}
+		}
+		if outgoing.is_empty() {
+			return weight
+		}
+		outgoing.sort();
+
+		outgoing.iter().for_each(<TermEnds<T, I>>::remove);
+		incoming.iter().for_each(Self::start_term);
+		incoming.sort();
+
+		<Members<T, I>>::put(&members);
+		T::MembershipChanged::change_members_sorted(&incoming, &outgoing, &members);
+		Self::rejig_prime(&members);
+
+		Self::deposit_event(RawEvent::MembersRotated(outgoing, incoming));
+		weight
+	}
+
 	fn rejig_prime(members: &[T::AccountId]) {
 		if let Some(prime) = Prime::<T, I>::get() {
 			match members.binary_search(&prime) {
@@ -457,6 +643,36 @@ mod benchmark {
 			assert!(<T::MembershipChanged>::get_prime().is_none());
 			#[cfg(test)] crate::tests::clean();
 		}
+
+		add_applicant {
+			let a in 0 .. T::MaxApplicants::get() - 1;
+			let applicants = (0..a).map(|i| account("applicant", i, SEED)).collect::<Vec<T::AccountId>>();
+			<Applicants<T, I>>::put(applicants);
+			let who = account::<T::AccountId>("applicant", a, SEED);
+		}: {
+			assert_ok!(<Membership<T, _>>::add_applicant(T::AddOrigin::successful_origin(), who.clone()));
+		} verify {
+			assert_eq!(<Applicants<T, _>>::get().last(), Some(&who));
+		}
+
+		// all the terms ended, so that every seat is handed over.
+		rotate_members {
+			let m in 1 .. T::MaxMembers::get();
+			let a in 0 .. T::MaxApplicants::get();
+
+			let members = (0..m).map(|i| account("member", i, SEED)).collect::<Vec<T::AccountId>>();
+			set_members::<T, I>(members.clone(), Some(members.len() - 1));
+			members.iter().for_each(|who| <TermEnds<T, I>>::insert(who, T::BlockNumber::zero()));
+			let applicants = (0..a).map(|i| account("applicant", i, SEED)).collect::<Vec<T::AccountId>>();
+			<Applicants<T, I>>::put(applicants);
+		}: {
+			<Membership<T, _>>::rotate_members(T::BlockNumber::zero());
+		} verify {
+			// members without a replacement keep their seat.
+			assert_eq!(<Members<T, _>>::get().len() as u32, m);
+			assert_eq!(<Applicants<T, _>>::get().len() as u32, a - m.min(a));
+			#[cfg(test)] crate::tests::clean();
+		}
 	}
 
 	impl_benchmark_test_suite!(Membership, crate::tests::new_bench_ext(), crate::tests::Test,);
@@ -467,7 +683,10 @@ mod tests {
 	use super::*;
 	use crate as pallet_membership;
 
-	use frame_support::{assert_ok, assert_noop, parameter_types, ord_parameter_types};
+	use frame_support::{
+		assert_ok, assert_noop, parameter_types, ord_parameter_types,
+		traits::{OnInitialize, OnRuntimeUpgrade},
+	};
 	use sp_core::H256;
 	use sp_runtime::{traits::{BlakeTwo256, IdentityLookup, BadOrigin}, testing::Header};
 	use frame_system::EnsureSignedBy;
@@ -489,6 +708,9 @@ mod tests {
 	parameter_types! {
 		pub const BlockHashCount: u64 = 250;
 		pub const MaxMembers: u32 = 10;
+		pub const TermDuration: u64 = 10;
+		pub const RotationPeriod: u64 = 5;
+		pub const MaxApplicants: u32 = 3;
 		pub BlockWeights: frame_system::limits::BlockWeights =
 			frame_system::limits::BlockWeights::simple_max(1024);
 		pub static Members: Vec<u64> = vec![];
@@ -566,6 +788,9 @@ mod tests {
 		type MembershipInitialized = TestChangeMembers;
 		type MembershipChanged = TestChangeMembers;
 		type MaxMembers = MaxMembers;
+		type TermDuration = TermDuration;
+		type RotationPeriod = RotationPeriod;
+		type MaxApplicants = MaxApplicants;
 		type WeightInfo = ();
 	}
 
@@ -712,6 +937,119 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn add_applicant_works() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(Membership::add_applicant(Origin::signed(5), 40), BadOrigin);
+			assert_noop!(Membership::add_applicant(Origin::signed(40), 40), BadOrigin);
+			assert_noop!(Membership::add_applicant(Origin::signed(1), 10), Error::<Test, _>::AlreadyMember);
+			assert_ok!(Membership::add_applicant(Origin::signed(1), 40));
+			assert_noop!(
+				Membership::add_applicant(Origin::signed(1), 40),
+				Error::<Test, _>::AlreadyApplicant,
+			);
+			assert_ok!(Membership::add_applicant(Origin::signed(1), 50));
+			assert_ok!(Membership::add_applicant(Origin::signed(1), 60));
+			assert_noop!(
+				Membership::add_applicant(Origin::signed(1), 70),
+				Error::<Test, _>::TooManyApplicants,
+			);
+			assert_eq!(Membership::applicants(), vec![40, 50, 60]);
+		});
+	}
+
+	#[test]
+	fn terms_follow_membership_changes() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(Membership::term_end(10), Some(10));
+
+			System::set_block_number(3);
+			assert_ok!(Membership::add_member(Origin::signed(1), 15));
+			assert_eq!(Membership::term_end(15), Some(13));
+			assert_ok!(Membership::change_key(Origin::signed(15), 25));
+			assert_eq!(Membership::term_end(15), None);
+			assert_eq!(Membership::term_end(25), Some(13));
+			assert_ok!(Membership::swap_member(Origin::signed(3), 25, 35));
+			assert_eq!(Membership::term_end(25), None);
+			assert_eq!(Membership::term_end(35), Some(13));
+			assert_ok!(Membership::remove_member(Origin::signed(2), 35));
+			assert_eq!(Membership::term_end(35), None);
+
+			System::set_block_number(4);
+			assert_ok!(Membership::reset_members(Origin::signed(4), vec![20, 40]));
+			assert_eq!(Membership::term_end(10), None);
+			assert_eq!(Membership::term_end(20), Some(10));
+			assert_eq!(Membership::term_end(40), Some(14));
+		});
+	}
+
+	#[test]
+	fn migration_starts_the_missing_terms() {
+		new_test_ext().execute_with(|| {
+			<StorageVersion<DefaultInstance>>::put(Releases::V0);
+			<TermEnds<Test>>::remove(20);
+			<TermEnds<Test>>::remove(30);
+
+			System::set_block_number(3);
+			Membership::on_runtime_upgrade();
+			assert_eq!(<StorageVersion<DefaultInstance>>::get(), Releases::V1);
+			assert_eq!(Membership::term_end(10), Some(10));
+			assert_eq!(Membership::term_end(20), Some(13));
+			assert_eq!(Membership::term_end(30), Some(13));
+
+			// the migration only runs once.
+			<TermEnds<Test>>::remove(20);
+			Membership::on_runtime_upgrade();
+			assert_eq!(Membership::term_end(20), None);
+		});
+	}
+
+	#[test]
+	fn members_rotate_when_their_term_ends() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(6);
+			assert_ok!(Membership::add_member(Origin::signed(1), 15));
+			assert_ok!(Membership::set_prime(Origin::signed(5), 15));
+			assert_ok!(Membership::add_applicant(Origin::signed(1), 40));
+			assert_ok!(Membership::add_applicant(Origin::signed(1), 50));
+
+			// not a rotation block.
+			Membership::on_initialize(9);
+			assert_eq!(Membership::members(), vec![10, 15, 20, 30]);
+
+			System::set_block_number(10);
+			Membership::on_initialize(10);
+			// only two applicants for three seats, 30 stays.
+			assert_eq!(Membership::members(), vec![15, 30, 40, 50]);
+			assert_eq!(MEMBERS.with(|m| m.borrow().clone()), Membership::members());
+			assert_eq!(Membership::prime(), Some(15));
+			assert_eq!(Membership::applicants(), Vec::<u64>::new());
+			assert_eq!(Membership::term_end(10), None);
+			assert_eq!(Membership::term_end(30), Some(10));
+			assert_eq!(Membership::term_end(40), Some(20));
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::Membership(RawEvent::MembersRotated(vec![10, 20], vec![40, 50])),
+			);
+
+			// nothing changes without applicants.
+			System::set_block_number(15);
+			Membership::on_initialize(15);
+			assert_eq!(Membership::members(), vec![15, 30, 40, 50]);
+
+			// a former member may be queued again, and replaces the member whose term ended first.
+			assert_ok!(Membership::add_applicant(Origin::signed(1), 10));
+			System::set_block_number(20);
+			Membership::on_initialize(20);
+			assert_eq!(Membership::members(), vec![10, 15, 40, 50]);
+			assert_eq!(Membership::prime(), Some(15));
+			assert_eq!(
+				System::events().last().unwrap().event,
+				Event::Membership(RawEvent::MembersRotated(vec![30], vec![10])),
+			);
+		});
+	}
+
 	#[test]
 	#[should_panic(expected = "Members cannot contain duplicate accounts.")]
 	fn genesis_build_panics_with_duplicate_members() {
@@ -0,0 +1,42 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the membership pallet.
+
+use super::*;
+
+/// Migration to limited membership terms.
+pub mod v1 {
+	use super::*;
+
+	/// Start the term of every member which has none, as if it was admitted at the upgrade.
+	///
+	/// Returns the weight consumed by the migration.
+	pub fn migrate<T: Config<I>, I: Instance>() -> Weight {
+		let members = <Members<T, I>>::get();
+		let mut migrated: Weight = 0;
+		if !T::TermDuration::get().is_zero() {
+			for who in members.iter().filter(|who| !<TermEnds<T, I>>::contains_key(who)) {
+				<Module<T, I>>::start_term(who);
+				migrated = migrated.saturating_add(1);
+			}
+		}
+
+		log::info!(target: "runtime::membership", "started the term of {} members", migrated);
+		T::DbWeight::get().reads_writes((members.len() as Weight).saturating_add(2), migrated)
+	}
+}
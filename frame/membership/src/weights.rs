@@ -51,6 +51,8 @@ pub trait WeightInfo {
 	fn change_key(m: u32, ) -> Weight;
 	fn set_prime(m: u32, ) -> Weight;
 	fn clear_prime(m: u32, ) -> Weight;
+	fn add_applicant(a: u32, ) -> Weight;
+	fn rotate_members(m: u32, a: u32, ) -> Weight;
 }
 
 /// Weights for pallet_membership using the Substrate node and recommended hardware.
@@ -104,6 +106,23 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add((1_000 as Weight).saturating_mul(m as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Placeholder estimated by hand: regenerate with the `pallet_membership` benchmarks.
+	fn add_applicant(a: u32, ) -> Weight {
+		(19_514_000 as Weight)
+			.saturating_add((98_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_membership` benchmarks.
+	fn rotate_members(m: u32, a: u32, ) -> Weight {
+		(26_870_000 as Weight)
+			.saturating_add((5_963_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((2_431_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(m as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
@@ -156,4 +175,21 @@ impl WeightInfo for () {
 			.saturating_add((1_000 as Weight).saturating_mul(m as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// Placeholder estimated by hand: regenerate with the `pallet_membership` benchmarks.
+	fn add_applicant(a: u32, ) -> Weight {
+		(19_514_000 as Weight)
+			.saturating_add((98_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_membership` benchmarks.
+	fn rotate_members(m: u32, a: u32, ) -> Weight {
+		(26_870_000 as Weight)
+			.saturating_add((5_963_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((2_431_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(m as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(m as Weight)))
+	}
 }
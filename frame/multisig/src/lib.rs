@@ -39,6 +39,9 @@
 //!   number of signed origins.
 //! * `approve_as_multi` - Approve a call from a composite origin.
 //! * `cancel_as_multi` - Cancel a call from a composite origin.
+//! * `as_multi_scheduled` - Give the final approval for a call from a composite origin and
+//!   schedule it for dispatch at a later block.
+//! * `cancel_scheduled_as_multi` - Cancel a scheduled call from a composite origin.
 //!
 //! [`Call`]: ./enum.Call.html
 //! [`Config`]: ./trait.Config.html
@@ -54,12 +57,14 @@ use sp_std::prelude::*;
 use codec::{Encode, Decode};
 use sp_io::hashing::blake2_256;
 use frame_support::{ensure, RuntimeDebug};
-use frame_support::{traits::{Get, ReservableCurrency, Currency},
+use frame_support::{traits::{Get, ReservableCurrency, Currency, schedule::{
+		Named as ScheduleNamed, DispatchTime, LOWEST_PRIORITY,
+	}},
 	weights::{Weight, GetDispatchInfo},
 	dispatch::{DispatchResultWithPostInfo, DispatchResult, DispatchErrorWithPostInfo, PostDispatchInfo},
 };
 use frame_system::{self as system, RawOrigin};
-use sp_runtime::{DispatchError, traits::{Dispatchable, Zero}};
+use sp_runtime::{DispatchError, traits::{Dispatchable, Zero, One, Saturating}};
 pub use weights::WeightInfo;
 
 pub use pallet::*;
@@ -92,8 +97,21 @@ pub struct Multisig<BlockNumber, Balance, AccountId> {
 	approvals: Vec<AccountId>,
 }
 
+/// A multisig operation which was approved and handed over to the scheduler.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+pub struct ScheduledMultisig<BlockNumber, AccountId> {
+	/// The block at which the call is dispatched. If the scheduler deferred the call to a block
+	/// whose agenda is full, this is the next block with space in its agenda instead.
+	pub when: BlockNumber,
+	/// The account who opened the operation, and who may cancel it until it is dispatched.
+	pub depositor: AccountId,
+}
+
 type CallHash = [u8; 32];
 
+/// Prefix of the names under which the multisig calls are scheduled.
+const MULTISIG_ID: [u8; 8] = *b"multisig";
+
 enum CallOrHash {
 	Call(OpaqueCall, bool),
 	Hash([u8; 32]),
@@ -136,6 +154,17 @@ pub mod pallet{
 		#[pallet::constant]
 		type MaxSignatories: Get<u16>;
 
+		/// The caller origin, overarching type of all pallets origins.
+		type PalletsOrigin: From<frame_system::RawOrigin<Self::AccountId>>;
+
+		/// The scheduler used to dispatch approved calls at a later block.
+		type Scheduler: ScheduleNamed<Self::BlockNumber, <Self as Config>::Call, Self::PalletsOrigin>;
+
+		/// The maximum number of multisig calls which may be scheduled for dispatch at the same
+		/// block.
+		#[pallet::constant]
+		type MaxScheduledPerBlock: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -163,6 +192,30 @@ pub mod pallet{
 		(OpaqueCall, T::AccountId, BalanceOf<T>),
 	>;
 
+	/// The multisig operations which were approved and are waiting to be dispatched by the
+	/// scheduler.
+	#[pallet::storage]
+	pub type ScheduledMultisigs<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		CallHash,
+		ScheduledMultisig<T::BlockNumber, T::AccountId>,
+	>;
+
+	/// The scheduled multisig operations, by the block at which they are dispatched.
+	///
+	/// At most `MaxScheduledPerBlock` operations are scheduled at each block.
+	#[pallet::storage]
+	pub type ScheduledAgenda<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(T::AccountId, CallHash), T::MaxScheduledPerBlock>,
+		ValueQuery,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Threshold must be 2 or greater.
@@ -193,6 +246,14 @@ pub mod pallet{
 		MaxWeightTooLow,
 		/// The data to be stored is already stored.
 		AlreadyStored,
+		/// An execution block was given, yet this is not the final approval.
+		NotFinalApproval,
+		/// The given execution block is not in the future.
+		ExecuteAtInPast,
+		/// The call is already scheduled for this multisig.
+		AlreadyScheduled,
+		/// There are already `MaxScheduledPerBlock` multisig calls scheduled at the given block.
+		TooManyScheduled,
 	}
 
 	#[pallet::event]
@@ -211,11 +272,47 @@ pub mod pallet{
 		/// A multisig operation has been executed. \[approving, timepoint, multisig, call_hash\]
 		MultisigExecuted(T::AccountId, Timepoint<T::BlockNumber>, T::AccountId, CallHash, DispatchResult),
 		/// A multisig operation has been cancelled. \[cancelling, timepoint, multisig, call_hash\]
-		MultisigCancelled(T::AccountId, Timepoint<T::BlockNumber>, T::AccountId, CallHash)
+		MultisigCancelled(T::AccountId, Timepoint<T::BlockNumber>, T::AccountId, CallHash),
+		/// A multisig operation has been scheduled for dispatch.
+		/// \[approving, timepoint, multisig, call_hash, execute_at\]
+		MultisigScheduled(T::AccountId, Timepoint<T::BlockNumber>, T::AccountId, CallHash, T::BlockNumber),
+		/// A scheduled multisig operation has been cancelled. \[cancelling, multisig, call_hash\]
+		ScheduledMultisigCancelled(T::AccountId, T::AccountId, CallHash),
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Forget the scheduled operations which were due in the previous block.
+		///
+		/// The scheduler may defer a call to a later block, after which it can no longer be
+		/// cancelled, so the operations are forgotten whether their call was dispatched or not.
+		/// An operation whose call is still known to the scheduler stays scheduled, at the block
+		/// the scheduler reports, or at the next block with space in its agenda.
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let due = ScheduledAgenda::<T>::take(now.saturating_sub(One::one()));
+			let mut full_agendas: Weight = 0;
+			for (id, call_hash) in due.iter() {
+				match T::Scheduler::next_dispatch_time(Self::schedule_name(id, call_hash)) {
+					Ok(mut when) => {
+						while ScheduledAgenda::<T>::try_append(when, (id.clone(), *call_hash)).is_err() {
+							when = when.saturating_add(One::one());
+							full_agendas += 1;
+						}
+						ScheduledMultisigs::<T>::mutate(id, call_hash, |maybe_scheduled|
+							if let Some(scheduled) = maybe_scheduled {
+								scheduled.when = when;
+							}
+						);
+					},
+					Err(()) => ScheduledMultisigs::<T>::remove(id, call_hash),
+				}
+			}
+			// Per operation: the scheduler lookup and agenda, and the scheduled operation. Plus
+			// the length of each full agenda an operation was rolled over.
+			let due = due.len() as Weight;
+			T::DbWeight::get().reads_writes(1 + 3 * due + full_agendas, 1 + 2 * due)
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -345,7 +442,7 @@ pub mod pallet{
 			max_weight: Weight,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			Self::operate(who, threshold, other_signatories, maybe_timepoint, CallOrHash::Call(call, store_call), max_weight)
+			Self::operate(who, threshold, other_signatories, maybe_timepoint, CallOrHash::Call(call, store_call), max_weight, None)
 		}
 
 		/// Register approval for a dispatch to be made from a deterministic composite account if
@@ -401,7 +498,7 @@ pub mod pallet{
 			max_weight: Weight,
 		) -> DispatchResultWithPostInfo {
 			let who = ensure_signed(origin)?;
-			Self::operate(who, threshold, other_signatories, maybe_timepoint, CallOrHash::Hash(call_hash), max_weight)
+			Self::operate(who, threshold, other_signatories, maybe_timepoint, CallOrHash::Hash(call_hash), max_weight, None)
 		}
 
 		/// Cancel a pre-existing, on-going multisig transaction. Any deposit reserved previously
@@ -460,6 +557,126 @@ pub mod pallet{
 			Self::deposit_event(Event::MultisigCancelled(who, timepoint, id, call_hash));
 			Ok(())
 		}
+
+		/// Give the final approval for a dispatch to be made from a deterministic composite
+		/// account, and schedule it for dispatch at block `execute_at` instead of dispatching it
+		/// immediately.
+		///
+		/// The deposit of the operation is returned once the call is scheduled. Until the call is
+		/// dispatched, the account who opened the operation may cancel it with
+		/// `cancel_scheduled_as_multi`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `threshold`: The total number of approvals for this dispatch before it is executed.
+		/// - `other_signatories`: The accounts (other than the sender) who can approve this
+		/// dispatch. May not be empty.
+		/// - `timepoint`: The timepoint (block number and transaction index) of the first approval
+		/// transaction for this dispatch.
+		/// - `call`: The call to be executed.
+		/// - `execute_at`: The block at which the call is dispatched. Must be in the future, with
+		/// less than `MaxScheduledPerBlock` multisig calls already scheduled at it.
+		/// - `max_weight`: The maximum weight of the call, which its dispatch weight must not
+		/// exceed.
+		///
+		/// Fails with `NotFinalApproval` if the threshold is not reached with this approval.
+		///
+		/// The call is scheduled with the lowest priority, so the scheduler dispatches it at a
+		/// later block if it does not fit in the scheduler weight of `execute_at`.
+		///
+		/// # <weight>
+		/// - `O(S + Z)`.
+		/// - One unreserve operation.
+		/// - One call encode & hash, both of complexity `O(Z)` where `Z` is tx-len.
+		/// - One scheduler insert.
+		/// - One event.
+		/// -------------------------------
+		/// - DB Weight:
+		///     - Reads: Multisig Storage, Calls, Scheduled Multisigs, Scheduler Lookup,
+		///       Scheduler Agenda, Scheduled Agenda
+		///     - Writes: Multisig Storage, Calls, Scheduled Multisigs, Scheduler Lookup,
+		///       Scheduler Agenda, Scheduled Agenda
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::as_multi_schedule(
+			other_signatories.len() as u32,
+			call.len() as u32,
+		))]
+		pub fn as_multi_scheduled(
+			origin: OriginFor<T>,
+			threshold: u16,
+			other_signatories: Vec<T::AccountId>,
+			timepoint: Timepoint<T::BlockNumber>,
+			call: OpaqueCall,
+			execute_at: T::BlockNumber,
+			max_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::operate(
+				who,
+				threshold,
+				other_signatories,
+				Some(timepoint),
+				CallOrHash::Call(call, false),
+				max_weight,
+				Some(execute_at),
+			)
+		}
+
+		/// Cancel a multisig operation which was scheduled with `as_multi_scheduled`, before it is
+		/// dispatched.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the account who opened the
+		/// operation.
+		///
+		/// - `threshold`: The total number of approvals for this dispatch before it is executed.
+		/// - `other_signatories`: The accounts (other than the sender) who can approve this
+		/// dispatch. May not be empty.
+		/// - `call_hash`: The hash of the scheduled call.
+		///
+		/// # <weight>
+		/// - `O(S)`.
+		/// - One scheduler removal.
+		/// - One event.
+		/// ----------------------------------
+		/// - DB Weight:
+		///     - Read: Scheduled Multisigs, Scheduler Lookup, Scheduler Agenda, Scheduled Agenda
+		///     - Write: Scheduled Multisigs, Scheduler Lookup, Scheduler Agenda, Scheduled Agenda
+		/// # </weight>
+		#[pallet::weight(T::WeightInfo::cancel_scheduled_as_multi(other_signatories.len() as u32))]
+		pub fn cancel_scheduled_as_multi(
+			origin: OriginFor<T>,
+			threshold: u16,
+			other_signatories: Vec<T::AccountId>,
+			call_hash: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
+			let max_sigs = T::MaxSignatories::get() as usize;
+			ensure!(!other_signatories.is_empty(), Error::<T>::TooFewSignatories);
+			ensure!(other_signatories.len() < max_sigs, Error::<T>::TooManySignatories);
+			let signatories = Self::ensure_sorted_and_insert(other_signatories, who.clone())?;
+
+			let id = Self::multi_account_id(&signatories, threshold);
+
+			let scheduled = ScheduledMultisigs::<T>::get(&id, call_hash)
+				.ok_or(Error::<T>::NotFound)?;
+			ensure!(scheduled.depositor == who, Error::<T>::NotOwner);
+
+			T::Scheduler::cancel_named(Self::schedule_name(&id, &call_hash))
+				.map_err(|_| Error::<T>::NotFound)?;
+			ScheduledMultisigs::<T>::remove(&id, &call_hash);
+			ScheduledAgenda::<T>::mutate_exists(scheduled.when, |maybe_agenda| {
+				if let Some(agenda) = maybe_agenda {
+					agenda.retain(|(i, h)| i != &id || h != &call_hash);
+					if agenda.is_empty() {
+						*maybe_agenda = None;
+					}
+				}
+			});
+
+			Self::deposit_event(Event::ScheduledMultisigCancelled(who, id, call_hash));
+			Ok(())
+		}
 	}
 }
 
@@ -480,6 +697,7 @@ impl<T: Config> Pallet<T> {
 		maybe_timepoint: Option<Timepoint<T::BlockNumber>>,
 		call_or_hash: CallOrHash,
 		max_weight: Weight,
+		maybe_execute_at: Option<T::BlockNumber>,
 	) -> DispatchResultWithPostInfo {
 		ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
 		let max_sigs = T::MaxSignatories::get() as usize;
@@ -518,7 +736,44 @@ impl<T: Config> Pallet<T> {
 				Self::get_call(&call_hash, maybe_call.as_ref().map(|c| c.as_ref()))
 			} else { None };
 
-			if let Some((call, call_len)) = maybe_approved_call {
+			if let (Some((call, call_len)), Some(execute_at)) = (&maybe_approved_call, maybe_execute_at) {
+				ensure!(execute_at > system::Pallet::<T>::block_number(), Error::<T>::ExecuteAtInPast);
+				ensure!(call.get_dispatch_info().weight <= max_weight, Error::<T>::MaxWeightTooLow);
+				ensure!(!ScheduledMultisigs::<T>::contains_key(&id, call_hash), Error::<T>::AlreadyScheduled);
+				ensure!(
+					ScheduledAgenda::<T>::decode_len(execute_at).unwrap_or(0)
+						< T::MaxScheduledPerBlock::get() as usize,
+					Error::<T>::TooManyScheduled,
+				);
+
+				T::Scheduler::schedule_named(
+					Self::schedule_name(&id, &call_hash),
+					DispatchTime::At(execute_at),
+					None,
+					// Not `HARD_DEADLINE`: the scheduler must be free to defer the call to a later
+					// block rather than go over its weight limit.
+					LOWEST_PRIORITY,
+					RawOrigin::Signed(id.clone()).into(),
+					call.clone(),
+				).map_err(|_| Error::<T>::AlreadyScheduled)?;
+
+				<Multisigs<T>>::remove(&id, call_hash);
+				Self::clear_call(&call_hash);
+				T::Currency::unreserve(&m.depositor, m.deposit);
+
+				ScheduledMultisigs::<T>::insert(&id, call_hash, ScheduledMultisig {
+					when: execute_at,
+					depositor: m.depositor,
+				});
+				ScheduledAgenda::<T>::try_append(execute_at, (id.clone(), call_hash))
+					.map_err(|_| Error::<T>::TooManyScheduled)?;
+				Self::deposit_event(Event::MultisigScheduled(who, timepoint, id, call_hash, execute_at));
+
+				Ok(Some(T::WeightInfo::as_multi_schedule(
+					other_signatories_len as u32,
+					*call_len as u32,
+				)).into())
+			} else if let Some((call, call_len)) = maybe_approved_call {
 				// verify weight
 				ensure!(call.get_dispatch_info().weight <= max_weight, Error::<T>::MaxWeightTooLow);
 
@@ -541,6 +796,7 @@ impl<T: Config> Pallet<T> {
 			} else {
 				// We cannot dispatch the call now; either it isn't available, or it is, but we
 				// don't have threshold approvals even with our signature.
+				ensure!(maybe_execute_at.is_none(), Error::<T>::NotFinalApproval);
 
 				// Store the call if desired.
 				let stored = if let Some(data) = maybe_call.filter(|_| store) {
@@ -652,6 +908,11 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// The name under which the call with `call_hash` of the multisig `id` is scheduled.
+	fn schedule_name(id: &T::AccountId, call_hash: &CallHash) -> Vec<u8> {
+		(MULTISIG_ID, id, call_hash).encode()
+	}
+
 	/// The current `Timepoint`.
 	pub fn timepoint() -> Timepoint<T::BlockNumber> {
 		Timepoint {
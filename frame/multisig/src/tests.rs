@@ -22,7 +22,7 @@
 use super::*;
 
 use frame_support::{
-	assert_ok, assert_noop, parameter_types, traits::Filter,
+	assert_ok, assert_noop, parameter_types, traits::{Filter, OnInitialize},
 };
 use sp_core::H256;
 use sp_runtime::{traits::{BlakeTwo256, IdentityLookup}, testing::Header};
@@ -39,6 +39,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
 		Multisig: pallet_multisig::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -87,6 +88,20 @@ impl pallet_balances::Config for Test {
 	type AccountStore = System;
 	type WeightInfo = ();
 }
+parameter_types! {
+	pub const MaximumSchedulerWeight: Weight = Weight::max_value();
+	pub const MaxScheduledPerBlock: u32 = 10;
+}
+impl pallet_scheduler::Config for Test {
+	type Event = Event;
+	type Origin = Origin;
+	type PalletsOrigin = OriginCaller;
+	type Call = Call;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = frame_system::EnsureRoot<u64>;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
+	type WeightInfo = ();
+}
 parameter_types! {
 	pub const DepositBase: u64 = 1;
 	pub const DepositFactor: u64 = 1;
@@ -110,6 +125,9 @@ impl Config for Test {
 	type DepositBase = DepositBase;
 	type DepositFactor = DepositFactor;
 	type MaxSignatories = MaxSignatories;
+	type PalletsOrigin = OriginCaller;
+	type Scheduler = Scheduler;
+	type MaxScheduledPerBlock = MaxScheduledPerBlock;
 	type WeightInfo = ();
 }
 
@@ -554,3 +572,154 @@ fn multisig_handles_no_preimage_after_all_approve() {
 		assert_eq!(Balances::free_balance(6), 15);
 	});
 }
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		Scheduler::on_initialize(System::block_number());
+		Multisig::on_initialize(System::block_number());
+	}
+}
+
+#[test]
+fn multisig_scheduled_execution_works() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		assert_ok!(Balances::transfer(Origin::signed(1), multi, 5));
+		assert_ok!(Balances::transfer(Origin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer(Origin::signed(3), multi, 5));
+
+		let call = Call::Balances(BalancesCall::transfer(6, 15));
+		let call_weight = call.get_dispatch_info().weight;
+		let data = call.encode();
+		let hash = blake2_256(&data);
+		assert_ok!(Multisig::as_multi(Origin::signed(1), 2, vec![2, 3], None, data.clone(), true, 0));
+		let timepoint = now();
+
+		assert_noop!(
+			Multisig::as_multi_scheduled(Origin::signed(2), 2, vec![1, 3], timepoint, data.clone(), 1, call_weight),
+			Error::<Test>::ExecuteAtInPast,
+		);
+		assert_noop!(
+			Multisig::as_multi_scheduled(Origin::signed(2), 2, vec![1, 3], timepoint, data.clone(), 3, call_weight - 1),
+			Error::<Test>::MaxWeightTooLow,
+		);
+		assert_ok!(Multisig::as_multi_scheduled(Origin::signed(2), 2, vec![1, 3], timepoint, data, 3, call_weight));
+		// The operation is over and its deposit is returned, but the call is not dispatched yet.
+		assert!(!Multisigs::<Test>::contains_key(&multi, hash));
+		assert_eq!(Balances::free_balance(1), 5);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(6), 0);
+		assert_eq!(
+			ScheduledMultisigs::<Test>::get(&multi, hash),
+			Some(ScheduledMultisig { when: 3, depositor: 1 }),
+		);
+
+		run_to_block(2);
+		assert_eq!(Balances::free_balance(6), 0);
+		run_to_block(3);
+		assert_eq!(Balances::free_balance(6), 15);
+		// The operation is forgotten at the next block.
+		run_to_block(4);
+		assert!(!ScheduledMultisigs::<Test>::contains_key(&multi, hash));
+		assert!(!ScheduledAgenda::<Test>::contains_key(3));
+	});
+}
+
+#[test]
+fn multisig_scheduling_requires_final_approval() {
+	new_test_ext().execute_with(|| {
+		let data = Call::Balances(BalancesCall::transfer(6, 15)).encode();
+		assert_ok!(Multisig::as_multi(Origin::signed(1), 3, vec![2, 3], None, data.clone(), false, 0));
+		assert_noop!(
+			Multisig::as_multi_scheduled(Origin::signed(2), 3, vec![1, 3], now(), data, 3, Weight::max_value()),
+			Error::<Test>::NotFinalApproval,
+		);
+	});
+}
+
+#[test]
+fn cancel_scheduled_multisig_works() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		assert_ok!(Balances::transfer(Origin::signed(1), multi, 5));
+		assert_ok!(Balances::transfer(Origin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer(Origin::signed(3), multi, 5));
+
+		let data = Call::Balances(BalancesCall::transfer(6, 15)).encode();
+		let hash = blake2_256(&data);
+		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 2, vec![2, 3], None, hash, 0));
+		assert_ok!(Multisig::as_multi_scheduled(Origin::signed(2), 2, vec![1, 3], now(), data, 3, Weight::max_value()));
+
+		assert_noop!(
+			Multisig::cancel_scheduled_as_multi(Origin::signed(2), 2, vec![1, 3], hash),
+			Error::<Test>::NotOwner,
+		);
+		assert_ok!(Multisig::cancel_scheduled_as_multi(Origin::signed(1), 2, vec![2, 3], hash));
+		assert!(!ScheduledMultisigs::<Test>::contains_key(&multi, hash));
+		assert!(!ScheduledAgenda::<Test>::contains_key(3));
+		assert_noop!(
+			Multisig::cancel_scheduled_as_multi(Origin::signed(1), 2, vec![2, 3], hash),
+			Error::<Test>::NotFound,
+		);
+
+		run_to_block(3);
+		assert_eq!(Balances::free_balance(6), 0);
+	});
+}
+
+#[test]
+fn scheduled_multisigs_per_block_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let approve = |amount: u64| {
+			let data = Call::Balances(BalancesCall::transfer(6, amount)).encode();
+			assert_ok!(Multisig::as_multi(Origin::signed(1), 2, vec![2, 3], None, data.clone(), false, 0));
+			data
+		};
+		let schedule = |data| {
+			Multisig::as_multi_scheduled(Origin::signed(2), 2, vec![1, 3], now(), data, 3, Weight::max_value())
+		};
+		for amount in 1 ..= MaxScheduledPerBlock::get() as u64 {
+			assert_ok!(schedule(approve(amount)));
+		}
+		let data = approve(MaxScheduledPerBlock::get() as u64 + 1);
+		assert_noop!(schedule(data), Error::<Test>::TooManyScheduled);
+	});
+}
+
+#[test]
+fn deferred_multisig_rolls_over_full_agenda() {
+	new_test_ext().execute_with(|| {
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		assert_ok!(Balances::transfer(Origin::signed(1), multi, 5));
+		assert_ok!(Balances::transfer(Origin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer(Origin::signed(3), multi, 5));
+
+		let data = Call::Balances(BalancesCall::transfer(6, 15)).encode();
+		let hash = blake2_256(&data);
+		assert_ok!(Multisig::approve_as_multi(Origin::signed(1), 2, vec![2, 3], None, hash, 0));
+		assert_ok!(Multisig::as_multi_scheduled(Origin::signed(2), 2, vec![1, 3], now(), data, 5, Weight::max_value()));
+
+		// As if the scheduler deferred the call from block 2 to block 5, whose agenda is full.
+		let agenda = ScheduledAgenda::<Test>::take(5);
+		ScheduledAgenda::<Test>::insert(2, agenda);
+		for other in 0 .. MaxScheduledPerBlock::get() as u64 {
+			assert_ok!(ScheduledAgenda::<Test>::try_append(5, (100 + other, [0u8; 32])));
+		}
+
+		run_to_block(3);
+		assert_eq!(
+			ScheduledMultisigs::<Test>::get(&multi, hash),
+			Some(ScheduledMultisig { when: 6, depositor: 1 }),
+		);
+		assert_eq!(ScheduledAgenda::<Test>::get(5).len(), MaxScheduledPerBlock::get() as usize);
+		assert_eq!(ScheduledAgenda::<Test>::get(6), vec![(multi, hash)]);
+
+		// The call is still dispatched at the block of the scheduler, and forgotten later.
+		run_to_block(5);
+		assert_eq!(Balances::free_balance(6), 15);
+		run_to_block(7);
+		assert!(!ScheduledMultisigs::<Test>::contains_key(&multi, hash));
+		assert!(!ScheduledAgenda::<Test>::contains_key(6));
+	});
+}
@@ -54,6 +54,8 @@ pub trait WeightInfo {
 	fn approve_as_multi_approve(s: u32, ) -> Weight;
 	fn approve_as_multi_complete(s: u32, ) -> Weight;
 	fn cancel_as_multi(s: u32, ) -> Weight;
+	fn as_multi_schedule(s: u32, z: u32, ) -> Weight;
+	fn cancel_scheduled_as_multi(s: u32, ) -> Weight;
 }
 
 /// Weights for pallet_multisig using the Substrate node and recommended hardware.
@@ -135,6 +137,21 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
+	// Placeholder estimated by hand: regenerate with the `pallet_multisig` benchmarks.
+	fn as_multi_schedule(s: u32, z: u32, ) -> Weight {
+		(96_412_000 as Weight)
+			.saturating_add((249_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((4_000 as Weight).saturating_mul(z as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_multisig` benchmarks.
+	fn cancel_scheduled_as_multi(s: u32, ) -> Weight {
+		(58_206_000 as Weight)
+			.saturating_add((131_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -215,4 +232,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
+	// Placeholder estimated by hand: regenerate with the `pallet_multisig` benchmarks.
+	fn as_multi_schedule(s: u32, z: u32, ) -> Weight {
+		(96_412_000 as Weight)
+			.saturating_add((249_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add((4_000 as Weight).saturating_mul(z as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_multisig` benchmarks.
+	fn cancel_scheduled_as_multi(s: u32, ) -> Weight {
+		(58_206_000 as Weight)
+			.saturating_add((131_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
 }
@@ -41,7 +41,7 @@ frame_support::construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Proxy: proxy::{Pallet, Call, Storage, Event<T>},
-		Utility: pallet_utility::{Pallet, Call, Event},
+		Utility: pallet_utility::{Pallet, Call, Event<T>},
 	}
 );
 
@@ -92,6 +92,8 @@ impl pallet_balances::Config for Test {
 impl pallet_utility::Config for Test {
 	type Event = Event;
 	type Call = Call;
+	type OriginDowngrade = ();
+	type BatchHook = ();
 	type WeightInfo = ();
 }
 parameter_types! {
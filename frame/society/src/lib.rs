@@ -319,6 +319,9 @@ pub trait Config<I = DefaultInstance>: system::Config {
 
 	/// The maximum number of candidates that we accept per round.
 	type MaxCandidateIntake: Get<u32>;
+
+	/// The number of past rotations, and of past challenges, whose selection seeds are kept.
+	type SeedRetention: Get<u32>;
 }
 
 /// A vote by a member on a candidate application.
@@ -466,6 +469,18 @@ decl_storage! {
 
 		/// The max number of members for the society at one time.
 		MaxMembers get(fn max_members) config(): u32;
+
+		/// The random seed used to select candidates at each period rotation, together with the
+		/// block number since which it was known. Keyed by the block number of the rotation and
+		/// kept for auditing the selection of the last `SeedRetention` rotations.
+		pub RotationSeeds get(fn rotation_seed):
+			map hasher(twox_64_concat) T::BlockNumber => Option<(T::Hash, T::BlockNumber)>;
+
+		/// The random seed used to pick the defender at each challenge, together with the block
+		/// number since which it was known. Keyed by the block number of the challenge and kept
+		/// for auditing the selection of the last `SeedRetention` challenges.
+		pub ChallengeSeeds get(fn challenge_seed):
+			map hasher(twox_64_concat) T::BlockNumber => Option<(T::Hash, T::BlockNumber)>;
 	}
 	add_extra_genesis {
 		config(members): Vec<T::AccountId>;
@@ -503,6 +518,9 @@ decl_module! {
 		/// Maximum candidate intake per round.
 		const MaxCandidateIntake: u32 = T::MaxCandidateIntake::get();
 
+		/// The number of past rotations, and of past challenges, whose selection seeds are kept.
+		const SeedRetention: u32 = T::SeedRetention::get();
+
 		// Used for handling module events.
 		fn deposit_event() = default;
 
@@ -1054,6 +1072,8 @@ decl_module! {
 				Self::rotate_period(&mut members);
 
 				weight += weights.max_block / 20;
+				// Recording the seed and pruning the expired one.
+				weight = weight.saturating_add(T::DbWeight::get().writes(2));
 			}
 
 			// Run a challenge rotation
@@ -1065,6 +1085,8 @@ decl_module! {
 				Self::rotate_challenge(&mut members);
 
 				weight += weights.max_block / 20;
+				// Recording the seed and pruning the expired one.
+				weight = weight.saturating_add(T::DbWeight::get().writes(2));
 			}
 
 			weight
@@ -1311,6 +1333,12 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 		}
 	}
 
+	/// The block of the rotation or challenge, happening every `period` blocks, whose seed falls
+	/// out of the retention window at `now`.
+	fn expired_seed_block(now: T::BlockNumber, period: T::BlockNumber) -> Option<T::BlockNumber> {
+		now.checked_sub(&period.saturating_mul(T::SeedRetention::get().into()))
+	}
+
 	/// End the current period and begin a new one.
 	fn rotate_period(members: &mut Vec<T::AccountId>) {
 		let phrase = b"society_rotation";
@@ -1320,7 +1348,12 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 		// we'll need a random seed here.
 		// TODO: deal with randomness freshness
 		// https://github.com/paritytech/substrate/issues/8312
-		let (seed, _) = T::Randomness::random(phrase);
+		let (seed, known_since) = T::Randomness::random(phrase);
+		let now = <system::Pallet<T>>::block_number();
+		<RotationSeeds<T, I>>::insert(now, (seed, known_since));
+		if let Some(expired) = Self::expired_seed_block(now, T::RotationPeriod::get()) {
+			<RotationSeeds<T, I>>::remove(expired);
+		}
 		// seed needs to be guaranteed to be 32 bytes.
 		let seed = <[u8; 32]>::decode(&mut TrailingZeroInput::new(seed.as_ref()))
 			.expect("input is padded with zeroes; qed");
@@ -1581,7 +1614,12 @@ impl<T: Config<I>, I: Instance> Module<T, I> {
 				// we'll need a random seed here.
 				// TODO: deal with randomness freshness
 				// https://github.com/paritytech/substrate/issues/8312
-				let (seed, _) = T::Randomness::random(phrase);
+				let (seed, known_since) = T::Randomness::random(phrase);
+				let now = <system::Pallet<T>>::block_number();
+				<ChallengeSeeds<T, I>>::insert(now, (seed, known_since));
+				if let Some(expired) = Self::expired_seed_block(now, T::ChallengePeriod::get()) {
+					<ChallengeSeeds<T, I>>::remove(expired);
+				}
 				// seed needs to be guaranteed to be 32 bytes.
 				let seed = <[u8; 32]>::decode(&mut TrailingZeroInput::new(seed.as_ref()))
 					.expect("input is padded with zeroes; qed");
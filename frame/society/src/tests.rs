@@ -561,6 +561,35 @@ fn founder_and_head_cannot_be_removed() {
 	});
 }
 
+#[test]
+fn selection_seeds_are_recorded() {
+	EnvBuilder::new().execute(|| {
+		assert_ok!(Society::add_member(&20));
+		assert_ok!(Society::add_member(&30));
+		run_to_block(3);
+		assert_eq!(Society::rotation_seed(3), None);
+		run_to_block(8);
+		// Rotations happen every 4 blocks, challenges every 8 blocks.
+		assert!(Society::rotation_seed(4).is_some());
+		assert!(Society::rotation_seed(8).is_some());
+		assert_eq!(Society::challenge_seed(4), None);
+		assert!(Society::challenge_seed(8).is_some());
+	});
+}
+
+#[test]
+fn selection_seeds_are_pruned() {
+	EnvBuilder::new().execute(|| {
+		run_to_block(16);
+		// Only the seeds of the last two rotations are kept.
+		assert_eq!(Society::rotation_seed(4), None);
+		assert_eq!(Society::rotation_seed(8), None);
+		assert!(Society::rotation_seed(12).is_some());
+		assert!(Society::rotation_seed(16).is_some());
+		assert_eq!(RotationSeeds::<Test>::iter().count(), 2);
+	});
+}
+
 #[test]
 fn challenges_work() {
 	EnvBuilder::new().execute(|| {
@@ -315,7 +315,9 @@ use frame_system::{
 	ensure_signed, ensure_root, pallet_prelude::*,
 	offchain::SendTransactionTypes,
 };
-use frame_election_provider_support::{ElectionProvider, VoteWeight, Supports, data_provider};
+use frame_election_provider_support::{
+	ElectionProvider, VoteWeight, Supports, data_provider, SortedListProvider, VoteWeightProvider,
+};
 pub use weights::WeightInfo;
 pub use pallet::*;
 
@@ -746,17 +748,41 @@ enum Releases {
 	V5_0_0, // blockable validators.
 	V6_0_0, // removal of all storage associated with offchain phragmen.
 	V7_0_0, // keep track of number of nominators / validators in map
+	V8_0_0, // populate the sorted list of nominators
 }
 
 impl Default for Releases {
 	fn default() -> Self {
-		Releases::V7_0_0
+		Releases::V8_0_0
 	}
 }
 
 pub mod migrations {
 	use super::*;
 
+	pub mod v8 {
+		use super::*;
+
+		pub fn pre_migrate<T: Config>() -> Result<(), &'static str> {
+			assert!(StorageVersion::<T>::get() == Releases::V7_0_0);
+			Ok(())
+		}
+
+		pub fn migrate<T: Config>() -> Weight {
+			log!(info, "Migrating staking to Releases::V8_0_0");
+			let inserted = T::SortedListProvider::regenerate(
+				Nominators::<T>::iter().map(|(nominator, _)| nominator),
+				Pallet::<T>::slashable_balance_of_fn(),
+			);
+			debug_assert_eq!(T::SortedListProvider::sanity_check(), Ok(()));
+
+			StorageVersion::<T>::put(Releases::V8_0_0);
+			log!(info, "Completed staking migration to Releases::V8_0_0, {} nominators listed", inserted);
+
+			T::BlockWeights::get().max_block
+		}
+	}
+
 	pub mod v7 {
 		use super::*;
 
@@ -920,6 +946,13 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxNominatorRewardedPerValidator: Get<u32>;
 
+		/// The nominators, sorted at least roughly by decreasing vote weight.
+		///
+		/// When the election snapshot cannot hold all the voters, the nominators are taken in this
+		/// order. Staking keeps it up to date as nominators come, go and change their bond. Use
+		/// [`UseNominatorsMap`] to take the nominators in storage order instead.
+		type SortedListProvider: SortedListProvider<Self::AccountId>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -1212,7 +1245,7 @@ pub mod pallet {
 	/// True if network has been upgraded to this version.
 	/// Storage version of the pallet.
 	///
-	/// This is set to v7.0.0 for new networks.
+	/// This is set to v8.0.0 for new networks.
 	#[pallet::storage]
 	pub(crate) type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
 
@@ -1391,6 +1424,8 @@ pub mod pallet {
 		fn on_runtime_upgrade() -> Weight {
 			if StorageVersion::<T>::get() == Releases::V6_0_0 {
 				migrations::v7::migrate::<T>()
+			} else if StorageVersion::<T>::get() == Releases::V7_0_0 {
+				migrations::v8::migrate::<T>()
 			} else {
 				T::DbWeight::get().reads(1)
 			}
@@ -1400,6 +1435,8 @@ pub mod pallet {
 		fn pre_upgrade() -> Result<(), &'static str> {
 			if StorageVersion::<T>::get() == Releases::V6_0_0 {
 				migrations::v7::pre_migrate::<T>()
+			} else if StorageVersion::<T>::get() == Releases::V7_0_0 {
+				migrations::v8::pre_migrate::<T>()
 			} else {
 				Ok(())
 			}
@@ -1520,7 +1557,7 @@ pub mod pallet {
 		/// - Independent of the arguments. Insignificant complexity.
 		/// - O(1).
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::bond_extra())]
+		#[pallet::weight(T::WeightInfo::bond_extra().saturating_add(T::SortedListProvider::on_update_weight()))]
 		pub fn bond_extra(
 			origin: OriginFor<T>,
 			#[pallet::compact] max_additional: BalanceOf<T>,
@@ -1563,7 +1600,7 @@ pub mod pallet {
 		/// Emits `Unbonded`.
 		///
 		/// See also [`Call::withdraw_unbonded`].
-		#[pallet::weight(T::WeightInfo::unbond())]
+		#[pallet::weight(T::WeightInfo::unbond().saturating_add(T::SortedListProvider::on_update_weight()))]
 		pub fn unbond(origin: OriginFor<T>, #[pallet::compact] value: BalanceOf<T>) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 			let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
@@ -1619,7 +1656,8 @@ pub mod pallet {
 		/// Complexity O(S) where S is the number of slashing spans to remove
 		/// NOTE: Weight annotation is the kill scenario, we refund otherwise.
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::withdraw_unbonded_kill(*num_slashing_spans))]
+		#[pallet::weight(T::WeightInfo::withdraw_unbonded_kill(*num_slashing_spans)
+			.saturating_add(T::SortedListProvider::on_remove_weight().max(T::SortedListProvider::on_update_weight())))]
 		pub fn withdraw_unbonded(
 			origin: OriginFor<T>,
 			num_slashing_spans: u32,
@@ -1645,7 +1683,8 @@ pub mod pallet {
 				Self::update_ledger(&controller, &ledger);
 
 				// This is only an update, so we use less overall weight.
-				Some(T::WeightInfo::withdraw_unbonded_update(num_slashing_spans))
+				Some(T::WeightInfo::withdraw_unbonded_update(num_slashing_spans)
+					.saturating_add(T::SortedListProvider::on_update_weight()))
 			};
 
 			// `old_total` should never be less than the new total because
@@ -1664,7 +1703,7 @@ pub mod pallet {
 		/// Effects will be felt at the beginning of the next era.
 		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
-		#[pallet::weight(T::WeightInfo::validate())]
+		#[pallet::weight(T::WeightInfo::validate().saturating_add(T::SortedListProvider::on_remove_weight()))]
 		pub fn validate(origin: OriginFor<T>, prefs: ValidatorPrefs) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 
@@ -1697,7 +1736,8 @@ pub mod pallet {
 		/// which is capped at CompactAssignments::LIMIT (MAX_NOMINATIONS).
 		/// - Both the reads and writes follow a similar pattern.
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::nominate(targets.len() as u32))]
+		#[pallet::weight(T::WeightInfo::nominate(targets.len() as u32)
+			.saturating_add(T::SortedListProvider::on_insert_weight()))]
 		pub fn nominate(
 			origin: OriginFor<T>,
 			targets: Vec<<T::Lookup as StaticLookup>::Source>,
@@ -1754,7 +1794,7 @@ pub mod pallet {
 		/// - Contains one read.
 		/// - Writes are limited to the `origin` account key.
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::chill())]
+		#[pallet::weight(T::WeightInfo::chill().saturating_add(T::SortedListProvider::on_remove_weight()))]
 		pub fn chill(origin: OriginFor<T>) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
@@ -1948,7 +1988,8 @@ pub mod pallet {
 		/// Writes: Bonded, Slashing Spans (if S > 0), Ledger, Payee, Validators, Nominators, Account, Locks
 		/// Writes Each: SpanSlash * S
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::force_unstake(*num_slashing_spans))]
+		#[pallet::weight(T::WeightInfo::force_unstake(*num_slashing_spans)
+			.saturating_add(T::SortedListProvider::on_remove_weight()))]
 		pub fn force_unstake(
 			origin: OriginFor<T>,
 			stash: T::AccountId,
@@ -2043,7 +2084,7 @@ pub mod pallet {
 		///   NOTE: weights are assuming that payouts are made to alive stash account (Staked).
 		///   Paying even a dead controller is cheaper weight-wise. We don't do any refunds here.
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::payout_stakers_alive_staked(T::MaxNominatorRewardedPerValidator::get()))]
+		#[pallet::weight(Pallet::<T>::payout_stakers_weight(T::MaxNominatorRewardedPerValidator::get()))]
 		pub fn payout_stakers(
 			origin: OriginFor<T>,
 			validator_stash: T::AccountId,
@@ -2062,7 +2103,8 @@ pub mod pallet {
 		/// - Bounded by `MAX_UNLOCKING_CHUNKS`.
 		/// - Storage changes: Can't increase storage, only decrease it.
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::rebond(MAX_UNLOCKING_CHUNKS as u32))]
+		#[pallet::weight(T::WeightInfo::rebond(MAX_UNLOCKING_CHUNKS as u32)
+			.saturating_add(T::SortedListProvider::on_update_weight()))]
 		pub fn rebond(
 			origin: OriginFor<T>,
 			#[pallet::compact] value: BalanceOf<T>,
@@ -2139,7 +2181,8 @@ pub mod pallet {
 		/// - Writes: Bonded, Slashing Spans (if S > 0), Ledger, Payee, Validators, Nominators, Stash Account, Locks
 		/// - Writes Each: SpanSlash * S
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::reap_stash(*num_slashing_spans))]
+		#[pallet::weight(T::WeightInfo::reap_stash(*num_slashing_spans)
+			.saturating_add(T::SortedListProvider::on_remove_weight()))]
 		pub fn reap_stash(
 			_origin: OriginFor<T>,
 			stash: T::AccountId,
@@ -2240,7 +2283,7 @@ pub mod pallet {
 		///
 		// TODO: Maybe we can deprecate `chill` in the future.
 		// https://github.com/paritytech/substrate/issues/9111
-		#[pallet::weight(T::WeightInfo::chill_other())]
+		#[pallet::weight(T::WeightInfo::chill_other().saturating_add(T::SortedListProvider::on_remove_weight()))]
 		pub fn chill_other(
 			origin: OriginFor<T>,
 			controller: T::AccountId,
@@ -2300,6 +2343,11 @@ impl<T: Config> Pallet<T> {
 		T::CurrencyToVote::to_vote(Self::slashable_balance_of(stash), issuance)
 	}
 
+	/// The current vote weight of `stash`.
+	pub fn weight_of(stash: &T::AccountId) -> VoteWeight {
+		Self::slashable_balance_of_vote_weight(stash, T::Currency::total_issuance())
+	}
+
 	/// Returns a closure around `slashable_balance_of_vote_weight` that can be passed around.
 	///
 	/// This prevents call sites from repeatedly requesting `total_issuance` from backend. But it is
@@ -2313,6 +2361,16 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// The weight of `payout_stakers` paying out `nominators` nominators.
+	///
+	/// With `RewardDestination::Staked`, the validator and each nominator are also moved in the
+	/// [`Config::SortedListProvider`].
+	fn payout_stakers_weight(nominators: u32) -> Weight {
+		T::WeightInfo::payout_stakers_alive_staked(nominators).saturating_add(
+			T::SortedListProvider::on_update_weight().saturating_mul(nominators as Weight + 1)
+		)
+	}
+
 	fn do_payout_stakers(validator_stash: T::AccountId, era: EraIndex) -> DispatchResultWithPostInfo {
 		// Validate input data
 		let current_era = CurrentEra::<T>::get().ok_or(
@@ -2422,7 +2480,7 @@ impl<T: Config> Pallet<T> {
 		}
 
 		debug_assert!(nominator_payout_count <= T::MaxNominatorRewardedPerValidator::get());
-		Ok(Some(T::WeightInfo::payout_stakers_alive_staked(nominator_payout_count)).into())
+		Ok(Some(Self::payout_stakers_weight(nominator_payout_count)).into())
 	}
 
 	/// Update the ledger for a controller.
@@ -2439,6 +2497,10 @@ impl<T: Config> Pallet<T> {
 			WithdrawReasons::all(),
 		);
 		<Ledger<T>>::insert(controller, ledger);
+
+		if T::SortedListProvider::contains(&ledger.stash) {
+			T::SortedListProvider::on_update(&ledger.stash, Self::weight_of(&ledger.stash));
+		}
 	}
 
 	/// Chill a stash account.
@@ -2876,9 +2938,10 @@ impl<T: Config> Pallet<T> {
 		SlashRewardFraction::<T>::put(fraction);
 	}
 
-	/// Get all of the voters that are eligible for the npos election.
+	/// Get the voters that are eligible for the npos election, at most `maybe_max_len` of them.
 	///
-	/// This will use all on-chain nominators, and all the validators will inject a self vote.
+	/// All the validators inject a self vote, then the nominators are taken in the order of
+	/// [`Config::SortedListProvider`] until `maybe_max_len` voters are taken.
 	///
 	/// ### Slashing
 	///
@@ -2886,11 +2949,14 @@ impl<T: Config> Pallet<T> {
 	/// auto-chilled.
 	///
 	/// Note that this is VERY expensive. Use with care.
-	pub fn get_npos_voters() -> Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> {
+	pub fn get_npos_voters(
+		maybe_max_len: Option<usize>,
+	) -> Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)> {
+		let max_len = maybe_max_len.unwrap_or(usize::MAX);
 		let weight_of = Self::slashable_balance_of_fn();
 		let mut all_voters = Vec::new();
 
-		for (validator, _) in <Validators<T>>::iter() {
+		for (validator, _) in <Validators<T>>::iter().take(max_len) {
 			// Append self vote.
 			let self_vote = (validator.clone(), weight_of(&validator), vec![validator.clone()]);
 			all_voters.push(self_vote);
@@ -2899,7 +2965,14 @@ impl<T: Config> Pallet<T> {
 		// Collect all slashing spans into a BTreeMap for further queries.
 		let slashing_spans = <SlashingSpans<T>>::iter().collect::<BTreeMap<_, _>>();
 
-		for (nominator, nominations) in Nominators::<T>::iter() {
+		for nominator in T::SortedListProvider::iter() {
+			if all_voters.len() >= max_len {
+				break
+			}
+			let nominations = match Nominators::<T>::get(&nominator) {
+				Some(nominations) => nominations,
+				None => continue,
+			};
 			let Nominations { submitted_in, mut targets, suppressed: _ } = nominations;
 
 			// Filter out nomination targets which were nominated before the most recent
@@ -2930,7 +3003,9 @@ impl<T: Config> Pallet<T> {
 	/// If the nominator already exists, their nominations will be updated.
 	pub fn do_add_nominator(who: &T::AccountId, nominations: Nominations<T::AccountId>) {
 		if !Nominators::<T>::contains_key(who) {
-			CounterForNominators::<T>::mutate(|x| x.saturating_inc())
+			CounterForNominators::<T>::mutate(|x| x.saturating_inc());
+			let inserted = T::SortedListProvider::on_insert(who.clone(), Self::weight_of(who));
+			debug_assert!(inserted.is_ok(), "new nominator already in the sorted list");
 		}
 		Nominators::<T>::insert(who, nominations);
 	}
@@ -2943,6 +3018,7 @@ impl<T: Config> Pallet<T> {
 		if Nominators::<T>::contains_key(who) {
 			Nominators::<T>::remove(who);
 			CounterForNominators::<T>::mutate(|x| x.saturating_dec());
+			T::SortedListProvider::on_remove(who);
 			true
 		} else {
 			false
@@ -2975,6 +3051,89 @@ impl<T: Config> Pallet<T> {
 	}
 }
 
+impl<T: Config> VoteWeightProvider<T::AccountId> for Pallet<T> {
+	fn vote_weight(who: &T::AccountId) -> VoteWeight {
+		Self::weight_of(who)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn set_vote_weight_of(who: &T::AccountId, weight: VoteWeight) {
+		use sp_std::convert::TryFrom;
+		// This leaves the stash in an inconsistent state, which is fine for benchmarks.
+		let active = <BalanceOf<T>>::try_from(weight).unwrap_or_else(|_| {
+			panic!("cannot convert a VoteWeight into BalanceOf, benchmark needs reconfiguring.")
+		});
+		let mut ledger = Self::ledger(who).unwrap_or_else(|| StakingLedger {
+			stash: who.clone(),
+			active: Zero::zero(),
+			total: Zero::zero(),
+			unlocking: vec![],
+			claimed_rewards: vec![],
+		});
+		ledger.active = active;
+		ledger.total = active;
+		<Ledger<T>>::insert(who, ledger);
+		<Bonded<T>>::insert(who, who);
+		// Burn the whole issuance, so that an issuance based `CurrencyToVote` converts 1:1.
+		sp_std::mem::forget(T::Currency::burn(T::Currency::total_issuance()));
+	}
+}
+
+/// A [`SortedListProvider`] taking the nominators in the order of the `Nominators` map.
+///
+/// The nominators are not sorted at all, and the map is maintained by staking itself, so all the
+/// updates are ignored.
+pub struct UseNominatorsMap<T>(sp_std::marker::PhantomData<T>);
+impl<T: Config> SortedListProvider<T::AccountId> for UseNominatorsMap<T> {
+	type Error = ();
+
+	fn iter() -> Box<dyn Iterator<Item = T::AccountId>> {
+		Box::new(Nominators::<T>::iter().map(|(nominator, _)| nominator))
+	}
+
+	fn count() -> u32 {
+		CounterForNominators::<T>::get()
+	}
+
+	fn contains(id: &T::AccountId) -> bool {
+		Nominators::<T>::contains_key(id)
+	}
+
+	fn on_insert(_id: T::AccountId, _weight: VoteWeight) -> Result<(), ()> {
+		Ok(())
+	}
+
+	fn on_update(_id: &T::AccountId, _weight: VoteWeight) {}
+
+	fn on_remove(_id: &T::AccountId) {}
+
+	fn on_insert_weight() -> Weight {
+		0
+	}
+
+	fn on_update_weight() -> Weight {
+		// Only `contains` reads the map.
+		T::DbWeight::get().reads(1)
+	}
+
+	fn on_remove_weight() -> Weight {
+		0
+	}
+
+	fn clear() {}
+
+	fn regenerate(
+		_all: impl IntoIterator<Item = T::AccountId>,
+		_weight_of: Box<dyn Fn(&T::AccountId) -> VoteWeight>,
+	) -> u32 {
+		0
+	}
+
+	fn sanity_check() -> Result<(), &'static str> {
+		Ok(())
+	}
+}
+
 impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::AccountId, T::BlockNumber>
 	for Pallet<T>
 {
@@ -2988,13 +3147,14 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 	) -> data_provider::Result<(Vec<(T::AccountId, VoteWeight, Vec<T::AccountId>)>, Weight)> {
 		let nominator_count = CounterForNominators::<T>::get();
 		let validator_count = CounterForValidators::<T>::get();
-		let voter_count = nominator_count.saturating_add(validator_count) as usize;
 		debug_assert!(<Nominators<T>>::iter().count() as u32 == CounterForNominators::<T>::get());
 		debug_assert!(<Validators<T>>::iter().count() as u32 == CounterForValidators::<T>::get());
+		debug_assert_eq!(T::SortedListProvider::count(), CounterForNominators::<T>::get());
 
-		if maybe_max_len.map_or(false, |max_len| voter_count > max_len) {
-			return Err("Voter snapshot too big");
-		}
+		// Only the voters taken are read.
+		let max_len = maybe_max_len.map_or(u32::MAX, |max_len| max_len.min(u32::MAX as usize) as u32);
+		let validator_count = validator_count.min(max_len);
+		let nominator_count = nominator_count.min(max_len.saturating_sub(validator_count));
 
 		let slashing_span_count = <SlashingSpans<T>>::iter().count();
 		let weight = T::WeightInfo::get_npos_voters(
@@ -3002,7 +3162,7 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 			validator_count,
 			slashing_span_count as u32,
 		);
-		Ok((Self::get_npos_voters(), weight))
+		Ok((Self::get_npos_voters(maybe_max_len), weight))
 	}
 
 	fn targets(maybe_max_len: Option<usize>) -> data_provider::Result<(Vec<T::AccountId>, Weight)> {
@@ -3098,6 +3258,7 @@ impl<T: Config> frame_election_provider_support::ElectionDataProvider<T::Account
 		<Ledger<T>>::remove_all(None);
 		<Validators<T>>::remove_all(None);
 		<Nominators<T>>::remove_all(None);
+		T::SortedListProvider::clear();
 	}
 
 	#[cfg(any(feature = "runtime-benchmarks", test))]
@@ -3291,6 +3452,7 @@ where
 		let mut add_db_reads_writes = |reads, writes| {
 			consumed_weight += T::DbWeight::get().reads_writes(reads, writes);
 		};
+		let mut sorted_list_weight: Weight = 0;
 
 		let active_era = {
 			let active_era = Self::active_era();
@@ -3376,6 +3538,11 @@ where
 							(1 + nominators_len) * slash_cost.0 + reward_cost.0 * reporters_len,
 							(1 + nominators_len) * slash_cost.1 + reward_cost.1 * reporters_len
 						);
+						// Every slashed ledger is updated in the sorted list.
+						sorted_list_weight = sorted_list_weight.saturating_add(
+							T::SortedListProvider::on_update_weight()
+								.saturating_mul(1 + nominators_len)
+						);
 					}
 				} else {
 					// Defer to end of some `slash_defer_duration` from now.
@@ -3390,7 +3557,7 @@ where
 			}
 		}
 
-		consumed_weight
+		consumed_weight.saturating_add(sorted_list_weight)
 	}
 }
 
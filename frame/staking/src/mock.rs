@@ -100,6 +100,7 @@ frame_support::construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Staking: staking::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
+		BagsList: pallet_bags_list::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -243,6 +244,19 @@ impl onchain::Config for Test {
 	type DataProvider = Staking;
 }
 
+parameter_types! {
+	pub const BagThresholds: &'static [VoteWeight] = &THRESHOLDS;
+}
+
+const THRESHOLDS: [VoteWeight; 9] = [10, 20, 30, 40, 50, 60, 1_000, 2_000, 10_000];
+
+impl pallet_bags_list::Config for Test {
+	type Event = Event;
+	type WeightInfo = ();
+	type VoteWeightProvider = Staking;
+	type BagThresholds = BagThresholds;
+}
+
 impl Config for Test {
 	const MAX_NOMINATIONS: u32 = 16;
 	type Currency = Balances;
@@ -262,6 +276,7 @@ impl Config for Test {
 	type MaxNominatorRewardedPerValidator = MaxNominatorRewardedPerValidator;
 	type ElectionProvider = onchain::OnChainSequentialPhragmen<Self>;
 	type GenesisElectionProvider = Self::ElectionProvider;
+	type SortedListProvider = BagsList;
 	type WeightInfo = ();
 }
 
@@ -493,6 +508,7 @@ fn post_conditions() {
 	check_exposures();
 	check_ledgers();
 	check_count();
+	check_list();
 }
 
 fn check_count() {
@@ -502,6 +518,14 @@ fn check_count() {
 	assert_eq!(validator_count, CounterForValidators::<Test>::get());
 }
 
+fn check_list() {
+	assert_ok!(<Test as Config>::SortedListProvider::sanity_check());
+	assert_eq!(
+		<Test as Config>::SortedListProvider::count(),
+		CounterForNominators::<Test>::get(),
+	);
+}
+
 fn check_ledgers() {
 	// check the ledger of all stakers.
 	Bonded::<Test>::iter().for_each(|(_, ctrl)| assert_ledger_consistent(ctrl))
@@ -3383,13 +3383,14 @@ fn payout_stakers_handles_weight_refund() {
 		assert!(half_max_nom_rewarded > 0);
 		assert!(max_nom_rewarded > half_max_nom_rewarded);
 
-		let max_nom_rewarded_weight
-			= <Test as Config>::WeightInfo::payout_stakers_alive_staked(max_nom_rewarded);
-		let half_max_nom_rewarded_weight
-			= <Test as Config>::WeightInfo::payout_stakers_alive_staked(half_max_nom_rewarded);
+		// The paid out stakers are also moved in the sorted list.
+		let max_nom_rewarded_weight = Staking::payout_stakers_weight(max_nom_rewarded);
+		let half_max_nom_rewarded_weight = Staking::payout_stakers_weight(half_max_nom_rewarded);
+		let validator_payout_weight = Staking::payout_stakers_weight(0);
 		let zero_nom_payouts_weight = <Test as Config>::WeightInfo::payout_stakers_alive_staked(0);
 		assert!(zero_nom_payouts_weight > 0);
-		assert!(half_max_nom_rewarded_weight > zero_nom_payouts_weight);
+		assert!(validator_payout_weight > zero_nom_payouts_weight);
+		assert!(half_max_nom_rewarded_weight > validator_payout_weight);
 		assert!(max_nom_rewarded_weight > half_max_nom_rewarded_weight);
 
 		let balance = 1000;
@@ -3417,7 +3418,7 @@ fn payout_stakers_handles_weight_refund() {
 		assert_ok!(result);
 		assert_eq!(
 			extract_actual_weight(&result, &info),
-			zero_nom_payouts_weight
+			validator_payout_weight
 		);
 
 		// The validator is not rewarded in this era; so there will be zero payouts to claim for this era.
@@ -3957,11 +3958,37 @@ mod election_data_provider {
 	#[test]
 	fn respects_len_limits() {
 		ExtBuilder::default().build_and_execute(|| {
-			assert_eq!(Staking::voters(Some(1)).unwrap_err(), "Voter snapshot too big");
+			assert_eq!(Staking::voters(Some(1)).unwrap().0.len(), 1);
 			assert_eq!(Staking::targets(Some(1)).unwrap_err(), "Target snapshot too big");
 		});
 	}
 
+	#[test]
+	fn voters_take_heaviest_nominators_first() {
+		ExtBuilder::default().build_and_execute(|| {
+			bond_nominator(201, 200, 5_000, vec![11]);
+			bond_nominator(301, 300, 50, vec![21]);
+			assert_eq!(CounterForNominators::<Test>::get(), 3);
+
+			// the 3 validators, then the nominators from the heaviest bag to the lightest.
+			let voters = |max| Staking::voters(Some(max))
+				.unwrap()
+				.0
+				.into_iter()
+				.map(|(who, _, _)| who)
+				.skip(3)
+				.collect::<Vec<_>>();
+			assert_eq!(voters(6), vec![201, 101, 301]);
+			assert_eq!(voters(5), vec![201, 101]);
+			assert_eq!(voters(4), vec![201]);
+
+			// more stake moves 301 to a heavier bag.
+			let _ = Balances::make_free_balance_be(&301, 20_050);
+			assert_ok!(Staking::bond_extra(Origin::signed(301), 20_000));
+			assert_eq!(voters(4), vec![301]);
+		});
+	}
+
 	#[test]
 	fn estimate_next_election_works() {
 		ExtBuilder::default().session_per_era(5).period(5).build_and_execute(|| {
@@ -243,14 +243,9 @@ pub fn len(
 	key: &[u8],
 ) -> Option<u32> {
 	match child_info.child_type() {
-		ChildType::ParentKeyId => {
-			let mut buffer = [0; 0];
-			sp_io::default_child_storage::read(
-				child_info.storage_key(),
-				key,
-				&mut buffer,
-				0,
-			)
-		}
+		ChildType::ParentKeyId => sp_io::default_child_storage::size(
+			child_info.storage_key(),
+			key,
+		),
 	}
 }
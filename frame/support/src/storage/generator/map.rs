@@ -144,10 +144,14 @@ impl<
 
 	/// Enumerate all elements in the map.
 	fn iter() -> Self::Iterator {
-		let prefix = G::prefix_hash();
+		Self::iter_from(G::prefix_hash())
+	}
+
+	/// Enumerate all elements in the map after a given key.
+	fn iter_from(starting_raw_key: Vec<u8>) -> Self::Iterator {
 		PrefixIterator {
-			prefix: prefix.clone(),
-			previous_key: prefix,
+			prefix: G::prefix_hash(),
+			previous_key: starting_raw_key,
 			drain: false,
 			closure: |raw_key_without_prefix, mut raw_value| {
 				let mut key_material = G::Hasher::reverse(raw_key_without_prefix);
@@ -321,6 +321,11 @@ pub trait IterableStorageMap<K: FullEncode, V: FullCodec>: StorageMap<K, V> {
 	/// this, you'll get undefined results.
 	fn iter() -> Self::Iterator;
 
+	/// Enumerate all elements in the map after the raw storage key `starting_raw_key`, in the
+	/// same order as [`Self::iter`]. If you alter the map while doing this, you'll get undefined
+	/// results.
+	fn iter_from(starting_raw_key: Vec<u8>) -> Self::Iterator;
+
 	/// Enumerate all keys in the map in no particular order, skipping over the elements. If you
 	/// alter the map while doing this, you'll get undefined results.
 	fn iter_keys() -> Self::KeyIterator;
@@ -297,6 +297,14 @@ where
 		<Self as crate::storage::IterableStorageMap<Key, Value>>::iter()
 	}
 
+	/// Enumerate all elements in the map after the raw storage key `starting_raw_key`, in the
+	/// same order as [`Self::iter`].
+	///
+	/// If you alter the map while doing this, you'll get undefined results.
+	pub fn iter_from(starting_raw_key: Vec<u8>) -> crate::storage::PrefixIterator<(Key, Value)> {
+		<Self as crate::storage::IterableStorageMap<Key, Value>>::iter_from(starting_raw_key)
+	}
+
 	/// Enumerate all keys in the map in no particular order.
 	///
 	/// If you alter the map while doing this, you'll get undefined results.
@@ -549,6 +557,8 @@ mod test {
 			A::insert(3, 10);
 			A::insert(4, 10);
 			assert_eq!(A::iter().collect::<Vec<_>>(), vec![(4, 10), (3, 10)]);
+			assert_eq!(A::iter_from(A::hashed_key_for(4)).collect::<Vec<_>>(), vec![(3, 10)]);
+			assert_eq!(A::iter_from(A::hashed_key_for(3)).collect::<Vec<_>>(), vec![]);
 			assert_eq!(A::drain().collect::<Vec<_>>(), vec![(4, 10), (3, 10)]);
 			assert_eq!(A::iter().collect::<Vec<_>>(), vec![]);
 
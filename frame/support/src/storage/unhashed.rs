@@ -86,6 +86,12 @@ pub fn exists(key: &[u8]) -> bool {
 	sp_io::storage::exists(key)
 }
 
+/// Return the length in bytes of the value at `key` without reading it. `None` if it does not
+/// exist.
+pub fn len(key: &[u8]) -> Option<u32> {
+	sp_io::storage::size(key)
+}
+
 /// Ensure `key` has no explicit entry in storage.
 pub fn kill(key: &[u8]) {
 	sp_io::storage::clear(key);
@@ -134,7 +134,7 @@ use sp_runtime::{RuntimeDebug, traits::SignedExtension};
 use sp_runtime::generic::{CheckedExtrinsic, UncheckedExtrinsic};
 use crate::dispatch::{DispatchErrorWithPostInfo, DispatchResultWithPostInfo, DispatchError};
 use sp_runtime::traits::SaturatedConversion;
-use sp_arithmetic::{Perbill, traits::{BaseArithmetic, Saturating, Unsigned}};
+use sp_arithmetic::{Perbill, traits::{BaseArithmetic, Saturating, Unsigned, Zero}};
 use smallvec::{smallvec, SmallVec};
 
 /// Re-export priority as type
@@ -685,32 +685,53 @@ pub trait WeightToFeePolynomial {
 	/// first will most likely saturate the result to zero mid evaluation.
 	fn polynomial() -> WeightToFeeCoefficients<Self::Balance>;
 
+	/// Returns the pieces of the weight to fee conversion, as `(weight threshold, polynomial)`
+	/// pairs sorted by ascending threshold, each polynomial applying from its threshold on.
+	///
+	/// Only needs to be implemented when the conversion is not a single polynomial, by default
+	/// [`Self::polynomial`] applies to all weights.
+	fn pieces() -> sp_std::vec::Vec<(Weight, WeightToFeeCoefficients<Self::Balance>)> {
+		sp_std::vec![(0, Self::polynomial())]
+	}
+
 	/// Calculates the fee from the passed `weight` according to the `polynomial`.
 	///
 	/// This should not be overriden in most circumstances. Calculation is done in the
 	/// `Balance` type and never overflows. All evaluation is saturating.
 	fn calc(weight: &Weight) -> Self::Balance {
-		Self::polynomial().iter().fold(Self::Balance::saturated_from(0u32), |mut acc, args| {
-			let w = Self::Balance::saturated_from(*weight).saturating_pow(args.degree.into());
-
-			// The sum could get negative. Therefore we only sum with the accumulator.
-			// The Perbill Mul implementation is non overflowing.
-			let frac = args.coeff_frac * w;
-			let integer = args.coeff_integer.saturating_mul(w);
-
-			if args.negative {
-				acc = acc.saturating_sub(frac);
-				acc = acc.saturating_sub(integer);
-			} else {
-				acc = acc.saturating_add(frac);
-				acc = acc.saturating_add(integer);
-			}
-
-			acc
-		})
+		evaluate_polynomial(&Self::polynomial(), *weight)
 	}
 }
 
+/// Evaluates the polynomial described by `coefficients` at `weight`.
+///
+/// Calculation is done in the `Balance` type and never overflows. All evaluation is saturating.
+fn evaluate_polynomial<Balance>(
+	coefficients: &[WeightToFeeCoefficient<Balance>],
+	weight: Weight,
+) -> Balance where
+	Balance: BaseArithmetic + From<u32> + Copy + Unsigned,
+{
+	coefficients.iter().fold(Balance::saturated_from(0u32), |mut acc, args| {
+		let w = Balance::saturated_from(weight).saturating_pow(args.degree.into());
+
+		// The sum could get negative. Therefore we only sum with the accumulator.
+		// The Perbill Mul implementation is non overflowing.
+		let frac = args.coeff_frac * w;
+		let integer = args.coeff_integer.saturating_mul(w);
+
+		if args.negative {
+			acc = acc.saturating_sub(frac);
+			acc = acc.saturating_sub(integer);
+		} else {
+			acc = acc.saturating_add(frac);
+			acc = acc.saturating_add(integer);
+		}
+
+		acc
+	})
+}
+
 /// Implementor of `WeightToFeePolynomial` that maps one unit of weight to one unit of fee.
 pub struct IdentityFee<T>(sp_std::marker::PhantomData<T>);
 
@@ -729,6 +750,49 @@ impl<T> WeightToFeePolynomial for IdentityFee<T> where
 	}
 }
 
+/// A piece of a [`PiecewisePolynomialFee`]: a weight threshold and the polynomial applying from
+/// that threshold on.
+pub type WeightToFeePiece<Balance> = (Weight, &'static [WeightToFeeCoefficient<Balance>]);
+
+/// Implementor of `WeightToFeePolynomial` that evaluates a piecewise polynomial given by `Pieces`.
+///
+/// `Pieces` returns a list of `(threshold, polynomial)` pairs, sorted by ascending threshold. A
+/// polynomial applies to all weights from its threshold up to the next threshold. Weights below
+/// the first threshold use the first polynomial. Each polynomial is evaluated on the full weight,
+/// so it is up to the configuration to make the pieces line up at the thresholds.
+///
+/// [`WeightToFeePolynomial::polynomial`] only returns the polynomial of the first piece, all the
+/// pieces are returned by [`WeightToFeePolynomial::pieces`].
+pub struct PiecewisePolynomialFee<Pieces, T>(sp_std::marker::PhantomData<(Pieces, T)>);
+
+impl<Pieces, T> WeightToFeePolynomial for PiecewisePolynomialFee<Pieces, T> where
+	Pieces: crate::traits::Get<&'static [WeightToFeePiece<T>]>,
+	T: BaseArithmetic + From<u32> + Copy + Unsigned + 'static,
+{
+	type Balance = T;
+
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		Pieces::get().first()
+			.map(|(_, polynomial)| polynomial.iter().cloned().collect())
+			.unwrap_or_default()
+	}
+
+	fn pieces() -> sp_std::vec::Vec<(Weight, WeightToFeeCoefficients<Self::Balance>)> {
+		Pieces::get().iter()
+			.map(|(threshold, polynomial)| (*threshold, polynomial.iter().cloned().collect()))
+			.collect()
+	}
+
+	fn calc(weight: &Weight) -> Self::Balance {
+		let pieces = Pieces::get();
+		pieces.iter()
+			.rev()
+			.find(|(threshold, _)| threshold <= weight)
+			.or_else(|| pieces.first())
+			.map_or_else(Zero::zero, |(_, polynomial)| evaluate_polynomial(polynomial, *weight))
+	}
+}
+
 /// A struct holding value for each `DispatchClass`.
 #[derive(Clone, Eq, PartialEq, Default, RuntimeDebug, Encode, Decode)]
 pub struct PerDispatchClass<T> {
@@ -1012,4 +1076,53 @@ mod tests {
 		assert_eq!(IdentityFee::<Balance>::calc(&50), 50);
 		assert_eq!(IdentityFee::<Balance>::calc(&Weight::max_value()), Balance::max_value());
 	}
+
+	// x for weights below 1_000, 0.5x + 500 from there on.
+	const FEE_PIECES: &[WeightToFeePiece<Balance>] = &[
+		(0, &[WeightToFeeCoefficient {
+			coeff_integer: 1,
+			coeff_frac: Perbill::zero(),
+			negative: false,
+			degree: 1,
+		}]),
+		(1_000, &[
+			WeightToFeeCoefficient {
+				coeff_integer: 0,
+				coeff_frac: Perbill::from_percent(50),
+				negative: false,
+				degree: 1,
+			},
+			WeightToFeeCoefficient {
+				coeff_integer: 500,
+				coeff_frac: Perbill::zero(),
+				negative: false,
+				degree: 0,
+			},
+		]),
+	];
+
+	parameter_types! {
+		pub const FeePieces: &'static [WeightToFeePiece<Balance>] = FEE_PIECES;
+		pub const NoFeePieces: &'static [WeightToFeePiece<Balance>] = &[];
+	}
+
+	#[test]
+	fn piecewise_polynomial_fee_works() {
+		type Fee = PiecewisePolynomialFee<FeePieces, Balance>;
+		assert_eq!(Fee::calc(&0), 0);
+		assert_eq!(Fee::calc(&999), 999);
+		assert_eq!(Fee::calc(&1_000), 1_000);
+		assert_eq!(Fee::calc(&3_000), 2_000);
+		assert_eq!(Fee::polynomial().len(), 1);
+		assert_eq!(
+			Fee::pieces().iter().map(|(threshold, polynomial)| (*threshold, polynomial.len()))
+				.collect::<Vec<_>>(),
+			vec![(0, 1), (1_000, 2)],
+		);
+		assert_eq!(IdentityFee::<Balance>::pieces().len(), 1);
+
+		type NoFee = PiecewisePolynomialFee<NoFeePieces, Balance>;
+		assert_eq!(NoFee::calc(&1_000), 0);
+		assert!(NoFee::polynomial().is_empty());
+	}
 }
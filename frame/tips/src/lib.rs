@@ -60,6 +60,7 @@ pub mod weights;
 
 use sp_std::prelude::*;
 use frame_support::{decl_module, decl_storage, decl_event, ensure, decl_error, Parameter};
+use frame_support::storage::IterableStorageMap;
 use frame_support::traits::{
 	Currency, Get, ExistenceRequirement::{KeepAlive},
 	ReservableCurrency
@@ -447,6 +448,22 @@ impl<T: Config> Module<T> {
 		T::PalletId::get().into_account()
 	}
 
+	/// Up to `count` open tips following the tip `start`, or from the first tip if `start` is
+	/// `None`.
+	///
+	/// Tips are returned in storage order, not by hash, so the last hash of a page is the `start`
+	/// of the next one. Only meant to be used by off-chain queries.
+	pub fn tips_paged(
+		start: Option<T::Hash>,
+		count: u32,
+	) -> Vec<(T::Hash, OpenTip<T::AccountId, BalanceOf<T>, T::BlockNumber, T::Hash>)> {
+		let tips = match start {
+			Some(start) => Tips::<T>::iter_from(Tips::<T>::hashed_key_for(start)),
+			None => Tips::<T>::iter(),
+		};
+		tips.take(count as usize).collect()
+	}
+
 	/// Given a mutable reference to an `OpenTip`, insert the tip into it and check whether it
 	/// closes, if so, then deposit the relevant event and set closing accordingly.
 	///
@@ -206,6 +206,24 @@ fn tip_new_cannot_be_used_twice() {
 	});
 }
 
+#[test]
+fn tips_paged_works() {
+	new_test_ext().execute_with(|| {
+		Balances::make_free_balance_be(&Treasury::account_id(), 101);
+		assert_ok!(TipsModTestInst::tip_new(Origin::signed(10), b"awesome.dot".to_vec(), 3, 10));
+		assert_ok!(TipsModTestInst::tip_new(Origin::signed(10), b"awesome.ksm".to_vec(), 4, 10));
+		assert_ok!(TipsModTestInst::tip_new(Origin::signed(10), b"awesome.rococo".to_vec(), 5, 10));
+
+		let all = TipsModTestInst::tips_paged(None, 10);
+		assert_eq!(all.len(), 3);
+		assert!(all.iter().any(|(hash, _)| *hash == tip_hash()));
+
+		assert_eq!(TipsModTestInst::tips_paged(None, 2), all[..2].to_vec());
+		assert_eq!(TipsModTestInst::tips_paged(Some(all[0].0), 10), all[1..].to_vec());
+		assert!(TipsModTestInst::tips_paged(Some(all[2].0), 10).is_empty());
+	});
+}
+
 #[test]
 fn report_awesome_and_tip_works() {
 	new_test_ext().execute_with(|| {
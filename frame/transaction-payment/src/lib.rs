@@ -269,6 +269,17 @@ pub mod pallet {
 		fn WeightToFee() -> Vec<WeightToFeeCoefficient<BalanceOf<T>>> {
 			T::WeightToFee::polynomial().to_vec()
 		}
+
+		#[allow(non_snake_case)]
+		/// The pieces of the conversion from weight to fee, as `(weight threshold, polynomial)`
+		/// pairs, each polynomial applying from its threshold on. `WeightToFee` is the polynomial
+		/// of the first piece.
+		fn WeightToFeePieces() -> Vec<(Weight, Vec<WeightToFeeCoefficient<BalanceOf<T>>>)> {
+			T::WeightToFee::pieces()
+				.into_iter()
+				.map(|(threshold, polynomial)| (threshold, polynomial.to_vec()))
+				.collect()
+		}
 	}
 
 	#[pallet::type_value]
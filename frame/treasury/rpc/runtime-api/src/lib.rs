@@ -0,0 +1,58 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for querying open treasury items.
+//!
+//! Covers spend proposals of the treasury pallet, bounties of the bounties pallet and tips of the
+//! tips pallet. The paginated methods return the items following the `start` item in storage order,
+//! so the last item of a page is the `start` of the next one.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::Parameter;
+use sp_std::vec::Vec;
+
+pub use pallet_bounties::{Bounty, BountyIndex, BountyStatus};
+pub use pallet_tips::OpenTip;
+pub use pallet_treasury::{Proposal, ProposalIndex};
+
+sp_api::decl_runtime_apis! {
+	pub trait TreasuryApi<AccountId, Balance, BlockNumber, Hash> where
+		AccountId: Parameter,
+		Balance: Parameter,
+		BlockNumber: Parameter,
+		Hash: Parameter,
+	{
+		/// Up to `count` open spend proposals following the proposal `start`.
+		fn proposals(
+			start: Option<ProposalIndex>,
+			count: u32,
+		) -> Vec<(ProposalIndex, Proposal<AccountId, Balance>)>;
+		/// Indices of the spend proposals that are approved but not yet paid out.
+		fn approved_proposals() -> Vec<ProposalIndex>;
+		/// Up to `count` open bounties, including their status, following the bounty `start`.
+		fn bounties(
+			start: Option<BountyIndex>,
+			count: u32,
+		) -> Vec<(BountyIndex, Bounty<AccountId, Balance, BlockNumber>)>;
+		/// Up to `count` open tips following the tip `start`.
+		fn tips(
+			start: Option<Hash>,
+			count: u32,
+		) -> Vec<(Hash, OpenTip<AccountId, Balance, BlockNumber, Hash>)>;
+	}
+}
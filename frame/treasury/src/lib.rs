@@ -482,6 +482,22 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			// Must never be less than 0 but better be safe.
 			.saturating_sub(T::Currency::minimum_balance())
 	}
+
+	/// Up to `count` open spend proposals following the proposal `start`, or from the first
+	/// proposal if `start` is `None`.
+	///
+	/// Proposals are returned in storage order, not by index, so the last index of a page is the
+	/// `start` of the next one. Only meant to be used by off-chain queries.
+	pub fn proposals_paged(
+		start: Option<ProposalIndex>,
+		count: u32,
+	) -> Vec<(ProposalIndex, Proposal<T::AccountId, BalanceOf<T, I>>)> {
+		let proposals = match start {
+			Some(start) => <Proposals<T, I>>::iter_from(<Proposals<T, I>>::hashed_key_for(start)),
+			None => <Proposals<T, I>>::iter(),
+		};
+		proposals.take(count as usize).collect()
+	}
 }
 
 impl<T: Config<I>, I: 'static> OnUnbalanced<NegativeImbalanceOf<T, I>> for Pallet<T, I> {
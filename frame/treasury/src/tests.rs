@@ -221,6 +221,34 @@ fn rejected_spend_proposal_ignored_on_spend_period() {
 	});
 }
 
+#[test]
+fn proposals_paged_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 1, 3));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 2, 3));
+		assert_ok!(Treasury::propose_spend(Origin::signed(0), 3, 3));
+		assert_ok!(Treasury::reject_proposal(Origin::root(), 1));
+
+		let indices = |start, count| Treasury::proposals_paged(start, count)
+			.into_iter()
+			.map(|(index, _)| index)
+			.collect::<Vec<_>>();
+		let all = indices(None, 10);
+		let mut sorted = all.clone();
+		sorted.sort();
+		assert_eq!(sorted, vec![0, 2]);
+		assert_eq!(indices(None, 1), all[..1].to_vec());
+		assert_eq!(indices(Some(all[0]), 10), all[1..].to_vec());
+		assert_eq!(indices(Some(all[1]), 10), Vec::<ProposalIndex>::new());
+		assert!(Treasury::proposals_paged(None, 10).contains(&(2, Proposal {
+			proposer: 0,
+			value: 3,
+			beneficiary: 3,
+			bond: 1,
+		})));
+	});
+}
+
 #[test]
 fn reject_already_rejected_spend_proposal_fails() {
 	new_test_ext().execute_with(|| {
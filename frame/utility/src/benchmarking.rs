@@ -40,7 +40,7 @@ benchmarks! {
 		let caller = whitelisted_caller();
 	}: _(RawOrigin::Signed(caller), calls)
 	verify {
-		assert_last_event::<T>(Event::BatchCompleted.into())
+		assert_last_event::<T>(Event::<T>::BatchCompleted.into())
 	}
 
 	as_derivative {
@@ -61,7 +61,33 @@ benchmarks! {
 		let caller = whitelisted_caller();
 	}: _(RawOrigin::Signed(caller), calls)
 	verify {
-		assert_last_event::<T>(Event::BatchCompleted.into())
+		assert_last_event::<T>(Event::<T>::BatchCompleted.into())
+	}
+
+	force_batch {
+		let c in 0 .. 1000;
+		let mut calls: Vec<<T as Config>::Call> = Vec::new();
+		for i in 0 .. c {
+			let call = frame_system::Call::remark(vec![]).into();
+			calls.push(call);
+		}
+		let caller = whitelisted_caller();
+	}: _(RawOrigin::Signed(caller), calls)
+	verify {
+		assert_last_event::<T>(Event::<T>::BatchCompleted.into())
+	}
+
+	batch_as {
+		let c in 0 .. 1000;
+		let mut calls: Vec<<T as Config>::Call> = Vec::new();
+		for i in 0 .. c {
+			let call = frame_system::Call::remark(vec![]).into();
+			calls.push(call);
+		}
+		let kind = T::OriginDowngrade::successful_kind();
+	}: _(RawOrigin::Root, kind, calls)
+	verify {
+		assert_last_event::<T>(Event::<T>::BatchCompleted.into())
 	}
 }
 
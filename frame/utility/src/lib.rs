@@ -39,12 +39,28 @@
 //! Since proxy filters are respected in all dispatches of this pallet, it should never need to be
 //! filtered by any proxy.
 //!
+//! The calls of a batch go through [`Config::BatchHook`] before and after being dispatched, which
+//! lets the runtime forbid some calls inside batches, e.g. nested batches.
+//!
+//! The [`Event`] of this pallet is generic over the runtime, since `DerivativeDispatched` carries
+//! the derivative account. This is a breaking change for runtimes, which must now declare the
+//! pallet with `Event<T>` in `construct_runtime!`:
+//!
+//! ```ignore
+//! Utility: pallet_utility::{Pallet, Call, Event<T>},
+//! ```
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
 //!
 //! #### For batch dispatch
 //! * `batch` - Dispatch multiple calls from the sender's origin.
+//! * `batch_all` - Dispatch multiple calls from the sender's origin, reverting all of them if one
+//!   fails.
+//! * `force_batch` - Dispatch multiple calls from the sender's origin, carrying on after failures.
+//! * `batch_as` - Dispatch multiple calls, each from an origin the sender's origin is downgraded
+//!   to by [`Config::OriginDowngrade`].
 //!
 //! #### For pseudonymal dispatch
 //! * `as_derivative` - Dispatch a call from a derivative signed origin.
@@ -56,17 +72,17 @@ mod tests;
 mod benchmarking;
 pub mod weights;
 
-use sp_std::prelude::*;
+use sp_std::{prelude::*, marker::PhantomData, convert::TryFrom};
 use codec::{Encode, Decode};
 use sp_core::TypeId;
 use sp_io::hashing::blake2_256;
 use frame_support::{
-	transactional,
+	storage::with_transaction,
 	traits::{OriginTrait, UnfilteredDispatchable, IsSubType},
-	weights::{GetDispatchInfo, extract_actual_weight},
-	dispatch::PostDispatchInfo,
+	weights::{Weight, GetDispatchInfo, extract_actual_weight},
+	dispatch::{PostDispatchInfo, Parameter, DispatchResult, DispatchResultWithPostInfo},
 };
-use sp_runtime::traits::Dispatchable;
+use sp_runtime::{traits::Dispatchable, DispatchError, DispatchErrorWithPostInfo, TransactionOutcome};
 pub use weights::WeightInfo;
 
 pub use pallet::*;
@@ -86,7 +102,7 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// The overarching event type.
-		type Event: From<Event> + IsType<<Self as frame_system::Config>::Event>;
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		/// The overarching call type.
 		type Call: Parameter + Dispatchable<Origin=Self::Origin, PostInfo=PostDispatchInfo>
@@ -95,18 +111,32 @@ pub mod pallet {
 			+ IsSubType<Call<Self>>
 			+ IsType<<Self as frame_system::Config>::Call>;
 
+		/// The downgrade of the origin of the calls dispatched by `batch_as`.
+		type OriginDowngrade: OriginDowngrade<Self::Origin>;
+
+		/// Hook called around the dispatch of each call of a batch.
+		type BatchHook: BatchHook<Self::Origin, <Self as Config>::Call>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::event]
+	#[pallet::metadata(T::AccountId = "AccountId")]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event {
+	pub enum Event<T: Config> {
 		/// Batch of dispatches did not complete fully. Index of first failing dispatch given, as
 		/// well as the error. \[index, error\]
 		BatchInterrupted(u32, DispatchError),
 		/// Batch of dispatches completed fully with no error.
 		BatchCompleted,
+		/// A call of a forced batch of dispatches failed. Index of the failing dispatch given, as
+		/// well as the error. \[index, error\]
+		ItemFailed(u32, DispatchError),
+		/// Forced batch of dispatches completed, but some of the calls failed.
+		BatchCompletedWithErrors,
+		/// A call was dispatched from a derivative account. \[pseudonym, index, result\]
+		DerivativeDispatched(T::AccountId, u16, DispatchResult),
 	}
 
 	#[pallet::call]
@@ -133,6 +163,8 @@ pub mod pallet {
 			let dispatch_infos = calls.iter().map(|call| call.get_dispatch_info()).collect::<Vec<_>>();
 			let dispatch_weight = dispatch_infos.iter()
 				.map(|di| di.weight)
+				// The batch hook is called around each of the calls.
+				.chain(calls.iter().map(|call| T::BatchHook::weight(call)))
 				.fold(0, |total: Weight, weight: Weight| total.saturating_add(weight))
 				.saturating_add(T::WeightInfo::batch(calls.len() as u32));
 			let dispatch_class = {
@@ -157,12 +189,14 @@ pub mod pallet {
 			let mut weight: Weight = 0;
 			for (index, call) in calls.into_iter().enumerate() {
 				let info = call.get_dispatch_info();
+				// Add the weight of the batch hook around this call.
+				weight = weight.saturating_add(T::BatchHook::weight(&call));
 				// If origin is root, don't apply any dispatch filters; root can call anything.
-				let result = if is_root {
-					call.dispatch_bypass_filter(origin.clone())
-				} else {
-					call.dispatch(origin.clone())
-				};
+				let (result, dispatched) =
+					Self::dispatch_batched(origin.clone(), call, index, is_root);
+				if dispatched {
+					T::BatchHook::post_dispatch(&origin, index as u32, &result);
+				}
 				// Add the weight of this call.
 				weight = weight.saturating_add(extract_actual_weight(&result, &info));
 				if let Err(e) = result {
@@ -191,6 +225,10 @@ pub mod pallet {
 		/// NOTE: Prior to version *12, this was called `as_limited_sub`.
 		///
 		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// A `DerivativeDispatched` event with the pseudonym and the result of the call is
+		/// deposited whether the call succeeds or not. The pseudonym can be computed in advance
+		/// with `derivative_account_id`.
 		#[pallet::weight({
 			let dispatch_info = call.get_dispatch_info();
 			(
@@ -209,17 +247,23 @@ pub mod pallet {
 			let mut origin = origin;
 			let who = ensure_signed(origin.clone())?;
 			let pseudonym = Self::derivative_account_id(who, index);
-			origin.set_caller_from(frame_system::RawOrigin::Signed(pseudonym));
+			origin.set_caller_from(frame_system::RawOrigin::Signed(pseudonym.clone()));
 			let info = call.get_dispatch_info();
 			let result = call.dispatch(origin);
 			// Always take into account the base weight of this call.
 			let mut weight = T::WeightInfo::as_derivative().saturating_add(T::DbWeight::get().reads_writes(1, 1));
 			// Add the real weight of the dispatch.
 			weight = weight.saturating_add(extract_actual_weight(&result, &info));
+			Self::deposit_event(Event::DerivativeDispatched(
+				pseudonym,
+				index,
+				result.as_ref().map(|_| ()).map_err(|e| e.error),
+			));
 			result.map_err(|mut err| {
 				err.post_info = Some(weight).into();
 				err
-			}).map(|_| Some(weight).into())
+			})?;
+			Ok(Some(weight).into())
 		}
 
 		/// Send a batch of dispatch calls and atomically execute them.
@@ -239,6 +283,8 @@ pub mod pallet {
 			let dispatch_infos = calls.iter().map(|call| call.get_dispatch_info()).collect::<Vec<_>>();
 			let dispatch_weight = dispatch_infos.iter()
 				.map(|di| di.weight)
+				// The batch hook is called around each of the calls.
+				.chain(calls.iter().map(|call| T::BatchHook::weight(call)))
 				.fold(0, |total: Weight, weight: Weight| total.saturating_add(weight))
 				.saturating_add(T::WeightInfo::batch_all(calls.len() as u32));
 			let dispatch_class = {
@@ -253,7 +299,6 @@ pub mod pallet {
 			};
 			(dispatch_weight, dispatch_class)
 		})]
-		#[transactional]
 		pub fn batch_all(
 			origin: OriginFor<T>,
 			calls: Vec<<T as Config>::Call>,
@@ -262,32 +307,204 @@ pub mod pallet {
 			let calls_len = calls.len();
 			// Track the actual weight of each of the batch calls.
 			let mut weight: Weight = 0;
+			// The results of the dispatched calls, only passed to the batch hook once the batch is
+			// committed or reverted so that the changes of the hook are kept either way.
+			let mut dispatched_results = Vec::new();
+			let result = with_transaction(|| {
+				for (index, call) in calls.into_iter().enumerate() {
+					let info = call.get_dispatch_info();
+					// Add the weight of the batch hook around this call.
+					weight = weight.saturating_add(T::BatchHook::weight(&call));
+					// If origin is root, bypass any dispatch filter; root can call anything.
+					let (result, dispatched) = if is_root {
+						Self::dispatch_batched(origin.clone(), call, index, true)
+					} else {
+						let mut filtered_origin = origin.clone();
+						// Don't allow users to nest `batch_all` calls.
+						filtered_origin.add_filter(move |c: &<T as frame_system::Config>::Call| {
+							let c = <T as Config>::Call::from_ref(c);
+							!matches!(c.is_sub_type(), Some(Call::batch_all(_)))
+						});
+						Self::dispatch_batched(filtered_origin, call, index, false)
+					};
+					if dispatched {
+						dispatched_results.push((index, result));
+					}
+					// Add the weight of this call.
+					weight = weight.saturating_add(extract_actual_weight(&result, &info));
+					if let Err(mut err) = result {
+						// Take the weight of this function itself into account.
+						let base_weight = T::WeightInfo::batch_all(index.saturating_add(1) as u32);
+						// Return the actual used weight + base_weight of this call.
+						err.post_info = Some(base_weight + weight).into();
+						return TransactionOutcome::Rollback(Err(err))
+					}
+				}
+				TransactionOutcome::Commit(Ok(()))
+			});
+			for (index, result) in dispatched_results {
+				T::BatchHook::post_dispatch(&origin, index as u32, &result);
+			}
+			result?;
+			Self::deposit_event(Event::BatchCompleted);
+			let base_weight = T::WeightInfo::batch_all(calls_len as u32);
+			Ok(Some(base_weight + weight).into())
+		}
+
+		/// Send a batch of dispatch calls, dispatching all of them even if some fail.
+		/// The changes made by a failed call are reverted, the other calls are unaffected.
+		///
+		/// May be called from any origin.
+		///
+		/// - `calls`: The calls to be dispatched from the same origin.
+		///
+		/// If origin is root then call are dispatch without checking origin filter. (This includes
+		/// bypassing `frame_system::Config::BaseCallFilter`).
+		///
+		/// # <weight>
+		/// - Complexity: O(C) where C is the number of calls to be batched.
+		/// # </weight>
+		///
+		/// This will return `Ok` in all circumstances. An `ItemFailed` event is deposited for each
+		/// failed call, along with its index and error. Then `BatchCompletedWithErrors` is deposited
+		/// if any call failed, `BatchCompleted` otherwise.
+		#[pallet::weight({
+			let dispatch_infos = calls.iter().map(|call| call.get_dispatch_info()).collect::<Vec<_>>();
+			let dispatch_weight = dispatch_infos.iter()
+				.map(|di| di.weight)
+				// The batch hook is called around each of the calls.
+				.chain(calls.iter().map(|call| T::BatchHook::weight(call)))
+				.fold(0, |total: Weight, weight: Weight| total.saturating_add(weight))
+				.saturating_add(T::WeightInfo::force_batch(calls.len() as u32));
+			let dispatch_class = {
+				let all_operational = dispatch_infos.iter()
+					.map(|di| di.class)
+					.all(|class| class == DispatchClass::Operational);
+				if all_operational {
+					DispatchClass::Operational
+				} else {
+					DispatchClass::Normal
+				}
+			};
+			(dispatch_weight, dispatch_class)
+		})]
+		pub fn force_batch(
+			origin: OriginFor<T>,
+			calls: Vec<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let is_root = ensure_root(origin.clone()).is_ok();
+			let calls_len = calls.len();
+			// Track the actual weight of each of the batch calls.
+			let mut weight: Weight = 0;
+			let mut has_error = false;
 			for (index, call) in calls.into_iter().enumerate() {
 				let info = call.get_dispatch_info();
-				// If origin is root, bypass any dispatch filter; root can call anything.
-				let result = if is_root {
-					call.dispatch_bypass_filter(origin.clone())
-				} else {
-					let mut filtered_origin = origin.clone();
-					// Don't allow users to nest `batch_all` calls.
-					filtered_origin.add_filter(move |c: &<T as frame_system::Config>::Call| {
-						let c = <T as Config>::Call::from_ref(c);
-						!matches!(c.is_sub_type(), Some(Call::batch_all(_)))
-					});
-					call.dispatch(filtered_origin)
-				};
+				// Add the weight of the batch hook around this call.
+				weight = weight.saturating_add(T::BatchHook::weight(&call));
+				let (result, dispatched) = with_transaction(|| {
+					// If origin is root, don't apply any dispatch filters; root can call anything.
+					let (result, dispatched) =
+						Self::dispatch_batched(origin.clone(), call, index, is_root);
+					if result.is_ok() {
+						TransactionOutcome::Commit((result, dispatched))
+					} else {
+						TransactionOutcome::Rollback((result, dispatched))
+					}
+				});
+				// Outside of the transaction of the call, so the changes of the hook are kept even
+				// when the call fails.
+				if dispatched {
+					T::BatchHook::post_dispatch(&origin, index as u32, &result);
+				}
 				// Add the weight of this call.
 				weight = weight.saturating_add(extract_actual_weight(&result, &info));
-				result.map_err(|mut err| {
+				if let Err(e) = result {
+					has_error = true;
+					Self::deposit_event(Event::ItemFailed(index as u32, e.error));
+				}
+			}
+			if has_error {
+				Self::deposit_event(Event::BatchCompletedWithErrors);
+			} else {
+				Self::deposit_event(Event::BatchCompleted);
+			}
+			let base_weight = T::WeightInfo::force_batch(calls_len as u32);
+			Ok(Some(base_weight + weight).into())
+		}
+
+		/// Send a batch of dispatch calls, each from the origin `T::OriginDowngrade` downgrades
+		/// the sender's origin to, given `kind` and the index of the call in the batch.
+		///
+		/// May be called from any origin, but calls fail if it cannot be downgraded to `kind`.
+		///
+		/// - `kind`: The kind of origin to dispatch the calls from.
+		/// - `calls`: The calls to be dispatched.
+		///
+		/// The downgraded origins are always subject to the call filters, even if the sender's
+		/// origin is root.
+		///
+		/// # <weight>
+		/// - Complexity: O(C) where C is the number of calls to be batched.
+		/// # </weight>
+		///
+		/// This will return `Ok` in all circumstances. As with `batch`, the batch is interrupted at
+		/// the first failed call or origin downgrade, and either `BatchInterrupted` or
+		/// `BatchCompleted` is deposited.
+		#[pallet::weight({
+			let dispatch_infos = calls.iter().map(|call| call.get_dispatch_info()).collect::<Vec<_>>();
+			let dispatch_weight = dispatch_infos.iter()
+				.map(|di| di.weight)
+				// The batch hook is called around each of the calls.
+				.chain(calls.iter().map(|call| T::BatchHook::weight(call)))
+				.fold(0, |total: Weight, weight: Weight| total.saturating_add(weight))
+				.saturating_add(T::WeightInfo::batch_as(calls.len() as u32));
+			let dispatch_class = {
+				let all_operational = dispatch_infos.iter()
+					.map(|di| di.class)
+					.all(|class| class == DispatchClass::Operational);
+				if all_operational {
+					DispatchClass::Operational
+				} else {
+					DispatchClass::Normal
+				}
+			};
+			(dispatch_weight, dispatch_class)
+		})]
+		pub fn batch_as(
+			origin: OriginFor<T>,
+			kind: OriginKindOf<T>,
+			calls: Vec<<T as Config>::Call>,
+		) -> DispatchResultWithPostInfo {
+			let calls_len = calls.len();
+			// Track the actual weight of each of the batch calls.
+			let mut weight: Weight = 0;
+			for (index, call) in calls.into_iter().enumerate() {
+				let info = call.get_dispatch_info();
+				let result = match T::OriginDowngrade::downgrade(&origin, &kind, index as u32) {
+					Ok(downgraded) => {
+						// Add the weight of the batch hook around this call.
+						weight = weight.saturating_add(T::BatchHook::weight(&call));
+						let (result, dispatched) =
+							Self::dispatch_batched(downgraded.clone(), call, index, false);
+						if dispatched {
+							T::BatchHook::post_dispatch(&downgraded, index as u32, &result);
+						}
+						// Add the weight of this call.
+						weight = weight.saturating_add(extract_actual_weight(&result, &info));
+						result.map(|_| ()).map_err(|e| e.error)
+					},
+					Err(e) => Err(e),
+				};
+				if let Err(e) = result {
+					Self::deposit_event(Event::BatchInterrupted(index as u32, e));
 					// Take the weight of this function itself into account.
-					let base_weight = T::WeightInfo::batch_all(index.saturating_add(1) as u32);
+					let base_weight = T::WeightInfo::batch_as(index.saturating_add(1) as u32);
 					// Return the actual used weight + base_weight of this call.
-					err.post_info = Some(base_weight + weight).into();
-					err
-				})?;
+					return Ok(Some(base_weight + weight).into());
+				}
 			}
 			Self::deposit_event(Event::BatchCompleted);
-			let base_weight = T::WeightInfo::batch_all(calls_len as u32);
+			let base_weight = T::WeightInfo::batch_as(calls_len as u32);
 			Ok(Some(base_weight + weight).into())
 		}
 	}
@@ -308,4 +525,118 @@ impl<T: Config> Pallet<T> {
 		let entropy = (b"modlpy/utilisuba", who, index).using_encoded(blake2_256);
 		T::AccountId::decode(&mut &entropy[..]).unwrap_or_default()
 	}
+
+	/// Dispatch the call of index `index` of a batch from `origin`, if the batch hook allows it.
+	///
+	/// A call rejected by the hook fails without being dispatched, and without using any weight.
+	/// Returns the result along with whether the call was dispatched, in which case the caller
+	/// passes the result to [`BatchHook::post_dispatch`], outside of any storage transaction
+	/// around the call.
+	fn dispatch_batched(
+		origin: T::Origin,
+		call: <T as Config>::Call,
+		index: usize,
+		bypass_filter: bool,
+	) -> (DispatchResultWithPostInfo, bool) {
+		if let Err(error) = T::BatchHook::pre_dispatch(&origin, &call, index as u32) {
+			return (Err(DispatchErrorWithPostInfo { post_info: Some(0).into(), error }), false)
+		}
+		let result = if bypass_filter {
+			call.dispatch_bypass_filter(origin)
+		} else {
+			call.dispatch(origin)
+		};
+		(result, true)
+	}
+}
+
+/// Hook called around the dispatch of each call of a batch, e.g. to forbid some calls inside
+/// batches or to keep track of the batched calls.
+pub trait BatchHook<Origin, Call> {
+	/// The weight of calling the hook around the dispatch of `call`, added to the weight of the
+	/// batch for each of its calls.
+	fn weight(_call: &Call) -> Weight {
+		0
+	}
+
+	/// Called before dispatching `call`, the call of index `index` of a batch, from `origin`.
+	///
+	/// Returning an error prevents the dispatch, and the call fails with this error as if it had
+	/// been dispatched.
+	fn pre_dispatch(_origin: &Origin, _call: &Call, _index: u32) -> DispatchResult {
+		Ok(())
+	}
+
+	/// Called after dispatching the call of index `index` of a batch from `origin`, with the
+	/// result of the dispatch.
+	///
+	/// Called outside of the storage transaction of the call, so the changes made here are kept
+	/// even when the changes of the call are reverted. For `batch_all`, called for all the
+	/// dispatched calls once the batch is committed or reverted.
+	///
+	/// Not called for the calls rejected by [`BatchHook::pre_dispatch`].
+	fn post_dispatch(_origin: &Origin, _index: u32, _result: &DispatchResultWithPostInfo) {}
+}
+
+/// Allows every call and does nothing.
+impl<Origin, Call> BatchHook<Origin, Call> for () {}
+
+/// The kind of origin the calls of a `batch_as` batch are dispatched from.
+pub type OriginKindOf<T> = <<T as Config>::OriginDowngrade as OriginDowngrade<
+	<T as frame_system::Config>::Origin,
+>>::Kind;
+
+/// Downgrade of an origin to the origins the calls of a `batch_as` batch are dispatched from.
+pub trait OriginDowngrade<Origin> {
+	/// The kinds of origin the calls may be dispatched from.
+	type Kind: Parameter;
+
+	/// The origin to dispatch the call of index `index` from, when the batch was sent from
+	/// `origin` with the kind `kind`.
+	///
+	/// Fails if `origin` may not dispatch calls from an origin of the kind `kind`.
+	fn downgrade(origin: &Origin, kind: &Self::Kind, index: u32) -> Result<Origin, DispatchError>;
+
+	/// A kind the root origin may be downgraded to, for benchmarks.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_kind() -> Self::Kind;
+}
+
+/// Does not allow to downgrade any origin.
+impl<Origin> OriginDowngrade<Origin> for () {
+	type Kind = ();
+
+	fn downgrade(_: &Origin, _: &(), _: u32) -> Result<Origin, DispatchError> {
+		Err(DispatchError::BadOrigin)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_kind() {}
+}
+
+/// Downgrade of the root origin to the derivative accounts of an account, the call of index `i`
+/// being dispatched from the derivative of index `i`.
+///
+/// See [`Pallet::derivative_account_id`].
+pub struct RootAsDerivative<T>(PhantomData<T>);
+
+impl<T: Config> OriginDowngrade<T::Origin> for RootAsDerivative<T> {
+	type Kind = T::AccountId;
+
+	fn downgrade(
+		origin: &T::Origin,
+		who: &T::AccountId,
+		index: u32,
+	) -> Result<T::Origin, DispatchError> {
+		frame_system::ensure_root(origin.clone())?;
+		let index = u16::try_from(index)
+			.map_err(|_| DispatchError::Other("Call index out of the derivative account range"))?;
+		let pseudonym = Pallet::<T>::derivative_account_id(who.clone(), index);
+		Ok(frame_system::RawOrigin::Signed(pseudonym).into())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn successful_kind() -> T::AccountId {
+		Default::default()
+	}
 }
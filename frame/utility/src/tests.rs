@@ -78,7 +78,7 @@ frame_support::construct_runtime!(
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Utility: utility::{Pallet, Call, Event},
+		Utility: utility::{Pallet, Call, Event<T>},
 		Example: example::{Pallet, Call},
 	}
 );
@@ -150,9 +150,55 @@ impl Filter<Call> for TestBaseCallFilter {
 		}
 	}
 }
+parameter_types! {
+	pub static ForbidNestedBatches: bool = false;
+	pub static DispatchedBatchCalls: Vec<(u32, bool)> = vec![];
+	pub static BatchHookWeight: Weight = 0;
+	pub static CountFailedBatchCalls: bool = false;
+}
+
+/// Storage key under which `TestBatchHook` counts the failed calls.
+const FAILED_BATCH_CALLS: &[u8] = b"failed_batch_calls";
+
+/// Rejects nested batches if `ForbidNestedBatches` is set, records the index and success of the
+/// dispatched calls, and counts the failed ones in storage if `CountFailedBatchCalls` is set.
+/// Weighs `BatchHookWeight` per call.
+pub struct TestBatchHook;
+impl BatchHook<Origin, Call> for TestBatchHook {
+	fn weight(_call: &Call) -> Weight {
+		BatchHookWeight::get()
+	}
+
+	fn pre_dispatch(_origin: &Origin, call: &Call, _index: u32) -> DispatchResult {
+		let nested_batch = matches!(
+			call,
+			Call::Utility(UtilityCall::batch(..)) |
+			Call::Utility(UtilityCall::batch_all(..)) |
+			Call::Utility(UtilityCall::force_batch(..)) |
+			Call::Utility(UtilityCall::batch_as(..))
+		);
+		if ForbidNestedBatches::get() && nested_batch {
+			return Err(DispatchError::Other("Nested batches are forbidden"))
+		}
+		Ok(())
+	}
+
+	fn post_dispatch(_origin: &Origin, index: u32, result: &DispatchResultWithPostInfo) {
+		let mut dispatched = DispatchedBatchCalls::get();
+		dispatched.push((index, result.is_ok()));
+		DispatchedBatchCalls::set(dispatched);
+		if CountFailedBatchCalls::get() && result.is_err() {
+			let failed = storage::unhashed::get_or_default::<u32>(FAILED_BATCH_CALLS);
+			storage::unhashed::put(FAILED_BATCH_CALLS, &(failed + 1));
+		}
+	}
+}
+
 impl Config for Test {
 	type Event = Event;
 	type Call = Call;
+	type OriginDowngrade = RootAsDerivative<Test>;
+	type BatchHook = TestBatchHook;
 	type WeightInfo = ();
 }
 
@@ -164,7 +210,13 @@ use pallet_balances::Call as BalancesCall;
 use pallet_balances::Error as BalancesError;
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	// Tests may share a thread, so the hook starts from its defaults every time.
+	ForbidNestedBatches::set(false);
+	DispatchedBatchCalls::set(vec![]);
+	BatchHookWeight::set(0);
+	CountFailedBatchCalls::set(false);
+
+	let mut t =frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 	pallet_balances::GenesisConfig::<Test> {
 		balances: vec![(1, 10), (2, 10), (3, 10), (4, 10), (5, 2)],
 	}.assimilate_storage(&mut t).unwrap();
@@ -183,11 +235,18 @@ fn as_derivative_works() {
 			1,
 			Box::new(Call::Balances(BalancesCall::transfer(6, 3))),
 		), BalancesError::<Test, _>::InsufficientBalance);
+		let sub_1_1 = Utility::derivative_account_id(1, 1);
+		System::assert_last_event(utility::Event::DerivativeDispatched(
+			sub_1_1,
+			1,
+			Err(BalancesError::<Test, _>::InsufficientBalance.into()),
+		).into());
 		assert_ok!(Utility::as_derivative(
 			Origin::signed(1),
 			0,
 			Box::new(Call::Balances(BalancesCall::transfer(2, 3))),
 		));
+		System::assert_last_event(utility::Event::DerivativeDispatched(sub_1_0, 0, Ok(())).into());
 		assert_eq!(Balances::free_balance(sub_1_0), 2);
 		assert_eq!(Balances::free_balance(2), 13);
 	});
@@ -562,3 +621,189 @@ fn batch_all_does_not_nest() {
 		assert_eq!(Balances::free_balance(2), 10);
 	});
 }
+
+#[test]
+fn force_batch_works() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Balances::free_balance(1), 10);
+		assert_eq!(Balances::free_balance(2), 10);
+		assert_ok!(
+			Utility::force_batch(Origin::signed(1), vec![
+				Call::Balances(BalancesCall::transfer(2, 5)),
+				Call::Balances(BalancesCall::transfer(2, 10)),
+				Call::Balances(BalancesCall::transfer(2, 5)),
+			]),
+		);
+		System::assert_has_event(
+			utility::Event::ItemFailed(1, BalancesError::<Test, _>::InsufficientBalance.into()).into()
+		);
+		System::assert_last_event(utility::Event::BatchCompletedWithErrors.into());
+		assert_eq!(Balances::free_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 20);
+
+		assert_ok!(
+			Utility::force_batch(Origin::signed(2), vec![
+				Call::Balances(BalancesCall::transfer(1, 5)),
+				Call::Balances(BalancesCall::transfer(1, 5)),
+			]),
+		);
+		System::assert_last_event(utility::Event::BatchCompleted.into());
+		assert_eq!(Balances::free_balance(1), 10);
+	});
+}
+
+#[test]
+fn force_batch_with_root_works() {
+	new_test_ext().execute_with(|| {
+		let k = b"a".to_vec();
+		assert_ok!(
+			Utility::force_batch(Origin::root(), vec![
+				Call::Example(ExampleCall::foobar(false, 0, None)),
+				Call::System(SystemCall::set_storage(vec![(k.clone(), k.clone())])),
+			]),
+		);
+		// `foobar` requires a signed origin.
+		System::assert_has_event(utility::Event::ItemFailed(0, DispatchError::BadOrigin).into());
+		System::assert_last_event(utility::Event::BatchCompletedWithErrors.into());
+		assert_eq!(storage::unhashed::get_raw(&k), Some(k));
+	});
+}
+
+#[test]
+fn batch_as_dispatches_from_derivatives() {
+	new_test_ext().execute_with(|| {
+		let sub_1_0 = Utility::derivative_account_id(1, 0);
+		let sub_1_1 = Utility::derivative_account_id(1, 1);
+		assert_ok!(Balances::transfer(Origin::signed(1), sub_1_0, 3));
+		assert_ok!(Balances::transfer(Origin::signed(1), sub_1_1, 3));
+
+		assert_ok!(Utility::batch_as(Origin::root(), 1, vec![
+			Call::Balances(BalancesCall::transfer(2, 1)),
+			Call::Balances(BalancesCall::transfer(2, 2)),
+		]));
+		System::assert_last_event(utility::Event::BatchCompleted.into());
+		assert_eq!(Balances::free_balance(sub_1_0), 2);
+		assert_eq!(Balances::free_balance(sub_1_1), 1);
+		assert_eq!(Balances::free_balance(2), 13);
+	});
+}
+
+#[test]
+fn batch_as_checks_origin_and_filters() {
+	new_test_ext().execute_with(|| {
+		let sub_1_0 = Utility::derivative_account_id(1, 0);
+		assert_ok!(Balances::transfer(Origin::signed(1), sub_1_0, 3));
+
+		// Only root may be downgraded.
+		assert_ok!(Utility::batch_as(Origin::signed(1), 1, vec![
+			Call::Balances(BalancesCall::transfer(2, 1)),
+		]));
+		System::assert_last_event(utility::Event::BatchInterrupted(0, DispatchError::BadOrigin).into());
+
+		// The downgraded origin does not bypass the call filter.
+		assert_ok!(Utility::batch_as(Origin::root(), 1, vec![
+			Call::Balances(BalancesCall::transfer_keep_alive(2, 1)),
+		]));
+		System::assert_last_event(utility::Event::BatchInterrupted(0, DispatchError::BadOrigin).into());
+		assert_eq!(Balances::free_balance(sub_1_0), 3);
+	});
+}
+
+#[test]
+fn batch_hook_can_forbid_nested_batches() {
+	new_test_ext().execute_with(|| {
+		ForbidNestedBatches::set(true);
+		let nested = Call::Utility(UtilityCall::batch(vec![
+			Call::Balances(BalancesCall::transfer(2, 1)),
+		]));
+		let transfer = Call::Balances(BalancesCall::transfer(2, 1));
+		let transfer_weight = transfer.get_dispatch_info().weight;
+
+		// The nested batch is not dispatched and uses no weight.
+		let result = Utility::batch(Origin::signed(1), vec![transfer.clone(), nested.clone()]);
+		assert_eq!(
+			result.unwrap().actual_weight,
+			Some(<Test as Config>::WeightInfo::batch(2) + transfer_weight),
+		);
+		// The message of `DispatchError::Other` isn't kept in the stored event.
+		System::assert_last_event(utility::Event::BatchInterrupted(1, DispatchError::Other("")).into());
+		assert_eq!(Balances::free_balance(2), 11);
+		assert_eq!(DispatchedBatchCalls::get(), vec![(0, true)]);
+
+		// `batch_all` fails as a whole.
+		assert_err_ignore_postinfo!(
+			Utility::batch_all(Origin::signed(1), vec![transfer, nested.clone()]),
+			DispatchError::Other("Nested batches are forbidden"),
+		);
+		assert_eq!(Balances::free_balance(2), 11);
+
+		// Root is subject to the hook as well.
+		assert_ok!(Utility::force_batch(Origin::root(), vec![nested]));
+		System::assert_last_event(utility::Event::BatchCompletedWithErrors.into());
+		assert_eq!(Balances::free_balance(2), 11);
+	});
+}
+
+#[test]
+fn batch_hook_sees_dispatched_calls() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Utility::force_batch(Origin::signed(1), vec![
+			Call::Balances(BalancesCall::transfer(2, 5)),
+			Call::Balances(BalancesCall::transfer(2, 10)),
+			Call::Balances(BalancesCall::transfer(2, 5)),
+		]));
+		assert_eq!(DispatchedBatchCalls::get(), vec![(0, true), (1, false), (2, true)]);
+
+		// Without the hook forbidding them, nested batches go through the hook too.
+		DispatchedBatchCalls::set(vec![]);
+		assert_ok!(Utility::batch(Origin::signed(2), vec![
+			Call::Utility(UtilityCall::batch(vec![Call::Balances(BalancesCall::transfer(1, 1))])),
+		]));
+		assert_eq!(DispatchedBatchCalls::get(), vec![(0, true), (0, true)]);
+	});
+}
+
+#[test]
+fn batch_hook_changes_are_kept_when_calls_fail() {
+	new_test_ext().execute_with(|| {
+		CountFailedBatchCalls::set(true);
+		let failing = Call::Balances(BalancesCall::transfer(2, 100));
+		let failed_calls = || storage::unhashed::get::<u32>(FAILED_BATCH_CALLS);
+
+		assert_ok!(Utility::force_batch(Origin::signed(1), vec![failing.clone()]));
+		assert_eq!(failed_calls(), Some(1));
+
+		assert_err_ignore_postinfo!(
+			Utility::batch_all(Origin::signed(1), vec![
+				Call::Balances(BalancesCall::transfer(2, 1)),
+				failing,
+			]),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+		assert_eq!(failed_calls(), Some(2));
+		assert_eq!(DispatchedBatchCalls::get(), vec![(0, false), (0, true), (1, false)]);
+		assert_eq!(Balances::free_balance(2), 10);
+	});
+}
+
+#[test]
+fn batch_hook_weight_is_counted_per_call() {
+	new_test_ext().execute_with(|| {
+		BatchHookWeight::set(7);
+		let transfer = Call::Balances(BalancesCall::transfer(2, 1));
+		let transfer_weight = transfer.get_dispatch_info().weight;
+		let calls = vec![transfer.clone(), transfer];
+		let expected = <Test as Config>::WeightInfo::batch(2) + (transfer_weight + 7) * 2;
+
+		let batch_call = Call::Utility(UtilityCall::batch(calls.clone()));
+		assert_eq!(batch_call.get_dispatch_info().weight, expected);
+		let result = Utility::batch(Origin::signed(1), calls.clone());
+		assert_eq!(result.unwrap().actual_weight, Some(expected));
+
+		let force_batch_call = Call::Utility(UtilityCall::force_batch(calls));
+		assert_eq!(
+			force_batch_call.get_dispatch_info().weight,
+			<Test as Config>::WeightInfo::force_batch(2) + (transfer_weight + 7) * 2,
+		);
+	});
+}
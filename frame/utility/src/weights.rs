@@ -47,6 +47,8 @@ pub trait WeightInfo {
 	fn batch(c: u32, ) -> Weight;
 	fn as_derivative() -> Weight;
 	fn batch_all(c: u32, ) -> Weight;
+	fn force_batch(c: u32, ) -> Weight;
+	fn batch_as(c: u32, ) -> Weight;
 }
 
 /// Weights for pallet_utility using the Substrate node and recommended hardware.
@@ -65,6 +67,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			// Standard Error: 0
 			.saturating_add((1_013_000 as Weight).saturating_mul(c as Weight))
 	}
+	// Placeholder derived from `batch_all`: regenerate with the `pallet_utility` benchmarks.
+	fn force_batch(c: u32, ) -> Weight {
+		(14_742_000 as Weight)
+			.saturating_add((1_341_000 as Weight).saturating_mul(c as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_utility` benchmarks.
+	fn batch_as(c: u32, ) -> Weight {
+		(15_206_000 as Weight)
+			.saturating_add((1_127_000 as Weight).saturating_mul(c as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -82,4 +94,14 @@ impl WeightInfo for () {
 			// Standard Error: 0
 			.saturating_add((1_013_000 as Weight).saturating_mul(c as Weight))
 	}
+	// Placeholder derived from `batch_all`: regenerate with the `pallet_utility` benchmarks.
+	fn force_batch(c: u32, ) -> Weight {
+		(14_742_000 as Weight)
+			.saturating_add((1_341_000 as Weight).saturating_mul(c as Weight))
+	}
+	// Placeholder estimated by hand: regenerate with the `pallet_utility` benchmarks.
+	fn batch_as(c: u32, ) -> Weight {
+		(15_206_000 as Weight)
+			.saturating_add((1_127_000 as Weight).saturating_mul(c as Weight))
+	}
 }
@@ -32,6 +32,10 @@
 //! either `vest` (in typical case where the sender is calling on their own behalf) or `vest_other`
 //! in case the sender is calling on another account's behalf.
 //!
+//! An account can have up to `MaxVestingSchedules` schedules at once, e.g. after several vested
+//! transfers. The lock covers the sum of their unvested amounts. Two schedules can be merged into
+//! one with `merge_schedules`, to make room for another schedule.
+//!
 //! ## Interface
 //!
 //! This pallet implements the `VestingSchedule` trait.
@@ -41,10 +45,15 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `vested_transfer` - Transfer funds to an account under a new vesting schedule.
+//! - `force_vested_transfer` - Transfer funds from any account to another under a new vesting
+//!   schedule.
+//! - `merge_schedules` - Merge two vesting schedules of the sender into one.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod benchmarking;
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
@@ -56,6 +65,7 @@ use codec::{Decode, Encode};
 use frame_support::{
 	ensure,
 	pallet_prelude::*,
+	storage::bounded_vec::BoundedVec,
 	traits::{
 		Currency, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, VestingSchedule,
 		WithdrawReasons,
@@ -64,10 +74,13 @@ use frame_support::{
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
 pub use pallet::*;
 use sp_runtime::{
-	traits::{AtLeast32BitUnsigned, Convert, MaybeSerializeDeserialize, StaticLookup, Zero},
+	traits::{
+		AtLeast32BitUnsigned, Convert, MaybeSerializeDeserialize, One, Saturating, StaticLookup,
+		Zero,
+	},
 	RuntimeDebug,
 };
-use sp_std::{fmt::Debug, prelude::*};
+use sp_std::{convert::TryInto, fmt::Debug, prelude::*};
 pub use weights::WeightInfo;
 
 type BalanceOf<T> =
@@ -77,6 +90,23 @@ type MaxLocksOf<T> =
 
 const VESTING_ID: LockIdentifier = *b"vesting ";
 
+// A value placed in storage that represents the current version of the Vesting storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	/// A single schedule per account.
+	V0,
+	/// Up to `MaxVestingSchedules` schedules per account.
+	V1,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V0
+	}
+}
+
 /// Struct to encode the vesting schedule of an individual account.
 #[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct VestingInfo<Balance, BlockNumber> {
@@ -108,6 +138,24 @@ impl<
 			Zero::zero()
 		}
 	}
+
+	/// Whether the schedule locks some funds and unlocks some of them every block.
+	pub fn is_valid(&self) -> bool {
+		!self.locked.is_zero() && !self.per_block.is_zero()
+	}
+
+	/// First block at which nothing is locked any more, converted to a balance.
+	pub fn ending_block_as_balance<
+		BlockNumberToBalance: Convert<BlockNumber, Balance>
+	>(&self) -> Balance {
+		let starting_block = BlockNumberToBalance::convert(self.starting_block);
+		let per_block = self.per_block.max(One::one());
+		let mut duration = self.locked / per_block;
+		if !(self.locked % per_block).is_zero() {
+			duration = duration.saturating_add(One::one());
+		}
+		starting_block.saturating_add(duration)
+	}
 }
 
 #[frame_support::pallet]
@@ -129,20 +177,30 @@ pub mod pallet {
 		#[pallet::constant]
 		type MinVestedTransfer: Get<BalanceOf<Self>>;
 
+		/// The maximum number of vesting schedules of an account.
+		#[pallet::constant]
+		type MaxVestingSchedules: Get<u32>;
+
 		/// Weight information for extrinsics in this pallet.
 		type WeightInfo: WeightInfo;
 	}
 
-	/// Information regarding the vesting of a given account.
+	/// The vesting schedules of a given account.
 	#[pallet::storage]
 	#[pallet::getter(fn vesting)]
 	pub type Vesting<T: Config> = StorageMap<
 		_,
 		Blake2_128Concat,
 		T::AccountId,
-		VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		BoundedVec<VestingInfo<BalanceOf<T>, T::BlockNumber>, T::MaxVestingSchedules>,
 	>;
 
+	/// Storage version of the pallet.
+	///
+	/// This is set to the latest version for new networks.
+	#[pallet::storage]
+	pub(super) type StorageVersion<T> = StorageValue<_, Releases, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
 	pub struct Pallet<T>(_);
@@ -179,14 +237,36 @@ pub mod pallet {
 				let length_as_balance = T::BlockNumberToBalance::convert(length);
 				let per_block = locked / length_as_balance.max(sp_runtime::traits::One::one());
 
-				Vesting::<T>::insert(who, VestingInfo {
+				let schedule = VestingInfo {
 					locked: locked,
 					per_block: per_block,
 					starting_block: begin
-				});
+				};
+				let schedules: BoundedVec<_, T::MaxVestingSchedules> = vec![schedule].try_into()
+					.expect("`MaxVestingSchedules` is checked to be at least 1; qed");
+				Vesting::<T>::insert(who, schedules);
 				let reasons = WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE;
 				T::Currency::set_lock(VESTING_ID, who, locked, reasons);
 			}
+
+			StorageVersion::<T>::put(Releases::V1);
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if StorageVersion::<T>::get() == Releases::V0 {
+				StorageVersion::<T>::put(Releases::V1);
+				migrations::v1::migrate::<T>()
+					.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+			} else {
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		fn integrity_test() {
+			assert!(T::MaxVestingSchedules::get() > 0, "`MaxVestingSchedules` must be at least 1");
 		}
 	}
 
@@ -207,10 +287,15 @@ pub mod pallet {
 	pub enum Error<T> {
 		/// The account given is not vesting.
 		NotVesting,
-		/// An existing vesting schedule already exists for this account that cannot be clobbered.
-		ExistingVestingSchedule,
+		/// The account already has `MaxVestingSchedules` vesting schedules. Merge some of them to
+		/// add another one.
+		AtMaxVestingSchedules,
 		/// Amount being transferred is too low to create a vesting schedule.
 		AmountLow,
+		/// The vesting schedule locks nothing or never unlocks anything.
+		InvalidScheduleParams,
+		/// The account has no vesting schedule of the given index.
+		ScheduleIndexOutOfBounds,
 	}
 
 	#[pallet::call]
@@ -218,7 +303,7 @@ pub mod pallet {
 		/// Unlock any vested funds of the sender account.
 		///
 		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
-		/// locked under this pallet.
+		/// locked under this pallet. The fully vested schedules of the sender are removed.
 		///
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
@@ -228,8 +313,9 @@ pub mod pallet {
 		///     - Reads: Vesting Storage, Balances Locks, [Sender Account]
 		///     - Writes: Vesting Storage, Balances Locks, [Sender Account]
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::vest_locked(MaxLocksOf::<T>::get())
-			.max(T::WeightInfo::vest_unlocked(MaxLocksOf::<T>::get()))
+		#[pallet::weight(
+			T::WeightInfo::vest_locked(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get())
+				.max(T::WeightInfo::vest_unlocked(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get()))
 		)]
 		pub fn vest(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
@@ -251,8 +337,9 @@ pub mod pallet {
 		///     - Reads: Vesting Storage, Balances Locks, Target Account
 		///     - Writes: Vesting Storage, Balances Locks, Target Account
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::vest_other_locked(MaxLocksOf::<T>::get())
-			.max(T::WeightInfo::vest_other_unlocked(MaxLocksOf::<T>::get()))
+		#[pallet::weight(
+			T::WeightInfo::vest_other_locked(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get())
+				.max(T::WeightInfo::vest_other_unlocked(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get()))
 		)]
 		pub fn vest_other(origin: OriginFor<T>, target: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
 			ensure_signed(origin)?;
@@ -263,11 +350,12 @@ pub mod pallet {
 		///
 		/// The dispatch origin for this call must be _Signed_.
 		///
-		/// - `target`: The account that should be transferred the vested funds.
+		/// - `target`: The account that should be transferred the vested funds. Must have less
+		/// than `MaxVestingSchedules` vesting schedules.
 		/// - `amount`: The amount of funds to transfer and will be vested.
 		/// - `schedule`: The vesting schedule attached to the transfer.
 		///
-		/// Emits `VestingCreated`.
+		/// Emits `VestingUpdated`.
 		///
 		/// # <weight>
 		/// - `O(1)`.
@@ -275,24 +363,16 @@ pub mod pallet {
 		///     - Reads: Vesting Storage, Balances Locks, Target Account, [Sender Account]
 		///     - Writes: Vesting Storage, Balances Locks, Target Account, [Sender Account]
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::vested_transfer(MaxLocksOf::<T>::get()))]
+		#[pallet::weight(
+			T::WeightInfo::vested_transfer(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get())
+		)]
 		pub fn vested_transfer(
 			origin: OriginFor<T>,
 			target: <T::Lookup as StaticLookup>::Source,
 			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
 		) -> DispatchResult {
 			let transactor = ensure_signed(origin)?;
-			ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
-
-			let who = T::Lookup::lookup(target)?;
-			ensure!(!Vesting::<T>::contains_key(&who), Error::<T>::ExistingVestingSchedule);
-
-			T::Currency::transfer(&transactor, &who, schedule.locked, ExistenceRequirement::AllowDeath)?;
-
-			Self::add_vesting_schedule(&who, schedule.locked, schedule.per_block, schedule.starting_block)
-				.expect("user does not have an existing vesting schedule; q.e.d.");
-
-			Ok(())
+			Self::do_vested_transfer(transactor, target, schedule)
 		}
 
 		/// Force a vested transfer.
@@ -300,11 +380,12 @@ pub mod pallet {
 		/// The dispatch origin for this call must be _Root_.
 		///
 		/// - `source`: The account whose funds should be transferred.
-		/// - `target`: The account that should be transferred the vested funds.
+		/// - `target`: The account that should be transferred the vested funds. Must have less
+		/// than `MaxVestingSchedules` vesting schedules.
 		/// - `amount`: The amount of funds to transfer and will be vested.
 		/// - `schedule`: The vesting schedule attached to the transfer.
 		///
-		/// Emits `VestingCreated`.
+		/// Emits `VestingUpdated`.
 		///
 		/// # <weight>
 		/// - `O(1)`.
@@ -312,7 +393,9 @@ pub mod pallet {
 		///     - Reads: Vesting Storage, Balances Locks, Target Account, Source Account
 		///     - Writes: Vesting Storage, Balances Locks, Target Account, Source Account
 		/// # </weight>
-		#[pallet::weight(T::WeightInfo::force_vested_transfer(MaxLocksOf::<T>::get()))]
+		#[pallet::weight(
+			T::WeightInfo::force_vested_transfer(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get())
+		)]
 		pub fn force_vested_transfer(
 			origin: OriginFor<T>,
 			source: <T::Lookup as StaticLookup>::Source,
@@ -320,18 +403,59 @@ pub mod pallet {
 			schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
 		) -> DispatchResult {
 			ensure_root(origin)?;
-			ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
-
-			let target = T::Lookup::lookup(target)?;
 			let source = T::Lookup::lookup(source)?;
-			ensure!(!Vesting::<T>::contains_key(&target), Error::<T>::ExistingVestingSchedule);
+			Self::do_vested_transfer(source, target, schedule)
+		}
 
-			T::Currency::transfer(&source, &target, schedule.locked, ExistenceRequirement::AllowDeath)?;
+		/// Merge two vesting schedules of the sender into one.
+		///
+		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
+		/// locked under this pallet.
+		///
+		/// The merged schedule locks what both schedules still lock, from the latest of their
+		/// starting blocks and the current block, until the later of their ending blocks. A
+		/// schedule which is already fully vested is just removed. The indices of the other
+		/// schedules of the sender may change, and the merged schedule is the last one.
+		///
+		/// - `schedule1_index`: Index of the first schedule to merge.
+		/// - `schedule2_index`: Index of the second schedule to merge. Does nothing if it is the
+		/// same as `schedule1_index`.
+		///
+		/// Emits either `VestingCompleted` or `VestingUpdated`.
+		///
+		/// # <weight>
+		/// - `O(S)` where S is the number of schedules of the sender.
+		/// - DbWeight: 3 Reads, 3 Writes
+		///     - Reads: Vesting Storage, Balances Locks, [Sender Account]
+		///     - Writes: Vesting Storage, Balances Locks, [Sender Account]
+		/// # </weight>
+		#[pallet::weight(
+			T::WeightInfo::merge_schedules(MaxLocksOf::<T>::get(), T::MaxVestingSchedules::get())
+		)]
+		pub fn merge_schedules(
+			origin: OriginFor<T>,
+			schedule1_index: u32,
+			schedule2_index: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			if schedule1_index == schedule2_index {
+				return Ok(())
+			}
 
-			Self::add_vesting_schedule(&target, schedule.locked, schedule.per_block, schedule.starting_block)
-				.expect("user does not have an existing vesting schedule; q.e.d.");
+			let mut schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?.into_inner();
+			let low = schedule1_index.min(schedule2_index) as usize;
+			let high = schedule1_index.max(schedule2_index) as usize;
+			ensure!(high < schedules.len(), Error::<T>::ScheduleIndexOutOfBounds);
+
+			// Remove the higher index first, so that the lower one stays in place.
+			let schedule_high = schedules.remove(high);
+			let schedule_low = schedules.remove(low);
+			let now = <frame_system::Pallet<T>>::block_number();
+			if let Some(merged) = Self::merge_vesting_info(now, schedule_low, schedule_high) {
+				schedules.push(merged);
+			}
 
-			Ok(())
+			Self::write_vesting(&who, schedules, now)
 		}
 	}
 }
@@ -340,21 +464,99 @@ impl<T: Config> Pallet<T> {
 	/// (Re)set or remove the pallet's currency lock on `who`'s account in accordance with their
 	/// current unvested amount.
 	fn update_lock(who: T::AccountId) -> DispatchResult {
-		let vesting = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
+		let schedules = Self::vesting(&who).ok_or(Error::<T>::NotVesting)?;
 		let now = <frame_system::Pallet<T>>::block_number();
-		let locked_now = vesting.locked_at::<T::BlockNumberToBalance>(now);
+		Self::write_vesting(&who, schedules.into_inner(), now)
+	}
 
-		if locked_now.is_zero() {
-			T::Currency::remove_lock(VESTING_ID, &who);
-			Vesting::<T>::remove(&who);
-			Self::deposit_event(Event::<T>::VestingCompleted(who));
+	/// Store the schedules of `who` which still lock funds at `now`, and (re)set or remove the
+	/// lock on the account accordingly.
+	fn write_vesting(
+		who: &T::AccountId,
+		schedules: Vec<VestingInfo<BalanceOf<T>, T::BlockNumber>>,
+		now: T::BlockNumber,
+	) -> DispatchResult {
+		let mut locked_now = BalanceOf::<T>::zero();
+		let schedules = schedules.into_iter()
+			.filter(|schedule| {
+				let locked = schedule.locked_at::<T::BlockNumberToBalance>(now);
+				locked_now = locked_now.saturating_add(locked);
+				!locked.is_zero()
+			})
+			.collect::<Vec<_>>();
+
+		if schedules.is_empty() {
+			T::Currency::remove_lock(VESTING_ID, who);
+			Vesting::<T>::remove(who);
+			Self::deposit_event(Event::<T>::VestingCompleted(who.clone()));
 		} else {
+			let schedules: BoundedVec<_, T::MaxVestingSchedules> = schedules.try_into()
+				.map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+			Vesting::<T>::insert(who, schedules);
 			let reasons = WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE;
-			T::Currency::set_lock(VESTING_ID, &who, locked_now, reasons);
-			Self::deposit_event(Event::<T>::VestingUpdated(who, locked_now));
+			T::Currency::set_lock(VESTING_ID, who, locked_now, reasons);
+			Self::deposit_event(Event::<T>::VestingUpdated(who.clone(), locked_now));
 		}
 		Ok(())
 	}
+
+	/// Merge two schedules into one, as of block `now`.
+	///
+	/// Returns `None` if both schedules are fully vested, and the other schedule if one of them
+	/// is.
+	fn merge_vesting_info(
+		now: T::BlockNumber,
+		schedule1: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+		schedule2: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+	) -> Option<VestingInfo<BalanceOf<T>, T::BlockNumber>> {
+		let locked1 = schedule1.locked_at::<T::BlockNumberToBalance>(now);
+		let locked2 = schedule2.locked_at::<T::BlockNumberToBalance>(now);
+		match (locked1.is_zero(), locked2.is_zero()) {
+			(true, true) => return None,
+			(true, false) => return Some(schedule2),
+			(false, true) => return Some(schedule1),
+			(false, false) => {},
+		}
+
+		let locked = locked1.saturating_add(locked2);
+		let starting_block = now.max(schedule1.starting_block).max(schedule2.starting_block);
+		let ending_block = schedule1.ending_block_as_balance::<T::BlockNumberToBalance>()
+			.max(schedule2.ending_block_as_balance::<T::BlockNumberToBalance>());
+		let duration = ending_block
+			.saturating_sub(T::BlockNumberToBalance::convert(starting_block));
+		// Rounding down `per_block` can only make the merged schedule end a little later.
+		let per_block = if duration.is_zero() {
+			locked
+		} else {
+			(locked / duration).max(One::one())
+		};
+
+		Some(VestingInfo { locked, per_block, starting_block })
+	}
+
+	/// Transfer `schedule.locked` from `source` to `target` and vest it with `schedule`.
+	fn do_vested_transfer(
+		source: T::AccountId,
+		target: <T::Lookup as StaticLookup>::Source,
+		schedule: VestingInfo<BalanceOf<T>, T::BlockNumber>,
+	) -> DispatchResult {
+		ensure!(schedule.locked >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+		ensure!(schedule.is_valid(), Error::<T>::InvalidScheduleParams);
+
+		let target = T::Lookup::lookup(target)?;
+		let schedules = Vesting::<T>::decode_len(&target).unwrap_or_default();
+		ensure!(
+			schedules < T::MaxVestingSchedules::get() as usize,
+			Error::<T>::AtMaxVestingSchedules,
+		);
+
+		T::Currency::transfer(&source, &target, schedule.locked, ExistenceRequirement::AllowDeath)?;
+
+		Self::add_vesting_schedule(&target, schedule.locked, schedule.per_block, schedule.starting_block)
+			.expect("the target has less than `MaxVestingSchedules` schedules; qed");
+
+		Ok(())
+	}
 }
 
 impl<T: Config> VestingSchedule<T::AccountId> for Pallet<T> where
@@ -365,9 +567,11 @@ impl<T: Config> VestingSchedule<T::AccountId> for Pallet<T> where
 
 	/// Get the amount that is currently being vested and cannot be transferred out of this account.
 	fn vesting_balance(who: &T::AccountId) -> Option<BalanceOf<T>> {
-		if let Some(v) = Self::vesting(who) {
+		if let Some(schedules) = Self::vesting(who) {
 			let now = <frame_system::Pallet<T>>::block_number();
-			let locked_now = v.locked_at::<T::BlockNumberToBalance>(now);
+			let locked_now = schedules.iter().fold(Zero::zero(), |total: BalanceOf<T>, schedule| {
+				total.saturating_add(schedule.locked_at::<T::BlockNumberToBalance>(now))
+			});
 			Some(T::Currency::free_balance(who).min(locked_now))
 		} else {
 			None
@@ -376,7 +580,7 @@ impl<T: Config> VestingSchedule<T::AccountId> for Pallet<T> where
 
 	/// Adds a vesting schedule to a given account.
 	///
-	/// If there already exists a vesting schedule for the given account, an `Err` is returned
+	/// If the account already has `MaxVestingSchedules` vesting schedules, an `Err` is returned
 	/// and nothing is updated.
 	///
 	/// On success, a linearly reducing amount of funds will be locked. In order to realise any
@@ -391,26 +595,22 @@ impl<T: Config> VestingSchedule<T::AccountId> for Pallet<T> where
 		starting_block: T::BlockNumber
 	) -> DispatchResult {
 		if locked.is_zero() { return Ok(()) }
-		if Vesting::<T>::contains_key(who) {
-			Err(Error::<T>::ExistingVestingSchedule)?
-		}
 		let vesting_schedule = VestingInfo {
 			locked,
 			per_block,
 			starting_block
 		};
-		Vesting::<T>::insert(who, vesting_schedule);
+		Vesting::<T>::try_append(who, vesting_schedule)
+			.map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
 		// it can't fail, but even if somehow it did, we don't really care.
 		let res = Self::update_lock(who.clone());
 		debug_assert!(res.is_ok());
 		Ok(())
 	}
 
-	/// Remove a vesting schedule for a given account.
+	/// Remove all the vesting schedules of a given account.
 	fn remove_vesting_schedule(who: &T::AccountId) {
 		Vesting::<T>::remove(who);
-		// it can't fail, but even if somehow it did, we don't really care.
-		let res = Self::update_lock(who.clone());
-		debug_assert!(res.is_ok());
+		T::Currency::remove_lock(VESTING_ID, who);
 	}
 }
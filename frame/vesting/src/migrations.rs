@@ -0,0 +1,43 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Storage migrations for the vesting pallet.
+
+use super::*;
+
+/// Migration to multiple vesting schedules per account.
+pub mod v1 {
+	use super::*;
+
+	/// Turn the single vesting schedule of every account into a list of schedules.
+	///
+	/// Returns the weight consumed by the migration.
+	pub fn migrate<T: Config>() -> Weight {
+		let mut migrated: Weight = 0;
+		Vesting::<T>::translate::<VestingInfo<BalanceOf<T>, T::BlockNumber>, _>(
+			|_, schedule| {
+				migrated = migrated.saturating_add(1);
+				let schedules: BoundedVec<_, T::MaxVestingSchedules> = vec![schedule].try_into()
+					.expect("`MaxVestingSchedules` is checked to be at least 1; qed");
+				Some(schedules)
+			},
+		);
+
+		log::info!(target: "runtime::vesting", "migrated {} vesting schedules", migrated);
+		T::DbWeight::get().reads_writes(migrated, migrated)
+	}
+}
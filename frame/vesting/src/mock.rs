@@ -86,6 +86,7 @@ impl pallet_balances::Config for Test {
 }
 parameter_types! {
 	pub const MinVestedTransfer: u64 = 256 * 2;
+	pub const MaxVestingSchedules: u32 = 3;
 	pub static ExistentialDeposit: u64 = 0;
 }
 impl Config for Test {
@@ -93,6 +94,7 @@ impl Config for Test {
 	type Currency = Balances;
 	type Event = Event;
 	type MinVestedTransfer = MinVestedTransfer;
+	type MaxVestingSchedules = MaxVestingSchedules;
 	type WeightInfo = ();
 }
 
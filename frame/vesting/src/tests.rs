@@ -49,9 +49,9 @@ fn check_vesting_status() {
 				per_block: 64, // Vesting over 20 blocks
 				starting_block: 10,
 			};
-			assert_eq!(Vesting::vesting(&1), Some(user1_vesting_schedule)); // Account 1 has a vesting schedule
-			assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule)); // Account 2 has a vesting schedule
-			assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule)); // Account 12 has a vesting schedule
+			assert_eq!(Vesting::vesting(&1).unwrap(), vec![user1_vesting_schedule]); // Account 1 has a vesting schedule
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![user2_vesting_schedule]); // Account 2 has a vesting schedule
+			assert_eq!(Vesting::vesting(&12).unwrap(), vec![user12_vesting_schedule]); // Account 12 has a vesting schedule
 
 			// Account 1 has only 128 units vested from their illiquid 256 * 5 units at block 1
 			assert_eq!(Vesting::vesting_balance(&1), Some(128 * 9));
@@ -172,7 +172,7 @@ fn liquid_funds_should_transfer_with_delayed_vesting() {
 				per_block: 64, // Vesting over 20 blocks
 				starting_block: 10,
 			};
-			assert_eq!(Vesting::vesting(&12), Some(user12_vesting_schedule));
+			assert_eq!(Vesting::vesting(&12).unwrap(), vec![user12_vesting_schedule]);
 
 			// Account 12 can still send liquid funds
 			assert_ok!(Balances::transfer(Some(12).into(), 3, 256 * 5));
@@ -199,7 +199,7 @@ fn vested_transfer_works() {
 			};
 			assert_ok!(Vesting::vested_transfer(Some(3).into(), 4, new_vesting_schedule));
 			// Now account 4 should have vesting.
-			assert_eq!(Vesting::vesting(&4), Some(new_vesting_schedule));
+			assert_eq!(Vesting::vesting(&4).unwrap(), vec![new_vesting_schedule]);
 			// Ensure the transfer happened correctly.
 			let user3_free_balance_updated = Balances::free_balance(&3);
 			assert_eq!(user3_free_balance_updated, 256 * 25);
@@ -238,17 +238,30 @@ fn vested_transfer_correctly_fails() {
 				per_block: 256, // Vesting over 20 blocks
 				starting_block: 10,
 			};
-			assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule));
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![user2_vesting_schedule]);
 
-			// The vesting schedule we will try to create, fails due to pre-existence of schedule.
+			// Account 2 can take `MaxVestingSchedules - 1` more schedules, then no more.
 			let new_vesting_schedule = VestingInfo {
 				locked: 256 * 5,
 				per_block: 64, // Vesting over 20 blocks
 				starting_block: 10,
 			};
+			assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule));
+			assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule));
 			assert_noop!(
 				Vesting::vested_transfer(Some(4).into(), 2, new_vesting_schedule),
-				Error::<Test>::ExistingVestingSchedule,
+				Error::<Test>::AtMaxVestingSchedules,
+			);
+
+			// Fails due to a schedule that never vests.
+			let new_vesting_schedule_zero_per_block = VestingInfo {
+				locked: 256 * 5,
+				per_block: 0,
+				starting_block: 10,
+			};
+			assert_noop!(
+				Vesting::vested_transfer(Some(4).into(), 3, new_vesting_schedule_zero_per_block),
+				Error::<Test>::InvalidScheduleParams,
 			);
 
 			// Fails due to too low transfer amount.
@@ -262,9 +275,9 @@ fn vested_transfer_correctly_fails() {
 				Error::<Test>::AmountLow,
 			);
 
-			// Verify no currency transfer happened.
-			assert_eq!(user2_free_balance, 256 * 20);
-			assert_eq!(user4_free_balance, 256 * 40);
+			// Only the two successful transfers happened.
+			assert_eq!(Balances::free_balance(&2), 256 * 30);
+			assert_eq!(Balances::free_balance(&4), 256 * 30);
 		});
 }
 
@@ -289,7 +302,7 @@ fn force_vested_transfer_works() {
 			assert_noop!(Vesting::force_vested_transfer(Some(4).into(), 3, 4, new_vesting_schedule), BadOrigin);
 			assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 3, 4, new_vesting_schedule));
 			// Now account 4 should have vesting.
-			assert_eq!(Vesting::vesting(&4), Some(new_vesting_schedule));
+			assert_eq!(Vesting::vesting(&4).unwrap(), vec![new_vesting_schedule]);
 			// Ensure the transfer happened correctly.
 			let user3_free_balance_updated = Balances::free_balance(&3);
 			assert_eq!(user3_free_balance_updated, 256 * 25);
@@ -328,17 +341,30 @@ fn force_vested_transfer_correctly_fails() {
 				per_block: 256, // Vesting over 20 blocks
 				starting_block: 10,
 			};
-			assert_eq!(Vesting::vesting(&2), Some(user2_vesting_schedule));
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![user2_vesting_schedule]);
 
-			// The vesting schedule we will try to create, fails due to pre-existence of schedule.
+			// Account 2 can take `MaxVestingSchedules - 1` more schedules, then no more.
 			let new_vesting_schedule = VestingInfo {
 				locked: 256 * 5,
 				per_block: 64, // Vesting over 20 blocks
 				starting_block: 10,
 			};
+			assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 4, 2, new_vesting_schedule));
+			assert_ok!(Vesting::force_vested_transfer(RawOrigin::Root.into(), 4, 2, new_vesting_schedule));
 			assert_noop!(
 				Vesting::force_vested_transfer(RawOrigin::Root.into(), 4, 2, new_vesting_schedule),
-				Error::<Test>::ExistingVestingSchedule,
+				Error::<Test>::AtMaxVestingSchedules,
+			);
+
+			// Fails due to a schedule that never vests.
+			let new_vesting_schedule_zero_per_block = VestingInfo {
+				locked: 256 * 5,
+				per_block: 0,
+				starting_block: 10,
+			};
+			assert_noop!(
+				Vesting::force_vested_transfer(RawOrigin::Root.into(), 4, 3, new_vesting_schedule_zero_per_block),
+				Error::<Test>::InvalidScheduleParams,
 			);
 
 			// Fails due to too low transfer amount.
@@ -352,8 +378,148 @@ fn force_vested_transfer_correctly_fails() {
 				Error::<Test>::AmountLow,
 			);
 
-			// Verify no currency transfer happened.
-			assert_eq!(user2_free_balance, 256 * 20);
-			assert_eq!(user4_free_balance, 256 * 40);
+			// Only the two successful transfers happened.
+			assert_eq!(Balances::free_balance(&2), 256 * 30);
+			assert_eq!(Balances::free_balance(&4), 256 * 30);
+		});
+}
+
+#[test]
+fn multiple_schedules_are_locked_together() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let sched1 = VestingInfo {
+				locked: 256 * 5,
+				per_block: 64, // Vesting over 20 blocks
+				starting_block: 10,
+			};
+			assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, sched1));
+			// Account 2 now vests its genesis schedule and the new one.
+			assert_eq!(Vesting::vesting(&2).unwrap().len(), 2);
+			assert_eq!(Vesting::vesting_balance(&2), Some(256 * 25));
+
+			System::set_block_number(20);
+			assert_eq!(Vesting::vesting_balance(&2), Some(256 * 10 + 64 * 10));
+
+			System::set_block_number(30);
+			assert_eq!(Vesting::vesting_balance(&2), Some(0));
+			assert_ok!(Vesting::vest(Some(2).into()));
+			assert_eq!(Vesting::vesting(&2), None);
+		});
+}
+
+#[test]
+fn vest_removes_finished_schedules() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let sched1 = VestingInfo {
+				locked: 256 * 5,
+				per_block: 64, // Vesting over 20 blocks
+				starting_block: 20,
+			};
+			assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, sched1));
+
+			// The genesis schedule of account 2 ends at block 30.
+			System::set_block_number(30);
+			assert_ok!(Vesting::vest(Some(2).into()));
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched1]);
+			assert_eq!(Vesting::vesting_balance(&2), Some(64 * 10));
+		});
+}
+
+#[test]
+fn merge_ongoing_schedules() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let sched1 = VestingInfo {
+				locked: 256 * 5,
+				per_block: 64, // Vesting over 20 blocks
+				starting_block: 10,
+			};
+			assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, sched1));
+
+			System::set_block_number(15);
+			let locked = Vesting::vesting_balance(&2).unwrap();
+			assert_eq!(locked, 256 * 15 + 64 * 15);
+
+			assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 1));
+			// What is left of both schedules vests from now until block 30.
+			let merged = VestingInfo { locked, per_block: locked / 15, starting_block: 15 };
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![merged]);
+			assert_eq!(Vesting::vesting_balance(&2), Some(locked));
+		});
+}
+
+#[test]
+fn merge_with_finished_schedule_keeps_the_other() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let sched1 = VestingInfo {
+				locked: 256 * 5,
+				per_block: 64, // Vesting over 20 blocks
+				starting_block: 40,
+			};
+			assert_ok!(Vesting::vested_transfer(Some(4).into(), 2, sched1));
+
+			// The genesis schedule of account 2 ends at block 30.
+			System::set_block_number(35);
+			assert_ok!(Vesting::merge_schedules(Some(2).into(), 1, 0));
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![sched1]);
+			assert_eq!(Vesting::vesting_balance(&2), Some(256 * 5));
+		});
+}
+
+#[test]
+fn merge_schedules_correctly_fails() {
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let user2_vesting_schedule = VestingInfo {
+				locked: 256 * 20,
+				per_block: 256, // Vesting over 20 blocks
+				starting_block: 10,
+			};
+			assert_noop!(
+				Vesting::merge_schedules(Some(2).into(), 0, 1),
+				Error::<Test>::ScheduleIndexOutOfBounds,
+			);
+			assert_noop!(
+				Vesting::merge_schedules(Some(3).into(), 0, 1),
+				Error::<Test>::NotVesting,
+			);
+			// Merging a schedule with itself does nothing.
+			assert_ok!(Vesting::merge_schedules(Some(2).into(), 0, 0));
+			assert_eq!(Vesting::vesting(&2).unwrap(), vec![user2_vesting_schedule]);
+		});
+}
+
+#[test]
+fn migration_to_v1_works() {
+	use frame_support::traits::OnRuntimeUpgrade;
+
+	ExtBuilder::default()
+		.existential_deposit(256)
+		.build()
+		.execute_with(|| {
+			let schedule = VestingInfo { locked: 256 * 5, per_block: 64, starting_block: 10 };
+			frame_support::storage::unhashed::put(
+				&crate::Vesting::<Test>::hashed_key_for(&7),
+				&schedule,
+			);
+			StorageVersion::<Test>::put(Releases::V0);
+
+			<Vesting as OnRuntimeUpgrade>::on_runtime_upgrade();
+
+			assert_eq!(Vesting::vesting(&7).unwrap(), vec![schedule]);
+			assert_eq!(StorageVersion::<Test>::get(), Releases::V1);
 		});
 }
@@ -15,26 +15,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for pallet_vesting
+//! Weights for pallet_vesting.
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
-//! DATE: 2021-06-19, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
-
-// Executed Command:
-// target/release/substrate
-// benchmark
-// --chain=dev
-// --steps=50
-// --repeat=20
-// --pallet=pallet_vesting
-// --extrinsic=*
-// --execution=wasm
-// --wasm-execution=compiled
-// --heap-pages=4096
-// --output=./frame/vesting/src/weights.rs
-// --template=./.maintain/frame-weight-template.hbs
-
+//! The `s` terms and `merge_schedules` are placeholders estimated by hand, not benchmark output:
+//! regenerate them with the `pallet_vesting` benchmarks.
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -44,103 +28,120 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for pallet_vesting.
 pub trait WeightInfo {
-	fn vest_locked(l: u32, ) -> Weight;
-	fn vest_unlocked(l: u32, ) -> Weight;
-	fn vest_other_locked(l: u32, ) -> Weight;
-	fn vest_other_unlocked(l: u32, ) -> Weight;
-	fn vested_transfer(l: u32, ) -> Weight;
-	fn force_vested_transfer(l: u32, ) -> Weight;
+	fn vest_locked(l: u32, s: u32, ) -> Weight;
+	fn vest_unlocked(l: u32, s: u32, ) -> Weight;
+	fn vest_other_locked(l: u32, s: u32, ) -> Weight;
+	fn vest_other_unlocked(l: u32, s: u32, ) -> Weight;
+	fn vested_transfer(l: u32, s: u32, ) -> Weight;
+	fn force_vested_transfer(l: u32, s: u32, ) -> Weight;
+	fn merge_schedules(l: u32, s: u32, ) -> Weight;
 }
 
 /// Weights for pallet_vesting using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
-	fn vest_locked(l: u32, ) -> Weight {
+	fn vest_locked(l: u32, s: u32, ) -> Weight {
 		(42_905_000 as Weight)
-			// Standard Error: 13_000
 			.saturating_add((232_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((113_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
-	fn vest_unlocked(l: u32, ) -> Weight {
+	fn vest_unlocked(l: u32, s: u32, ) -> Weight {
 		(45_650_000 as Weight)
-			// Standard Error: 12_000
 			.saturating_add((215_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((99_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
-	fn vest_other_locked(l: u32, ) -> Weight {
+	fn vest_other_locked(l: u32, s: u32, ) -> Weight {
 		(42_273_000 as Weight)
-			// Standard Error: 15_000
 			.saturating_add((246_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((121_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
-	fn vest_other_unlocked(l: u32, ) -> Weight {
+	fn vest_other_unlocked(l: u32, s: u32, ) -> Weight {
 		(45_324_000 as Weight)
-			// Standard Error: 12_000
 			.saturating_add((214_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((104_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
-	fn vested_transfer(l: u32, ) -> Weight {
+	fn vested_transfer(l: u32, s: u32, ) -> Weight {
 		(96_661_000 as Weight)
-			// Standard Error: 10_000
 			.saturating_add((211_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((187_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(3 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
-	fn force_vested_transfer(l: u32, ) -> Weight {
+	fn force_vested_transfer(l: u32, s: u32, ) -> Weight {
 		(98_812_000 as Weight)
-			// Standard Error: 13_000
 			.saturating_add((139_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((192_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 	}
+	// Placeholder estimated by hand: regenerate with the `pallet_vesting` benchmarks.
+	fn merge_schedules(l: u32, s: u32, ) -> Weight {
+		(57_283_000 as Weight)
+			.saturating_add((214_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((264_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-	fn vest_locked(l: u32, ) -> Weight {
+	fn vest_locked(l: u32, s: u32, ) -> Weight {
 		(42_905_000 as Weight)
-			// Standard Error: 13_000
 			.saturating_add((232_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((113_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
-	fn vest_unlocked(l: u32, ) -> Weight {
+	fn vest_unlocked(l: u32, s: u32, ) -> Weight {
 		(45_650_000 as Weight)
-			// Standard Error: 12_000
 			.saturating_add((215_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((99_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
-	fn vest_other_locked(l: u32, ) -> Weight {
+	fn vest_other_locked(l: u32, s: u32, ) -> Weight {
 		(42_273_000 as Weight)
-			// Standard Error: 15_000
 			.saturating_add((246_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((121_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
-	fn vest_other_unlocked(l: u32, ) -> Weight {
+	fn vest_other_unlocked(l: u32, s: u32, ) -> Weight {
 		(45_324_000 as Weight)
-			// Standard Error: 12_000
 			.saturating_add((214_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((104_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
-	fn vested_transfer(l: u32, ) -> Weight {
+	fn vested_transfer(l: u32, s: u32, ) -> Weight {
 		(96_661_000 as Weight)
-			// Standard Error: 10_000
 			.saturating_add((211_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((187_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
-	fn force_vested_transfer(l: u32, ) -> Weight {
+	fn force_vested_transfer(l: u32, s: u32, ) -> Weight {
 		(98_812_000 as Weight)
-			// Standard Error: 13_000
 			.saturating_add((139_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((192_000 as Weight).saturating_mul(s as Weight))
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 	}
+	// Placeholder estimated by hand: regenerate with the `pallet_vesting` benchmarks.
+	fn merge_schedules(l: u32, s: u32, ) -> Weight {
+		(57_283_000 as Weight)
+			.saturating_add((214_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add((264_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
 }
@@ -19,7 +19,8 @@
 
 use std::sync::Arc;
 
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor};
+use codec::Encode;
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT, NumberFor, HashFor, Hash as HashT};
 use sp_runtime::generic::BlockId;
 use sp_runtime::Justifications;
 use log::warn;
@@ -84,6 +85,22 @@ pub trait HeaderBackend<Block: BlockT>: Send + Sync {
 pub trait Backend<Block: BlockT>: HeaderBackend<Block> + HeaderMetadata<Block, Error=Error> {
 	/// Get block body. Returns `None` if block is not found.
 	fn body(&self, id: BlockId<Block>) -> Result<Option<Vec<<Block as BlockT>::Extrinsic>>>;
+	/// Get the number of extrinsics in the block body. Returns `None` if the body is not found.
+	///
+	/// Backends that keep the count separately should override this to avoid reading the body.
+	fn extrinsics_count(&self, id: BlockId<Block>) -> Result<Option<u32>> {
+		Ok(self.body(id)?.map(|body| body.len() as u32))
+	}
+	/// Get the root of the extrinsics trie built from the block body. Returns `None` if the body
+	/// is not found.
+	///
+	/// Comparing it with the `extrinsics_root` of the header checks the stored body. Backends
+	/// that keep the root separately should override this to avoid reading the body.
+	fn extrinsics_root(&self, id: BlockId<Block>) -> Result<Option<Block::Hash>> {
+		Ok(self.body(id)?.map(|body|
+			HashFor::<Block>::ordered_trie_root(body.iter().map(Encode::encode).collect())
+		))
+	}
 	/// Get block justifications. Returns `None` if no justification exists.
 	fn justifications(&self, id: BlockId<Block>) -> Result<Option<Justifications>>;
 	/// Get last finalized block hash.
@@ -96,6 +113,21 @@ pub trait Backend<Block: BlockT>: HeaderBackend<Block> + HeaderMetadata<Block, E
 	/// Results must be ordered best (longest, highest) chain first.
 	fn leaves(&self) -> Result<Vec<Block::Hash>>;
 
+	/// Returns hashes of the leaves which finalizing a block of number `block_number` would
+	/// discard, i.e. the leaves of a lower number.
+	fn displaced_leaves_after_finalizing(
+		&self,
+		block_number: NumberFor<Block>,
+	) -> Result<Vec<Block::Hash>> {
+		let mut displaced = Vec::new();
+		for leaf in self.leaves()? {
+			if self.number(leaf)?.map_or(false, |number| number < block_number) {
+				displaced.push(leaf);
+			}
+		}
+		Ok(displaced)
+	}
+
 	/// Return hashes of all blocks that are children of the block with `parent_hash`.
 	fn children(&self, parent_hash: Block::Hash) -> Result<Vec<Block::Hash>>;
 
@@ -155,6 +155,12 @@ impl From<NextConfigDescriptor> for BabeEpochConfiguration {
 	}
 }
 
+/// Register the BABE pre-runtime digest in `registry`.
+#[cfg(feature = "std")]
+pub fn register_pre_digest(registry: &mut sp_runtime::generic::PreRuntimeDigestRegistry) {
+	registry.register_codec::<PreDigest>(BABE_ENGINE_ID);
+}
+
 /// A digest item which is usable with BABE consensus.
 pub trait CompatibleDigestItem: Sized {
 	/// Construct a digest item which contains a BABE pre-digest.
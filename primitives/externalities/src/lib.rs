@@ -63,6 +63,20 @@ pub trait Externalities: ExtensionStore {
 	/// This may be optimized for large values.
 	fn storage_hash(&self, key: &[u8]) -> Option<Vec<u8>>;
 
+	/// Get the length in bytes of the storage value.
+	///
+	/// This may be optimized to not copy the value.
+	fn storage_size(&self, key: &[u8]) -> Option<u32> {
+		self.storage(key).map(|value| value.len() as u32)
+	}
+
+	/// Get the length in bytes of the child storage value.
+	///
+	/// This may be optimized to not copy the value.
+	fn child_storage_size(&self, child_info: &ChildInfo, key: &[u8]) -> Option<u32> {
+		self.child_storage(child_info, key).map(|value| value.len() as u32)
+	}
+
 	/// Get child storage value hash.
 	///
 	/// This may be optimized for large values.
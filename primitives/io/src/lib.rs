@@ -113,6 +113,22 @@ pub trait Storage {
 		})
 	}
 
+	/// Returns the length in bytes of the value of `key` in the storage or `None` if the key can
+	/// not be found.
+	///
+	/// Unlike `read` with an empty buffer, the value does not need to be copied on the host.
+	fn size(&self, key: &[u8]) -> Option<u32> {
+		self.storage_size(key)
+	}
+
+	/// Returns the SCALE encoded hash of the value of `key` in the storage or `None` if the key
+	/// can not be found.
+	///
+	/// The value does not need to be copied on the host.
+	fn hash(&self, key: &[u8]) -> Option<Vec<u8>> {
+		self.storage_hash(key)
+	}
+
 	/// Set `key` to `value` in the storage.
 	fn set(&mut self, key: &[u8], value: &[u8]) {
 		self.set_storage(key.to_vec(), value.to_vec());
@@ -289,6 +305,24 @@ pub trait DefaultChildStorage {
 			})
 	}
 
+	/// Get the length in bytes of a child storage value.
+	///
+	/// Returns `None` if the value for `key` in the child storage denoted by `storage_key` can
+	/// not be found.
+	fn size(&self, storage_key: &[u8], key: &[u8]) -> Option<u32> {
+		let child_info = ChildInfo::new_default(storage_key);
+		self.child_storage_size(&child_info, key)
+	}
+
+	/// Get the SCALE encoded hash of a child storage value.
+	///
+	/// Returns `None` if the value for `key` in the child storage denoted by `storage_key` can
+	/// not be found.
+	fn hash(&self, storage_key: &[u8], key: &[u8]) -> Option<Vec<u8>> {
+		let child_info = ChildInfo::new_default(storage_key);
+		self.child_storage_hash(&child_info, key)
+	}
+
 	/// Set a child storage value.
 	///
 	/// Set `key` to `value` in the child storage denoted by `storage_key`.
@@ -1529,6 +1563,27 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn storage_size_and_hash_work() {
+		let mut t = BasicExternalities::default();
+		t.execute_with(|| {
+			storage::set(b"hello", b"world");
+			default_child_storage::set(b"child", b"hello", b"world!");
+
+			assert_eq!(storage::size(b"hello"), Some(5));
+			assert_eq!(storage::hash(b"hello"), Some(hashing::blake2_256(b"world").encode()));
+			assert_eq!(storage::size(b"foo"), None);
+			assert_eq!(storage::hash(b"foo"), None);
+
+			assert_eq!(default_child_storage::size(b"child", b"hello"), Some(6));
+			assert_eq!(
+				default_child_storage::hash(b"child", b"hello"),
+				Some(hashing::blake2_256(b"world!").encode()),
+			);
+			assert_eq!(default_child_storage::hash(b"child", b"foo"), None);
+		});
+	}
+
 	#[test]
 	fn clear_prefix_works() {
 		let mut t = BasicExternalities::new(Storage {
@@ -472,6 +472,66 @@ impl ChangesTrieSignal {
 
 impl<'a, Hash: Encode> codec::EncodeLike for DigestItemRef<'a, Hash> {}
 
+/// Decoder of a pre-runtime digest payload into a human readable description.
+///
+/// Returns `None` if the payload could not be decoded.
+#[cfg(feature = "std")]
+pub type PreRuntimeDigestDecoder = fn(&[u8]) -> Option<String>;
+
+/// Registry of pre-runtime digest payload decoders, keyed by consensus engine id.
+///
+/// Allows client code to describe the pre-runtime digests of all known engines without
+/// depending on their digest types.
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+pub struct PreRuntimeDigestRegistry {
+	decoders: std::collections::HashMap<ConsensusEngineId, PreRuntimeDigestDecoder>,
+}
+
+#[cfg(feature = "std")]
+impl PreRuntimeDigestRegistry {
+	/// Register `decoder` for the pre-runtime digests of `engine`, replacing any previous one.
+	pub fn register(&mut self, engine: ConsensusEngineId, decoder: PreRuntimeDigestDecoder) {
+		self.decoders.insert(engine, decoder);
+	}
+
+	/// Register the pre-runtime digests of `engine` as SCALE encoded `T`, described by its
+	/// `Debug` output.
+	pub fn register_codec<T: Decode + std::fmt::Debug>(&mut self, engine: ConsensusEngineId) {
+		self.register(engine, |mut data| T::decode(&mut data).ok().map(|v| format!("{:?}", v)));
+	}
+
+	/// Returns `true` if a decoder is registered for `engine`.
+	pub fn is_registered(&self, engine: &ConsensusEngineId) -> bool {
+		self.decoders.contains_key(engine)
+	}
+
+	/// Describe the pre-runtime digest `data` of `engine`.
+	///
+	/// Returns `None` if no decoder is registered for `engine` or the decoding fails.
+	pub fn decode(&self, engine: &ConsensusEngineId, data: &[u8]) -> Option<String> {
+		self.decoders.get(engine).and_then(|decoder| decoder(data))
+	}
+
+	/// Describe all pre-runtime digests in `digest` that can be decoded.
+	pub fn decode_digest<Hash>(&self, digest: &Digest<Hash>) -> Vec<(ConsensusEngineId, String)> {
+		digest.logs()
+			.iter()
+			.filter_map(DigestItem::as_pre_runtime)
+			.filter_map(|(engine, data)| self.decode(&engine, data).map(|d| (engine, d)))
+			.collect()
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Debug for PreRuntimeDigestRegistry {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let mut engines = self.decoders.keys().collect::<Vec<_>>();
+		engines.sort();
+		f.debug_struct("PreRuntimeDigestRegistry").field("engines", &engines).finish()
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -491,4 +551,23 @@ mod tests {
 			r#"{"logs":["0x0204000000","0x000c010203","0x05746573740c010203"]}"#
 		);
 	}
+
+	#[test]
+	fn pre_runtime_digest_registry_decodes_known_engines() {
+		let mut registry = PreRuntimeDigestRegistry::default();
+		registry.register_codec::<u64>(*b"test");
+		assert!(registry.is_registered(b"test"));
+		assert!(!registry.is_registered(b"none"));
+
+		let digest = Digest::<u64> {
+			logs: vec![
+				DigestItem::PreRuntime(*b"test", 42u64.encode()),
+				DigestItem::PreRuntime(*b"none", 42u64.encode()),
+				DigestItem::PreRuntime(*b"test", vec![1]),
+				DigestItem::Seal(*b"test", 42u64.encode()),
+			],
+		};
+
+		assert_eq!(registry.decode_digest(&digest), vec![(*b"test", "42".to_string())]);
+	}
 }
@@ -36,6 +36,8 @@ pub use self::block::{Block, SignedBlock, BlockId};
 pub use self::digest::{
 	Digest, DigestItem, DigestItemRef, OpaqueDigestItemId, ChangesTrieSignal,
 };
+#[cfg(feature = "std")]
+pub use self::digest::{PreRuntimeDigestDecoder, PreRuntimeDigestRegistry};
 
 use crate::codec::Encode;
 use sp_std::prelude::*;
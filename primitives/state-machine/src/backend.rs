@@ -69,6 +69,20 @@ pub trait Backend<H: Hasher>: sp_std::fmt::Debug {
 		self.child_storage(child_info, key).map(|v| v.map(|v| H::hash(&v)))
 	}
 
+	/// Get the size of the keyed storage value or None if there is nothing associated.
+	fn storage_size(&self, key: &[u8]) -> Result<Option<u32>, Self::Error> {
+		self.storage(key).map(|v| v.map(|v| v.len() as u32))
+	}
+
+	/// Get the size of the child keyed storage value or None if there is nothing associated.
+	fn child_storage_size(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<u32>, Self::Error> {
+		self.child_storage(child_info, key).map(|v| v.map(|v| v.len() as u32))
+	}
+
 	/// true if a key exists in storage.
 	fn exists_storage(&self, key: &[u8]) -> Result<bool, Self::Error> {
 		Ok(self.storage(key)?.is_some())
@@ -155,6 +169,21 @@ pub trait Backend<H: Hasher>: sp_std::fmt::Debug {
 		delta: impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
 	) -> (H::Out, bool, Self::Transaction) where H::Out: Ord;
 
+	/// Calculate the storage roots of several child tries, see [`Self::child_storage_root`].
+	///
+	/// The results are in the order of `child_deltas`.
+	fn child_storage_roots<'a>(
+		&self,
+		child_deltas: impl Iterator<Item = (
+			&'a ChildInfo,
+			impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		)>,
+	) -> Vec<(H::Out, bool, Self::Transaction)> where H::Out: Ord {
+		child_deltas
+			.map(|(child_info, child_delta)| self.child_storage_root(child_info, child_delta))
+			.collect()
+	}
+
 	/// Get all key/value pairs into a Vec.
 	fn pairs(&self) -> Vec<(StorageKey, StorageValue)>;
 
@@ -193,11 +222,15 @@ pub trait Backend<H: Hasher>: sp_std::fmt::Debug {
 		)>,
 	) -> (H::Out, Self::Transaction) where H::Out: Ord + Encode {
 		let mut txs: Self::Transaction = Default::default();
-		let mut child_roots: Vec<_> = Default::default();
+		let mut child_infos = Vec::new();
 		// child first
-		for (child_info, child_delta) in child_deltas {
-			let (child_root, empty, child_txs) =
-				self.child_storage_root(&child_info, child_delta);
+		let child_results = self.child_storage_roots(
+			child_deltas.inspect(|(child_info, _)| child_infos.push(*child_info)),
+		);
+		let mut child_roots: Vec<_> = Default::default();
+		for (child_info, (child_root, empty, child_txs))
+			in child_infos.into_iter().zip(child_results)
+		{
 			let prefixed_storage_key = child_info.prefixed_storage_key();
 			txs.consolidate(child_txs);
 			if empty {
@@ -22,7 +22,7 @@ use std::cell::RefCell;
 use std::collections::VecDeque;
 use codec::{Decode, Encode, Codec};
 use hash_db::Hasher;
-use num_traits::Zero;
+use num_traits::{One, Zero};
 use sp_core::storage::PrefixedStorageKey;
 use sp_trie::Recorder;
 use crate::changes_trie::{AnchorBlockId, ConfigurationRange, RootsStorage, Storage, BlockNumber};
@@ -66,12 +66,98 @@ pub fn key_changes<'a, H: Hasher, Number: BlockNumber>(
 
 			extrinsics: Default::default(),
 			blocks: Default::default(),
+			skip_digests_before_begin: false,
 
 			_hasher: ::std::marker::PhantomData::<H>::default(),
 		},
 	})
 }
 
+/// Changes of a key within a block range, returned by [`key_changes_in_range`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyChangesInRange<Number> {
+	/// Pairs of (block, extrinsic) where the key has been changed, last block first.
+	pub changes: Vec<(Number, u32)>,
+	/// Inclusive block ranges that have not been checked for changes, last range first.
+	///
+	/// These blocks have no changes tries, either because changes tries were disabled or
+	/// because they are pruned.
+	pub unchecked: Vec<(Number, Number)>,
+}
+
+impl<Number> KeyChangesInRange<Number> {
+	/// Returns `true` if all blocks of the range have been checked for changes.
+	pub fn is_complete(&self) -> bool {
+		self.unchecked.is_empty()
+	}
+}
+
+/// Return changes of given key at the `begin..=end` blocks range, together with the blocks that
+/// could not be checked.
+///
+/// `configs` are the ranges in which changes tries are enabled, together with the anchor block
+/// to query each of them at, last range first. Blocks before `first_available` have their changes
+/// tries pruned. Unlike [`key_changes`], blocks without changes tries don't fail the query, they
+/// are reported in [`KeyChangesInRange::unchecked`] instead.
+/// `max` is the number of best known block.
+pub fn key_changes_in_range<'a, H: Hasher, Number: BlockNumber>(
+	configs: &'a [(ConfigurationRange<'a, Number>, AnchorBlockId<H::Out, Number>)],
+	storage: &'a dyn Storage<H, Number>,
+	first_available: Number,
+	begin: Number,
+	end: Number,
+	max: Number,
+	storage_key: Option<&'a PrefixedStorageKey>,
+	key: &'a [u8],
+) -> Result<KeyChangesInRange<Number>, String> where H::Out: Encode {
+	let mut result = KeyChangesInRange { changes: Vec::new(), unchecked: Vec::new() };
+	// The last block that is neither checked nor reported as unchecked yet.
+	let mut unaccounted = if begin <= end { Some(end.clone()) } else { None };
+	for (config, anchor) in configs {
+		let last = match unaccounted.clone() {
+			Some(last) => last,
+			None => break,
+		};
+		let config_first = std::cmp::max(begin.clone(), config.zero.clone() + One::one());
+		let config_last = config.end.clone().map_or(last.clone(), |end| std::cmp::min(end, last.clone()));
+		if config_first > config_last {
+			continue;
+		}
+		if config_last < last {
+			result.unchecked.push((config_last.clone() + One::one(), last));
+		}
+
+		let checked_first = std::cmp::max(config_first.clone(), first_available.clone());
+		if checked_first <= config_last {
+			let mut changes = key_changes(
+				config.clone(),
+				storage,
+				checked_first.clone(),
+				anchor,
+				max.clone(),
+				storage_key,
+				key,
+			)?;
+			// The range may start at the first available block, and the digests before it
+			// point at pruned changes tries.
+			changes.essence.skip_digests_before_begin = true;
+			for change in changes {
+				result.changes.push(change?);
+			}
+		}
+		if checked_first > config_first {
+			let pruned_last = std::cmp::min(checked_first - One::one(), config_last);
+			result.unchecked.push((config_first.clone(), pruned_last));
+		}
+
+		unaccounted = config_first.checked_sub(&One::one()).filter(|last| *last >= begin);
+	}
+	if let Some(last) = unaccounted {
+		result.unchecked.push((begin, last));
+	}
+
+	Ok(result)
+}
 
 /// Returns proof of changes of given key at given blocks range.
 /// `max` is the number of best known block.
@@ -105,6 +191,7 @@ pub fn key_changes_proof<'a, H: Hasher, Number: BlockNumber>(
 
 			extrinsics: Default::default(),
 			blocks: Default::default(),
+			skip_digests_before_begin: false,
 
 			_hasher: ::std::marker::PhantomData::<H>::default(),
 		},
@@ -176,6 +263,7 @@ pub fn key_changes_proof_check_with_db<'a, H: Hasher, Number: BlockNumber>(
 
 			extrinsics: Default::default(),
 			blocks: Default::default(),
+			skip_digests_before_begin: false,
 
 			_hasher: ::std::marker::PhantomData::<H>::default(),
 		},
@@ -201,6 +289,8 @@ pub struct DrilldownIteratorEssence<'a, H, Number>
 
 	extrinsics: VecDeque<(Number, u32)>,
 	blocks: VecDeque<(Number, Option<u32>)>,
+	/// Don't visit the digest blocks before `begin`, which only cover out-of-range blocks.
+	skip_digests_before_begin: bool,
 
 	_hasher: ::std::marker::PhantomData<H>,
 }
@@ -280,8 +370,10 @@ impl<'a, H, Number> DrilldownIteratorEssence<'a, H, Number>
 						let begin = self.begin.clone();
 						let end = self.end.number.clone();
 						let config = self.config.clone();
+						let skip_before_begin = self.skip_digests_before_begin;
 						self.blocks.extend(blocks.into_iter()
 							.rev()
+							.filter(|b| !skip_before_begin || *b >= begin)
 							.filter(|b| level.map(|level| level > 1).unwrap_or(true) || (*b >= begin && *b <= end))
 							.map(|b| {
 								let prev_level = level
@@ -451,6 +543,76 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn key_changes_in_range_reports_pruned_blocks() {
+		let (config, storage) = prepare_for_drilldown();
+		let configs = vec![
+			(configuration_range(&config, 0), AnchorBlockId { hash: Default::default(), number: 16 }),
+		];
+		let query = |first_available| key_changes_in_range::<BlakeTwo256, u64>(
+			&configs,
+			&storage,
+			first_available,
+			1,
+			16,
+			16,
+			None,
+			&[42],
+		);
+
+		let result = query(1).unwrap();
+		assert_eq!(result.changes, vec![(8, 2), (8, 1), (6, 3), (3, 0)]);
+		assert!(result.is_complete());
+
+		let result = query(5).unwrap();
+		assert_eq!(result.changes, vec![(8, 2), (8, 1), (6, 3)]);
+		assert_eq!(result.unchecked, vec![(1, 4)]);
+
+		let result = query(17).unwrap();
+		assert_eq!(result.changes, vec![]);
+		assert_eq!(result.unchecked, vec![(1, 16)]);
+	}
+
+	#[test]
+	fn key_changes_in_range_reports_blocks_without_changes_tries() {
+		let (config, storage) = prepare_for_drilldown();
+		// Changes tries are enabled at 1..=8 and disabled afterwards.
+		let configs = vec![(
+			ConfigurationRange { config: &config, zero: 0, end: Some(8) },
+			AnchorBlockId { hash: Default::default(), number: 8 },
+		)];
+
+		let result = key_changes_in_range::<BlakeTwo256, u64>(
+			&configs,
+			&storage,
+			1,
+			1,
+			16,
+			16,
+			None,
+			&[42],
+		).unwrap();
+		assert_eq!(result.changes, vec![(8, 2), (8, 1), (6, 3), (3, 0)]);
+		assert_eq!(result.unchecked, vec![(9, 16)]);
+
+		// Nothing is known about blocks before the first configuration either.
+		let configs = vec![(
+			ConfigurationRange { config: &config, zero: 4, end: Some(8) },
+			AnchorBlockId { hash: Default::default(), number: 8 },
+		)];
+		let result = key_changes_in_range::<BlakeTwo256, u64>(
+			&configs,
+			&storage,
+			1,
+			1,
+			16,
+			16,
+			None,
+			&[42],
+		).unwrap();
+		assert_eq!(result.unchecked, vec![(9, 16), (1, 4)]);
+	}
+
 	#[test]
 	fn drilldown_iterator_works() {
 		let (config, storage) = prepare_for_drilldown();
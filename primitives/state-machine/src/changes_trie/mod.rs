@@ -61,8 +61,9 @@ mod surface_iterator;
 pub use self::build_cache::{BuildCache, CachedBuildData, CacheAction};
 pub use self::storage::InMemoryStorage;
 pub use self::changes_iterator::{
-	key_changes, key_changes_proof,
+	key_changes, key_changes_in_range, key_changes_proof,
 	key_changes_proof_check, key_changes_proof_check_with_db,
+	KeyChangesInRange,
 };
 pub use self::prune::prune;
 
@@ -46,4 +46,10 @@ pub enum ExecutionError {
 
 	#[cfg_attr(feature = "std", error("Invalid execution proof"))]
 	InvalidProof,
+
+	#[cfg_attr(feature = "std", error("Execution proof exceeds the size limit"))]
+	ProofSizeExceeded,
+
+	#[cfg_attr(feature = "std", error("Execution exceeds the limits on the state it reads"))]
+	LimitsExceeded,
 }
@@ -128,6 +128,7 @@ impl<'a, H, N, B> Ext<'a, H, N, B>
 		storage_transaction_cache: &'a mut StorageTransactionCache<B::Transaction, H, N>,
 		backend: &'a B,
 	) -> Self {
+		overlay.sync_cache(storage_transaction_cache);
 		Ext {
 			overlay,
 			backend,
@@ -146,6 +147,7 @@ impl<'a, H, N, B> Ext<'a, H, N, B>
 		changes_trie_state: Option<ChangesTrieState<'a, H, N>>,
 		extensions: Option<&'a mut Extensions>,
 	) -> Self {
+		overlay.sync_cache(storage_transaction_cache);
 		Self {
 			overlay,
 			backend,
@@ -163,6 +165,19 @@ impl<'a, H, N, B> Ext<'a, H, N, B>
 	fn mark_dirty(&mut self) {
 		self.storage_transaction_cache.reset();
 	}
+
+	/// Invalidates the cached storage root and db transaction of the main storage.
+	///
+	/// Called on changes of the main storage, which keep the cached child trie roots valid.
+	fn mark_top_dirty(&mut self) {
+		self.storage_transaction_cache.reset_top();
+	}
+
+	/// Invalidates the cached storage root and db transaction of the main storage and of the
+	/// child trie `child_info`.
+	fn mark_child_dirty(&mut self, child_info: &ChildInfo) {
+		self.storage_transaction_cache.reset_child(child_info.storage_key());
+	}
 }
 
 #[cfg(test)]
@@ -235,6 +250,21 @@ where
 		result.map(|r| r.encode())
 	}
 
+	fn storage_size(&self, key: &[u8]) -> Option<u32> {
+		let _guard = guard();
+		let result = self.overlay
+			.storage(key)
+			.map(|x| x.map(|x| x.len() as u32))
+			.unwrap_or_else(|| self.backend.storage_size(key).expect(EXT_NOT_ALLOWED_TO_FAIL));
+
+		trace!(target: "state", "{:04x}: Size {}={:?}",
+			self.id,
+			HexDisplay::from(&key),
+			result,
+		);
+		result
+	}
+
 	fn child_storage(
 		&self,
 		child_info: &ChildInfo,
@@ -259,6 +289,30 @@ where
 		result
 	}
 
+	fn child_storage_size(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Option<u32> {
+		let _guard = guard();
+		let result = self.overlay
+			.child_storage(child_info, key)
+			.map(|x| x.map(|x| x.len() as u32))
+			.unwrap_or_else(||
+				self.backend.child_storage_size(child_info, key)
+					.expect(EXT_NOT_ALLOWED_TO_FAIL)
+			);
+
+		trace!(target: "state", "{:04x}: ChildSize({}) {}={:?}",
+			self.id,
+			HexDisplay::from(&child_info.storage_key()),
+			HexDisplay::from(&key),
+			result,
+		);
+
+		result
+	}
+
 	fn child_storage_hash(
 		&self,
 		child_info: &ChildInfo,
@@ -426,7 +480,7 @@ where
 			),
 		);
 
-		self.mark_dirty();
+		self.mark_top_dirty();
 		self.overlay.set_storage(key, value);
 	}
 
@@ -444,7 +498,7 @@ where
 		);
 		let _guard = guard();
 
-		self.mark_dirty();
+		self.mark_child_dirty(child_info);
 		self.overlay.set_child_storage(child_info, key, value);
 	}
 
@@ -458,7 +512,7 @@ where
 			HexDisplay::from(&child_info.storage_key()),
 		);
 		let _guard = guard();
-		self.mark_dirty();
+		self.mark_child_dirty(child_info);
 		self.overlay.clear_child_storage(child_info);
 		self.limit_remove_from_backend(Some(child_info), None, limit)
 	}
@@ -475,7 +529,7 @@ where
 			return (false, 0);
 		}
 
-		self.mark_dirty();
+		self.mark_top_dirty();
 		self.overlay.clear_prefix(prefix);
 		self.limit_remove_from_backend(None, Some(prefix), limit)
 	}
@@ -493,7 +547,7 @@ where
 		);
 		let _guard = guard();
 
-		self.mark_dirty();
+		self.mark_child_dirty(child_info);
 		self.overlay.clear_child_prefix(child_info, prefix);
 		self.limit_remove_from_backend(Some(child_info), Some(prefix), limit)
 	}
@@ -510,7 +564,7 @@ where
 		);
 
 		let _guard = guard();
-		self.mark_dirty();
+		self.mark_top_dirty();
 
 		let backend = &mut self.backend;
 		let current_value = self.overlay.value_mut_or_insert_with(
@@ -556,20 +610,17 @@ where
 			);
 			root.encode()
 		} else {
-			let root = if let Some((changes, info)) = self.overlay.child_changes(storage_key) {
-				let delta = changes.map(|(k, v)| (k.as_ref(), v.value().map(AsRef::as_ref)));
-				Some(self.backend.child_storage_root(info, delta))
-			} else {
-				None
-			};
+			let root = self.overlay.child_storage_root(
+				self.backend,
+				child_info,
+				self.storage_transaction_cache,
+			);
 
-			if let Some((root, is_empty, _)) = root {
+			if let Some((root, is_empty)) = root {
 				let root = root.encode();
 				// We store update in the overlay in order to be able to use 'self.storage_transaction'
 				// cache. This is brittle as it rely on Ext only querying the trie backend for
-				// storage root.
-				// A better design would be to manage 'child_storage_transaction' in a
-				// similar way as 'storage_transaction' but for each child trie.
+				// storage root. The child trie transaction itself is cached with the root.
 				if is_empty {
 					self.overlay.set_storage(prefixed_storage_key.into_inner(), None);
 				} else {
@@ -1154,6 +1205,111 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn child_storage_roots_are_cached_until_changed() {
+		let child_info1 = ChildInfo::new_default(b"Child1");
+		let child_info2 = ChildInfo::new_default(b"Child2");
+		let backend: TestBackend = Storage { top: map![], children_default: map![] }.into();
+		let mut cache = StorageTransactionCache::default();
+		let mut overlay = OverlayedChanges::default();
+
+		let root = {
+			let mut ext = TestExt::new(&mut overlay, &mut cache, &backend, None, None);
+			ext.set_child_storage(&child_info1, vec![10], vec![10]);
+			ext.set_child_storage(&child_info2, vec![20], vec![20]);
+			ext.storage_root();
+			assert_eq!(ext.storage_transaction_cache.child_roots.len(), 2);
+
+			// Only the changed child trie has to be recomputed.
+			ext.set_child_storage(&child_info1, vec![11], vec![11]);
+			ext.set_storage(vec![1], vec![1]);
+			let child_roots = &ext.storage_transaction_cache.child_roots;
+			assert!(!child_roots.contains_key(child_info1.storage_key()));
+			assert!(child_roots.contains_key(child_info2.storage_key()));
+			ext.storage_root()
+		};
+
+		let delta = overlay.changes().map(|(k, v)| (&k[..], v.value().map(|v| &v[..])));
+		let child_delta = overlay.children()
+			.map(|(changes, info)| (info, changes.map(
+				|(k, v)| (&k[..], v.value().map(|v| &v[..]))
+			)));
+		let (expected_root, mut expected_transaction) = backend.full_storage_root(delta, child_delta);
+		assert_eq!(root, expected_root.encode());
+
+		let mut changes = overlay.drain_storage_changes(
+			&backend,
+			None,
+			Default::default(),
+			&mut cache,
+		).unwrap();
+		assert_eq!(changes.transaction_storage_root, expected_root);
+		assert_eq!(changes.transaction.drain(), expected_transaction.drain());
+	}
+
+	#[test]
+	fn cached_child_storage_roots_are_dropped_on_rollback() {
+		let child_info = ChildInfo::new_default(b"Child1");
+		let backend: TestBackend = Storage { top: map![], children_default: map![] }.into();
+		let mut cache = StorageTransactionCache::default();
+		let mut overlay = OverlayedChanges::default();
+
+		let root = {
+			let mut ext = TestExt::new(&mut overlay, &mut cache, &backend, None, None);
+			ext.set_child_storage(&child_info, vec![10], vec![10]);
+			ext.storage_root()
+		};
+
+		// Rolled back outside of `Ext`, like `sp-api` does.
+		overlay.start_transaction();
+		{
+			let mut ext = TestExt::new(&mut overlay, &mut cache, &backend, None, None);
+			ext.set_child_storage(&child_info, vec![11], vec![11]);
+			ext.child_storage_root(&child_info);
+		}
+		overlay.rollback_transaction().unwrap();
+
+		let mut ext = TestExt::new(&mut overlay, &mut cache, &backend, None, None);
+		assert_eq!(ext.storage_root(), root);
+	}
+
+	#[test]
+	fn storage_size_works() {
+		let child_info = ChildInfo::new_default(b"Child1");
+		let child_info = &child_info;
+		let mut cache = StorageTransactionCache::default();
+		let mut overlay = OverlayedChanges::default();
+		overlay.set_storage(vec![20], None);
+		overlay.set_storage(vec![30], Some(vec![1, 2, 3]));
+		overlay.set_child_storage(child_info, vec![20], None);
+		let backend = Storage {
+			top: map![
+				vec![10] => vec![1, 2],
+				vec![20] => vec![1, 2],
+				vec![30] => vec![1]
+			],
+			children_default: map![
+				child_info.storage_key().to_vec() => StorageChild {
+					data: map![
+						vec![10] => vec![1, 2, 3, 4],
+						vec![20] => vec![1]
+					],
+					child_info: child_info.to_owned(),
+				}
+			],
+		}.into();
+
+		let ext = TestExt::new(&mut overlay, &mut cache, &backend, None, None);
+
+		assert_eq!(ext.storage_size(&[10]), Some(2));
+		assert_eq!(ext.storage_size(&[20]), None);
+		assert_eq!(ext.storage_size(&[30]), Some(3));
+		assert_eq!(ext.storage_size(&[40]), None);
+		assert_eq!(ext.child_storage_size(child_info, &[10]), Some(4));
+		assert_eq!(ext.child_storage_size(child_info, &[20]), None);
+		assert_eq!(ext.child_storage_size(child_info, &[30]), None);
+	}
+
 	#[test]
 	fn clear_prefix_cannot_delete_a_child_root() {
 		let child_info = ChildInfo::new_default(b"Child1");
@@ -34,6 +34,10 @@ mod basic;
 pub(crate) mod overlayed_changes;
 #[cfg(feature = "std")]
 mod proving_backend;
+#[cfg(feature = "std")]
+mod metered_backend;
+#[cfg(feature = "std")]
+mod proof_check_cache;
 mod trie_backend;
 mod trie_backend_essence;
 mod stats;
@@ -124,6 +128,7 @@ pub use crate::overlayed_changes::{
 	OffchainChangesCollection,
 	OffchainOverlayedChanges,
 	IndexOperation,
+	ExportedChange, ExportedChanges, DivergentKey,
 };
 pub use crate::backend::Backend;
 pub use crate::trie_backend_essence::{TrieBackendStorage, Storage};
@@ -144,7 +149,7 @@ mod changes_trie {
 #[cfg(feature = "std")]
 mod std_reexport {
 	pub use sp_trie::{trie_types::{Layout, TrieDBMut}, StorageProof, TrieMut, DBValue, MemoryDB};
-	pub use crate::testing::TestExternalities;
+	pub use crate::testing::{TestExternalities, TestExternalitiesSnapshot};
 	pub use crate::basic::BasicExternalities;
 	pub use crate::read_only::{ReadOnlyExternalities, InspectState};
 	pub use crate::changes_trie::{
@@ -156,7 +161,8 @@ mod std_reexport {
 		BuildCache as ChangesTrieBuildCache,
 		CacheAction as ChangesTrieCacheAction,
 		ConfigurationRange as ChangesTrieConfigurationRange,
-		key_changes, key_changes_proof,
+		KeyChangesInRange,
+		key_changes, key_changes_in_range, key_changes_proof,
 		key_changes_proof_check, key_changes_proof_check_with_db,
 		prune as prune_changes_tries,
 		disabled_state as disabled_changes_trie_state,
@@ -165,6 +171,8 @@ mod std_reexport {
 	pub use crate::proving_backend::{
 		create_proof_check_backend, ProofRecorder, ProvingBackend, ProvingBackendRecorder,
 	};
+	pub use crate::metered_backend::{ExecutionLimits, ExecutionUsage, MeteredBackend};
+	pub use crate::proof_check_cache::ExecutionProofCheckCache;
 	pub use crate::error::{Error, ExecutionError};
 	pub use crate::in_memory_backend::new_in_mem;
 }
@@ -215,6 +223,39 @@ mod execution {
 		NativeElseWasm,
 	}
 
+	/// Execution strategies of runtime calls, overriding the strategy the call is made with.
+	///
+	/// Overrides are matched against the name of the called method in the order they were added,
+	/// the first match wins. A pattern ending with `*` matches all the methods starting with the
+	/// rest of the pattern, e.g. `Core_*`; any other pattern only matches the method of that name.
+	#[derive(Clone, Debug, Default, PartialEq, Eq)]
+	pub struct ExecutionStrategyOverrides {
+		overrides: Vec<(String, ExecutionStrategy)>,
+	}
+
+	impl ExecutionStrategyOverrides {
+		/// Add an override of the strategy of the methods matching `pattern`.
+		pub fn with_override(mut self, pattern: impl Into<String>, strategy: ExecutionStrategy) -> Self {
+			self.overrides.push((pattern.into(), strategy));
+			self
+		}
+
+		/// Returns `true` if there are no overrides.
+		pub fn is_empty(&self) -> bool {
+			self.overrides.is_empty()
+		}
+
+		/// The strategy `method` should be executed with, if it is overridden.
+		pub fn strategy_for(&self, method: &str) -> Option<ExecutionStrategy> {
+			self.overrides.iter()
+				.find(|(pattern, _)| match pattern.strip_suffix('*') {
+					Some(prefix) => method.starts_with(prefix),
+					None => method == pattern,
+				})
+				.map(|(_, strategy)| *strategy)
+		}
+	}
+
 	/// Storage backend trust level.
 	#[derive(Debug, Clone)]
 	pub enum BackendTrustLevel {
@@ -386,6 +427,23 @@ mod execution {
 			).map(NativeOrEncoded::into_encoded)
 		}
 
+		/// Execute the call like [`Self::execute`] and compare the changes it leaves in the overlay
+		/// with `expected`.
+		///
+		/// To replay a recorded execution, the overlay of the state machine should contain the
+		/// recorded changes the call was executed on, see [`OverlayedChanges::import`], and
+		/// `expected` the changes exported after the recorded execution.
+		///
+		/// Returns the first key whose change differs, or `None` if all changes are the same.
+		pub fn replay(
+			&mut self,
+			strategy: ExecutionStrategy,
+			expected: &ExportedChanges,
+		) -> Result<Option<DivergentKey>, Box<dyn Error>> {
+			self.execute(strategy)?;
+			Ok(self.overlay.export().first_divergent_key(expected))
+		}
+
 		fn execute_aux<R, NC>(
 			&mut self,
 			use_native: bool,
@@ -564,6 +622,55 @@ mod execution {
 		}
 	}
 
+	/// Execute a call like [`StateMachine::execute`], but abort with
+	/// [`ExecutionError::LimitsExceeded`] once the call exceeds the given `limits` on the state
+	/// it reads.
+	///
+	/// Returns the SCALE encoded result of the executed function and the state it has read.
+	pub fn execute_with_limits<B, H, N, Exec, Spawn>(
+		backend: &B,
+		changes_trie_state: Option<ChangesTrieState<H, N>>,
+		overlay: &mut OverlayedChanges,
+		exec: &Exec,
+		spawn_handle: Spawn,
+		method: &str,
+		call_data: &[u8],
+		extensions: Extensions,
+		runtime_code: &RuntimeCode,
+		strategy: ExecutionStrategy,
+		limits: ExecutionLimits,
+	) -> Result<(Vec<u8>, ExecutionUsage), Box<dyn Error>>
+	where
+		B: Backend<H>,
+		H: Hasher,
+		H::Out: Ord + 'static + codec::Codec,
+		Exec: CodeExecutor + 'static + Clone,
+		N: crate::changes_trie::BlockNumber,
+		Spawn: SpawnNamed + Send + 'static,
+	{
+		let metered_backend = MeteredBackend::new(backend, limits);
+		let mut sm = StateMachine::<_, H, N, Exec>::new(
+			&metered_backend,
+			changes_trie_state,
+			overlay,
+			exec,
+			method,
+			call_data,
+			extensions,
+			runtime_code,
+			spawn_handle,
+		);
+
+		let result = sm.execute(strategy);
+		// Reads beyond the limits fail, so the execution usually fails as well. Report the
+		// actual cause instead of the resulting execution error.
+		if sm.backend.limits_exceeded() {
+			return Err(Box::new(ExecutionError::LimitsExceeded))
+		}
+		let result = result?;
+		Ok((result, sm.backend.usage()))
+	}
+
 	/// Prove execution using the given state backend, overlayed changes, and call executor.
 	pub fn prove_execution<B, H, N, Exec, Spawn>(
 		mut backend: B,
@@ -622,6 +729,70 @@ mod execution {
 		Spawn: SpawnNamed + Send + 'static,
 	{
 		let proving_backend = proving_backend::ProvingBackend::new(trie_backend);
+		prove_execution_on_proving_backend::<_, H, N, _, _>(
+			proving_backend,
+			overlay,
+			exec,
+			spawn_handle,
+			method,
+			call_data,
+			runtime_code,
+		)
+	}
+
+	/// Prove execution using the given trie backend like [`prove_execution_on_trie_backend`],
+	/// but abort with [`ExecutionError::ProofSizeExceeded`] once the estimated encoded size of
+	/// the proof grows beyond `proof_size_limit` bytes.
+	pub fn prove_execution_on_trie_backend_with_size_limit<S, H, N, Exec, Spawn>(
+		trie_backend: &TrieBackend<S, H>,
+		overlay: &mut OverlayedChanges,
+		exec: &Exec,
+		spawn_handle: Spawn,
+		method: &str,
+		call_data: &[u8],
+		runtime_code: &RuntimeCode,
+		proof_size_limit: usize,
+	) -> Result<(Vec<u8>, StorageProof), Box<dyn Error>>
+	where
+		S: trie_backend_essence::TrieBackendStorage<H>,
+		H: Hasher,
+		H::Out: Ord + 'static + codec::Codec,
+		Exec: CodeExecutor + 'static + Clone,
+		N: crate::changes_trie::BlockNumber,
+		Spawn: SpawnNamed + Send + 'static,
+	{
+		let proving_backend = proving_backend::ProvingBackend::new_with_size_limit(
+			trie_backend,
+			proof_size_limit,
+		);
+		prove_execution_on_proving_backend::<_, H, N, _, _>(
+			proving_backend,
+			overlay,
+			exec,
+			spawn_handle,
+			method,
+			call_data,
+			runtime_code,
+		)
+	}
+
+	fn prove_execution_on_proving_backend<S, H, N, Exec, Spawn>(
+		proving_backend: proving_backend::ProvingBackend<'_, S, H>,
+		overlay: &mut OverlayedChanges,
+		exec: &Exec,
+		spawn_handle: Spawn,
+		method: &str,
+		call_data: &[u8],
+		runtime_code: &RuntimeCode,
+	) -> Result<(Vec<u8>, StorageProof), Box<dyn Error>>
+	where
+		S: trie_backend_essence::TrieBackendStorage<H>,
+		H: Hasher,
+		H::Out: Ord + 'static + codec::Codec,
+		Exec: CodeExecutor + 'static + Clone,
+		N: crate::changes_trie::BlockNumber,
+		Spawn: SpawnNamed + Send + 'static,
+	{
 		let mut sm = StateMachine::<_, H, N, Exec>::new(
 			&proving_backend,
 			None,
@@ -637,7 +808,13 @@ mod execution {
 		let result = sm.execute_using_consensus_failure_handler::<_, NeverNativeValue, fn() -> _>(
 			always_wasm(),
 			None,
-		)?;
+		);
+		// Reads beyond the limit fail, so the execution usually fails as well. Report the
+		// actual cause instead of the resulting execution error.
+		if sm.backend.proof_size_exceeded() {
+			return Err(Box::new(ExecutionError::ProofSizeExceeded))
+		}
+		let result = result?;
 		let proof = sm.backend.extract_proof();
 		Ok((result.into_encoded(), proof))
 	}
@@ -672,6 +849,46 @@ mod execution {
 		)
 	}
 
+	/// Check execution proof, generated by `prove_execution` call, unless the result of the same
+	/// call on the same state is in `cache`.
+	///
+	/// The call is executed on top of an empty overlay. Its result is added to `cache` once the
+	/// proof is checked.
+	pub fn execution_proof_check_cached<H, N, Exec, Spawn>(
+		cache: &ExecutionProofCheckCache<H>,
+		root: H::Out,
+		proof: StorageProof,
+		exec: &Exec,
+		spawn_handle: Spawn,
+		method: &str,
+		call_data: &[u8],
+		runtime_code: &RuntimeCode,
+	) -> Result<Vec<u8>, Box<dyn Error>>
+	where
+		H: Hasher,
+		Exec: CodeExecutor + Clone + 'static,
+		H::Out: Ord + 'static + codec::Codec,
+		N: crate::changes_trie::BlockNumber,
+		Spawn: SpawnNamed + Send + 'static,
+	{
+		if let Some(result) = cache.get(&root, &runtime_code.hash, method, call_data) {
+			return Ok(result)
+		}
+
+		let result = execution_proof_check::<H, N, _, _>(
+			root,
+			proof,
+			&mut Default::default(),
+			exec,
+			spawn_handle,
+			method,
+			call_data,
+			runtime_code,
+		)?;
+		cache.insert(root, &runtime_code.hash, method, call_data, result.clone());
+		Ok(result)
+	}
+
 	/// Check execution proof on proving backend, generated by `prove_execution` call.
 	pub fn execution_proof_check_on_trie_backend<H, N, Exec, Spawn>(
 		trie_backend: &TrieBackend<MemoryDB<H>, H>,
@@ -809,6 +1026,72 @@ mod execution {
 		Ok(proving_backend.extract_proof())
 	}
 
+	/// Generate storage read proof for `keys` as a sequence of standalone proof chunks.
+	///
+	/// Every chunk proves a run of consecutive `keys` and is closed once its estimated encoded
+	/// size reaches `chunk_size`, so it may exceed `chunk_size` by the nodes of a single key.
+	/// The nodes of a chunk are sorted, which makes the output depend only on the state and on
+	/// `keys`. Chunks are generated lazily and can be checked with [`read_proof_check_stream`].
+	pub fn prove_read_chunks_on_trie_backend<'a, S, H, I>(
+		trie_backend: &'a TrieBackend<S, H>,
+		keys: I,
+		chunk_size: usize,
+	) -> ReadProofChunks<'a, S, H, I::IntoIter>
+	where
+		S: trie_backend_essence::TrieBackendStorage<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+		I: IntoIterator,
+		I::Item: AsRef<[u8]>,
+	{
+		ReadProofChunks {
+			trie_backend,
+			keys: keys.into_iter(),
+			chunk_size,
+		}
+	}
+
+	/// Iterator over storage read proof chunks, created by [`prove_read_chunks_on_trie_backend`].
+	pub struct ReadProofChunks<'a, S: trie_backend_essence::TrieBackendStorage<H>, H: Hasher, I> {
+		trie_backend: &'a TrieBackend<S, H>,
+		keys: I,
+		chunk_size: usize,
+	}
+
+	impl<'a, S, H, I> Iterator for ReadProofChunks<'a, S, H, I>
+	where
+		S: trie_backend_essence::TrieBackendStorage<H>,
+		H: Hasher,
+		H::Out: Ord + Codec,
+		I: Iterator,
+		I::Item: AsRef<[u8]>,
+	{
+		type Item = Result<StorageProof, Box<dyn Error>>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			let mut key = self.keys.next()?;
+			let proving_backend = proving_backend::ProvingBackend::<_, H>::new(self.trie_backend);
+			loop {
+				if let Err(e) = proving_backend.storage(key.as_ref()) {
+					return Some(Err(Box::new(e)))
+				}
+
+				if proving_backend.estimate_encoded_size() >= self.chunk_size {
+					break
+				}
+
+				match self.keys.next() {
+					Some(next) => key = next,
+					None => break,
+				}
+			}
+
+			let mut nodes = proving_backend.extract_proof().into_nodes();
+			nodes.sort();
+			Some(Ok(StorageProof::new(nodes)))
+		}
+	}
+
 	/// Generate storage read proof on pre-created trie backend.
 	pub fn prove_child_read_on_trie_backend<S, H, I>(
 		trie_backend: &TrieBackend<S, H>,
@@ -852,6 +1135,71 @@ mod execution {
 		Ok(result)
 	}
 
+	/// Check storage read proof chunks, generated by `prove_read_chunks_on_trie_backend` call.
+	///
+	/// Yields the values of `keys` in order. `keys` must be given in the order used to generate
+	/// the chunks. Only the chunk covering the current key is held in memory.
+	pub fn read_proof_check_stream<H, P, I>(
+		root: H::Out,
+		proofs: P,
+		keys: I,
+	) -> ReadProofCheckStream<H, P::IntoIter, I::IntoIter>
+	where
+		H: Hasher,
+		H::Out: Ord + Codec,
+		P: IntoIterator<Item = StorageProof>,
+		I: IntoIterator,
+		I::Item: AsRef<[u8]>,
+	{
+		ReadProofCheckStream {
+			root,
+			proofs: proofs.into_iter(),
+			keys: keys.into_iter(),
+			current: None,
+		}
+	}
+
+	/// Iterator over the values checked against storage read proof chunks, created by
+	/// [`read_proof_check_stream`].
+	pub struct ReadProofCheckStream<H: Hasher, P, I> {
+		root: H::Out,
+		proofs: P,
+		keys: I,
+		current: Option<TrieBackend<MemoryDB<H>, H>>,
+	}
+
+	impl<H, P, I> Iterator for ReadProofCheckStream<H, P, I>
+	where
+		H: Hasher,
+		H::Out: Ord + Codec,
+		P: Iterator<Item = StorageProof>,
+		I: Iterator,
+		I::Item: AsRef<[u8]>,
+	{
+		type Item = Result<(Vec<u8>, Option<Vec<u8>>), Box<dyn Error>>;
+
+		fn next(&mut self) -> Option<Self::Item> {
+			let key = self.keys.next()?;
+			loop {
+				// A failed read means the current chunk does not cover the key.
+				if let Some(backend) = self.current.as_ref() {
+					if let Ok(value) = read_proof_check_on_proving_backend(backend, key.as_ref()) {
+						return Some(Ok((key.as_ref().to_vec(), value)))
+					}
+				}
+
+				let proof = match self.proofs.next() {
+					Some(proof) => proof,
+					None => return Some(Err(Box::new(ExecutionError::InvalidProof))),
+				};
+				match create_proof_check_backend::<H>(self.root.clone(), proof) {
+					Ok(backend) => self.current = Some(backend),
+					Err(e) => return Some(Err(e)),
+				}
+			}
+		}
+	}
+
 	/// Check child storage range proof, generated by `prove_range_read` call.
 	pub fn read_range_proof_check<H>(
 		root: H::Out,
@@ -1138,6 +1486,18 @@ mod tests {
 		assert!(consensus_failed);
 	}
 
+	#[test]
+	fn strategy_overrides_match_method_names() {
+		let overrides = ExecutionStrategyOverrides::default()
+			.with_override("Core_version", ExecutionStrategy::NativeElseWasm)
+			.with_override("Core_*", ExecutionStrategy::AlwaysWasm);
+
+		assert_eq!(overrides.strategy_for("Core_version"), Some(ExecutionStrategy::NativeElseWasm));
+		assert_eq!(overrides.strategy_for("Core_execute_block"), Some(ExecutionStrategy::AlwaysWasm));
+		assert_eq!(overrides.strategy_for("Metadata_metadata"), None);
+		assert_eq!(overrides.strategy_for("Core"), None);
+	}
+
 	#[test]
 	fn prove_execution_and_proof_check_works() {
 		let executor = DummyCodeExecutor {
@@ -1177,6 +1537,188 @@ mod tests {
 		assert_eq!(remote_result, local_result);
 	}
 
+	#[test]
+	fn execution_proof_check_cached_skips_known_calls() {
+		let executor = DummyCodeExecutor {
+			change_changes_trie_config: false,
+			native_available: true,
+			native_succeeds: true,
+			fallback_succeeds: true,
+		};
+
+		let remote_backend = trie_backend::tests::test_trie();
+		let remote_root = remote_backend.storage_root(std::iter::empty()).0;
+		let (remote_result, remote_proof) = prove_execution::<_, _, u64, _, _>(
+			remote_backend,
+			&mut Default::default(),
+			&executor,
+			TaskExecutor::new(),
+			"test",
+			&[],
+			&RuntimeCode::empty(),
+		).unwrap();
+
+		let cache = ExecutionProofCheckCache::<BlakeTwo256>::new(8);
+		let check = |proof| execution_proof_check_cached::<BlakeTwo256, u64, _, _>(
+			&cache,
+			remote_root,
+			proof,
+			&executor,
+			TaskExecutor::new(),
+			"test",
+			&[],
+			&RuntimeCode::empty(),
+		);
+
+		assert_eq!(check(remote_proof).unwrap(), remote_result);
+		assert_eq!(cache.len(), 1);
+		// An empty proof cannot be checked, so the result comes from the cache.
+		assert_eq!(check(StorageProof::empty()).unwrap(), remote_result);
+	}
+
+	#[test]
+	fn prove_execution_with_size_limit_works() {
+		let executor = DummyCodeExecutor {
+			change_changes_trie_config: false,
+			native_available: true,
+			native_succeeds: true,
+			fallback_succeeds: true,
+		};
+
+		let remote_backend = trie_backend::tests::test_trie();
+		let remote_root = remote_backend.storage_root(std::iter::empty()).0;
+		let (remote_result, remote_proof) =
+			prove_execution_on_trie_backend_with_size_limit::<_, _, u64, _, _>(
+				&remote_backend,
+				&mut Default::default(),
+				&executor,
+				TaskExecutor::new(),
+				"test",
+				&[],
+				&RuntimeCode::empty(),
+				1024 * 1024,
+			).unwrap();
+
+		let local_result = execution_proof_check::<BlakeTwo256, u64, _, _>(
+			remote_root,
+			remote_proof,
+			&mut Default::default(),
+			&executor,
+			TaskExecutor::new(),
+			"test",
+			&[],
+			&RuntimeCode::empty(),
+		).unwrap();
+
+		assert_eq!(remote_result, vec![66]);
+		assert_eq!(remote_result, local_result);
+	}
+
+	#[test]
+	fn prove_execution_reports_exceeded_proof_size() {
+		let executor = DummyCodeExecutor {
+			change_changes_trie_config: false,
+			native_available: false,
+			native_succeeds: false,
+			fallback_succeeds: false,
+		};
+
+		let remote_backend = trie_backend::tests::test_trie();
+		// Even an empty proof has an encoded size, so the limit is exceeded from the start.
+		let err = prove_execution_on_trie_backend_with_size_limit::<_, _, u64, _, _>(
+			&remote_backend,
+			&mut Default::default(),
+			&executor,
+			TaskExecutor::new(),
+			"test",
+			&[],
+			&RuntimeCode::empty(),
+			0,
+		).unwrap_err();
+
+		assert_eq!(format!("{:?}", err), format!("{:?}", ExecutionError::ProofSizeExceeded));
+	}
+
+	#[test]
+	fn replay_reports_first_divergent_key() {
+		let backend = trie_backend::tests::test_trie();
+		let wasm_code = RuntimeCode::empty();
+		let executor = DummyCodeExecutor {
+			change_changes_trie_config: true,
+			native_available: false,
+			native_succeeds: false,
+			fallback_succeeds: true,
+		};
+
+		let mut overlayed_changes = OverlayedChanges::default();
+		StateMachine::new(
+			&backend,
+			changes_trie::disabled_state::<_, u64>(),
+			&mut overlayed_changes,
+			&executor,
+			"test",
+			&[],
+			Default::default(),
+			&wasm_code,
+			TaskExecutor::new(),
+		).execute(ExecutionStrategy::AlwaysWasm).unwrap();
+		let recorded = overlayed_changes.export();
+
+		let mut replay = |expected: &ExportedChanges| {
+			let mut overlayed_changes = OverlayedChanges::default();
+			let divergent = StateMachine::new(
+				&backend,
+				changes_trie::disabled_state::<_, u64>(),
+				&mut overlayed_changes,
+				&executor,
+				"test",
+				&[],
+				Default::default(),
+				&wasm_code,
+				TaskExecutor::new(),
+			).replay(ExecutionStrategy::AlwaysWasm, expected).unwrap();
+			divergent
+		};
+
+		assert_eq!(replay(&recorded), None);
+		assert_eq!(
+			replay(&Default::default()),
+			Some(DivergentKey {
+				child_storage_key: None,
+				key: sp_core::storage::well_known_keys::CHANGES_TRIE_CONFIG.to_vec(),
+			}),
+		);
+	}
+
+	#[test]
+	fn execute_with_limits_works() {
+		let executor = DummyCodeExecutor {
+			change_changes_trie_config: false,
+			native_available: true,
+			native_succeeds: true,
+			fallback_succeeds: true,
+		};
+		let backend = trie_backend::tests::test_trie();
+
+		let (result, usage) = execute_with_limits::<_, _, u64, _, _>(
+			&backend,
+			None,
+			&mut Default::default(),
+			&executor,
+			TaskExecutor::new(),
+			"test",
+			&[],
+			Default::default(),
+			&RuntimeCode::empty(),
+			ExecutionStrategy::NativeWhenPossible,
+			ExecutionLimits { max_reads: Some(2), ..Default::default() },
+		).unwrap();
+
+		assert_eq!(result, vec![66]);
+		assert_eq!(usage.reads, 2);
+		assert_eq!(usage.read_bytes, 14);
+	}
+
 	#[test]
 	fn clear_prefix_in_ext_works() {
 		let initial: BTreeMap<_, _> = map![
@@ -1530,6 +2072,59 @@ mod tests {
 		compact_remote_proof.to_storage_proof::<BlakeTwo256>(Some(remote_root)).unwrap().0
 	}
 
+	#[test]
+	fn prove_read_chunks_and_stream_check_works() {
+		let remote_backend = trie_backend::tests::test_trie();
+		let remote_root = remote_backend.storage_root(std::iter::empty()).0;
+		let keys: Vec<Vec<u8>> = vec![
+			b"key".to_vec(),
+			b"value1".to_vec(),
+			b"value2".to_vec(),
+			vec![0xff],
+		];
+
+		// every key gets its own chunk with a chunk size of one byte
+		let chunks = prove_read_chunks_on_trie_backend(&remote_backend, &keys, 1)
+			.collect::<Result<Vec<_>, _>>()
+			.unwrap();
+		assert_eq!(chunks.len(), keys.len());
+
+		let regenerated = prove_read_chunks_on_trie_backend(&remote_backend, &keys, 1)
+			.collect::<Result<Vec<_>, _>>()
+			.unwrap();
+		assert_eq!(chunks, regenerated);
+
+		let expected = read_proof_check::<BlakeTwo256, _>(
+			remote_root,
+			prove_read(trie_backend::tests::test_trie(), &keys).unwrap(),
+			&keys,
+		).unwrap();
+		let checked = read_proof_check_stream::<BlakeTwo256, _, _>(
+			remote_root,
+			chunks.clone(),
+			&keys,
+		).collect::<Result<Vec<_>, _>>().unwrap();
+		assert_eq!(checked.len(), keys.len());
+		assert!(checked.iter().all(|(key, value)| expected.get(key) == Some(value)));
+
+		// a single chunk covers all keys with a large chunk size
+		let chunks = prove_read_chunks_on_trie_backend(&remote_backend, &keys, 1024 * 1024)
+			.collect::<Result<Vec<_>, _>>()
+			.unwrap();
+		assert_eq!(chunks.len(), 1);
+
+		// missing chunks are detected
+		let keys = vec![b"key".to_vec(), vec![0xff]];
+		let chunks = prove_read_chunks_on_trie_backend(&remote_backend, &keys, 1)
+			.take(1)
+			.collect::<Result<Vec<_>, _>>()
+			.unwrap();
+		let checked = read_proof_check_stream::<BlakeTwo256, _, _>(remote_root, chunks, &keys)
+			.collect::<Vec<_>>();
+		assert!(checked[0].is_ok());
+		assert!(checked[1].is_err());
+	}
+
 	#[test]
 	fn prove_read_and_proof_check_works() {
 		let child_info = ChildInfo::new_default(b"sub1");
@@ -1704,7 +2299,10 @@ mod tests {
 			ext.set_child_storage(&child_info_1, b"abc".to_vec(), b"def".to_vec());
 			ext.set_child_storage(&child_info_2, b"abc".to_vec(), b"def".to_vec());
 			ext.storage_root();
-			cache.transaction.unwrap()
+			drop(ext);
+			overlay.drain_storage_changes(&backend, None, Default::default(), &mut cache)
+				.unwrap()
+				.transaction
 		};
 		let mut duplicate = false;
 		for (k, (value, rc)) in transaction.drain().iter() {
@@ -0,0 +1,358 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! State backend that bounds the state read by a call.
+
+use std::{
+	marker::PhantomData,
+	sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering},
+	time::{Duration, Instant},
+};
+use hash_db::Hasher;
+use sp_core::storage::ChildInfo;
+use crate::{Backend, StorageKey, StorageValue, UsageInfo, StateMachineStats};
+
+/// Limits on the state read by a call and on the duration of the call.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutionLimits {
+	/// Maximal number of storage reads, including every key visited while iterating.
+	pub max_reads: Option<u32>,
+	/// Maximal number of bytes of keys and values read from storage.
+	///
+	/// Reads of a value hash or size count the key and the returned hash or size, whether or
+	/// not the wrapped backend answers them without reading the value.
+	pub max_read_bytes: Option<u64>,
+	/// Maximal duration of the call.
+	///
+	/// The duration is only checked on storage reads, so it does not bound calls that stop
+	/// reading storage.
+	pub max_duration: Option<Duration>,
+}
+
+/// State read by a call so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExecutionUsage {
+	/// Number of storage reads.
+	pub reads: u32,
+	/// Number of bytes of keys and values read from storage.
+	pub read_bytes: u64,
+	/// Time elapsed since the backend was created.
+	pub duration: Duration,
+}
+
+/// Wrapper around a backend which counts the reads from it and fails all of them once the
+/// [`ExecutionLimits`] are exceeded.
+pub struct MeteredBackend<'a, H: Hasher, B: Backend<H>> {
+	backend: &'a B,
+	limits: ExecutionLimits,
+	started: Instant,
+	reads: AtomicU32,
+	read_bytes: AtomicU64,
+	exceeded: AtomicBool,
+	_phantom: PhantomData<H>,
+}
+
+impl<'a, H: Hasher, B: Backend<H>> MeteredBackend<'a, H, B> {
+	/// Create a new metered backend reading from `backend`.
+	pub fn new(backend: &'a B, limits: ExecutionLimits) -> Self {
+		MeteredBackend {
+			backend,
+			limits,
+			started: Instant::now(),
+			reads: AtomicU32::new(0),
+			read_bytes: AtomicU64::new(0),
+			exceeded: AtomicBool::new(false),
+			_phantom: PhantomData,
+		}
+	}
+
+	/// Returns the state read so far.
+	pub fn usage(&self) -> ExecutionUsage {
+		ExecutionUsage {
+			reads: self.reads.load(Ordering::Relaxed),
+			read_bytes: self.read_bytes.load(Ordering::Relaxed),
+			duration: self.started.elapsed(),
+		}
+	}
+
+	/// Returns `true` if a read went beyond the limits of this backend.
+	pub fn limits_exceeded(&self) -> bool {
+		self.exceeded.load(Ordering::Relaxed)
+	}
+
+	/// Account for a read of `bytes` bytes, failing if the limits are exceeded.
+	fn meter(&self, bytes: usize) -> Result<(), String> {
+		if self.limits_exceeded() {
+			return Err("Execution limits exceeded".into())
+		}
+
+		let reads = self.reads.fetch_add(1, Ordering::Relaxed).saturating_add(1);
+		let read_bytes = self.read_bytes.fetch_add(bytes as u64, Ordering::Relaxed)
+			.saturating_add(bytes as u64);
+		let exceeded = self.limits.max_reads.map_or(false, |max| reads > max)
+			|| self.limits.max_read_bytes.map_or(false, |max| read_bytes > max)
+			|| self.limits.max_duration.map_or(false, |max| self.started.elapsed() > max);
+
+		if exceeded {
+			self.exceeded.store(true, Ordering::Relaxed);
+			return Err("Execution limits exceeded".into())
+		}
+		Ok(())
+	}
+
+	fn meter_value(&self, key: &[u8], value: Option<&[u8]>) -> Result<(), String> {
+		self.meter(key.len() + value.map_or(0, |v| v.len()))
+	}
+}
+
+impl<'a, H: Hasher, B: Backend<H>> std::fmt::Debug for MeteredBackend<'a, H, B> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "MeteredBackend({:?})", self.backend)
+	}
+}
+
+impl<'a, H, B> Backend<H> for MeteredBackend<'a, H, B>
+	where
+		H: Hasher,
+		B: Backend<H>,
+{
+	type Error = String;
+	type Transaction = B::Transaction;
+	type TrieBackendStorage = B::TrieBackendStorage;
+
+	fn storage(&self, key: &[u8]) -> Result<Option<StorageValue>, Self::Error> {
+		let value = self.backend.storage(key).map_err(|e| e.to_string())?;
+		self.meter_value(key, value.as_deref())?;
+		Ok(value)
+	}
+
+	fn storage_hash(&self, key: &[u8]) -> Result<Option<H::Out>, Self::Error> {
+		let hash = self.backend.storage_hash(key).map_err(|e| e.to_string())?;
+		self.meter_value(key, hash.as_ref().map(|h| h.as_ref()))?;
+		Ok(hash)
+	}
+
+	fn storage_size(&self, key: &[u8]) -> Result<Option<u32>, Self::Error> {
+		let size = self.backend.storage_size(key).map_err(|e| e.to_string())?;
+		self.meter(key.len() + size.map_or(0, |_| std::mem::size_of::<u32>()))?;
+		Ok(size)
+	}
+
+	fn exists_storage(&self, key: &[u8]) -> Result<bool, Self::Error> {
+		let exists = self.backend.exists_storage(key).map_err(|e| e.to_string())?;
+		self.meter(key.len())?;
+		Ok(exists)
+	}
+
+	fn child_storage(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<StorageValue>, Self::Error> {
+		let value = self.backend.child_storage(child_info, key).map_err(|e| e.to_string())?;
+		self.meter_value(key, value.as_deref())?;
+		Ok(value)
+	}
+
+	fn child_storage_hash(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<H::Out>, Self::Error> {
+		let hash = self.backend.child_storage_hash(child_info, key).map_err(|e| e.to_string())?;
+		self.meter_value(key, hash.as_ref().map(|h| h.as_ref()))?;
+		Ok(hash)
+	}
+
+	fn child_storage_size(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<u32>, Self::Error> {
+		let size = self.backend.child_storage_size(child_info, key).map_err(|e| e.to_string())?;
+		self.meter(key.len() + size.map_or(0, |_| std::mem::size_of::<u32>()))?;
+		Ok(size)
+	}
+
+	fn exists_child_storage(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<bool, Self::Error> {
+		let exists = self.backend.exists_child_storage(child_info, key)
+			.map_err(|e| e.to_string())?;
+		self.meter(key.len())?;
+		Ok(exists)
+	}
+
+	fn next_storage_key(&self, key: &[u8]) -> Result<Option<StorageKey>, Self::Error> {
+		let next = self.backend.next_storage_key(key).map_err(|e| e.to_string())?;
+		self.meter(next.as_ref().map_or(0, |k| k.len()))?;
+		Ok(next)
+	}
+
+	fn next_child_storage_key(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<StorageKey>, Self::Error> {
+		let next = self.backend.next_child_storage_key(child_info, key)
+			.map_err(|e| e.to_string())?;
+		self.meter(next.as_ref().map_or(0, |k| k.len()))?;
+		Ok(next)
+	}
+
+	fn apply_to_key_values_while<F: FnMut(Vec<u8>, Vec<u8>) -> bool>(
+		&self,
+		child_info: Option<&ChildInfo>,
+		prefix: Option<&[u8]>,
+		start_at: Option<&[u8]>,
+		mut f: F,
+		allow_missing: bool,
+	) -> Result<bool, Self::Error> {
+		let completed = self.backend.apply_to_key_values_while(
+			child_info,
+			prefix,
+			start_at,
+			|k, v| self.meter_value(&k, Some(&v[..])).is_ok() && f(k, v),
+			allow_missing,
+		).map_err(|e| e.to_string())?;
+
+		if self.limits_exceeded() {
+			return Err("Execution limits exceeded".into())
+		}
+		Ok(completed)
+	}
+
+	fn apply_to_keys_while<F: FnMut(&[u8]) -> bool>(
+		&self,
+		child_info: Option<&ChildInfo>,
+		prefix: Option<&[u8]>,
+		mut f: F,
+	) {
+		self.backend.apply_to_keys_while(
+			child_info,
+			prefix,
+			|k| self.meter(k.len()).is_ok() && f(k),
+		)
+	}
+
+	fn for_keys_with_prefix<F: FnMut(&[u8])>(&self, prefix: &[u8], mut f: F) {
+		self.apply_to_keys_while(None, Some(prefix), |k| { f(k); true })
+	}
+
+	fn for_key_values_with_prefix<F: FnMut(&[u8], &[u8])>(&self, prefix: &[u8], mut f: F) {
+		let _ = self.apply_to_key_values_while(
+			None,
+			Some(prefix),
+			None,
+			|k, v| { f(&k, &v); true },
+			true,
+		);
+	}
+
+	fn for_child_keys_with_prefix<F: FnMut(&[u8])>(
+		&self,
+		child_info: &ChildInfo,
+		prefix: &[u8],
+		mut f: F,
+	) {
+		self.apply_to_keys_while(Some(child_info), Some(prefix), |k| { f(k); true })
+	}
+
+	fn pairs(&self) -> Vec<(StorageKey, StorageValue)> {
+		self.backend.pairs()
+			.into_iter()
+			.take_while(|(k, v)| self.meter_value(k, Some(&v[..])).is_ok())
+			.collect()
+	}
+
+	fn storage_root<'b>(
+		&self,
+		delta: impl Iterator<Item=(&'b [u8], Option<&'b [u8]>)>,
+	) -> (H::Out, Self::Transaction) where H::Out: Ord {
+		self.backend.storage_root(delta)
+	}
+
+	fn child_storage_root<'b>(
+		&self,
+		child_info: &ChildInfo,
+		delta: impl Iterator<Item=(&'b [u8], Option<&'b [u8]>)>,
+	) -> (H::Out, bool, Self::Transaction) where H::Out: Ord {
+		self.backend.child_storage_root(child_info, delta)
+	}
+
+	fn register_overlay_stats(&self, stats: &StateMachineStats) {
+		self.backend.register_overlay_stats(stats)
+	}
+
+	fn usage_info(&self) -> UsageInfo {
+		self.backend.usage_info()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::trie_backend::tests::test_trie;
+
+	#[test]
+	fn reads_fail_once_limits_are_exceeded() {
+		let trie_backend = test_trie();
+		let limits = ExecutionLimits { max_reads: Some(2), ..Default::default() };
+		let backend = MeteredBackend::new(&trie_backend, limits);
+
+		assert_eq!(backend.storage(b"key").unwrap(), Some(b"value".to_vec()));
+		assert_eq!(backend.storage(b"value1").unwrap(), Some(vec![42]));
+		assert!(!backend.limits_exceeded());
+		assert!(backend.storage(b"value2").is_err());
+		assert!(backend.limits_exceeded());
+		// Once exceeded, all reads fail.
+		assert!(backend.next_storage_key(b"key").is_err());
+		assert_eq!(backend.usage().reads, 3);
+
+		let limits = ExecutionLimits { max_read_bytes: Some(8), ..Default::default() };
+		let backend = MeteredBackend::new(&trie_backend, limits);
+		assert!(backend.storage(b"key").is_ok());
+		assert_eq!(backend.usage().read_bytes, 8);
+		assert!(backend.storage(b"key").is_err());
+	}
+
+	#[test]
+	fn hash_and_size_reads_are_metered() {
+		let trie_backend = test_trie();
+		let backend = MeteredBackend::new(&trie_backend, Default::default());
+
+		assert_eq!(backend.storage_size(b"key").unwrap(), Some(5));
+		assert!(backend.storage_hash(b"key").unwrap().is_some());
+		assert!(backend.exists_storage(b"key").unwrap());
+		assert_eq!(backend.usage().reads, 3);
+		assert_eq!(backend.usage().read_bytes, 3 + 4 + 3 + 32 + 3);
+	}
+
+	#[test]
+	fn iteration_stops_once_limits_are_exceeded() {
+		let trie_backend = test_trie();
+		let all_keys = trie_backend.keys(b"");
+		let limits = ExecutionLimits { max_reads: Some(2), ..Default::default() };
+		let backend = MeteredBackend::new(&trie_backend, limits);
+
+		assert!(all_keys.len() > 2);
+		assert_eq!(backend.keys(b""), all_keys[..2].to_vec());
+		assert!(backend.limits_exceeded());
+	}
+}
@@ -22,7 +22,7 @@ mod offchain;
 
 pub use offchain::OffchainOverlayedChanges;
 use crate::{
-	backend::Backend,
+	backend::{Backend, Consolidate},
 	stats::StateMachineStats,
 };
 use sp_std::{vec::Vec, any::{TypeId, Any}, boxed::Box};
@@ -41,7 +41,7 @@ use crate::changes_trie::BlockNumber;
 use std::collections::{HashMap as Map, hash_map::Entry as MapEntry};
 #[cfg(not(feature = "std"))]
 use sp_std::collections::btree_map::{BTreeMap as Map, Entry as MapEntry};
-use sp_std::collections::btree_set::BTreeSet;
+use sp_std::collections::{btree_set::BTreeSet, btree_map::BTreeMap};
 use codec::{Decode, Encode};
 use sp_core::storage::{well_known_keys::EXTRINSIC_INDEX, ChildInfo};
 use sp_core::offchain::OffchainOverlayedChange;
@@ -109,6 +109,66 @@ pub struct OverlayedChanges {
 	collect_extrinsics: bool,
 	/// Collect statistic on this execution.
 	stats: StateMachineStats,
+	/// The number of transactions rolled back so far.
+	rolled_back: u64,
+}
+
+/// A change exported by [`OverlayedChanges::export`]: the key, its new value (`None` if it is
+/// removed) and the indices of the extrinsics which changed it.
+pub type ExportedChange = (StorageKey, Option<StorageValue>, Vec<u32>);
+
+/// SCALE encodable copy of the changes of an [`OverlayedChanges`].
+///
+/// Created with [`OverlayedChanges::export`] and applied to another overlay with
+/// [`OverlayedChanges::import`].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct ExportedChanges {
+	/// Changes to the top level storage, ordered by key.
+	pub top: Vec<ExportedChange>,
+	/// Changes to the default child tries, by child storage key without the common prefix.
+	/// Ordered by child storage key.
+	pub children: Vec<(StorageKey, Vec<ExportedChange>)>,
+}
+
+/// A key whose change differs between two [`ExportedChanges`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DivergentKey {
+	/// Storage key of the child trie of the key, `None` for the top level storage.
+	pub child_storage_key: Option<StorageKey>,
+	/// The key.
+	pub key: StorageKey,
+}
+
+impl ExportedChanges {
+	/// Returns the first key whose change differs between `self` and `other`, either in value or
+	/// in the extrinsics which made it.
+	///
+	/// The top level storage is compared first, then the child tries in order.
+	pub fn first_divergent_key(&self, other: &Self) -> Option<DivergentKey> {
+		type Flattened<'a> = BTreeMap<
+			(Option<&'a [u8]>, &'a [u8]),
+			(&'a Option<StorageValue>, &'a Vec<u32>),
+		>;
+
+		fn flatten(changes: &ExportedChanges) -> Flattened<'_> {
+			let top = changes.top.iter().map(|(k, v, e)| ((None, &k[..]), (v, e)));
+			let children = changes.children.iter().flat_map(|(child, changes)|
+				changes.iter().map(move |(k, v, e)| ((Some(&child[..]), &k[..]), (v, e)))
+			);
+			top.chain(children).collect()
+		}
+
+		let ours = flatten(self);
+		let theirs = flatten(other);
+		ours.keys()
+			.chain(theirs.keys())
+			.filter(|key| ours.get(*key) != theirs.get(*key))
+			.min()
+			.map(|(child, key)| DivergentKey {
+				child_storage_key: child.map(|c| c.to_vec()),
+				key: key.to_vec(),
+			})
+	}
 }
 
 /// Transcation index operation.
@@ -193,11 +253,19 @@ impl<Transaction, H: Hasher, N: BlockNumber> StorageChanges<Transaction, H, N> {
 /// The storage transaction are calculated as part of the `storage_root` and
 /// `changes_trie_storage_root`. These transactions can be reused for importing the block into the
 /// storage. So, we cache them to not require a recomputation of those transactions.
+///
+/// The roots of the child tries are cached separately, so that they are only recomputed when the
+/// child trie changes.
 pub struct StorageTransactionCache<Transaction, H: Hasher, N: BlockNumber> {
-	/// Contains the changes for the main and the child storages as one transaction.
+	/// Contains the changes for the main storage. The changes of the child storages are in
+	/// `child_roots`, and are only merged into it when the changes are drained.
 	pub(crate) transaction: Option<Transaction>,
-	/// The storage root after applying the transaction.
+	/// The storage root after applying the transaction and the child storage changes.
 	pub(crate) transaction_storage_root: Option<H::Out>,
+	/// The root of every changed child trie, whether it is empty and its changes, by storage key.
+	pub(crate) child_roots: BTreeMap<StorageKey, (H::Out, bool, Transaction)>,
+	/// The number of transactions rolled back in the overlay when the cache was filled.
+	pub(crate) rolled_back: u64,
 	/// Contains the changes trie transaction.
 	#[cfg(feature = "std")]
 	pub(crate) changes_trie_transaction: Option<Option<ChangesTrieTransaction<H, N>>>,
@@ -212,7 +280,26 @@ pub struct StorageTransactionCache<Transaction, H: Hasher, N: BlockNumber> {
 impl<Transaction, H: Hasher, N: BlockNumber> StorageTransactionCache<Transaction, H, N> {
 	/// Reset the cached transactions.
 	pub fn reset(&mut self) {
-		*self = Self::default();
+		*self = Self { rolled_back: self.rolled_back, ..Self::default() };
+	}
+
+	/// Reset the cached transactions of the main storage, after a change of the main storage.
+	pub(crate) fn reset_top(&mut self) {
+		self.transaction = None;
+		self.transaction_storage_root = None;
+		#[cfg(feature = "std")]
+		{
+			self.changes_trie_transaction = None;
+			self.changes_trie_transaction_storage_root = None;
+		}
+	}
+
+	/// Reset the cached transactions after a change of the child storage `storage_key`.
+	///
+	/// The main storage holds the root of the child trie, so its transaction is reset as well.
+	pub(crate) fn reset_child(&mut self, storage_key: &[u8]) {
+		self.reset_top();
+		self.child_roots.remove(storage_key);
 	}
 }
 
@@ -221,6 +308,8 @@ impl<Transaction, H: Hasher, N: BlockNumber> Default for StorageTransactionCache
 		Self {
 			transaction: None,
 			transaction_storage_root: None,
+			child_roots: BTreeMap::new(),
+			rolled_back: 0,
 			#[cfg(feature = "std")]
 			changes_trie_transaction: None,
 			#[cfg(feature = "std")]
@@ -423,6 +512,7 @@ impl OverlayedChanges {
 		});
 		self.offchain.overlay_mut().rollback_transaction()
 			.expect("Top and offchain changesets are started in lockstep; qed");
+		self.rolled_back += 1;
 		Ok(())
 	}
 
@@ -520,6 +610,56 @@ impl OverlayedChanges {
 		self.children.get(key).map(|(overlay, info)| (overlay.changes(), info))
 	}
 
+	/// Export all changes as seen by the current transaction, together with the extrinsics which
+	/// made them.
+	pub fn export(&self) -> ExportedChanges {
+		fn export_set(changeset: &OverlayedChangeSet) -> Vec<ExportedChange> {
+			changeset.changes()
+				.map(|(k, v)| (k.clone(), v.value().cloned(), v.extrinsics().into_iter().collect()))
+				.collect()
+		}
+
+		let mut children = self.children.iter()
+			.map(|(storage_key, (changeset, _))| (storage_key.clone(), export_set(changeset)))
+			.collect::<Vec<_>>();
+		children.sort_by(|a, b| a.0.cmp(&b.0));
+
+		ExportedChanges {
+			top: export_set(&self.top),
+			children,
+		}
+	}
+
+	/// Apply changes exported by [`Self::export`] on top of the changes of `self`, keeping the
+	/// extrinsics which made them.
+	///
+	/// Can be rolled back or committed when called inside a transaction.
+	pub fn import(&mut self, changes: ExportedChanges) {
+		fn import_set(changeset: &mut OverlayedChangeSet, changes: Vec<ExportedChange>) {
+			for (key, value, extrinsics) in changes {
+				if extrinsics.is_empty() {
+					changeset.set(key, value, None);
+					continue;
+				}
+				for extrinsic in extrinsics {
+					changeset.set(key.clone(), value.clone(), Some(extrinsic));
+				}
+			}
+		}
+
+		import_set(&mut self.top, changes.top);
+		for (storage_key, child_changes) in changes.children {
+			let top = &self.top;
+			let (changeset, _) = self.children.entry(storage_key.clone()).or_insert_with(||
+				(
+					top.spawn_child(),
+					ChildInfo::new_default_from_vec(storage_key),
+				)
+			);
+			import_set(changeset, child_changes);
+		}
+	}
+
 	/// Get an list of all index operations.
 	pub fn transaction_index_ops(&self) -> &[IndexOperation] {
 		&self.transaction_index_ops
@@ -550,14 +690,19 @@ impl OverlayedChanges {
 		mut cache: &mut StorageTransactionCache<B::Transaction, H, N>,
 	) -> Result<StorageChanges<B::Transaction, H, N>, DefaultError>
 		where H::Out: Ord + Encode + 'static {
+		self.sync_cache(cache);
+
 		// If the transaction does not exist, we generate it.
 		if cache.transaction.is_none() {
 			self.storage_root(backend, &mut cache);
 		}
 
-		let (transaction, transaction_storage_root) = cache.transaction.take()
+		let (mut transaction, transaction_storage_root) = cache.transaction.take()
 			.and_then(|t| cache.transaction_storage_root.take().map(|tr| (t, tr)))
 			.expect("Transaction was be generated as part of `storage_root`; qed");
+		for (_, (_, _, child_transaction)) in sp_std::mem::take(&mut cache.child_roots) {
+			transaction.consolidate(child_transaction);
+		}
 
 		// If the transaction does not exist, we generate it.
 		#[cfg(feature = "std")]
@@ -630,13 +775,33 @@ impl OverlayedChanges {
 	) -> H::Out
 		where H::Out: Ord + Encode,
 	{
-		let delta = self.changes().map(|(k, v)| (&k[..], v.value().map(|v| &v[..])));
-		let child_delta = self.children()
-			.map(|(changes, info)| (info, changes.map(
-				|(k, v)| (&k[..], v.value().map(|v| &v[..]))
-			)));
-
-		let (root, transaction) = backend.full_storage_root(delta, child_delta);
+		self.sync_cache(cache);
+
+		// Only the child tries which changed since their root was computed are recomputed.
+		let (dirty_keys, dirty_deltas): (Vec<_>, Vec<_>) = self.children()
+			.filter(|(_, info)| !cache.child_roots.contains_key(info.storage_key()))
+			.map(|(changes, info)| (
+				info.storage_key().to_vec(),
+				(info, changes.map(|(k, v)| (&k[..], v.value().map(|v| &v[..])))),
+			))
+			.unzip();
+		let dirty_roots = backend.child_storage_roots(dirty_deltas.into_iter());
+		cache.child_roots.extend(dirty_keys.into_iter().zip(dirty_roots));
+
+		let child_roots = self.children()
+			.map(|(_, info)| {
+				let (root, is_empty, _) = cache.child_roots
+					.get(info.storage_key())
+					.expect("Roots of all the changed child tries were cached above; qed");
+				let root = if *is_empty { None } else { Some(root.encode()) };
+				(info.prefixed_storage_key().into_inner(), root)
+			})
+			.collect::<Vec<_>>();
+		let delta = self.changes()
+			.map(|(k, v)| (&k[..], v.value().map(|v| &v[..])))
+			.chain(child_roots.iter().map(|(k, v)| (&k[..], v.as_ref().map(|v| &v[..]))));
+
+		let (root, transaction) = backend.storage_root(delta);
 
 		cache.transaction = Some(transaction);
 		cache.transaction_storage_root = Some(root);
@@ -644,6 +809,44 @@ impl OverlayedChanges {
 		root
 	}
 
+	/// Generate the root of the child trie `child_info`, and whether it is empty.
+	///
+	/// The root is reused from the given `cache` if the child trie did not change since it was
+	/// last computed, and cached otherwise. Returns `None` if the child trie has no changes.
+	pub fn child_storage_root<H: Hasher, N: BlockNumber, B: Backend<H>>(
+		&self,
+		backend: &B,
+		child_info: &ChildInfo,
+		cache: &mut StorageTransactionCache<B::Transaction, H, N>,
+	) -> Option<(H::Out, bool)>
+		where H::Out: Ord + Encode,
+	{
+		self.sync_cache(cache);
+
+		let storage_key = child_info.storage_key();
+		if let Some((root, is_empty, _)) = cache.child_roots.get(storage_key) {
+			return Some((*root, *is_empty))
+		}
+
+		let (changes, info) = self.child_changes(storage_key)?;
+		let delta = changes.map(|(k, v)| (&k[..], v.value().map(|v| &v[..])));
+		let (root, is_empty, transaction) = backend.child_storage_root(info, delta);
+		cache.child_roots.insert(storage_key.to_vec(), (root, is_empty, transaction));
+		Some((root, is_empty))
+	}
+
+	/// Reset the given `cache` if a transaction was rolled back since it was filled, as the
+	/// cached roots may include the discarded changes.
+	pub(crate) fn sync_cache<Transaction, H: Hasher, N: BlockNumber>(
+		&self,
+		cache: &mut StorageTransactionCache<Transaction, H, N>,
+	) {
+		if cache.rolled_back != self.rolled_back {
+			cache.reset();
+			cache.rolled_back = self.rolled_back;
+		}
+	}
+
 	/// Generate the changes trie root.
 	///
 	/// Returns the changes trie root and caches the storage transaction into the given `cache`.
@@ -660,6 +863,7 @@ impl OverlayedChanges {
 		panic_on_storage_error: bool,
 		cache: &mut StorageTransactionCache<B::Transaction, H, N>,
 	) -> Result<Option<H::Out>, ()> where H::Out: Ord + Encode + 'static {
+		self.sync_cache(cache);
 		build_changes_trie::<_, H, N>(
 			backend,
 			changes_trie_state,
@@ -981,6 +1185,45 @@ mod tests {
 		assert_extrinsics(&overlay.top, vec![100], vec![NO_EXTRINSIC_INDEX]);
 	}
 
+	#[test]
+	fn export_and_import_works() {
+		let child_info = ChildInfo::new_default(b"child");
+		let mut overlay = OverlayedChanges::default();
+		overlay.set_collect_extrinsics(true);
+		overlay.set_extrinsic_index(0);
+		overlay.set_storage(vec![1], Some(vec![2]));
+		overlay.set_child_storage(&child_info, vec![3], Some(vec![4]));
+		overlay.set_extrinsic_index(1);
+		overlay.set_storage(vec![1], Some(vec![5]));
+		overlay.set_storage(vec![6], None);
+
+		let exported = overlay.export();
+		assert_eq!(exported.children, vec![(b"child".to_vec(), vec![(vec![3], Some(vec![4]), vec![0])])]);
+		assert!(exported.top.contains(&(vec![1], Some(vec![5]), vec![0, 1])));
+		assert!(exported.top.contains(&(vec![6], None, vec![1])));
+
+		let decoded = ExportedChanges::decode(&mut &exported.encode()[..]).unwrap();
+		let mut imported = OverlayedChanges::default();
+		imported.import(decoded);
+		assert_eq!(imported.export(), exported);
+		assert_eq!(imported.child_storage(&child_info, &[3]), Some(Some(&[4][..])));
+		assert_eq!(exported.first_divergent_key(&imported.export()), None);
+
+		imported.set_child_storage(&child_info, vec![3], Some(vec![7]));
+		imported.set_storage(vec![8], Some(vec![9]));
+		assert_eq!(
+			exported.first_divergent_key(&imported.export()),
+			Some(DivergentKey { child_storage_key: None, key: vec![8] }),
+		);
+		imported.set_storage(vec![8], None);
+		let mut exported_without_8 = imported.export();
+		exported_without_8.top.retain(|(k, _, _)| k != &vec![8]);
+		assert_eq!(
+			exported.first_divergent_key(&exported_without_8),
+			Some(DivergentKey { child_storage_key: Some(b"child".to_vec()), key: vec![3] }),
+		);
+	}
+
 	#[test]
 	fn next_storage_key_change_works() {
 		let mut overlay = OverlayedChanges::default();
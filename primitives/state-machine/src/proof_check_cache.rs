@@ -0,0 +1,118 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cache of the results of checked execution proofs.
+
+use hash_db::Hasher;
+use lru::LruCache;
+use parking_lot::Mutex;
+
+/// Key of a checked execution: the state root, the hash of the runtime code, the method and the
+/// hash of the call data.
+type CacheKey<H> = (<H as Hasher>::Out, Vec<u8>, String, <H as Hasher>::Out);
+
+/// Bounded cache of the results of the execution proofs checked by
+/// [`execution_proof_check_cached`](crate::execution_proof_check_cached).
+///
+/// A call is deterministic given the state and the runtime code, so once a proof of it has been
+/// checked against a state root, any other proof of the same call against that root yields the
+/// same result. Checking it again is only needed to detect an invalid proof, which light clients
+/// verifying the same call repeatedly do not care about.
+pub struct ExecutionProofCheckCache<H: Hasher> {
+	results: Mutex<LruCache<CacheKey<H>, Vec<u8>>>,
+}
+
+impl<H: Hasher> ExecutionProofCheckCache<H> {
+	/// Create a cache holding the results of at most `capacity` calls.
+	pub fn new(capacity: usize) -> Self {
+		ExecutionProofCheckCache { results: Mutex::new(LruCache::new(capacity)) }
+	}
+
+	/// The cached result of calling `method` with `call_data` on the state of `root`.
+	pub fn get(
+		&self,
+		root: &H::Out,
+		code_hash: &[u8],
+		method: &str,
+		call_data: &[u8],
+	) -> Option<Vec<u8>> {
+		let key = (*root, code_hash.to_vec(), method.to_owned(), H::hash(call_data));
+		self.results.lock().get(&key).cloned()
+	}
+
+	/// Cache the result of calling `method` with `call_data` on the state of `root`.
+	pub fn insert(
+		&self,
+		root: H::Out,
+		code_hash: &[u8],
+		method: &str,
+		call_data: &[u8],
+		result: Vec<u8>,
+	) {
+		let key = (root, code_hash.to_vec(), method.to_owned(), H::hash(call_data));
+		self.results.lock().put(key, result);
+	}
+
+	/// The number of cached results.
+	pub fn len(&self) -> usize {
+		self.results.lock().len()
+	}
+
+	/// Whether no result is cached.
+	pub fn is_empty(&self) -> bool {
+		self.results.lock().is_empty()
+	}
+
+	/// Remove all the cached results.
+	pub fn clear(&self) {
+		self.results.lock().clear()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::{Blake2Hasher, H256};
+
+	#[test]
+	fn results_are_keyed_by_the_whole_call() {
+		let cache = ExecutionProofCheckCache::<Blake2Hasher>::new(2);
+		let root = H256::repeat_byte(1);
+		cache.insert(root, b"code", "test", &[1], vec![42]);
+
+		assert_eq!(cache.get(&root, b"code", "test", &[1]), Some(vec![42]));
+		assert_eq!(cache.get(&H256::repeat_byte(2), b"code", "test", &[1]), None);
+		assert_eq!(cache.get(&root, b"other code", "test", &[1]), None);
+		assert_eq!(cache.get(&root, b"code", "other", &[1]), None);
+		assert_eq!(cache.get(&root, b"code", "test", &[2]), None);
+	}
+
+	#[test]
+	fn least_recently_used_result_is_evicted() {
+		let cache = ExecutionProofCheckCache::<Blake2Hasher>::new(2);
+		let root = H256::repeat_byte(1);
+		cache.insert(root, b"code", "test", &[1], vec![1]);
+		cache.insert(root, b"code", "test", &[2], vec![2]);
+		assert!(cache.get(&root, b"code", "test", &[1]).is_some());
+		cache.insert(root, b"code", "test", &[3], vec![3]);
+
+		assert_eq!(cache.len(), 2);
+		assert_eq!(cache.get(&root, b"code", "test", &[1]), Some(vec![1]));
+		assert_eq!(cache.get(&root, b"code", "test", &[2]), None);
+		assert_eq!(cache.get(&root, b"code", "test", &[3]), Some(vec![3]));
+	}
+}
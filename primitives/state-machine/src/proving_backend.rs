@@ -183,6 +183,9 @@ pub struct ProvingBackend<'a, S: 'a + TrieBackendStorage<H>, H: 'a + Hasher> (
 pub struct ProofRecorderBackend<'a, S: 'a + TrieBackendStorage<H>, H: 'a + Hasher> {
 	backend: &'a S,
 	proof_recorder: ProofRecorder<H::Out>,
+	/// Maximal estimated encoded size of the recorded proof. Once it is exceeded every read fails,
+	/// including reads of already recorded nodes.
+	size_limit: Option<usize>,
 }
 
 impl<'a, S: 'a + TrieBackendStorage<H>, H: 'a + Hasher> ProvingBackend<'a, S, H>
@@ -198,12 +201,27 @@ impl<'a, S: 'a + TrieBackendStorage<H>, H: 'a + Hasher> ProvingBackend<'a, S, H>
 	pub fn new_with_recorder(
 		backend: &'a TrieBackend<S, H>,
 		proof_recorder: ProofRecorder<H::Out>,
+	) -> Self {
+		Self::new_with_recorder_and_size_limit(backend, proof_recorder, None)
+	}
+
+	/// Create new proving backend which fails all reads once the estimated encoded size of the
+	/// recorded proof exceeds `size_limit` bytes.
+	pub fn new_with_size_limit(backend: &'a TrieBackend<S, H>, size_limit: usize) -> Self {
+		Self::new_with_recorder_and_size_limit(backend, Default::default(), Some(size_limit))
+	}
+
+	fn new_with_recorder_and_size_limit(
+		backend: &'a TrieBackend<S, H>,
+		proof_recorder: ProofRecorder<H::Out>,
+		size_limit: Option<usize>,
 	) -> Self {
 		let essence = backend.essence();
 		let root = essence.root().clone();
 		let recorder = ProofRecorderBackend {
 			backend: essence.backend_storage(),
 			proof_recorder,
+			size_limit,
 		};
 		ProvingBackend(TrieBackend::new(recorder, root))
 	}
@@ -220,6 +238,17 @@ impl<'a, S: 'a + TrieBackendStorage<H>, H: 'a + Hasher> ProvingBackend<'a, S, H>
 	pub fn estimate_encoded_size(&self) -> usize {
 		self.0.essence().backend_storage().proof_recorder.estimate_encoded_size()
 	}
+
+	/// Returns `true` if the recorded proof has grown beyond the size limit of this backend.
+	pub fn proof_size_exceeded(&self) -> bool {
+		self.0.essence().backend_storage().proof_size_exceeded()
+	}
+}
+
+impl<'a, S: 'a + TrieBackendStorage<H>, H: 'a + Hasher> ProofRecorderBackend<'a, S, H> {
+	fn proof_size_exceeded(&self) -> bool {
+		self.size_limit.map_or(false, |limit| self.proof_recorder.estimate_encoded_size() > limit)
+	}
 }
 
 impl<'a, S: 'a + TrieBackendStorage<H>, H: 'a + Hasher> TrieBackendStorage<H>
@@ -228,12 +257,23 @@ impl<'a, S: 'a + TrieBackendStorage<H>, H: 'a + Hasher> TrieBackendStorage<H>
 	type Overlay = S::Overlay;
 
 	fn get(&self, key: &H::Out, prefix: Prefix) -> Result<Option<DBValue>, String> {
+		// Checked before the recorder lookup, so a proof that is already too big can't be used to
+		// continue the execution from the nodes recorded so far.
+		if self.proof_size_exceeded() {
+			return Err("Proof size limit exceeded".into());
+		}
+
 		if let Some(v) = self.proof_recorder.get(key) {
 			return Ok(v);
 		}
 
 		let backend_value = self.backend.get(key, prefix)?;
 		self.proof_recorder.record(key.clone(), backend_value.clone());
+
+		if self.proof_size_exceeded() {
+			return Err("Proof size limit exceeded".into());
+		}
+
 		Ok(backend_value)
 	}
 }
@@ -268,6 +308,30 @@ impl<'a, S, H> Backend<H> for ProvingBackend<'a, S, H>
 		self.0.child_storage(child_info, key)
 	}
 
+	fn storage_hash(&self, key: &[u8]) -> Result<Option<H::Out>, Self::Error> {
+		self.0.storage_hash(key)
+	}
+
+	fn storage_size(&self, key: &[u8]) -> Result<Option<u32>, Self::Error> {
+		self.0.storage_size(key)
+	}
+
+	fn child_storage_hash(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<H::Out>, Self::Error> {
+		self.0.child_storage_hash(child_info, key)
+	}
+
+	fn child_storage_size(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<u32>, Self::Error> {
+		self.0.child_storage_size(child_info, key)
+	}
+
 	fn apply_to_key_values_while<F: FnMut(Vec<u8>, Vec<u8>) -> bool>(
 		&self,
 		child_info: Option<&ChildInfo>,
@@ -402,6 +466,37 @@ mod tests {
 		assert!(!backend.extract_proof().is_empty());
 	}
 
+	#[test]
+	fn reads_fail_once_proof_size_limit_is_exceeded() {
+		let trie_backend = test_trie();
+		let backend = ProvingBackend::new_with_size_limit(&trie_backend, 1);
+		assert!(!backend.proof_size_exceeded());
+		assert!(backend.storage(b"key").is_err());
+		assert!(backend.proof_size_exceeded());
+
+		let backend = ProvingBackend::new_with_size_limit(&trie_backend, 1024 * 1024);
+		assert_eq!(backend.storage(b"key").unwrap(), Some(b"value".to_vec()));
+		assert!(!backend.proof_size_exceeded());
+	}
+
+	#[test]
+	fn recorded_nodes_are_not_served_once_proof_size_limit_is_exceeded() {
+		let trie_backend = test_trie();
+		let size_after_key = {
+			let backend = test_proving(&trie_backend);
+			backend.storage(b"key").unwrap();
+			backend.estimate_encoded_size()
+		};
+
+		let backend = ProvingBackend::new_with_size_limit(&trie_backend, size_after_key);
+		assert_eq!(backend.storage(b"key").unwrap(), Some(b"value".to_vec()));
+		// Unlike `value1`, which is inlined in the root, needs a node that isn't recorded yet.
+		assert!(backend.storage(&[200]).is_err());
+		assert!(backend.proof_size_exceeded());
+		// All the nodes of `key` are recorded, but the proof is already too big.
+		assert!(backend.storage(b"key").is_err());
+	}
+
 	#[test]
 	fn proof_is_invalid_when_does_not_contains_root() {
 		use sp_core::H256;
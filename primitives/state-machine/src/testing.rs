@@ -61,6 +61,24 @@ where
 	pub extensions: Extensions,
 }
 
+/// Storage state of a [`TestExternalities`], created by [`TestExternalities::snapshot`].
+pub struct TestExternalitiesSnapshot<H: Hasher>
+where
+	H::Out: codec::Codec + Ord,
+{
+	overlay: OverlayedChanges,
+	backend: InMemoryBackend<H>,
+}
+
+impl<H: Hasher> Clone for TestExternalitiesSnapshot<H>
+where
+	H::Out: codec::Codec + Ord,
+{
+	fn clone(&self) -> Self {
+		Self { overlay: self.overlay.clone(), backend: self.backend.clone() }
+	}
+}
+
 impl<H: Hasher, N: ChangesTrieBlockNumber> TestExternalities<H, N>
 where
 	H::Out: Ord + 'static + codec::Codec,
@@ -141,6 +159,26 @@ where
 		self.backend.insert(vec![(None, vec![(k, Some(v))])]);
 	}
 
+	/// Capture the storage state, including the pending changes and open transactions.
+	///
+	/// The state can be reset to the snapshot any number of times with [`Self::restore`], which
+	/// avoids building the genesis storage again for every run of a test.
+	pub fn snapshot(&self) -> TestExternalitiesSnapshot<H> {
+		TestExternalitiesSnapshot {
+			overlay: self.overlay.clone(),
+			backend: self.backend.clone(),
+		}
+	}
+
+	/// Reset the storage state to the given `snapshot`.
+	///
+	/// Extensions, the offchain database and the changes trie storage are not affected.
+	pub fn restore(&mut self, snapshot: TestExternalitiesSnapshot<H>) {
+		self.overlay = snapshot.overlay;
+		self.backend = snapshot.backend;
+		self.storage_transaction_cache = Default::default();
+	}
+
 	/// Registers the given extension for this instance.
 	pub fn register_extension<E: Any + Extension>(&mut self, ext: E) {
 		self.extensions.register(ext);
@@ -322,6 +360,33 @@ mod tests {
 		assert_eq!(&ext.storage(CODE).unwrap(), &code);
 	}
 
+	#[test]
+	fn snapshot_and_restore_work() {
+		let mut ext = TestExternalities::<BlakeTwo256, u64>::default();
+		ext.insert(b"doe".to_vec(), b"reindeer".to_vec());
+		ext.ext().set_storage(b"dog".to_vec(), b"puppy".to_vec());
+		let snapshot = ext.snapshot();
+		let root = ext.ext().storage_root();
+
+		for _ in 0..2 {
+			{
+				let mut ext = ext.ext();
+				ext.set_storage(b"dog".to_vec(), b"cat".to_vec());
+				ext.clear_storage(b"doe");
+			}
+			ext.commit_all().unwrap();
+			ext.insert(b"dogglesworth".to_vec(), b"cat".to_vec());
+			assert_ne!(ext.ext().storage_root(), root);
+
+			ext.restore(snapshot.clone());
+			let mut ext = ext.ext();
+			assert_eq!(ext.storage(b"doe"), Some(b"reindeer".to_vec()));
+			assert_eq!(ext.storage(b"dog"), Some(b"puppy".to_vec()));
+			assert_eq!(ext.storage(b"dogglesworth"), None);
+			assert_eq!(ext.storage_root(), root);
+		}
+	}
+
 	#[test]
 	fn check_send() {
 		fn assert_send<T: Send>() {}
@@ -85,6 +85,14 @@ impl<S: TrieBackendStorage<H>, H: Hasher> Backend<H> for TrieBackend<S, H> where
 		self.essence.storage(key)
 	}
 
+	fn storage_hash(&self, key: &[u8]) -> Result<Option<H::Out>, Self::Error> {
+		self.essence.storage_hash(key)
+	}
+
+	fn storage_size(&self, key: &[u8]) -> Result<Option<u32>, Self::Error> {
+		self.essence.storage_size(key)
+	}
+
 	fn child_storage(
 		&self,
 		child_info: &ChildInfo,
@@ -93,6 +101,22 @@ impl<S: TrieBackendStorage<H>, H: Hasher> Backend<H> for TrieBackend<S, H> where
 		self.essence.child_storage(child_info, key)
 	}
 
+	fn child_storage_hash(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<H::Out>, Self::Error> {
+		self.essence.child_storage_hash(child_info, key)
+	}
+
+	fn child_storage_size(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+	) -> Result<Option<u32>, Self::Error> {
+		self.essence.child_storage_size(child_info, key)
+	}
+
 	fn next_storage_key(&self, key: &[u8]) -> Result<Option<StorageKey>, Self::Error> {
 		self.essence.next_storage_key(key)
 	}
@@ -244,6 +268,29 @@ impl<S: TrieBackendStorage<H>, H: Hasher> Backend<H> for TrieBackend<S, H> where
 		(root, is_default, write_overlay)
 	}
 
+	#[cfg(feature = "parallel-storage-root")]
+	fn child_storage_roots<'a>(
+		&self,
+		child_deltas: impl Iterator<Item = (
+			&'a ChildInfo,
+			impl Iterator<Item=(&'a [u8], Option<&'a [u8]>)>,
+		)>,
+	) -> Vec<(H::Out, bool, Self::Transaction)> where H::Out: Ord {
+		use rayon::prelude::*;
+
+		// Child tries are independent of each other, so their roots are computed concurrently.
+		// The results keep the order of `child_deltas`, which keeps the transaction deterministic.
+		let child_deltas: Vec<(&ChildInfo, Vec<_>)> = child_deltas
+			.map(|(child_info, child_delta)| (child_info, child_delta.collect()))
+			.collect();
+		child_deltas
+			.into_par_iter()
+			.map(|(child_info, child_delta)| {
+				self.child_storage_root(child_info, child_delta.into_iter())
+			})
+			.collect()
+	}
+
 	fn as_trie_backend(&mut self) -> Option<&TrieBackend<Self::TrieBackendStorage, H>> {
 		Some(self)
 	}
@@ -317,6 +364,21 @@ pub mod tests {
 		);
 	}
 
+	#[test]
+	fn read_hash_and_size_without_value() {
+		let test_trie = test_trie();
+		let child_info = ChildInfo::new_default(CHILD_KEY_1);
+		assert_eq!(test_trie.storage_hash(b"key").unwrap(), Some(BlakeTwo256::hash(b"value")));
+		assert_eq!(test_trie.storage_size(b":code").unwrap(), Some(9));
+		assert_eq!(
+			test_trie.child_storage_hash(&child_info, b"value3").unwrap(),
+			Some(BlakeTwo256::hash(&[142])),
+		);
+		assert_eq!(test_trie.child_storage_size(&child_info, b"value4").unwrap(), Some(1));
+		assert_eq!(test_trie.storage_hash(b"non-existing-key").unwrap(), None);
+		assert_eq!(test_trie.child_storage_size(&child_info, b"key").unwrap(), None);
+	}
+
 	#[test]
 	fn read_from_storage_returns_none() {
 		assert_eq!(test_trie().storage(b"non-existing-key").unwrap(), None);
@@ -369,4 +431,64 @@ pub mod tests {
 		expected.insert(b"value2".to_vec());
 		assert_eq!(seen, expected);
 	}
+
+	#[test]
+	fn full_storage_root_matches_serial_computation() {
+		use crate::backend::Consolidate;
+
+		let trie = test_trie();
+		let existing_child = ChildInfo::new_default(CHILD_KEY_1);
+		let new_children: Vec<_> = (0u8..8)
+			.map(|i| ChildInfo::new_default(&[b's', b'u', b'b', b'-', i]))
+			.collect();
+
+		let top_delta: Vec<(Vec<u8>, Option<Vec<u8>>)> = (0u32..512)
+			.map(|i| (format!("key{}", i).into_bytes(), Some(i.encode())))
+			// Deletions of existing keys.
+			.chain((128u8..200).map(|i| (vec![i], None)))
+			.chain(iter::once((b"value1".to_vec(), None)))
+			.collect();
+		let mut child_deltas: Vec<(&ChildInfo, Vec<(Vec<u8>, Option<Vec<u8>>)>)> = new_children
+			.iter()
+			.enumerate()
+			.map(|(n, child_info)| {
+				let delta = (0u32..(64 * (n as u32 + 1)))
+					.map(|i| (format!("child{}", i).into_bytes(), Some(vec![n as u8; 40])))
+					.collect();
+				(child_info, delta)
+			})
+			.collect();
+		// Emptying the existing child trie removes it from the top trie.
+		child_deltas.push((&existing_child, vec![(b"value3".to_vec(), None), (b"value4".to_vec(), None)]));
+
+		fn as_delta(delta: &[(Vec<u8>, Option<Vec<u8>>)]) -> Vec<(&[u8], Option<&[u8]>)> {
+			delta.iter().map(|(k, v)| (&k[..], v.as_ref().map(|v| &v[..]))).collect()
+		}
+
+		let (root, mut tx) = trie.full_storage_root(
+			as_delta(&top_delta).into_iter(),
+			child_deltas.iter().map(|(child_info, delta)| (*child_info, as_delta(delta).into_iter())),
+		);
+
+		// Reference: each child trie in turn, then the top trie.
+		let mut serial_tx: PrefixedMemoryDB<BlakeTwo256> = Default::default();
+		let mut child_roots = Vec::new();
+		for (child_info, delta) in &child_deltas {
+			let (child_root, empty, child_tx) =
+				trie.child_storage_root(child_info, as_delta(delta).into_iter());
+			serial_tx.consolidate(child_tx);
+			let child_root = if empty { None } else { Some(child_root.encode()) };
+			child_roots.push((child_info.prefixed_storage_key().into_inner(), child_root));
+		}
+		let (serial_root, top_tx) = trie.storage_root(
+			as_delta(&top_delta).into_iter().chain(
+				child_roots.iter().map(|(k, v)| (&k[..], v.as_ref().map(|v| &v[..])))
+			),
+		);
+		serial_tx.consolidate(top_tx);
+
+		assert_eq!(root, serial_root);
+		assert_eq!(tx.drain(), serial_tx.drain());
+		assert_ne!(root, trie.storage_root(iter::empty()).0);
+	}
 }
@@ -24,8 +24,8 @@ use sp_std::{ops::Deref, boxed::Box, vec::Vec};
 use crate::{warn, debug};
 use hash_db::{self, Hasher, Prefix};
 use sp_trie::{Trie, MemoryDB, PrefixedMemoryDB, DBValue,
-	empty_child_trie_root, read_trie_value, read_child_trie_value,
-	KeySpacedDB, TrieDBIterator};
+	empty_child_trie_root, read_trie_value, read_child_trie_value, read_trie_value_map,
+	read_child_trie_value_map, KeySpacedDB, TrieDBIterator};
 use sp_trie::trie_types::{TrieDB, TrieError, Layout};
 use crate::{backend::Consolidate, StorageKey, StorageValue};
 use sp_core::storage::ChildInfo;
@@ -189,6 +189,47 @@ impl<S: TrieBackendStorage<H>, H: Hasher> TrieBackendEssence<S, H> where H::Out:
 			.map_err(map_e)
 	}
 
+	/// Get the hash of the value of storage at given key, without copying the value.
+	pub fn storage_hash(&self, key: &[u8]) -> Result<Option<H::Out>> {
+		self.storage_map(key, H::hash)
+	}
+
+	/// Get the size of the value of storage at given key, without copying the value.
+	pub fn storage_size(&self, key: &[u8]) -> Result<Option<u32>> {
+		self.storage_map(key, |value| value.len() as u32)
+	}
+
+	/// Get the hash of the value of child storage at given key, without copying the value.
+	pub fn child_storage_hash(&self, child_info: &ChildInfo, key: &[u8]) -> Result<Option<H::Out>> {
+		self.child_storage_map(child_info, key, H::hash)
+	}
+
+	/// Get the size of the value of child storage at given key, without copying the value.
+	pub fn child_storage_size(&self, child_info: &ChildInfo, key: &[u8]) -> Result<Option<u32>> {
+		self.child_storage_map(child_info, key, |value| value.len() as u32)
+	}
+
+	fn storage_map<T>(&self, key: &[u8], f: impl FnOnce(&[u8]) -> T) -> Result<Option<T>> {
+		let map_e = |e| format!("Trie lookup error: {}", e);
+
+		read_trie_value_map::<Layout<H>, _, _, _>(self, &self.root, key, f).map_err(map_e)
+	}
+
+	fn child_storage_map<T>(
+		&self,
+		child_info: &ChildInfo,
+		key: &[u8],
+		f: impl FnOnce(&[u8]) -> T,
+	) -> Result<Option<T>> {
+		let root = self.child_root(child_info)?
+			.unwrap_or_else(|| empty_child_trie_root::<Layout<H>>().encode());
+
+		let map_e = |e| format!("Trie lookup error: {}", e);
+
+		read_child_trie_value_map::<Layout<H>, _, _, _>(child_info.keyspace(), self, &root, key, f)
+			.map_err(map_e)
+	}
+
 	/// Retrieve all entries keys of storage and call `f` for each of those keys.
 	/// Aborts as soon as `f` returns false.
 	///
@@ -396,7 +437,7 @@ impl<'a, S: 'a + TrieBackendStorage<H>, H: Hasher> hash_db::HashDBRef<H, DBValue
 /// Key-value pairs storage that is used by trie backend essence.
 pub trait TrieBackendStorage<H: Hasher>: Send + Sync {
 	/// Type of in-memory overlay.
-	type Overlay: hash_db::HashDB<H, DBValue> + Default + Consolidate;
+	type Overlay: hash_db::HashDB<H, DBValue> + Default + Consolidate + Send;
 	/// Get the value stored at key.
 	fn get(&self, key: &H::Out, prefix: Prefix) -> Result<Option<DBValue>>;
 }
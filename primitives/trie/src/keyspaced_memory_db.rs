@@ -0,0 +1,174 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! In-memory storage of child trie nodes, kept apart by keyspace.
+//!
+//! [`crate::KeySpacedDBMut`] prepends the keyspace to the prefix of every key it writes, so all
+//! child tries end up mixed in a single database. [`KeySpacedMemoryDB`] instead holds one
+//! [`PrefixedMemoryDB`] per keyspace. The nodes of a child trie can then be dropped at once when
+//! the child trie is deleted, and two databases can be merged keyspace by keyspace without
+//! touching the keys. The keyspaces are only applied when flattening the database with
+//! [`KeySpacedMemoryDB::into_prefixed_memory_db`].
+
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+use hash_db::{HashDB, Hasher};
+use crate::PrefixedMemoryDB;
+
+/// Child trie nodes, in one [`PrefixedMemoryDB`] per keyspace.
+pub struct KeySpacedMemoryDB<H: Hasher> {
+	keyspaces: BTreeMap<Vec<u8>, PrefixedMemoryDB<H>>,
+}
+
+impl<H: Hasher> Default for KeySpacedMemoryDB<H> {
+	fn default() -> Self {
+		KeySpacedMemoryDB { keyspaces: BTreeMap::new() }
+	}
+}
+
+impl<H: Hasher> KeySpacedMemoryDB<H> {
+	/// The database of the keyspace `keyspace`, if it has one.
+	pub fn keyspace(&self, keyspace: &[u8]) -> Option<&PrefixedMemoryDB<H>> {
+		self.keyspaces.get(keyspace)
+	}
+
+	/// The database of the keyspace `keyspace`, created empty if it has none.
+	///
+	/// The keys written to it must not include the keyspace.
+	pub fn keyspace_mut(&mut self, keyspace: &[u8]) -> &mut PrefixedMemoryDB<H> {
+		self.keyspaces.entry(keyspace.to_vec()).or_default()
+	}
+
+	/// The keyspaces having a database, in order.
+	pub fn keyspaces(&self) -> impl Iterator<Item = &[u8]> {
+		self.keyspaces.keys().map(|keyspace| &keyspace[..])
+	}
+
+	/// Remove and return the database of the keyspace `keyspace`, e.g. when deleting its child
+	/// trie.
+	pub fn drain_keyspace(&mut self, keyspace: &[u8]) -> Option<PrefixedMemoryDB<H>> {
+		self.keyspaces.remove(keyspace)
+	}
+
+	/// Merge the databases of `other` into these, keyspace by keyspace.
+	pub fn consolidate(&mut self, other: Self) {
+		for (keyspace, db) in other.keyspaces {
+			match self.keyspaces.get_mut(&keyspace) {
+				Some(existing) => existing.consolidate(db),
+				None => {
+					self.keyspaces.insert(keyspace, db);
+				},
+			}
+		}
+	}
+
+	/// Flatten into a single database, with the keys [`crate::KeySpacedDBMut`] would have
+	/// written for every keyspace.
+	pub fn into_prefixed_memory_db(self) -> PrefixedMemoryDB<H> {
+		let mut flat = PrefixedMemoryDB::<H>::default();
+		for (keyspace, mut db) in self.keyspaces {
+			for (key, (value, rc)) in db.drain() {
+				// A prefixed key is the prefix followed by the hash, so prepending the keyspace to
+				// the prefix is prepending it to the key.
+				let (prefix, hash_bytes) = key.split_at(key.len() - H::LENGTH);
+				let mut hash = H::Out::default();
+				hash.as_mut().copy_from_slice(hash_bytes);
+				let mut keyspaced_prefix = keyspace.clone();
+				keyspaced_prefix.extend_from_slice(prefix);
+				let keyspaced_prefix = (&keyspaced_prefix[..], None);
+
+				if rc > 0 {
+					for _ in 0 .. rc {
+						flat.emplace(hash, keyspaced_prefix, value.clone());
+					}
+				} else {
+					for _ in rc .. 0 {
+						flat.remove(&hash, keyspaced_prefix);
+					}
+				}
+			}
+		}
+		flat
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::Blake2Hasher;
+	use trie_db::TrieMut;
+	use crate::{KeySpacedDBMut, TrieDBMut, TrieHash, read_child_trie_value};
+
+	type Layout = crate::Layout<Blake2Hasher>;
+
+	fn build_trie(db: &mut dyn HashDB<Blake2Hasher, trie_db::DBValue>, seed: u8) -> TrieHash<Layout> {
+		let mut root = Default::default();
+		{
+			let mut trie = TrieDBMut::<Layout>::new(db, &mut root);
+			for i in 0 .. 16u8 {
+				trie.insert(&[seed, i], &[i; 40]).unwrap();
+			}
+		}
+		root
+	}
+
+	#[test]
+	fn flattening_matches_keyspaced_writes() {
+		let mut keyspaced = KeySpacedMemoryDB::<Blake2Hasher>::default();
+		let root1 = build_trie(keyspaced.keyspace_mut(b"child1"), 1);
+		let root2 = build_trie(keyspaced.keyspace_mut(b"child2"), 2);
+
+		let mut direct = PrefixedMemoryDB::<Blake2Hasher>::default();
+		for &(keyspace, seed, root) in &[(b"child1", 1, root1), (b"child2", 2, root2)] {
+			let mut db = KeySpacedDBMut::<_, Blake2Hasher>::new(&mut direct, &keyspace[..]);
+			assert_eq!(build_trie(&mut db, seed), root);
+		}
+
+		let mut flat = keyspaced.into_prefixed_memory_db();
+		assert_eq!(
+			read_child_trie_value::<Layout, _>(b"child2", &flat, root2.as_ref(), &[2, 3]),
+			Ok(Some(vec![3; 40])),
+		);
+		assert_eq!(flat.drain(), direct.drain());
+	}
+
+	#[test]
+	fn consolidate_and_drain_keyspaces() {
+		let mut db1 = KeySpacedMemoryDB::<Blake2Hasher>::default();
+		build_trie(db1.keyspace_mut(b"child1"), 1);
+		let mut db2 = KeySpacedMemoryDB::<Blake2Hasher>::default();
+		build_trie(db2.keyspace_mut(b"child1"), 3);
+		let root2 = build_trie(db2.keyspace_mut(b"child2"), 2);
+		let child1_nodes = db1.keyspace(b"child1").unwrap().keys().len()
+			+ db2.keyspace(b"child1").unwrap().keys().len();
+
+		db1.consolidate(db2);
+		assert_eq!(db1.keyspaces().collect::<Vec<_>>(), vec![&b"child1"[..], &b"child2"[..]]);
+		// The nodes of both tries of `child1` are kept.
+		assert_eq!(db1.keyspace(b"child1").unwrap().keys().len(), child1_nodes);
+
+		assert!(db1.drain_keyspace(b"child1").is_some());
+		assert!(db1.drain_keyspace(b"child1").is_none());
+		assert_eq!(db1.keyspaces().collect::<Vec<_>>(), vec![&b"child2"[..]]);
+
+		let flat = db1.into_prefixed_memory_db();
+		assert_eq!(
+			read_child_trie_value::<Layout, _>(b"child2", &flat, root2.as_ref(), &[2, 7]),
+			Ok(Some(vec![7; 40])),
+		);
+		assert!(read_child_trie_value::<Layout, _>(b"child1", &flat, root2.as_ref(), &[2, 7]).is_err());
+	}
+}
@@ -22,6 +22,10 @@
 mod error;
 mod node_header;
 mod node_codec;
+mod keyspaced_memory_db;
+#[cfg(feature = "std")]
+mod node_cache;
+mod proof_check;
 mod storage_proof;
 mod trie_codec;
 mod trie_stream;
@@ -37,6 +41,10 @@ pub use trie_stream::TrieStream;
 /// The Substrate format implementation of `NodeCodec`.
 pub use node_codec::NodeCodec;
 pub use storage_proof::{StorageProof, CompactProof};
+pub use proof_check::{ProofChecker, ProofCheckError, verify_storage_proof};
+pub use keyspaced_memory_db::KeySpacedMemoryDB;
+#[cfg(feature = "std")]
+pub use node_cache::{TrieNodeCache, TrieNodeCacheStats};
 /// Various re-exports from the `trie-db` crate.
 pub use trie_db::{
 	Trie, TrieMut, DBValue, Recorder, CError, Query, TrieLayout, TrieConfiguration, nibble_ops, TrieDBIterator,
@@ -176,6 +184,23 @@ pub fn verify_trie_proof<'a, L: TrieConfiguration, I, K, V>(
 	verify_proof::<Layout<L::Hash>, _, _, _>(root, proof, items)
 }
 
+/// Determine the root of the trie containing the key/value pairs of the given chunks.
+///
+/// Keys must be unique and sorted across all the chunks, otherwise the returned root is wrong.
+/// Chunks are consumed lazily and the trie is built from the left, keeping only the path to the
+/// last key in memory, so this works on datasets too big to be collected, e.g. when importing a
+/// state snapshot chunk by chunk.
+pub fn trie_root_from_sorted_chunks<L: TrieConfiguration, I, C, A, B>(chunks: I) -> TrieHash<L> where
+	I: IntoIterator<Item = C>,
+	C: IntoIterator<Item = (A, B)>,
+	A: AsRef<[u8]> + Ord,
+	B: AsRef<[u8]>,
+{
+	let mut cb = trie_db::TrieRoot::<L::Hash, _>::default();
+	trie_db::trie_visit::<L, _, _, _, _>(chunks.into_iter().flatten(), &mut cb);
+	cb.root.unwrap_or_default()
+}
+
 /// Determine a trie root given a hash DB and delta values.
 pub fn delta_trie_root<L: TrieConfiguration, I, A, B, DB, V>(
 	db: &mut DB,
@@ -228,6 +253,22 @@ pub fn read_trie_value_with<
 	TrieDB::<L>::new(&*db, root)?.get_with(key, query).map(|x| x.map(|val| val.to_vec()))
 }
 
+/// Read a value from the trie and map it with `f`, without copying it.
+///
+/// Useful to get e.g. the size or the hash of a value.
+pub fn read_trie_value_map<L, DB, T, F>(
+	db: &DB,
+	root: &TrieHash<L>,
+	key: &[u8],
+	f: F,
+) -> Result<Option<T>, Box<TrieError<L>>> where
+	L: TrieConfiguration,
+	DB: hash_db::HashDBRef<L::Hash, trie_db::DBValue>,
+	F: FnOnce(&[u8]) -> T,
+{
+	TrieDB::<L>::new(&*db, root)?.get_with(key, f)
+}
+
 /// Determine the empty trie root.
 pub fn empty_trie_root<L: TrieConfiguration>() -> <L::Hash as Hasher>::Out {
 	L::trie_root::<_, Vec<u8>, Vec<u8>>(core::iter::empty())
@@ -279,6 +320,95 @@ pub fn child_delta_trie_root<L: TrieConfiguration, I, A, B, DB, RD, V>(
 	)
 }
 
+/// Remove at most `limit` keys from a child trie, in lexicographic order.
+///
+/// Returns the new root of the child trie, the number of removed keys and the first key left in
+/// the child trie. The child trie is empty once no key is left; otherwise the removal can be
+/// resumed with the returned root.
+pub fn clear_child_trie_bounded<L: TrieConfiguration, DB>(
+	keyspace: &[u8],
+	db: &mut DB,
+	root_slice: &[u8],
+	limit: u32,
+) -> Result<(TrieHash<L>, u32, Option<Vec<u8>>), Box<TrieError<L>>>
+	where
+		DB: hash_db::HashDB<L::Hash, trie_db::DBValue>
+{
+	let mut root = TrieHash::<L>::default();
+	// root is fetched from DB, not writable by runtime, so it's always valid.
+	root.as_mut().copy_from_slice(root_slice);
+
+	// The trie can't be modified while iterating it, collect the keys to remove first.
+	let mut keys = Vec::new();
+	let mut next_key = None;
+	{
+		let hash_db: &dyn hash_db::HashDB<L::Hash, trie_db::DBValue> = &*db;
+		let db = KeySpacedDB::new(&hash_db, keyspace);
+		let trie = TrieDB::<L>::new(&db, &root)?;
+		for x in trie.iter()? {
+			let (key, _) = x?;
+			if keys.len() as u32 >= limit {
+				next_key = Some(key);
+				break;
+			}
+			keys.push(key);
+		}
+	}
+
+	{
+		let mut db = KeySpacedDBMut::new(&mut *db, keyspace);
+		let mut trie = TrieDBMut::<L>::from_existing(&mut db, &mut root)?;
+		for key in &keys {
+			trie.remove(key)?;
+		}
+	}
+
+	Ok((root, keys.len() as u32, next_key))
+}
+
+/// Check whether `root_slice` is the root of an empty child trie.
+///
+/// A child trie is removed from the parent trie once it is empty, so this only holds for roots
+/// which have not been stored yet. Use [`generate_empty_child_trie_proof`] to prove that a child
+/// trie is empty.
+pub fn is_empty_child_trie_root<L: TrieConfiguration>(root_slice: &[u8]) -> bool {
+	empty_child_trie_root::<L>().as_ref() == root_slice
+}
+
+/// Create a proof that the child trie stored under `prefixed_child_key` in the parent trie of
+/// root `root` is empty.
+///
+/// This is a proof of the parent key, to be checked with [`verify_empty_child_trie_proof`].
+pub fn generate_empty_child_trie_proof<L: TrieConfiguration, DB>(
+	db: &DB,
+	root: TrieHash<L>,
+	prefixed_child_key: &[u8],
+) -> Result<Vec<Vec<u8>>, Box<TrieError<L>>> where
+	DB: hash_db::HashDBRef<L::Hash, trie_db::DBValue>,
+{
+	generate_trie_proof::<L, _, _, _>(db, root, &[prefixed_child_key])
+}
+
+/// Verify a proof made by [`generate_empty_child_trie_proof`].
+///
+/// The child trie is empty if `prefixed_child_key` is not in the parent trie of root `root`, or
+/// holds the root of an empty child trie.
+pub fn verify_empty_child_trie_proof<L: TrieConfiguration>(
+	root: &TrieHash<L>,
+	proof: &[Vec<u8>],
+	prefixed_child_key: &[u8],
+) -> Result<(), VerifyError<TrieHash<L>, error::Error>> {
+	verify_trie_proof::<L, _, _, &[u8]>(root, proof, &[(prefixed_child_key, None)])
+		.or_else(|_| {
+			let empty_root = empty_child_trie_root::<L>();
+			verify_trie_proof::<L, _, _, _>(
+				root,
+				proof,
+				&[(prefixed_child_key, Some(empty_root.as_ref()))],
+			)
+		})
+}
+
 /// Record all keys for a given root.
 pub fn record_all_keys<L: TrieConfiguration, DB>(
 	db: &DB,
@@ -302,6 +432,70 @@ pub fn record_all_keys<L: TrieConfiguration, DB>(
 	Ok(())
 }
 
+/// Record all keys starting with `prefix` for a given root.
+///
+/// Only the nodes on the path to `prefix` and the nodes below it are recorded, so the recorded
+/// proof is enough to enumerate all the keys under `prefix`.
+pub fn record_keys_with_prefix<L: TrieConfiguration, DB>(
+	db: &DB,
+	root: &TrieHash<L>,
+	prefix: &[u8],
+	recorder: &mut Recorder<TrieHash<L>>
+) -> Result<(), Box<TrieError<L>>> where
+	DB: hash_db::HashDBRef<L::Hash, trie_db::DBValue>
+{
+	let trie = TrieDB::<L>::new(&*db, root)?;
+
+	for x in PrefixRecordingIterator::new(&trie, prefix, recorder)? {
+		x?;
+	}
+
+	Ok(())
+}
+
+/// Iterator over the entries of a trie with a key starting with a given prefix.
+///
+/// The nodes accessed to read the entries are recorded in the given recorder.
+pub struct PrefixRecordingIterator<'a, 'r, L: TrieConfiguration> {
+	trie: &'a TrieDB<'a, L>,
+	iter: TrieDBIterator<'a, L>,
+	recorder: &'r mut Recorder<TrieHash<L>>,
+}
+
+impl<'a, 'r, L: TrieConfiguration> PrefixRecordingIterator<'a, 'r, L> {
+	/// Create a new iterator over the entries of `trie` starting with `prefix`.
+	pub fn new(
+		trie: &'a TrieDB<'a, L>,
+		prefix: &[u8],
+		recorder: &'r mut Recorder<TrieHash<L>>,
+	) -> Result<Self, Box<TrieError<L>>> {
+		// Record the path to the prefix, this is what proves that there is no other key with
+		// this prefix, including when there is no key at all.
+		trie.get_with(prefix, &mut *recorder)?;
+		let iter = TrieDBIterator::new_prefixed(trie, prefix)?;
+
+		Ok(Self { trie, iter, recorder })
+	}
+}
+
+impl<'a, 'r, L: TrieConfiguration> Iterator for PrefixRecordingIterator<'a, 'r, L> {
+	type Item = Result<(Vec<u8>, DBValue), Box<TrieError<L>>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (key, value) = match self.iter.next()? {
+			Ok(entry) => entry,
+			Err(e) => return Some(Err(e)),
+		};
+
+		// there's currently no API like iter_with()
+		// => lookup each key using get_with
+		match self.trie.get_with(&key, &mut *self.recorder) {
+			Ok(_) => Some(Ok((key, value))),
+			Err(e) => Some(Err(e)),
+		}
+	}
+}
+
 /// Read a value from the child trie.
 pub fn read_child_trie_value<L: TrieConfiguration, DB>(
 	keyspace: &[u8],
@@ -339,6 +533,26 @@ pub fn read_child_trie_value_with<L: TrieConfiguration, Q: Query<L::Hash, Item=D
 	TrieDB::<L>::new(&db, &root)?.get_with(key, query).map(|x| x.map(|val| val.to_vec()))
 }
 
+/// Read a value from the child trie and map it with `f`, without copying it.
+pub fn read_child_trie_value_map<L, DB, T, F>(
+	keyspace: &[u8],
+	db: &DB,
+	root_slice: &[u8],
+	key: &[u8],
+	f: F,
+) -> Result<Option<T>, Box<TrieError<L>>> where
+	L: TrieConfiguration,
+	DB: hash_db::HashDBRef<L::Hash, trie_db::DBValue>,
+	F: FnOnce(&[u8]) -> T,
+{
+	let mut root = TrieHash::<L>::default();
+	// root is fetched from DB, not writable by runtime, so it's always valid.
+	root.as_mut().copy_from_slice(root_slice);
+
+	let db = KeySpacedDB::new(&*db, keyspace);
+	TrieDB::<L>::new(&db, &root)?.get_with(key, f)
+}
+
 /// `HashDB` implementation that append a encoded prefix (unique id bytes) in addition to the
 /// prefix of every key value.
 pub struct KeySpacedDB<'a, DB, H>(&'a DB, &'a [u8], PhantomData<H>);
@@ -631,6 +845,30 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn trie_root_from_sorted_chunks_works() {
+		assert_eq!(
+			trie_root_from_sorted_chunks::<Layout, _, Vec<(Vec<u8>, Vec<u8>)>, _, _>(vec![]),
+			hashed_null_node::<Layout>(),
+		);
+
+		let mut input = StandardMap {
+			alphabet: Alphabet::Custom(b"@QWERTYUIOPASDFGHJKLZXCVBNM[/]^_".to_vec()),
+			min_key: 5,
+			journal_key: 0,
+			value_mode: ValueMode::Index,
+			count: 1000,
+		}.make_with(&mut [0; 32]);
+		input.sort();
+		input.dedup_by(|a, b| a.0 == b.0);
+
+		let chunks = input.chunks(64).map(|chunk| chunk.iter().cloned());
+		assert_eq!(
+			trie_root_from_sorted_chunks::<Layout, _, _, _, _>(chunks),
+			Layout::trie_root(input.clone()),
+		);
+	}
+
 	#[test]
 	fn random_should_work() {
 		let mut seed = <Blake2Hasher as Hasher>::Out::zero();
@@ -751,6 +989,70 @@ mod tests {
 		assert_eq!(pairs, iter_pairs);
 	}
 
+	#[test]
+	fn clear_child_trie_bounded_works() {
+		let keyspace = b"child";
+		let pairs = (0u8..5).map(|i| (vec![i], Some(vec![i; 40]))).collect::<Vec<_>>();
+
+		let mut memdb = MemoryDB::<Blake2Hasher>::default();
+		let root = child_delta_trie_root::<Layout, _, _, _, _, _, _>(
+			keyspace,
+			&mut memdb,
+			empty_child_trie_root::<Layout>(),
+			pairs,
+		).unwrap();
+		assert!(!is_empty_child_trie_root::<Layout>(root.as_ref()));
+
+		let (root, removed, next_key) =
+			clear_child_trie_bounded::<Layout, _>(keyspace, &mut memdb, root.as_ref(), 2).unwrap();
+		assert_eq!(removed, 2);
+		assert_eq!(next_key, Some(vec![2]));
+		assert_eq!(read_child_trie_value::<Layout, _>(keyspace, &memdb, root.as_ref(), &[1]).unwrap(), None);
+		assert_eq!(
+			read_child_trie_value::<Layout, _>(keyspace, &memdb, root.as_ref(), &[2]).unwrap(),
+			Some(vec![2; 40]),
+		);
+
+		let (root, removed, next_key) =
+			clear_child_trie_bounded::<Layout, _>(keyspace, &mut memdb, root.as_ref(), 10).unwrap();
+		assert_eq!(removed, 3);
+		assert_eq!(next_key, None);
+		assert!(is_empty_child_trie_root::<Layout>(root.as_ref()));
+	}
+
+	#[test]
+	fn record_keys_with_prefix_works() {
+		let value = vec![42u8; 40];
+		let pairs = vec![
+			(hex!("0102").to_vec(), value.clone()),
+			(hex!("010203").to_vec(), value.clone()),
+			(hex!("010304").to_vec(), value.clone()),
+			(hex!("0203").to_vec(), value.clone()),
+			(hex!("0204").to_vec(), value.clone()),
+		];
+
+		let mut memdb = MemoryDB::default();
+		let mut root = Default::default();
+		populate_trie::<Layout>(&mut memdb, &mut root, &pairs);
+
+		let mut recorder = Recorder::new();
+		record_keys_with_prefix::<Layout, _>(&memdb, &root, &hex!("0102"), &mut recorder).unwrap();
+
+		let mut proof_db = MemoryDB::<Blake2Hasher>::default();
+		for record in recorder.drain() {
+			proof_db.insert(EMPTY_PREFIX, &record.data);
+		}
+
+		let trie = TrieDB::<Layout>::new(&proof_db, &root).unwrap();
+		let keys = TrieDBIterator::new_prefixed(&trie, &hex!("0102")).unwrap()
+			.map(|x| x.unwrap().0)
+			.collect::<Vec<_>>();
+		assert_eq!(keys, vec![hex!("0102").to_vec(), hex!("010203").to_vec()]);
+
+		// Keys outside of the prefix are not part of the proof.
+		assert!(trie.get(&hex!("0203")).is_err());
+	}
+
 	#[test]
 	fn proof_non_inclusion_works() {
 		let pairs = vec![
@@ -786,6 +1088,28 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn empty_child_trie_proof_works() {
+		let empty_root = empty_child_trie_root::<Layout>();
+		let pairs = vec![
+			(b"absent_sibling".to_vec(), hex!("01").to_vec()),
+			(b"empty_child".to_vec(), empty_root.as_ref().to_vec()),
+			(b"child".to_vec(), vec![42; 32]),
+		];
+
+		let mut memdb = MemoryDB::default();
+		let mut root = Default::default();
+		populate_trie::<Layout>(&mut memdb, &mut root, &pairs);
+
+		for key in &[&b"absent"[..], b"empty_child"] {
+			let proof = generate_empty_child_trie_proof::<Layout, _>(&memdb, root, key).unwrap();
+			assert!(verify_empty_child_trie_proof::<Layout>(&root, &proof, key).is_ok());
+		}
+
+		let proof = generate_empty_child_trie_proof::<Layout, _>(&memdb, root, b"child").unwrap();
+		assert!(verify_empty_child_trie_proof::<Layout>(&root, &proof, b"child").is_err());
+	}
+
 	#[test]
 	fn proof_inclusion_works() {
 		let pairs = vec![
@@ -0,0 +1,291 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Cache of trie nodes that can be shared between block executions.
+
+use std::{
+	collections::{HashMap, VecDeque},
+	sync::{Arc, atomic::{AtomicU64, Ordering}},
+};
+use parking_lot::Mutex;
+use crate::DBValue;
+
+/// Number of independently locked shards of the cache.
+const SHARDS: usize = 16;
+
+/// Size-bounded cache of encoded trie nodes, keyed by the database key of the node.
+///
+/// The database key is the node hash, prefixed with the node prefix when the database does not
+/// reference count the nodes (see [`prefixed_key`](crate::prefixed_key)). Keying by the database
+/// key means a node is cached for exactly the entry that was read, so removing the entry from the
+/// cache when it is deleted from the database stops it from being served.
+///
+/// The least recently used nodes are evicted once the cache is full. The cache is split in shards,
+/// each behind its own lock, so concurrent reads of nodes in different shards do not contend.
+///
+/// Cloning the cache returns a new handle to the same cache.
+#[derive(Clone)]
+pub struct TrieNodeCache {
+	inner: Arc<Inner>,
+}
+
+/// Reads of a [`TrieNodeCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrieNodeCacheStats {
+	/// Number of reads of cached nodes.
+	pub hits: u64,
+	/// Number of reads of nodes not in the cache.
+	pub misses: u64,
+}
+
+struct Inner {
+	shards: Vec<Mutex<Shard>>,
+	/// Number of removals from the cache, see [`TrieNodeCache::generation`].
+	generation: AtomicU64,
+	hits: AtomicU64,
+	misses: AtomicU64,
+}
+
+struct Shard {
+	/// Cached nodes, with the tick of their last use.
+	nodes: HashMap<Vec<u8>, (DBValue, u64)>,
+	/// Uses of the nodes, least recent first. An entry is stale once its node is removed or used
+	/// again.
+	order: VecDeque<(Vec<u8>, u64)>,
+	/// Tick of the last use of a node.
+	tick: u64,
+	/// Total size of the cached nodes, in bytes.
+	size: usize,
+	/// Maximum total size of the cached nodes, in bytes.
+	max_size: usize,
+}
+
+impl Shard {
+	fn get(&mut self, key: &[u8]) -> Option<DBValue> {
+		let tick = self.tick + 1;
+		let (node, last_use) = self.nodes.get_mut(key)?;
+		*last_use = tick;
+		let node = node.clone();
+		self.tick = tick;
+		self.order.push_back((key.to_vec(), tick));
+		self.compact();
+		Some(node)
+	}
+
+	fn insert(&mut self, key: Vec<u8>, node: DBValue) {
+		if node.len() > self.max_size || self.nodes.contains_key(&key) {
+			return
+		}
+
+		while self.size + node.len() > self.max_size {
+			match self.order.pop_front() {
+				Some((oldest, tick)) => if self.is_last_use(&oldest, tick) {
+					if let Some((evicted, _)) = self.nodes.remove(&oldest) {
+						self.size -= evicted.len();
+					}
+				},
+				None => break,
+			}
+		}
+
+		self.tick += 1;
+		self.size += node.len();
+		self.order.push_back((key.clone(), self.tick));
+		self.nodes.insert(key, (node, self.tick));
+	}
+
+	fn remove(&mut self, key: &[u8]) {
+		if let Some((node, _)) = self.nodes.remove(key) {
+			self.size -= node.len();
+			self.compact();
+		}
+	}
+
+	fn is_last_use(&self, key: &[u8], tick: u64) -> bool {
+		self.nodes.get(key).map_or(false, |(_, last_use)| *last_use == tick)
+	}
+
+	/// Drop the stale entries of the queue once they make up most of it.
+	fn compact(&mut self) {
+		if self.order.len() > 2 * self.nodes.len() {
+			let Shard { nodes, order, .. } = self;
+			order.retain(|(key, tick)| {
+				nodes.get(key).map_or(false, |(_, last_use)| last_use == tick)
+			});
+		}
+	}
+}
+
+impl TrieNodeCache {
+	/// Create a new cache holding at most `max_size` bytes of nodes.
+	pub fn new(max_size: usize) -> Self {
+		let shards = (0..SHARDS)
+			.map(|_| Mutex::new(Shard {
+				nodes: HashMap::new(),
+				order: VecDeque::new(),
+				tick: 0,
+				size: 0,
+				max_size: max_size / SHARDS,
+			}))
+			.collect();
+		TrieNodeCache {
+			inner: Arc::new(Inner {
+				shards,
+				generation: AtomicU64::new(0),
+				hits: AtomicU64::new(0),
+				misses: AtomicU64::new(0),
+			}),
+		}
+	}
+
+	fn shard(&self, key: &[u8]) -> &Mutex<Shard> {
+		// Keys end with the node hash, so the last byte is evenly distributed.
+		let index = key.last().map_or(0, |byte| *byte as usize % SHARDS);
+		&self.inner.shards[index]
+	}
+
+	/// Get the node with the given database key.
+	pub fn get(&self, key: &[u8]) -> Option<DBValue> {
+		let node = self.shard(key).lock().get(key);
+		match node {
+			Some(_) => self.inner.hits.fetch_add(1, Ordering::Relaxed),
+			None => self.inner.misses.fetch_add(1, Ordering::Relaxed),
+		};
+		node
+	}
+
+	/// Returns the current generation of the cache, which changes with every removal.
+	///
+	/// Read it before reading a node from the database and pass it to
+	/// [`TrieNodeCache::insert`], so that a node deleted from the database while it was being
+	/// read is not cached.
+	pub fn generation(&self) -> u64 {
+		self.inner.generation.load(Ordering::Acquire)
+	}
+
+	/// Insert a node read from the database, evicting the least recently used nodes if the cache is
+	/// full.
+	///
+	/// The node is not inserted if nodes were removed since `generation` was read.
+	pub fn insert(&self, key: Vec<u8>, node: DBValue, generation: u64) {
+		let mut shard = self.shard(&key).lock();
+		if self.generation() != generation {
+			return
+		}
+		shard.insert(key, node);
+	}
+
+	/// Remove the nodes with the given database keys.
+	///
+	/// Call this once the nodes are deleted from the database.
+	pub fn remove<'a>(&self, keys: impl IntoIterator<Item = &'a [u8]>) {
+		self.inner.generation.fetch_add(1, Ordering::AcqRel);
+		for key in keys {
+			self.shard(key).lock().remove(key);
+		}
+	}
+
+	/// Total size of the cached nodes, in bytes.
+	pub fn size(&self) -> usize {
+		self.inner.shards.iter().map(|shard| shard.lock().size).sum()
+	}
+
+	/// Returns the reads of the cache since the last call, and resets them.
+	pub fn take_stats(&self) -> TrieNodeCacheStats {
+		TrieNodeCacheStats {
+			hits: self.inner.hits.swap(0, Ordering::Relaxed),
+			misses: self.inner.misses.swap(0, Ordering::Relaxed),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn cache_is_size_bounded() {
+		// Keys ending with the same byte share a shard of 10 bytes.
+		let cache = TrieNodeCache::new(10 * SHARDS);
+		let first = vec![1, 0];
+		let second = vec![2, 0];
+		let third = vec![3, 0];
+
+		cache.insert(first.clone(), vec![1; 4], cache.generation());
+		cache.insert(second.clone(), vec![2; 4], cache.generation());
+		assert_eq!(cache.size(), 8);
+
+		// The oldest node is evicted to make room.
+		cache.insert(third.clone(), vec![3; 4], cache.generation());
+		assert_eq!(cache.get(&first), None);
+		assert_eq!(cache.get(&second), Some(vec![2; 4]));
+		assert_eq!(cache.get(&third), Some(vec![3; 4]));
+		assert_eq!(cache.size(), 8);
+
+		// Nodes bigger than the shard are not cached.
+		cache.insert(first.clone(), vec![1; 11], cache.generation());
+		assert_eq!(cache.get(&first), None);
+
+		cache.clone().remove(vec![&second[..]]);
+		assert_eq!(cache.get(&second), None);
+		assert_eq!(cache.size(), 4);
+
+		assert_eq!(cache.take_stats(), TrieNodeCacheStats { hits: 2, misses: 3 });
+		assert_eq!(cache.take_stats(), TrieNodeCacheStats::default());
+	}
+
+	#[test]
+	fn least_recently_used_nodes_are_evicted() {
+		let cache = TrieNodeCache::new(10 * SHARDS);
+		let first = vec![1, 0];
+		let second = vec![2, 0];
+		let third = vec![3, 0];
+
+		cache.insert(first.clone(), vec![1; 4], cache.generation());
+		cache.insert(second.clone(), vec![2; 4], cache.generation());
+		assert_eq!(cache.get(&first), Some(vec![1; 4]));
+
+		cache.insert(third.clone(), vec![3; 4], cache.generation());
+		assert_eq!(cache.get(&second), None);
+		assert_eq!(cache.get(&first), Some(vec![1; 4]));
+
+		// The use of a removed node does not evict the node inserted again.
+		let cache = TrieNodeCache::new(10 * SHARDS);
+		cache.insert(third.clone(), vec![3; 4], cache.generation());
+		cache.insert(first.clone(), vec![1; 4], cache.generation());
+		cache.remove(vec![&third[..]]);
+		cache.insert(third.clone(), vec![3; 4], cache.generation());
+		cache.insert(second.clone(), vec![2; 4], cache.generation());
+		assert_eq!(cache.get(&first), None);
+		assert_eq!(cache.get(&third), Some(vec![3; 4]));
+		assert_eq!(cache.size(), 8);
+	}
+
+	#[test]
+	fn nodes_read_before_a_removal_are_not_cached() {
+		let cache = TrieNodeCache::new(1024);
+		let key = vec![1, 2, 3];
+
+		let generation = cache.generation();
+		cache.remove(vec![&key[..]]);
+		cache.insert(key.clone(), vec![1; 4], generation);
+		assert_eq!(cache.get(&key), None);
+
+		cache.insert(key.clone(), vec![1; 4], cache.generation());
+		assert_eq!(cache.get(&key), Some(vec![1; 4]));
+	}
+}
@@ -0,0 +1,258 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2021 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Checking of storage proofs without building a database.
+//!
+//! The nodes of the proof are indexed by hash in place, and keys are looked up by walking the
+//! encoded nodes from the root. Nothing is copied out of the proof, which keeps the check cheap
+//! enough to run on large proofs inside the runtime.
+
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+use hash_db::Hasher;
+use trie_db::{NibbleSlice, NodeCodec as NodeCodecT, node::{Node, NodeHandle}};
+use crate::{Error, NodeCodec, StorageProof, TrieConfiguration, TrieHash};
+
+/// Error of a storage proof check.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ProofCheckError<H> {
+	/// A node needed to look up a key is not in the proof. \[hash\]
+	MissingNode(H),
+	/// A node of the proof could not be decoded.
+	Decode(Error),
+	/// The value of a key differs from the expected one. \[key\]
+	ValueMismatch(Vec<u8>),
+}
+
+#[cfg(feature = "std")]
+impl<H: std::fmt::Debug> std::fmt::Display for ProofCheckError<H> {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		match self {
+			ProofCheckError::MissingNode(hash) => write!(f, "Node {:?} missing from proof", hash),
+			ProofCheckError::Decode(e) => write!(f, "Invalid proof node: {}", e),
+			ProofCheckError::ValueMismatch(key) => write!(f, "Value mismatch for key {:?}", key),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl<H: std::fmt::Debug> std::error::Error for ProofCheckError<H> {}
+
+/// The nodes of a [`StorageProof`], indexed by hash to look keys up in them.
+pub struct ProofChecker<'a, L: TrieConfiguration> {
+	nodes: BTreeMap<TrieHash<L>, &'a [u8]>,
+}
+
+impl<'a, L: TrieConfiguration> ProofChecker<'a, L> where TrieHash<L>: Ord {
+	/// Index the nodes of `proof`.
+	pub fn new(proof: &'a StorageProof) -> Self {
+		let nodes = proof.iter_encoded_nodes()
+			.map(|node| (L::Hash::hash(node), node))
+			.collect();
+		ProofChecker { nodes }
+	}
+
+	/// Read the value of `key` in the trie of root `root`.
+	///
+	/// Returns `None` if the proof shows that `key` is not in the trie, and an error if the proof
+	/// does not hold the nodes needed to tell.
+	pub fn read(
+		&self,
+		root: &TrieHash<L>,
+		key: &[u8],
+	) -> Result<Option<&'a [u8]>, ProofCheckError<TrieHash<L>>> {
+		// The null node of an empty trie is not recorded when reading it.
+		if *root == <NodeCodec<L::Hash> as NodeCodecT>::hashed_null_node() {
+			return Ok(None)
+		}
+
+		let mut partial = NibbleSlice::new(key);
+		let mut encoded = self.node(root)?;
+		loop {
+			let node = NodeCodec::<L::Hash>::decode(encoded).map_err(ProofCheckError::Decode)?;
+			let (slice, children, value) = match node {
+				Node::Empty => return Ok(None),
+				Node::Leaf(slice, value) => {
+					return Ok(if slice == partial { Some(value) } else { None })
+				},
+				Node::Extension(slice, child) => {
+					if !partial.starts_with(&slice) {
+						return Ok(None)
+					}
+					partial = partial.mid(slice.len());
+					encoded = self.child(&child)?;
+					continue
+				},
+				Node::Branch(children, value) => (NibbleSlice::new(&[]), children, value),
+				Node::NibbledBranch(slice, children, value) => (slice, children, value),
+			};
+
+			if !partial.starts_with(&slice) {
+				return Ok(None)
+			}
+			partial = partial.mid(slice.len());
+			if partial.is_empty() {
+				return Ok(value)
+			}
+			match &children[partial.at(0) as usize] {
+				Some(child) => {
+					partial = partial.mid(1);
+					encoded = self.child(child)?;
+				},
+				None => return Ok(None),
+			}
+		}
+	}
+
+	/// Check that the keys of `items` have the given values in the trie of root `root`.
+	///
+	/// A key with a value is checked for inclusion, a key without one for non-inclusion.
+	pub fn verify<'i, I, K, V>(
+		&self,
+		root: &TrieHash<L>,
+		items: I,
+	) -> Result<(), ProofCheckError<TrieHash<L>>> where
+		I: IntoIterator<Item=&'i (K, Option<V>)>,
+		K: 'i + AsRef<[u8]>,
+		V: 'i + AsRef<[u8]>,
+	{
+		for (key, expected) in items {
+			let value = self.read(root, key.as_ref())?;
+			if value != expected.as_ref().map(AsRef::as_ref) {
+				return Err(ProofCheckError::ValueMismatch(key.as_ref().to_vec()))
+			}
+		}
+		Ok(())
+	}
+
+	fn node(&self, hash: &TrieHash<L>) -> Result<&'a [u8], ProofCheckError<TrieHash<L>>> {
+		self.nodes.get(hash).copied().ok_or(ProofCheckError::MissingNode(*hash))
+	}
+
+	fn child(&self, child: &NodeHandle<'a>) -> Result<&'a [u8], ProofCheckError<TrieHash<L>>> {
+		match child {
+			NodeHandle::Inline(encoded) => Ok(*encoded),
+			NodeHandle::Hash(hash) => {
+				let mut child_hash = TrieHash::<L>::default();
+				if hash.len() != child_hash.as_ref().len() {
+					return Err(ProofCheckError::Decode(Error::BadFormat))
+				}
+				child_hash.as_mut().copy_from_slice(hash);
+				self.node(&child_hash)
+			},
+		}
+	}
+}
+
+/// Verify a set of key-value pairs against a trie root and a [`StorageProof`].
+///
+/// Like [`crate::verify_trie_proof`], but for proofs recorded while reading the trie, e.g. by a
+/// proving backend, rather than proofs made by [`crate::generate_trie_proof`]. See
+/// [`ProofChecker::verify`].
+pub fn verify_storage_proof<'i, L, I, K, V>(
+	root: &TrieHash<L>,
+	proof: &StorageProof,
+	items: I,
+) -> Result<(), ProofCheckError<TrieHash<L>>> where
+	L: TrieConfiguration,
+	TrieHash<L>: Ord,
+	I: IntoIterator<Item=&'i (K, Option<V>)>,
+	K: 'i + AsRef<[u8]>,
+	V: 'i + AsRef<[u8]>,
+{
+	ProofChecker::<L>::new(proof).verify(root, items)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::Blake2Hasher;
+	use trie_db::{Trie, TrieMut};
+	use crate::{MemoryDB, Recorder, TrieDB, TrieDBMut};
+
+	type Layout = crate::Layout<Blake2Hasher>;
+
+	fn test_trie() -> (MemoryDB<Blake2Hasher>, TrieHash<Layout>) {
+		let mut db = MemoryDB::default();
+		let mut root = Default::default();
+		{
+			let mut trie = TrieDBMut::<Layout>::new(&mut db, &mut root);
+			trie.insert(b"do", b"verb").unwrap();
+			trie.insert(b"dog", b"puppy").unwrap();
+			trie.insert(b"doge", &[0xaa; 64]).unwrap();
+			trie.insert(b"horse", &[0xbb; 64]).unwrap();
+		}
+		(db, root)
+	}
+
+	fn record(db: &MemoryDB<Blake2Hasher>, root: &TrieHash<Layout>, keys: &[&str]) -> StorageProof {
+		let mut recorder = Recorder::new();
+		let trie = TrieDB::<Layout>::new(db, root).unwrap();
+		for key in keys {
+			trie.get_with(key.as_bytes(), &mut recorder).unwrap();
+		}
+		StorageProof::new(recorder.drain().into_iter().map(|record| record.data).collect())
+	}
+
+	#[test]
+	fn reads_included_and_missing_keys() {
+		let (db, root) = test_trie();
+		let proof = record(&db, &root, &["dog", "doge", "dogs", "cat"]);
+		let checker = ProofChecker::<Layout>::new(&proof);
+
+		assert_eq!(checker.read(&root, b"dog"), Ok(Some(&b"puppy"[..])));
+		assert_eq!(checker.read(&root, b"doge"), Ok(Some(&[0xaa; 64][..])));
+		assert_eq!(checker.read(&root, b"dogs"), Ok(None));
+		assert_eq!(checker.read(&root, b"cat"), Ok(None));
+		// The leaf of `horse` was not recorded.
+		assert!(matches!(checker.read(&root, b"horse"), Err(ProofCheckError::MissingNode(_))));
+		assert!(matches!(
+			checker.read(&Default::default(), b"dog"),
+			Err(ProofCheckError::MissingNode(_)),
+		));
+	}
+
+	#[test]
+	fn reads_from_the_empty_trie_without_nodes() {
+		let root = <NodeCodec<Blake2Hasher> as NodeCodecT>::hashed_null_node();
+		let proof = StorageProof::empty();
+		let checker = ProofChecker::<Layout>::new(&proof);
+
+		assert_eq!(checker.read(&root, b"dog"), Ok(None));
+		assert_eq!(checker.read(&root, b""), Ok(None));
+	}
+
+	#[test]
+	fn verify_checks_values() {
+		let (db, root) = test_trie();
+		let proof = record(&db, &root, &["dog", "cat"]);
+
+		let items: Vec<(&[u8], Option<&[u8]>)> = vec![(&b"dog"[..], Some(&b"puppy"[..])), (&b"cat"[..], None)];
+		assert_eq!(verify_storage_proof::<Layout, _, _, _>(&root, &proof, &items), Ok(()));
+
+		let items: Vec<(&[u8], Option<&[u8]>)> = vec![(&b"dog"[..], Some(&b"kitten"[..]))];
+		assert_eq!(
+			verify_storage_proof::<Layout, _, _, _>(&root, &proof, &items),
+			Err(ProofCheckError::ValueMismatch(b"dog".to_vec())),
+		);
+
+		let items: Vec<(&[u8], Option<&[u8]>)> = vec![(&b"cat"[..], Some(&b"meow"[..]))];
+		assert_eq!(
+			verify_storage_proof::<Layout, _, _, _>(&root, &proof, &items),
+			Err(ProofCheckError::ValueMismatch(b"cat".to_vec())),
+		);
+	}
+}
@@ -64,6 +64,11 @@ impl StorageProof {
 		StorageProofNodeIterator::new(self)
 	}
 
+	/// Returns an iterator over the encoded trie nodes of the proof, in no particular order.
+	pub fn iter_encoded_nodes(&self) -> impl Iterator<Item = &[u8]> {
+		self.trie_nodes.iter().map(Vec::as_slice)
+	}
+
 	/// Convert into plain node vector.
 	pub fn into_nodes(self) -> Vec<Vec<u8>> {
 		self.trie_nodes
@@ -193,6 +193,7 @@
 //!				block_construction: sc_client_api::ExecutionStrategy::NativeWhenPossible,
 //!				offchain_worker: sc_client_api::ExecutionStrategy::NativeWhenPossible,
 //!				other: sc_client_api::ExecutionStrategy::NativeWhenPossible,
+//!				overrides: Default::default(),
 //! 		},
 //! 		chain_spec: Box::new(development_config()),
 //! 		log_targets: vec![],
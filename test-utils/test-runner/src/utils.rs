@@ -59,7 +59,7 @@ pub fn default_config(task_executor: TaskExecutor, mut chain_spec: Box<dyn Chain
 		Default::default(),
 		None,
 	);
-	let informant_output_format = OutputFormat { enable_color: false };
+	let informant_output_format = OutputFormat { enable_color: false, ..Default::default() };
 	network_config.allow_non_globals_in_dht = true;
 
 	network_config
@@ -85,6 +85,7 @@ pub fn default_config(task_executor: TaskExecutor, mut chain_spec: Box<dyn Chain
 		},
 		state_cache_size: 16777216,
 		state_cache_child_ratio: None,
+		trie_node_cache_size: 0,
 		chain_spec,
 		wasm_method: WasmExecutionMethod::Interpreted,
 		execution_strategies: ExecutionStrategies {
@@ -93,6 +94,7 @@ pub fn default_config(task_executor: TaskExecutor, mut chain_spec: Box<dyn Chain
 			block_construction: sc_client_api::ExecutionStrategy::AlwaysWasm,
 			offchain_worker: sc_client_api::ExecutionStrategy::AlwaysWasm,
 			other: sc_client_api::ExecutionStrategy::AlwaysWasm,
+			overrides: Default::default(),
 		},
 		rpc_http: None,
 		rpc_ws: None,
@@ -102,6 +104,7 @@ pub fn default_config(task_executor: TaskExecutor, mut chain_spec: Box<dyn Chain
 		rpc_cors: None,
 		rpc_methods: Default::default(),
 		rpc_max_payload: None,
+		rpc_call_limits: Default::default(),
 		prometheus_config: None,
 		telemetry_endpoints: None,
 		telemetry_external_transport: None,
@@ -122,6 +125,7 @@ pub fn default_config(task_executor: TaskExecutor, mut chain_spec: Box<dyn Chain
 		keep_blocks: KeepBlocks::All,
 		state_pruning: Default::default(),
 		transaction_storage: TransactionStorageMode::BlockBody,
+		storage_monitor: None,
 	}
 }
 
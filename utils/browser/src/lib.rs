@@ -96,6 +96,7 @@ where
 		state_pruning: Default::default(),
 		keep_blocks: KeepBlocks::All,
 		transaction_storage: TransactionStorageMode::BlockBody,
+		storage_monitor: None,
 		rpc_cors: Default::default(),
 		rpc_http: Default::default(),
 		rpc_ipc: Default::default(),
@@ -104,8 +105,10 @@ where
 		rpc_http_threads: Default::default(),
 		rpc_methods: Default::default(),
 		rpc_max_payload: Default::default(),
+		rpc_call_limits: Default::default(),
 		state_cache_child_ratio: Default::default(),
 		state_cache_size: Default::default(),
+		trie_node_cache_size: Default::default(),
 		tracing_receiver: Default::default(),
 		tracing_targets: Default::default(),
 		transaction_pool: Default::default(),
@@ -116,6 +119,7 @@ where
 		base_path: None,
 		informant_output_format: sc_informant::OutputFormat {
 			enable_color: false,
+			..Default::default()
 		},
 		disable_log_reloading: false,
 	};